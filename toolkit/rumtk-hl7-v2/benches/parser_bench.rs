@@ -0,0 +1,137 @@
+/*
+ * rumtk attempts to implement HL7 and medical protocols for interoperability in medicine.
+ * This toolkit aims to be reliable, simple, performant, and standards compliant.
+ * Copyright (C) 2024  Luis M. Santos, M.D.
+ *
+ * This library is free software; you can redistribute it and/or
+ * modify it under the terms of the GNU Lesser General Public
+ * License as published by the Free Software Foundation; either
+ * version 2.1 of the License, or (at your option) any later version.
+ *
+ * This library is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+ * Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public
+ * License along with this library; if not, write to the Free Software
+ * Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301  USA
+ */
+
+//!
+//! Compares the owned parser ([V2Message]) against the borrowed parser ([V2MessageRef]).
+//! Run with `cargo bench -p rumtk-hl7-v2`.
+//!
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use rumtk_core::strings::RUMString;
+use rumtk_hl7_v2::hl7_v2_message_ref::v2_message_ref::V2MessageRef;
+use rumtk_hl7_v2::hl7_v2_parser::v2_parser::V2Message;
+
+/**************************** Constants**************************************/
+
+const ORU_HEADER: &str = "MSH|^~\\&|LABVENDOR|LAB|EHR|HOSP|20240102120000||ORU^R01^ORU_R01|MSG0042|P|2.5.1\r\
+    PID|1||PATID14567^^^NIST MPI&2.16.840.1.113883.3.72.5.30.2&ISO^MR||Hernandez^Maria^^^^^L||19880906|F||2054-5^Black or African American^HL70005|3248 E FlorenceAve^^Huntington Park^CA^90255^^H\r\
+    PV1|1|O|2000^2012^01||||004777^ATTEND^AARON^A\r";
+const ORU_ORDER: &str = "ORC|RE|ORD231-1^NIST EHR^2.16.840.1.113883.3.72.5.24^ISO|||||||20130116090021-0800|||134569827^Feller^Hans^^^^^^NPI&2.16.840.1.113883.4.6&ISO^L^^^NPI\r\
+    OBR|1|ORD231-1^NIST EHR^2.16.840.1.113883.3.72.5.24^ISO||34555-3^Creatinine 24H renal clearance panel^LN^^^^^^CreatinineClearance|||201301151130-0800|201301160912-0800||||||||134569827^Feller^Hans^^^^^^NPI&2.16.840.1.113883.4.6&ISO^L^^^NPI\r";
+const ORU_OBSERVATION: &str = "OBX|1|NM|3167-4^Volume of 24 hour Urine^LN^1904^Urine Volume of 24 hour collection^99USL^2.44^^Urine Volume 24hour collection||1250|mL^milliliter^UCUM^ml^mililiter^L^1.7^^ml|||||F|||20130116|||||||||||||||SCI\r\
+    NTE|1|L|Reference range adjusted for age\\T\\sex\r";
+const ORDERS: usize = 10;
+const OBSERVATIONS_PER_ORDER: usize = 20;
+
+/**************************** Helpers ***************************************/
+
+///
+/// Builds a large ORU similar to what a busy lab feed sends.
+///
+fn build_large_oru() -> String {
+    let mut message = String::from(ORU_HEADER);
+    for _ in 0..ORDERS {
+        message.push_str(ORU_ORDER);
+        for _ in 0..OBSERVATIONS_PER_ORDER {
+            message.push_str(ORU_OBSERVATION);
+        }
+    }
+    message
+}
+
+/**************************** Benchmarks ************************************/
+
+fn bench_parse(c: &mut Criterion) {
+    let message = build_large_oru();
+    let mut group = c.benchmark_group("parse_large_oru");
+    group.throughput(Throughput::Bytes(message.len() as u64));
+    group.bench_function("owned", |b| {
+        b.iter(|| V2Message::try_from_str(black_box(&message)).unwrap())
+    });
+    group.bench_function("borrowed", |b| {
+        b.iter(|| V2MessageRef::try_from_str(black_box(&message)).unwrap())
+    });
+    group.bench_function("borrowed_then_promoted", |b| {
+        b.iter(|| {
+            V2MessageRef::try_from_str(black_box(&message))
+                .unwrap()
+                .to_owned_message()
+                .unwrap()
+        })
+    });
+    group.finish();
+}
+
+fn bench_find_component(c: &mut Criterion) {
+    let message = build_large_oru();
+    let owned = V2Message::try_from_str(&message).unwrap();
+    let borrowed = V2MessageRef::try_from_str(&message).unwrap();
+    let pattern = RUMString::from("OBX(150)-3.2");
+    let mut group = c.benchmark_group("find_component_large_oru");
+    group.bench_function("owned", |b| {
+        b.iter(|| {
+            owned
+                .find_component(black_box(&pattern))
+                .unwrap()
+                .to_string()
+        })
+    });
+    group.bench_function("borrowed", |b| {
+        b.iter(|| borrowed.find_component(black_box(&pattern)).unwrap())
+    });
+    group.finish();
+}
+
+fn bench_parse_and_read(c: &mut Criterion) {
+    let message = build_large_oru();
+    let patterns = [
+        RUMString::from("PID-7"),
+        RUMString::from("PID-3.4.2"),
+        RUMString::from("PID-5.1"),
+        RUMString::from("OBX(1)-5"),
+    ];
+    let mut group = c.benchmark_group("parse_and_read_large_oru");
+    group.throughput(Throughput::Bytes(message.len() as u64));
+    group.bench_function("owned", |b| {
+        b.iter(|| {
+            let parsed = V2Message::try_from_str(black_box(&message)).unwrap();
+            for pattern in patterns.iter() {
+                black_box(parsed.find_component(pattern).unwrap());
+            }
+        })
+    });
+    group.bench_function("borrowed", |b| {
+        b.iter(|| {
+            let parsed = V2MessageRef::try_from_str(black_box(&message)).unwrap();
+            for pattern in patterns.iter() {
+                black_box(parsed.find_component(pattern).unwrap());
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_parse,
+    bench_find_component,
+    bench_parse_and_read
+);
+criterion_main!(benches);
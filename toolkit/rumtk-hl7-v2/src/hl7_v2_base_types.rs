@@ -19,21 +19,27 @@
  */
 
 pub mod v2_base_types {
+    use crate::hl7_v2_base_types::v2_primitives::V2PrimitiveCasting;
     use crate::hl7_v2_constants::{
-        V2_DATETIME_MICRO_LENGTH, V2_DATETIME_THOUSAND_TICK, V2_MSHEADER_PATTERN,
-        V2_SEARCH_EXPR_TYPE, V2_SEGMENT_IDS, V2_SEGMENT_TERMINATOR, V2_TRUNCATION_CHARACTER,
+        V2_CUSTOM_SEGMENT_ID_START, V2_CUSTOM_SEGMENT_PREFIX, V2_DATETIME_MICRO_LENGTH,
+        V2_HEADER_SEGMENTS, V2_MSHEADER_PATTERN, V2_SEARCH_EXPR_TYPE, V2_SEGMENT_IDS,
+        V2_SEGMENT_NAMES, V2_SEGMENT_TERMINATOR, V2_TRUNCATION_CHARACTER, V2_UNKNOWN_SEGMENT_ID,
     };
-    use crate::hl7_v2_search::REGEX_V2_SEARCH_DEFAULT;
+    use crate::hl7_v2_search::{REGEX_V2_SEARCH_DEFAULT, REGEX_V2_SEARCH_SUBCOMPONENT};
+    use crate::hl7_v2_versions::v2_versions::V2Version;
     use chrono::prelude::*;
+    use chrono::Duration;
     use rumtk_core::core::{is_unique, RUMResult};
-    use rumtk_core::json::serialization::{Deserialize, Serialize};
+    use rumtk_core::json::serialization::{Deserialize, Deserializer, Serialize, Serializer};
     use rumtk_core::maths::generate_tenth_factor;
     use rumtk_core::search::rumtk_search::{
         string_search, string_search_named_captures, SearchGroups,
     };
-    use rumtk_core::strings::{format_compact, StringUtils, ToCompactString};
+    use rumtk_core::strings::format_compact;
     use rumtk_core::strings::{RUMString, RUMStringConversions, UTFStringExtensions};
-    use std::fmt::Debug;
+    use serde::de::Error;
+    use std::cmp::Ordering;
+    use std::fmt::{Debug, Display};
     /**************************** Constants**************************************/
     // Regex
     const REGEX_DT_TIMEZONE: &str = r"(\-|\+)\d{4}";
@@ -48,7 +54,7 @@ pub mod v2_base_types {
     /// Basic type used to derive other types for the standard implementation.
     ///
     pub type V2String = RUMString;
-    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
     pub struct V2ParserCharacters {
         pub segment_terminator: RUMString,
         pub field_separator: RUMString,
@@ -57,6 +63,13 @@ pub mod v2_base_types {
         pub escape_character: RUMString,
         pub subcomponent_separator: RUMString,
         pub truncation_character: RUMString,
+        ///
+        /// Whether the header declares the truncation character as the 5th encoding character
+        /// (v2.7+). Without it, the truncation character has no special meaning in the message,
+        /// so it is neither escaped nor used to mark truncated values.
+        ///
+        #[serde(default)]
+        pub truncation_declared: bool,
     }
 
     impl Default for V2ParserCharacters {
@@ -75,6 +88,7 @@ pub mod v2_base_types {
                 escape_character: RUMString::from("\\"),
                 subcomponent_separator: RUMString::from("&"),
                 truncation_character: RUMString::from("#"),
+                truncation_declared: false,
             }
         }
         pub fn from_str(msh_segment: &str) -> V2Result<Self> {
@@ -92,6 +106,7 @@ pub mod v2_base_types {
                     escape_character: key_chars.get(3).unwrap().to_rumstring(),
                     subcomponent_separator: key_chars.get(4).unwrap().to_rumstring(),
                     truncation_character: key_chars.get(5).unwrap().to_rumstring(),
+                    truncation_declared: true,
                 }),
                 4 => Ok(V2ParserCharacters {
                     segment_terminator: V2_SEGMENT_TERMINATOR.to_rumstring(),
@@ -101,6 +116,7 @@ pub mod v2_base_types {
                     escape_character: key_chars.get(3).unwrap().to_rumstring(),
                     subcomponent_separator: key_chars.get(4).unwrap().to_rumstring(),
                     truncation_character: V2_TRUNCATION_CHARACTER.to_rumstring(),
+                    truncation_declared: false,
                 }),
                 _ => Err("Wrong count of parsing characters in message header!".to_rumstring()),
             }
@@ -156,20 +172,593 @@ pub mod v2_base_types {
             fragment.replace("\\\\", "\\")
         }
 
+        ///
+        /// Same as [V2ParserCharacters::from_msh] but also accepts the file and batch header
+        /// segments (FHS and BHS). Each one declares its own encoding characters.
+        ///
+        pub fn from_header(header_segment: &str) -> V2Result<Self> {
+            match header_segment.get(0..3) {
+                Some(name) if is_header_segment_name(name) => {
+                    V2ParserCharacters::from_str(&header_segment[3..])
+                }
+                _ => Err(format_compact!(
+                    "The segment is not one of the header segments {:?}! This message is malformed!",
+                    V2_HEADER_SEGMENTS
+                )),
+            }
+        }
+
+        ///
+        /// Returns the encoding characters the way they are written in the header segments
+        /// (e.g. MSH-2). The truncation character is only included if it is declared.
+        ///
+        pub fn encoding_characters(&self) -> V2String {
+            let mut encoding_chars = format_compact!(
+                "{}{}{}{}",
+                &self.component_separator,
+                &self.repetition_separator,
+                &self.escape_character,
+                &self.subcomponent_separator
+            );
+            if self.declares_truncation() {
+                encoding_chars.push_str(&self.truncation_character);
+            }
+            encoding_chars
+        }
+
+        ///
+        /// Checks that these characters can delimit a message. The separators and the escape
+        /// character must be single, distinct, non alphanumeric characters, and none of them can
+        /// be part of the segment terminator.
+        ///
+        pub fn validate(&self) -> V2Result<()> {
+            let mut delimiters = vec![
+                self.field_separator.as_str(),
+                self.component_separator.as_str(),
+                self.repetition_separator.as_str(),
+                self.escape_character.as_str(),
+                self.subcomponent_separator.as_str(),
+            ];
+            if self.declares_truncation() {
+                delimiters.push(self.truncation_character.as_str());
+            }
+            for delimiter in delimiters.iter() {
+                let mut chars = delimiter.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) if !c.is_alphanumeric() && !c.is_whitespace() => {}
+                    _ => {
+                        return Err(format_compact!(
+                            "Invalid delimiter [{}]! Delimiters must be a single non alphanumeric character.",
+                            delimiter
+                        ))
+                    }
+                }
+                if self.segment_terminator.contains(delimiter) {
+                    return Err(format_compact!(
+                        "Delimiter [{}] is part of the segment terminator!",
+                        delimiter
+                    ));
+                }
+            }
+            if self.segment_terminator.is_empty() {
+                return Err("The segment terminator cannot be empty!".to_rumstring());
+            }
+            match is_unique(&delimiters) {
+                true => Ok(()),
+                false => Err(format_compact!(
+                    "Delimiters must be distinct! Got {:?}",
+                    &delimiters
+                )),
+            }
+        }
+
+        ///
+        /// Rewrites a value stored in wire format with these characters so it can be placed in a
+        /// message delimited by `target`. Escaped delimiters stand for the characters of this set,
+        /// so they become plain text unless they are delimiters in `target` too. Any text that
+        /// collides with the delimiters of `target` is escaped per Section 2.7.1. Other escape
+        /// sequences are kept and written with the escape character of `target`.
+        ///
+        pub fn redelimit(&self, input: &str, target: &V2ParserCharacters) -> V2String {
+            if self == target {
+                return target.escape_delimiters(input);
+            }
+            let escape_char = self.escape_character.as_str();
+            let mut redelimited = V2String::with_capacity(input.len());
+            let mut remaining = input;
+            while let Some(start) = remaining
+                .find(escape_char)
+                .filter(|_| !escape_char.is_empty())
+            {
+                let sequence_start = &remaining[start + escape_char.len()..];
+                let end = match sequence_start.find(escape_char) {
+                    Some(end) => end,
+                    None => break,
+                };
+                let sequence = &sequence_start[..end];
+                redelimited.push_str(&self.redelimit_text(&remaining[..start], target));
+                match self.delimiter_for(sequence) {
+                    Some(delimiter) => redelimited.push_str(&target.escape(delimiter)),
+                    None => {
+                        redelimited.push_str(&target.escape_character);
+                        redelimited.push_str(sequence);
+                        redelimited.push_str(&target.escape_character);
+                    }
+                }
+                remaining = &sequence_start[end + escape_char.len()..];
+            }
+            redelimited.push_str(&self.redelimit_text(remaining, target));
+            redelimited
+        }
+
+        ///
+        /// Escapes literal text for `target`. A declared truncation character is not text, it
+        /// marks a truncated value, so it becomes the truncation character of `target`.
+        ///
+        fn redelimit_text(&self, text: &str, target: &V2ParserCharacters) -> V2String {
+            if !self.declares_truncation() || !text.contains(self.truncation_character.as_str()) {
+                return target.escape(text);
+            }
+            let marker = match target.declares_truncation() {
+                true => &target.truncation_character,
+                false => &self.truncation_character,
+            };
+            let pieces: Vec<V2String> = text
+                .split(self.truncation_character.as_str())
+                .map(|piece| target.escape(piece))
+                .collect();
+            pieces.join(marker).to_rumstring()
+        }
+
+        ///
+        /// True if the truncation character has special meaning in the message. See
+        /// [V2ParserCharacters::truncation_declared].
+        ///
+        pub fn declares_truncation(&self) -> bool {
+            self.truncation_declared && !self.truncation_character.is_empty()
+        }
+
+        fn delimiter_for(&self, sequence: &str) -> Option<&RUMString> {
+            match sequence {
+                "F" => Some(&self.field_separator),
+                "S" => Some(&self.component_separator),
+                "T" => Some(&self.subcomponent_separator),
+                "R" => Some(&self.repetition_separator),
+                "E" => Some(&self.escape_character),
+                "P" => Some(&self.truncation_character),
+                _ => None,
+            }
+        }
+
         fn is_msh(msh_segment_token: &str) -> bool {
             &msh_segment_token[0..3] == V2_MSHEADER_PATTERN
         }
+
+        ///
+        /// Escapes every character with special meaning in this message, including the escape
+        /// character. Use this on plain text before placing it in a message.
+        ///
+        /// ## Per Section 2.7.1
+        ///
+        /// ```text
+        /// \F\    field separator
+        /// \S\    component separator
+        /// \T\    subcomponent separator
+        /// \R\    repetition separator
+        /// \E\    escape character
+        /// \P\    truncation character
+        /// ```
+        ///
+        /// The truncation character is only escaped if the message declares it in MSH-2.
+        /// Otherwise, it is an ordinary data character. The segment terminator is not a valid
+        /// data character, so we emit it as hexadecimal data (\X0D\).
+        ///
+        pub fn escape(&self, input: &str) -> V2String {
+            self.escape_with(input, true)
+        }
+
+        ///
+        /// Escapes the delimiter characters in the input but leaves the escape character alone.
+        /// Values stored in a message are kept in wire format, so any escape character in them
+        /// already starts an escape sequence that must survive generation untouched. The same
+        /// goes for the truncation character, which marks a truncated value.
+        ///
+        pub fn escape_delimiters(&self, input: &str) -> V2String {
+            self.escape_with(input, false)
+        }
+
+        fn escape_with(&self, input: &str, plain_text: bool) -> V2String {
+            let mut escaped = V2String::with_capacity(input.len());
+            let mut buffer = [0u8; 4];
+            for c in input.chars() {
+                let c_str: &str = c.encode_utf8(&mut buffer);
+                let sequence = if c_str == self.field_separator {
+                    "F"
+                } else if c_str == self.component_separator {
+                    "S"
+                } else if c_str == self.subcomponent_separator {
+                    "T"
+                } else if c_str == self.repetition_separator {
+                    "R"
+                } else if plain_text && c_str == self.escape_character {
+                    "E"
+                } else if plain_text
+                    && self.declares_truncation()
+                    && c_str == self.truncation_character
+                {
+                    "P"
+                } else if c_str == self.segment_terminator {
+                    "X0D"
+                } else {
+                    escaped.push(c);
+                    continue;
+                };
+                escaped.push_str(&self.escape_character);
+                escaped.push_str(sequence);
+                escaped.push_str(&self.escape_character);
+            }
+            escaped
+        }
+
+        ///
+        /// Interprets the escape sequences in the input.
+        ///
+        /// ## Per Section 2.7
+        ///
+        /// ```text
+        /// \H\        start highlighting
+        /// \N\        normal text (end highlighting)
+        /// \F\        field separator
+        /// \S\        component separator
+        /// \T\        subcomponent separator
+        /// \R\        repetition separator
+        /// \E\        escape character
+        /// \P\        truncation character
+        /// \Xdddd...\ hexadecimal data
+        /// \Cxxyy\    single-byte character set escape sequence
+        /// \Mxxyyzz\  multi-byte character set escape sequence
+        /// ```
+        ///
+        /// Highlighting and character set switches have no textual representation and are
+        /// dropped. Hexadecimal data is decoded as UTF-8, falling back to ISO 8859-1 when the
+        /// bytes are not valid UTF-8. Any other sequence (formatting commands, locally defined
+        /// escapes) is left in place for the receiving application to handle.
+        ///
+        pub fn unescape(&self, input: &str) -> V2Result<V2String> {
+            let escape_char = self.escape_character.as_str();
+            if escape_char.is_empty() || !input.contains(escape_char) {
+                return Ok(input.to_rumstring());
+            }
+
+            let mut unescaped = V2String::with_capacity(input.len());
+            let mut remaining = input;
+            while let Some(start) = remaining.find(escape_char) {
+                unescaped.push_str(&remaining[..start]);
+                let sequence_start = &remaining[start + escape_char.len()..];
+                let end = match sequence_start.find(escape_char) {
+                    Some(end) => end,
+                    None => {
+                        return Err(format_compact!(
+                            "Unterminated escape sequence found in {}!",
+                            input
+                        ))
+                    }
+                };
+                let sequence = &sequence_start[..end];
+                match sequence {
+                    "F" => unescaped.push_str(&self.field_separator),
+                    "S" => unescaped.push_str(&self.component_separator),
+                    "T" => unescaped.push_str(&self.subcomponent_separator),
+                    "R" => unescaped.push_str(&self.repetition_separator),
+                    "E" => unescaped.push_str(&self.escape_character),
+                    "P" => unescaped.push_str(&self.truncation_character),
+                    "H" | "N" => {}
+                    _ if sequence.starts_with('X') => {
+                        unescaped.push_str(&Self::decode_hex_sequence(&sequence[1..])?)
+                    }
+                    _ if sequence.starts_with('C') || sequence.starts_with('M') => {}
+                    _ => {
+                        unescaped.push_str(escape_char);
+                        unescaped.push_str(sequence);
+                        unescaped.push_str(escape_char);
+                    }
+                }
+                remaining = &sequence_start[end + escape_char.len()..];
+            }
+            unescaped.push_str(remaining);
+
+            Ok(unescaped)
+        }
+
+        fn decode_hex_sequence(hex: &str) -> V2Result<V2String> {
+            if hex.len() % 2 != 0 || !hex.is_ascii() {
+                return Err(format_compact!(
+                    "Malformed hexadecimal escape sequence X{}!",
+                    hex
+                ));
+            }
+            let mut bytes: Vec<u8> = Vec::with_capacity(hex.len() / 2);
+            for i in (0..hex.len()).step_by(2) {
+                match u8::from_str_radix(&hex[i..i + 2], 16) {
+                    Ok(byte) => bytes.push(byte),
+                    Err(e) => {
+                        return Err(format_compact!(
+                            "Malformed hexadecimal escape sequence X{}! Error: {}",
+                            hex,
+                            e
+                        ))
+                    }
+                }
+            }
+            match String::from_utf8(bytes) {
+                Ok(decoded) => Ok(decoded.to_rumstring()),
+                Err(e) => Ok(e.into_bytes().iter().map(|b| *b as char).collect()),
+            }
+        }
+    }
+    ///
+    /// How strict the parser should be about the contents of a message.
+    ///
+    /// - **Strict** => Only standard segments and Z-segments (site defined segments per Section
+    ///   2.5.2) are accepted.
+    /// - **Lenient** => Any well-formed 3-character segment id is accepted. Unknown segments are
+    ///   stored, searchable, and regenerated like any other segment.
+    ///
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+    pub enum V2ParseMode {
+        #[default]
+        Strict,
+        Lenient,
     }
+
+    ///
+    /// How bad a parsing defect is.
+    ///
+    /// - **Warning** => The parser repaired the defect. No data was lost.
+    /// - **Error** => The defect cost us data (e.g. a segment was dropped) or, in
+    ///   [V2ParseMode::Strict], aborted parsing.
     ///
-    /// Object representing the exact indices needed to search for a field or component.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+    pub enum V2DiagnosticSeverity {
+        Warning,
+        Error,
+    }
+
+    ///
+    /// A defect found while parsing a message, along with where it was found.
+    ///
+    /// - **offset** => Byte offset into the raw input.
+    /// - **segment** => 1-indexed position of the segment among the lines of the raw input.
+    /// - **field** and **component** => Same numbering used by [V2SearchIndex] and search
+    ///   patterns. Field 0 is the segment id.
+    ///
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct V2Diagnostic {
+        pub severity: V2DiagnosticSeverity,
+        pub message: RUMString,
+        pub offset: usize,
+        pub segment: Option<usize>,
+        pub segment_name: Option<RUMString>,
+        pub field: Option<isize>,
+        pub component: Option<isize>,
+    }
+
+    impl V2Diagnostic {
+        pub fn new(severity: V2DiagnosticSeverity, offset: usize, message: &str) -> Self {
+            V2Diagnostic {
+                severity,
+                message: message.to_rumstring(),
+                offset,
+                segment: None,
+                segment_name: None,
+                field: None,
+                component: None,
+            }
+        }
+
+        pub fn at_segment(mut self, segment: usize, segment_name: &str) -> Self {
+            self.segment = Some(segment);
+            self.segment_name = Some(segment_name.to_rumstring());
+            self
+        }
+
+        pub fn at_field(mut self, field: isize, component: Option<isize>) -> Self {
+            self.field = Some(field);
+            self.component = component;
+            self
+        }
+
+        pub fn is_error(&self) -> bool {
+            self.severity == V2DiagnosticSeverity::Error
+        }
+    }
+
+    impl Display for V2Diagnostic {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "[{:?}] byte {}", self.severity, self.offset)?;
+            if let Some(segment) = self.segment {
+                write!(f, ", segment {}", segment)?;
+                if let Some(name) = &self.segment_name {
+                    write!(f, " ({})", name)?;
+                }
+            }
+            if let Some(field) = self.field {
+                write!(f, ", field {}", field)?;
+            }
+            if let Some(component) = self.component {
+                write!(f, ", component {}", component)?;
+            }
+            write!(f, ": {}", self.message)
+        }
+    }
+
+    ///
+    /// Every diagnostic produced while parsing one message, in the order they were found.
+    ///
+    pub type V2Diagnostics = Vec<V2Diagnostic>;
+
+    ///
+    /// A segment id is made of 3 characters. The first one must be an uppercase letter and the
+    /// rest uppercase letters or digits.
+    ///
+    pub fn is_valid_segment_name(name: &str) -> bool {
+        let bytes = name.as_bytes();
+        bytes.len() == 3
+            && bytes[0].is_ascii_uppercase()
+            && bytes[1..]
+                .iter()
+                .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+    }
+
+    ///
+    /// Z-segments are reserved for locally defined segments.
+    ///
+    /// ## Per Section 2.5.2
+    ///
+    /// ```text
+    /// Segment ID codes beginning with the letter Z are reserved for locally defined segments. No
+    /// HL7-defined segment ID code will begin with the letter Z.
+    /// ```
+    ///
+    pub fn is_custom_segment_name(name: &str) -> bool {
+        is_valid_segment_name(name) && name.starts_with(V2_CUSTOM_SEGMENT_PREFIX)
+    }
+
+    ///
+    /// True for MSH, FHS, and BHS. The field after the segment id in these segments holds the
+    /// encoding characters and is never split or escaped.
+    ///
+    pub fn is_header_segment_name(name: &str) -> bool {
+        V2_HEADER_SEGMENTS.contains(&name)
+    }
+
+    ///
+    /// Looks up the id of a standard segment. Ids of other segments are assigned per message, so
+    /// look those up with [V2SegmentIds::find] on the message's own table instead.
+    ///
+    pub fn find_segment_id(name: &str) -> Option<u8> {
+        V2_SEGMENT_IDS.get(name).copied()
+    }
+
+    ///
+    /// Segment ids used by one message. Standard segments use the ids in [V2_SEGMENT_IDS].
+    /// Z-segments, and in [V2ParseMode::Lenient] any well-formed segment id, get an id from the
+    /// range starting at [V2_CUSTOM_SEGMENT_ID_START] the first time the message contains them.
+    ///
+    /// Each message owns its table, so the custom ids of one message never run out because of
+    /// segments seen in other messages, and looking a name up never assigns an id.
+    ///
+    #[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+    #[serde(transparent)]
+    pub struct V2SegmentIds {
+        names: Vec<RUMString>,
+    }
+
+    impl V2SegmentIds {
+        pub fn new() -> Self {
+            V2SegmentIds { names: Vec::new() }
+        }
+
+        ///
+        /// Returns the id of a segment without assigning one.
+        ///
+        pub fn find(&self, name: &str) -> Option<u8> {
+            match V2_SEGMENT_IDS.get(name) {
+                Some(id) => Some(*id),
+                None => self
+                    .names
+                    .iter()
+                    .position(|custom| custom == name)
+                    .map(|indx| V2_CUSTOM_SEGMENT_ID_START + indx as u8),
+            }
+        }
+
+        ///
+        /// Returns the id of a segment, assigning the next custom id if the segment is not a
+        /// standard one and the table has not seen it yet. Fails if `mode` does not accept the
+        /// segment or if the message already uses every custom id.
+        ///
+        pub fn get_or_assign(&mut self, name: &str, mode: V2ParseMode) -> V2Result<u8> {
+            if let Some(id) = self.find(name) {
+                return Ok(id);
+            }
+
+            let accepted = match mode {
+                V2ParseMode::Strict => is_custom_segment_name(name),
+                V2ParseMode::Lenient => is_valid_segment_name(name),
+            };
+            if !accepted {
+                return Err(format_compact!(
+                    "Segment name {} is not a valid segment!",
+                    name
+                ));
+            }
+
+            let next_id = V2_CUSTOM_SEGMENT_ID_START as usize + self.names.len();
+            if next_id > u8::MAX as usize {
+                return Err(format_compact!(
+                    "Cannot add segment {}! A message can only have {} distinct custom segments.",
+                    name,
+                    u8::MAX - V2_CUSTOM_SEGMENT_ID_START + 1
+                ));
+            }
+            self.names.push(name.to_rumstring());
+            Ok(next_id as u8)
+        }
+
+        ///
+        /// Resolves the segment of a search index. Segments without a standard id are looked up
+        /// by name. Yields [V2_UNKNOWN_SEGMENT_ID] if the table has not seen the segment.
+        ///
+        pub fn find_indexed(&self, index: &V2SearchIndex) -> u8 {
+            match index.segment {
+                V2_UNKNOWN_SEGMENT_ID => self
+                    .find(&index.segment_name)
+                    .unwrap_or(V2_UNKNOWN_SEGMENT_ID),
+                id => id,
+            }
+        }
+
+        ///
+        /// Names of the custom segments, in the order their ids were assigned.
+        ///
+        pub fn custom_names(&self) -> &[RUMString] {
+            &self.names
+        }
+
+        ///
+        /// Reverse of [V2SegmentIds::find].
+        ///
+        pub fn name(&self, id: &u8) -> V2Result<RUMString> {
+            if let Some(name) = V2_SEGMENT_NAMES.get(id) {
+                return Ok(name.to_rumstring());
+            }
+            match id
+                .checked_sub(V2_CUSTOM_SEGMENT_ID_START)
+                .and_then(|indx| self.names.get(indx as usize))
+            {
+                Some(name) => Ok(name.clone()),
+                None => Err(format_compact!("Segment id {} is not in use!", id)),
+            }
+        }
+    }
+
+    ///
+    /// Object representing the exact indices needed to search for a field, component or
+    /// subcomponent.
+    ///
+    /// Segments missing from [V2_SEGMENT_IDS] have no id until a message assigns them one. Their
+    /// index uses [V2_UNKNOWN_SEGMENT_ID] and the message resolves `segment_name` instead.
     ///
     #[derive(Debug, PartialEq, Eq, Default, Clone)]
     pub struct V2SearchIndex {
         pub segment: u8,
+        pub segment_name: RUMString,
         pub segment_group: u8,
         pub field_group: u8,
         pub field: i16,
         pub component: i16,
+        pub subcomponent: i16,
     }
 
     impl V2SearchIndex {
@@ -181,21 +770,51 @@ pub mod v2_base_types {
             _component: i16,
         ) -> V2SearchIndex {
             V2SearchIndex {
-                segment: *V2_SEGMENT_IDS.get(_segment).unwrap(),
+                segment: find_segment_id(_segment).unwrap_or(V2_UNKNOWN_SEGMENT_ID),
+                segment_name: _segment.to_rumstring(),
                 segment_group: _segment_group,
                 field_group: _sub_field,
                 field: _field,
                 component: _component,
+                subcomponent: 1,
             }
         }
 
+        ///
+        /// Same index, pointing at another subcomponent of the component. [V2SearchIndex::new]
+        /// points at the first one.
+        ///
+        pub fn with_subcomponent(mut self, subcomponent: i16) -> V2SearchIndex {
+            self.subcomponent = subcomponent;
+            self
+        }
+
+        ///
+        /// Compiles a plain search pattern. Query expressions (wildcards, ranges, or `WHERE`
+        /// clauses) can match more than one position so they do not map to a single index. They
+        /// compile to an index with an unknown segment. Use
+        /// [V2Query](crate::hl7_v2_query::v2_query::V2Query) for those instead.
+        ///
         pub fn from(expr: &str) -> V2SearchIndex {
             match Self::expr_type(expr) {
                 V2_SEARCH_EXPR_TYPE::V2_DEFAULT => Self::from_v2_default(expr),
+                V2_SEARCH_EXPR_TYPE::V2_QUERY => V2SearchIndex {
+                    segment: V2_UNKNOWN_SEGMENT_ID,
+                    ..Default::default()
+                },
             }
         }
 
         fn from_v2_default(expr: &str) -> V2SearchIndex {
+            let subcomponent_groups: SearchGroups =
+                string_search_named_captures(expr, REGEX_V2_SEARCH_SUBCOMPONENT, "");
+            let (expr, _subcomponent) = match subcomponent_groups.get("subcomponent") {
+                Some(subcomponent) if !subcomponent.is_empty() => (
+                    subcomponent_groups.get("components").unwrap().as_str(),
+                    subcomponent.parse().unwrap_or(1),
+                ),
+                _ => (expr, 1),
+            };
             let expr_groups: SearchGroups =
                 string_search_named_captures(expr, REGEX_V2_SEARCH_DEFAULT, "1");
             let _segment = expr_groups.get("segment").unwrap();
@@ -208,9 +827,18 @@ pub mod v2_base_types {
             let _sub_field: u8 = expr_groups.get("sub_field").unwrap().parse().unwrap_or(1);
             let _component: i16 = expr_groups.get("component").unwrap().parse().unwrap_or(1);
             V2SearchIndex::new(_segment, _segment_group, _field, _sub_field, _component)
+                .with_subcomponent(_subcomponent)
         }
 
-        fn expr_type(expr: &str) -> V2_SEARCH_EXPR_TYPE {
+        ///
+        /// Tells plain search patterns apart from query expressions. Wildcards, ranges, and
+        /// whitespace (`WHERE` clauses) only appear in the latter.
+        ///
+        pub fn expr_type(expr: &str) -> V2_SEARCH_EXPR_TYPE {
+            let expr = expr.trim();
+            if expr.contains('*') || expr.contains("..") || expr.contains(char::is_whitespace) {
+                return V2_SEARCH_EXPR_TYPE::V2_QUERY;
+            }
             V2_SEARCH_EXPR_TYPE::V2_DEFAULT
         }
     }
@@ -422,6 +1050,7 @@ pub mod v2_base_types {
     ///         + use of the plus sign (+0000) represents the civil time zone offset is known to be zero,
     ///         + use of the minus sign (-0000) represents UTC (without offset)
     /// ```
+    #[derive(Clone)]
     pub struct V2DateTime {
         year: u16,
         month: u8,
@@ -429,8 +1058,74 @@ pub mod v2_base_types {
         hour: u8,
         minute: u8,
         second: u8,
+        /// Fraction of a second in ten thousandths, which is the finest precision of DTM.
         microsecond: u32,
         offset: V2String,
+        offset_minutes: Option<i16>,
+        precision: V2DateTimePrecision,
+    }
+
+    ///
+    /// Precision of a [V2DateTime], given by the number of characters populated.
+    ///
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+    pub enum V2DateTimePrecision {
+        Year,
+        Month,
+        Day,
+        Hour,
+        Minute,
+        Second,
+        TenthSecond,
+        HundredthSecond,
+        ThousandthSecond,
+        TenThousandthSecond,
+    }
+
+    impl V2DateTimePrecision {
+        fn from_digits(date_digits: usize, fraction_digits: usize) -> V2DateTimePrecision {
+            match (date_digits, fraction_digits) {
+                (0..=5, _) => V2DateTimePrecision::Year,
+                (6..=7, _) => V2DateTimePrecision::Month,
+                (8..=9, _) => V2DateTimePrecision::Day,
+                (10..=11, _) => V2DateTimePrecision::Hour,
+                (12..=13, _) => V2DateTimePrecision::Minute,
+                (_, 0) => V2DateTimePrecision::Second,
+                (_, 1) => V2DateTimePrecision::TenthSecond,
+                (_, 2) => V2DateTimePrecision::HundredthSecond,
+                (_, 3) => V2DateTimePrecision::ThousandthSecond,
+                _ => V2DateTimePrecision::TenThousandthSecond,
+            }
+        }
+
+        fn fraction_digits(&self) -> usize {
+            match self {
+                V2DateTimePrecision::TenthSecond => 1,
+                V2DateTimePrecision::HundredthSecond => 2,
+                V2DateTimePrecision::ThousandthSecond => 3,
+                V2DateTimePrecision::TenThousandthSecond => 4,
+                _ => 0,
+            }
+        }
+    }
+
+    ///
+    /// Time zone of a [V2DateTime] as interpreted for a version. See [V2DateTime::time_zone].
+    ///
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum V2TimeZone {
+        ///
+        /// No offset was sent, so the value is in the local time of the sender.
+        ///
+        Local,
+        ///
+        /// Civil time zone offset from UTC in minutes.
+        ///
+        Offset(i16),
+        ///
+        /// UTC without a known civil offset.
+        ///
+        Utc,
     }
 
     impl V2DateTime {
@@ -444,6 +1139,8 @@ pub mod v2_base_types {
                 second: 0,
                 microsecond: 0,
                 offset: V2String::from("0"),
+                offset_minutes: None,
+                precision: V2DateTimePrecision::Second,
             }
         }
         pub fn default() -> V2DateTime {
@@ -467,15 +1164,27 @@ pub mod v2_base_types {
         }
 
         pub fn from_utc_datetime(utc_dt: &DateTime<Utc>) -> V2DateTime {
+            V2DateTime::from_chrono(utc_dt)
+        }
+
+        ///
+        /// Builds a V2DateTime with the local time and offset of `dt`, to a precision of ten
+        /// thousandths of a second.
+        ///
+        pub fn from_chrono<Tz: TimeZone>(dt: &DateTime<Tz>) -> V2DateTime {
+            let offset_minutes = (dt.offset().fix().local_minus_utc() / 60) as i16;
             V2DateTime {
-                year: utc_dt.year() as u16,
-                month: utc_dt.month() as u8,
-                day: utc_dt.day() as u8,
-                hour: utc_dt.hour() as u8,
-                minute: utc_dt.minute() as u8,
-                second: utc_dt.second() as u8,
-                microsecond: utc_dt.nanosecond() / (V2_DATETIME_THOUSAND_TICK as u32),
-                offset: utc_dt.offset().to_compact_string(),
+                year: dt.year() as u16,
+                month: dt.month() as u8,
+                day: dt.day() as u8,
+                hour: dt.hour() as u8,
+                minute: dt.minute() as u8,
+                second: dt.second() as u8,
+                // Leap seconds are carried in the nanoseconds.
+                microsecond: (dt.nanosecond() / 100_000).min(9999),
+                offset: Self::format_offset(offset_minutes, false),
+                offset_minutes: Some(offset_minutes),
+                precision: V2DateTimePrecision::TenThousandthSecond,
             }
         }
 
@@ -487,8 +1196,10 @@ pub mod v2_base_types {
         ///
         /// Return an instance of V2DateTime. This instance may be empty if the input is malformed.
         ///
+        #[allow(clippy::should_implement_trait)]
         pub fn from_str(item: &str) -> V2DateTime {
             let offset = string_search(item, REGEX_DT_TIMEZONE, "");
+            let offset_minutes = Self::parse_offset(&offset);
             let time_part = item.replace(&offset.as_str(), "");
             let dt_vec: Vec<&str> = time_part.split('.').collect();
             let (year, month, day, hour, minute, second) =
@@ -504,6 +1215,8 @@ pub mod v2_base_types {
                     second,
                     microsecond: 0,
                     offset,
+                    offset_minutes,
+                    precision: V2DateTimePrecision::from_digits(dt_vec[0].len(), 0),
                 },
                 2 => {
                     let ms_string = dt_vec.last().unwrap();
@@ -526,12 +1239,38 @@ pub mod v2_base_types {
                         second,
                         microsecond,
                         offset,
+                        offset_minutes,
+                        precision: V2DateTimePrecision::from_digits(dt_vec[0].len(), ms_string_len),
                     }
                 }
                 _ => V2DateTime::new(),
             }
         }
 
+        ///
+        /// Parses a +/-HHMM offset into minutes east of UTC.
+        ///
+        fn parse_offset(offset: &str) -> Option<i16> {
+            if offset.len() != 5 {
+                return None;
+            }
+            let hours = offset[1..3].parse::<i16>().ok()?;
+            let minutes = offset[3..5].parse::<i16>().ok()?;
+            match &offset[0..1] {
+                "-" => Some(-(hours * 60 + minutes)),
+                _ => Some(hours * 60 + minutes),
+            }
+        }
+
+        fn format_offset(offset_minutes: i16, negative_zero: bool) -> V2String {
+            let sign = match offset_minutes < 0 || (offset_minutes == 0 && negative_zero) {
+                true => '-',
+                false => '+',
+            };
+            let minutes = offset_minutes.unsigned_abs();
+            format_compact!("{}{:02}{:02}", sign, minutes / 60, minutes % 60)
+        }
+
         /// Take date time string in the format YYYY\[MMDDHHmmss\] and decompose it into numerical
         /// date time components.
         /// Meaning, we take a string and we return a tuple of numbers.
@@ -578,6 +1317,197 @@ pub mod v2_base_types {
             (year, month, day, hour, minute, second)
         }
 
+        pub fn year(&self) -> u16 {
+            self.year
+        }
+
+        pub fn month(&self) -> u8 {
+            self.month
+        }
+
+        pub fn day(&self) -> u8 {
+            self.day
+        }
+
+        pub fn hour(&self) -> u8 {
+            self.hour
+        }
+
+        pub fn minute(&self) -> u8 {
+            self.minute
+        }
+
+        pub fn second(&self) -> u8 {
+            self.second
+        }
+
+        pub fn precision(&self) -> V2DateTimePrecision {
+            self.precision
+        }
+
+        ///
+        /// Offset from UTC in minutes, if one was sent.
+        ///
+        pub fn offset_minutes(&self) -> Option<i16> {
+            self.offset_minutes
+        }
+
+        ///
+        /// Interprets the offset per `version`. Before v2.9, `+0000` and `-0000` both mean UTC.
+        /// As of v2.9, `+0000` means the civil offset is known to be zero and only `-0000` means
+        /// UTC. See [V2Version::distinguishes_zero_offset].
+        ///
+        pub fn time_zone(&self, version: V2Version) -> V2TimeZone {
+            match self.offset_minutes {
+                None => V2TimeZone::Local,
+                Some(0) if !version.distinguishes_zero_offset() || self.offset.starts_with('-') => {
+                    V2TimeZone::Utc
+                }
+                Some(offset_minutes) => V2TimeZone::Offset(offset_minutes),
+            }
+        }
+
+        ///
+        /// Formats the value as DTM with the precision and offset it was parsed with, so
+        /// `201301` is generated as `201301`.
+        ///
+        pub fn to_v2(&self) -> V2String {
+            let mut v2 = format_compact!("{:04}", self.year);
+            let parts = [
+                (V2DateTimePrecision::Month, self.month),
+                (V2DateTimePrecision::Day, self.day),
+                (V2DateTimePrecision::Hour, self.hour),
+                (V2DateTimePrecision::Minute, self.minute),
+                (V2DateTimePrecision::Second, self.second),
+            ];
+            for (precision, value) in parts {
+                if self.precision >= precision {
+                    v2.push_str(&format_compact!("{:02}", value));
+                }
+            }
+            let fraction_digits = self.precision.fraction_digits();
+            if fraction_digits > 0 {
+                let fraction = format_compact!("{:04}", self.microsecond);
+                v2.push('.');
+                v2.push_str(&fraction[..fraction_digits]);
+            }
+            if let Some(offset_minutes) = self.offset_minutes {
+                v2.push_str(&Self::format_offset(
+                    offset_minutes,
+                    self.offset.starts_with('-'),
+                ));
+            }
+            v2
+        }
+
+        ///
+        /// Date and time without the offset. Fails if the components do not make a valid date or
+        /// time, like a 13th month.
+        ///
+        pub fn to_naive(&self) -> V2Result<NaiveDateTime> {
+            NaiveDate::from_ymd_opt(self.year as i32, self.month as u32, self.day as u32)
+                .and_then(|date| {
+                    date.and_hms_micro_opt(
+                        self.hour as u32,
+                        self.minute as u32,
+                        self.second as u32,
+                        self.microsecond * 100,
+                    )
+                })
+                .ok_or_else(|| format_compact!("Invalid date/time {}!", self.to_v2()))
+        }
+
+        ///
+        /// Converts to a chrono date time in the offset of the value. Values without an offset
+        /// are in the local time of the sender, which is given as `default_offset` (e.g. the
+        /// offset of MSH-7).
+        ///
+        pub fn to_chrono(&self, default_offset: FixedOffset) -> V2Result<DateTime<FixedOffset>> {
+            let offset = match self.offset_minutes {
+                Some(offset_minutes) => match FixedOffset::east_opt(offset_minutes as i32 * 60) {
+                    Some(offset) => offset,
+                    None => return Err(format_compact!("Invalid offset {}!", &self.offset)),
+                },
+                None => default_offset,
+            };
+            match self.to_naive()?.and_local_timezone(offset).single() {
+                Some(dt) => Ok(dt),
+                None => Err(format_compact!("Invalid date/time {}!", self.to_v2())),
+            }
+        }
+
+        ///
+        /// Ten thousandths of a second since the Unix epoch, taking the offset into account.
+        /// Values without an offset are taken as UTC.
+        ///
+        fn ticks(&self) -> Option<i64> {
+            let naive = self.to_naive().ok()?;
+            let offset_ticks = self.offset_minutes.unwrap_or(0) as i64 * 60 * 10_000;
+            Some(naive.and_utc().timestamp_micros() / 100 - offset_ticks)
+        }
+
+        ///
+        /// Time elapsed from `earlier` to this value. Values without an offset are taken as UTC.
+        ///
+        pub fn duration_since(&self, earlier: &V2DateTime) -> V2Result<Duration> {
+            match (self.ticks(), earlier.ticks()) {
+                (Some(ticks), Some(earlier_ticks)) => {
+                    Ok(Duration::microseconds((ticks - earlier_ticks) * 100))
+                }
+                _ => Err(format_compact!(
+                    "Cannot compute the time between {} and {}!",
+                    self.to_v2(),
+                    earlier.to_v2()
+                )),
+            }
+        }
+
+        ///
+        /// Adds `duration` to the value. The offset and precision are kept.
+        ///
+        pub fn checked_add(&self, duration: Duration) -> V2Result<V2DateTime> {
+            let naive = match self.to_naive()?.checked_add_signed(duration) {
+                Some(naive) => naive,
+                None => {
+                    return Err(format_compact!(
+                        "{} + {} overflows!",
+                        self.to_v2(),
+                        duration
+                    ))
+                }
+            };
+            Ok(V2DateTime {
+                year: naive.year() as u16,
+                month: naive.month() as u8,
+                day: naive.day() as u8,
+                hour: naive.hour() as u8,
+                minute: naive.minute() as u8,
+                second: naive.second() as u8,
+                microsecond: naive.nanosecond() / 100_000,
+                offset: self.offset.clone(),
+                offset_minutes: self.offset_minutes,
+                precision: self.precision,
+            })
+        }
+
+        ///
+        /// Age in whole years at `reference`, counting calendar dates and ignoring offsets. Birth
+        /// dates sent with a precision coarser than a day are taken as the first day of the
+        /// period, so the age may be one year more than the actual age.
+        ///
+        pub fn age_at(&self, reference: &V2DateTime) -> V2Result<u32> {
+            let birth = self.to_naive()?.date();
+            let reference_date = reference.to_naive()?.date();
+            match reference_date.years_since(birth) {
+                Some(years) => Ok(years),
+                None => Err(format_compact!(
+                    "Reference date {} is before the birth date {}!",
+                    reference.to_v2(),
+                    self.to_v2()
+                )),
+            }
+        }
+
         pub fn as_utc_string(&self) -> V2String {
             format_compact!(
                 "{year:0<4}-{month:0>2}-{day:0>2}T{hour:0>2}:{minute:0>2}:{second:0>2}.{microsecond:0<4}{offset}",
@@ -592,8 +1522,12 @@ pub mod v2_base_types {
             )
         }
 
-        pub fn as_utc_datetime(&self) -> DateTime<Utc> {
-            self.as_utc_string().parse().unwrap()
+        ///
+        /// Instant of the value in UTC. Values without an offset are taken as UTC. Fails for
+        /// values that are not a valid date or time, see [V2DateTime::to_chrono].
+        ///
+        pub fn as_utc_datetime(&self) -> V2Result<DateTime<Utc>> {
+            Ok(self.to_chrono(Utc.fix())?.with_timezone(&Utc))
         }
         pub fn as_v2_date(&self) -> V2String {
             format_compact!("{:04}{:02}{:02}", &self.year, &self.month, &self.day)
@@ -610,6 +1544,64 @@ pub mod v2_base_types {
                 &self.microsecond
             )
         }
+
+        fn sort_key(&self) -> (Option<i64>, V2DateTimePrecision, Option<i16>, [u32; 7]) {
+            (
+                self.ticks(),
+                self.precision,
+                self.offset_minutes,
+                [
+                    self.year as u32,
+                    self.month as u32,
+                    self.day as u32,
+                    self.hour as u32,
+                    self.minute as u32,
+                    self.second as u32,
+                    self.microsecond,
+                ],
+            )
+        }
+    }
+
+    ///
+    /// Values are ordered by the instant they stand for. Values for the same instant are ordered
+    /// by precision, then by offset, so `200701` and `20070101` are not equal.
+    ///
+    impl Ord for V2DateTime {
+        fn cmp(&self, other: &Self) -> Ordering {
+            self.sort_key().cmp(&other.sort_key())
+        }
+    }
+
+    impl PartialOrd for V2DateTime {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl PartialEq for V2DateTime {
+        fn eq(&self, other: &Self) -> bool {
+            self.cmp(other) == Ordering::Equal
+        }
+    }
+
+    impl Eq for V2DateTime {}
+
+    ///
+    /// Serialized as the DTM string given by [V2DateTime::to_v2].
+    ///
+    impl Serialize for V2DateTime {
+        fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+            s.serialize_str(&self.to_v2())
+        }
+    }
+
+    impl<'de> Deserialize<'de> for V2DateTime {
+        fn deserialize<D: Deserializer<'de>>(d: D) -> Result<V2DateTime, D::Error> {
+            RUMString::deserialize(d)?
+                .to_v2datetime()
+                .map_err(D::Error::custom)
+        }
     }
 
     impl Debug for V2DateTime {
@@ -623,6 +1615,7 @@ pub mod v2_base_types {
                 .field("second", &self.second)
                 .field("microsecond", &self.microsecond)
                 .field("offset", &self.offset)
+                .field("precision", &self.precision)
                 .finish()
         }
     }
@@ -793,8 +1786,7 @@ pub mod v2_primitives {
     pub use crate::hl7_v2_base_types::v2_base_types::*;
     use rumtk_core::search::rumtk_search::string_search;
     use rumtk_core::strings::{
-        format_compact, AsStr, CompactString, RUMString, ToCompactString, UTFStringExtensions,
-        DOT_STR,
+        format_compact, AsStr, CompactString, RUMString, UTFStringExtensions, DOT_STR,
     };
 
     /**************************** Constants**************************************/
@@ -809,6 +1801,7 @@ pub mod v2_primitives {
 
     // Regex
     const REGEX_VALIDATE_NM: &str = r"\+|\-|\d+\.\d+e\d+|\d+e\d+|\d+\.\d+|\d+";
+    const REGEX_VALIDATE_NM_STRICT: &str = r"^(\+|\-)?\d+(\.\d*)?$";
     const REGEX_VALIDATE_SI: &str = r"^\d{1,4}";
     const REGEX_VALIDATE_SNM: &str = r"\+|\d+";
     const REGEX_VALIDATE_DATETIME: &str = r"^\d{4,14}\.\d{1,4}(\+|\-)\d{4}|^\d{4,14}(\+|\-)\d{4}|^\d{2,6}(\+|\-)\d{4}|^\d{4,14}\.\d{1,4}|^\d{2,6}\.\d{1,4}|^\d{4,14}|^\d{2,6}";
@@ -823,6 +1816,14 @@ pub mod v2_primitives {
         }
     }
 
+    ///
+    /// Rejects values whose components do not make a valid date or time, like `20070231`.
+    ///
+    fn checked_datetime(dt: V2DateTime) -> V2Result<V2DateTime> {
+        dt.to_naive()?;
+        Ok(dt)
+    }
+
     pub trait V2PrimitiveCasting: AsStr {
         #[inline(always)]
         fn to_v2datetime(&self) -> V2Result<V2DateTime> {
@@ -834,7 +1835,7 @@ pub mod v2_primitives {
             )?;
             match input.len() {
                 0..=3 => Err(format_compact!("Cannot build V2DateTime type due to the string input being smaller than 4 characters. => [{}] ", input)),
-                _ => Ok(V2DateTime::from_str(&validated)),
+                _ => checked_datetime(V2DateTime::from_str(&validated)),
             }
         }
 
@@ -848,7 +1849,7 @@ pub mod v2_primitives {
             )?;
             match input.len() {
                 0..=3 => Err(format_compact!("Cannot build V2DateTime type due to the string input being smaller than 4 characters. => [{}] ", input)),
-                _ => Ok(V2Date::from_str(&validated)),
+                _ => checked_datetime(V2Date::from_str(&validated)),
             }
         }
 
@@ -862,7 +1863,9 @@ pub mod v2_primitives {
             )?;
             match input.len() {
                 0..=1 => Err(format_compact!("Cannot build V2DateTime type due to the string input being smaller than 2 characters. => [{}] ", input)),
-                _ => Ok(V2Date::from_str(format_compact!("19700101{}", &validated).as_str())),
+                _ => checked_datetime(V2Date::from_str(
+                    format_compact!("19700101{}", &validated).as_str(),
+                )),
             }
         }
 
@@ -879,13 +1882,74 @@ pub mod v2_primitives {
                 validate_type(&truncated_input.trim().to_lowercase(), REGEX_VALIDATE_NM)?;
             match validated.parse::<V2NM>() {
                 Ok(val) => Ok(val),
-                Err(why) => Err(format_compact!(
+                Err(_) => Err(format_compact!(
+                    "Error parsing string into numeric type V2NM. Input: {}",
+                    validated
+                )),
+            }
+        }
+
+        ///
+        /// Casts to NM without skipping characters outside the format. Per Section 2A.3.47, an NM
+        /// is an optional leading sign, digits, and an optional decimal point, so `+5` and `5.`
+        /// are accepted while `1,250` and `5e3` are errors.
+        ///
+        #[inline(always)]
+        fn to_v2decimal(&self) -> V2Result<V2NM> {
+            let input: &str = self.as_str();
+            let validated = validate_type(input.trim(), REGEX_VALIDATE_NM_STRICT)?;
+            match validated.parse::<V2NM>() {
+                Ok(val) => Ok(val),
+                Err(_) => Err(format_compact!(
                     "Error parsing string into numeric type V2NM. Input: {}",
                     validated
                 )),
             }
         }
 
+        ///
+        /// Casts an NM holding a whole number, e.g. `+12` or `12.00`, to an integer.
+        ///
+        #[inline(always)]
+        fn to_v2integer(&self) -> V2Result<i64> {
+            let input: &str = self.as_str();
+            let validated = validate_type(input.trim(), REGEX_VALIDATE_NM_STRICT)?;
+            let (whole, fraction) = match validated.split_once('.') {
+                Some((whole, fraction)) => (whole, fraction),
+                None => (validated.as_str(), ""),
+            };
+            if fraction.chars().any(|c| c != '0') {
+                return Err(format_compact!(
+                    "Error parsing string into an integer. {} has a fractional part!",
+                    validated
+                ));
+            }
+            match whole.parse::<i64>() {
+                Ok(val) => Ok(val),
+                Err(_) => Err(format_compact!(
+                    "Error parsing string into an integer. {} is out of range!",
+                    validated
+                )),
+            }
+        }
+
+        ///
+        /// Casts a Yes/No indicator (HL7 Table 0136) to a boolean. `true` and `false` are
+        /// accepted too.
+        ///
+        #[inline(always)]
+        fn to_v2boolean(&self) -> V2Result<bool> {
+            let input: &str = self.as_str();
+            match input.trim() {
+                "Y" | "y" | "true" => Ok(true),
+                "N" | "n" | "false" => Ok(false),
+                _ => Err(format_compact!(
+                    "Error parsing string into a Yes/No indicator. Expected Y or N but got [{}]!",
+                    input
+                )),
+            }
+        }
+
         #[inline(always)]
         fn to_v2sequenceid(&self) -> V2Result<V2SI> {
             let input: &str = self.as_str();
@@ -895,7 +1959,7 @@ pub mod v2_primitives {
                 validate_type(&truncated_input.trim().to_lowercase(), REGEX_VALIDATE_SI)?;
             match validated.parse::<V2SI>() {
                 Ok(val) => Ok(val),
-                Err(why) => Err(format_compact!(
+                Err(_) => Err(format_compact!(
                     "Error parsing string into Sequence ID type V2SI. Input: {}",
                     validated
                 )),
@@ -962,7 +2026,95 @@ pub mod v2_primitives {
     impl V2PrimitiveCasting for str {}
     impl V2PrimitiveCasting for V2String {}
 
-    #[derive(Debug)]
+    const V2_SN_COMPARATORS: [&str; 6] = [">", "<", ">=", "<=", "=", "<>"];
+    const V2_SN_SEPARATORS: [&str; 5] = ["-", "+", "/", ".", ":"];
+
+    ///
+    /// # 2A.3.69 SN - structured numeric
+    ///
+    /// Casted SN value. See [V2ComplexType::SN](crate::hl7_v2_field_descriptors::v2_field_descriptor::V2ComplexType::SN).
+    ///
+    /// ## Examples:
+    /// ```text
+    ///     |>^100|         greater than 100
+    ///     |^100^-^200|    equal to range of 100 through 200
+    ///     |^1^:^128|      ratio of 1 to 128, e.g., the results of a serological test
+    /// ```
+    ///
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct V2StructuredNumeric {
+        pub comparator: Option<V2String>,
+        pub num1: Option<V2NM>,
+        pub separator: Option<V2String>,
+        pub num2: Option<V2NM>,
+    }
+
+    impl V2StructuredNumeric {
+        ///
+        /// Builds the value from its components in order (comparator, num1, separator/suffix,
+        /// num2). Missing trailing components are treated as not valued.
+        ///
+        pub fn from_parts(parts: &[&str]) -> V2Result<V2StructuredNumeric> {
+            let part = |indx: usize| parts.get(indx).map_or("", |p| p.trim());
+            let comparator = match part(0) {
+                "" => None,
+                c if V2_SN_COMPARATORS.contains(&c) => Some(V2String::from(c)),
+                c => return Err(format_compact!("Invalid SN comparator [{}]!", c)),
+            };
+            let separator = match part(2) {
+                "" => None,
+                s if V2_SN_SEPARATORS.contains(&s) => Some(V2String::from(s)),
+                s => return Err(format_compact!("Invalid SN separator/suffix [{}]!", s)),
+            };
+            let num1 = match part(1) {
+                "" => None,
+                n => Some(n.to_v2decimal()?),
+            };
+            let num2 = match part(3) {
+                "" => None,
+                n => Some(n.to_v2decimal()?),
+            };
+            if num1.is_none() && num2.is_none() {
+                return Err(format_compact!("SN value {:?} has no number!", parts));
+            }
+            Ok(V2StructuredNumeric {
+                comparator,
+                num1,
+                separator,
+                num2,
+            })
+        }
+    }
+
+    ///
+    /// # 2A.3.11 CQ - composite quantity with units
+    ///
+    /// Casted CQ value. The units keep the identifier of the CWE only, e.g. `kg`.
+    ///
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct V2Quantity {
+        pub quantity: V2NM,
+        pub units: Option<V2String>,
+    }
+
+    impl V2Quantity {
+        ///
+        /// Builds the value from the quantity and the units identifier.
+        ///
+        pub fn from_parts(parts: &[&str]) -> V2Result<V2Quantity> {
+            let quantity = match parts.first().map_or("", |p| p.trim()) {
+                "" => return Err(format_compact!("CQ value {:?} has no quantity!", parts)),
+                q => q.to_v2decimal()?,
+            };
+            let units = match parts.get(1).map_or("", |p| p.trim()) {
+                "" => None,
+                u => Some(V2String::from(u)),
+            };
+            Ok(V2Quantity { quantity, units })
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
     pub enum V2PrimitiveType {
         String,
         DateTime,
@@ -0,0 +1,710 @@
+/*
+ * rumtk attempts to implement HL7 and medical protocols for interoperability in medicine.
+ * This toolkit aims to be reliable, simple, performant, and standards compliant.
+ * Copyright (C) 2024  Luis M. Santos, M.D.
+ *
+ * This library is free software; you can redistribute it and/or
+ * modify it under the terms of the GNU Lesser General Public
+ * License as published by the Free Software Foundation; either
+ * version 2.1 of the License, or (at your option) any later version.
+ *
+ * This library is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+ * Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public
+ * License along with this library; if not, write to the Free Software
+ * Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301  USA
+ */
+
+///
+/// HL7 batch protocol. Groups many messages into batches and batches into files.
+///
+/// ## Per Section 2.10.3
+///
+/// ```text
+/// The structure of an HL7 file is as follows:
+///
+///     [FHS]                 (file header segment)
+///     { [BHS]               (batch header segment)
+///       { [MSH              (zero or more HL7 messages)
+///         ....
+///         ....
+///         ....
+///       ] }
+///       [BTS]               (batch trailer segment)
+///     }
+///     [FTS]                 (file trailer segment)
+/// ```
+///
+pub mod v2_batch {
+    use crate::hl7_v2_base_types::v2_base_types::{
+        V2ParseMode, V2ParserCharacters, V2Result, V2String,
+    };
+    use crate::hl7_v2_constants::{
+        V2_BATCH_HEADER_PATTERN, V2_BATCH_TRAILER_PATTERN, V2_FILE_HEADER_PATTERN,
+        V2_FILE_TRAILER_PATTERN, V2_MSHEADER_PATTERN, V2_SEGMENT_IDS,
+    };
+    use crate::hl7_v2_parser::v2_parser::{V2Message, V2Segment};
+    use rumtk_core::strings::{
+        format_compact, AsStr, CompactStringExt, RUMString, RUMStringConversions,
+    };
+
+    /**************************** Constants**************************************/
+
+    const V2_DEFAULT_ENCODING_CHARACTERS: &str = "^~\\&";
+
+    /**************************** Types *****************************************/
+
+    ///
+    /// Problems found while reading a batch or file. None of them stop parsing. Positions are
+    /// 1-indexed.
+    ///
+    #[derive(Debug, PartialEq, Clone)]
+    pub enum V2BatchError {
+        ///
+        /// The `message`th message of the `batch`th batch could not be parsed.
+        ///
+        Message {
+            batch: usize,
+            message: usize,
+            error: RUMString,
+        },
+        ///
+        /// BTS-1 or FTS-1 does not match the number of messages or batches found.
+        ///
+        Count {
+            segment: RUMString,
+            declared: usize,
+            found: usize,
+        },
+        ///
+        /// A segment does not fit the batch protocol, e.g. a segment outside of any message or a
+        /// second file header. `segment` is the position of the offending segment in the input.
+        ///
+        Structure { segment: usize, error: RUMString },
+    }
+
+    ///
+    /// A batch of messages with its optional BHS header and BTS trailer.
+    ///
+    /// ## Per Section 2.10.3.1
+    ///
+    /// ```text
+    /// The batch trailer segment BTS-1 Batch Message Count contains the count of the individual
+    /// messages contained within the batch.
+    /// ```
+    ///
+    #[derive(Debug, PartialEq)]
+    pub struct V2Batch {
+        separators: V2ParserCharacters,
+        header: Option<V2Segment>,
+        messages: Vec<V2Message>,
+        trailer: Option<V2Segment>,
+        message_count: usize,
+        errors: Vec<V2BatchError>,
+    }
+
+    impl V2Batch {
+        ///
+        /// Creates a batch with a BHS header and a BTS trailer counting the messages. The header
+        /// uses the separators of the first message.
+        ///
+        pub fn new(messages: Vec<V2Message>) -> V2Result<Self> {
+            let (separators, encoding_characters) = match messages.first() {
+                Some(message) => (
+                    message.separators().clone(),
+                    get_message_encoding_characters(message)?,
+                ),
+                None => (
+                    V2ParserCharacters::new(),
+                    V2_DEFAULT_ENCODING_CHARACTERS.to_rumstring(),
+                ),
+            };
+            let header = build_header(V2_BATCH_HEADER_PATTERN, &encoding_characters, &separators)?;
+            let trailer = build_trailer(V2_BATCH_TRAILER_PATTERN, messages.len(), &separators)?;
+            Ok(V2Batch {
+                separators,
+                header: Some(header),
+                message_count: messages.len(),
+                messages,
+                trailer: Some(trailer),
+                errors: Vec::new(),
+            })
+        }
+
+        pub fn try_from_str(raw_batch: &str) -> V2Result<Self> {
+            Self::try_from_str_with_mode(raw_batch, V2ParseMode::Strict)
+        }
+
+        ///
+        /// Parses a single batch. Fails if the input is a file (has FHS or FTS) or has more than
+        /// one batch. Use [V2File] for those.
+        ///
+        pub fn try_from_str_with_mode(raw_batch: &str, mode: V2ParseMode) -> V2Result<Self> {
+            let mut file = V2File::try_from_str_with_mode(raw_batch, mode)?;
+            if file.header.is_some() || file.trailer.is_some() {
+                return Err(format_compact!(
+                    "Input is a batch file! Use V2File to parse it."
+                ));
+            }
+            if file.batches.len() != 1 {
+                return Err(format_compact!(
+                    "Expected a single batch but found {}!",
+                    file.batches.len()
+                ));
+            }
+            let mut batch = file.batches.remove(0);
+            batch.errors.append(&mut file.errors);
+            Ok(batch)
+        }
+
+        fn from_header(header: Option<V2Segment>, separators: V2ParserCharacters) -> Self {
+            V2Batch {
+                separators,
+                header,
+                messages: Vec::new(),
+                trailer: None,
+                message_count: 0,
+                errors: Vec::new(),
+            }
+        }
+
+        pub fn header(&self) -> Option<&V2Segment> {
+            self.header.as_ref()
+        }
+
+        pub fn header_mut(&mut self) -> Option<&mut V2Segment> {
+            self.header.as_mut()
+        }
+
+        pub fn trailer(&self) -> Option<&V2Segment> {
+            self.trailer.as_ref()
+        }
+
+        pub fn trailer_mut(&mut self) -> Option<&mut V2Segment> {
+            self.trailer.as_mut()
+        }
+
+        pub fn separators(&self) -> &V2ParserCharacters {
+            &self.separators
+        }
+
+        ///
+        /// Messages that were parsed successfully, in input order.
+        ///
+        pub fn messages(&self) -> &Vec<V2Message> {
+            &self.messages
+        }
+
+        pub fn len(&self) -> usize {
+            self.messages.len()
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.messages.is_empty()
+        }
+
+        pub fn errors(&self) -> &Vec<V2BatchError> {
+            &self.errors
+        }
+
+        pub fn is_valid(&self) -> bool {
+            self.errors.is_empty()
+        }
+
+        ///
+        /// Adds a message and updates BTS-1 if the batch has a trailer.
+        ///
+        pub fn push(&mut self, message: V2Message) -> V2Result<()> {
+            self.messages.push(message);
+            self.message_count += 1;
+            if let Some(trailer) = self.trailer.as_mut() {
+                set_count(trailer, self.message_count, &self.separators)?;
+            }
+            Ok(())
+        }
+
+        ///
+        /// BTS-1 if present and valued.
+        ///
+        pub fn declared_count(&self) -> V2Result<Option<usize>> {
+            match &self.trailer {
+                Some(trailer) => get_count(trailer),
+                None => Ok(None),
+            }
+        }
+
+        pub fn to_string(&self) -> V2String {
+            let mut batch = Vec::with_capacity(self.messages.len() + 2);
+            if let Some(header) = &self.header {
+                batch.push(header.to_string(&self.separators));
+            }
+            for message in self.messages.iter() {
+                batch.push(message.to_string());
+            }
+            if let Some(trailer) = &self.trailer {
+                batch.push(trailer.to_string(&self.separators));
+            }
+            batch.join_compact(self.separators.segment_terminator.as_str())
+        }
+
+        ///
+        /// Generates the batch with a different set of encoding characters and segment
+        /// terminator. See [V2Message::to_string_with].
+        ///
+        pub fn to_string_with(&self, separators: &V2ParserCharacters) -> V2Result<V2String> {
+            separators.validate()?;
+            let mut batch = Vec::with_capacity(self.messages.len() + 2);
+            if let Some(header) = &self.header {
+                batch.push(header.to_string_with(&self.separators, separators));
+            }
+            for message in self.messages.iter() {
+                batch.push(message.to_string_with(separators)?);
+            }
+            if let Some(trailer) = &self.trailer {
+                batch.push(trailer.to_string_with(&self.separators, separators));
+            }
+            Ok(batch.join_compact(separators.segment_terminator.as_str()))
+        }
+
+        fn validate_count(&mut self, segment: usize) {
+            match self.declared_count() {
+                Ok(Some(declared)) if declared != self.message_count => {
+                    self.errors.push(V2BatchError::Count {
+                        segment: V2_BATCH_TRAILER_PATTERN.to_rumstring(),
+                        declared,
+                        found: self.message_count,
+                    })
+                }
+                Err(error) => self.errors.push(V2BatchError::Structure { segment, error }),
+                _ => {}
+            }
+        }
+    }
+
+    ///
+    /// A batch file with its optional FHS header and FTS trailer.
+    ///
+    /// ## Per Section 2.10.3.2
+    ///
+    /// ```text
+    /// The file trailer segment FTS-1 File Batch Count contains the number of batches contained in
+    /// this file.
+    /// ```
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use rumtk_hl7_v2::hl7_v2_batch::v2_batch::V2File;
+    /// let raw = "FHS|^~\\&|APP\rBHS|^~\\&|APP\rMSH|^~\\&|APP|FAC|||20240102||ADT^A01^ADT_A01|1|P|2.5.1\rEVN|A01\rBTS|1\rFTS|1";
+    /// let file = V2File::try_from_str(raw).unwrap();
+    /// assert!(file.is_valid());
+    /// assert_eq!(file.messages().count(), 1);
+    /// ```
+    ///
+    #[derive(Debug, PartialEq)]
+    pub struct V2File {
+        separators: V2ParserCharacters,
+        header: Option<V2Segment>,
+        batches: Vec<V2Batch>,
+        trailer: Option<V2Segment>,
+        errors: Vec<V2BatchError>,
+    }
+
+    impl V2File {
+        ///
+        /// Creates a file with an FHS header and an FTS trailer counting the batches. The header
+        /// uses the separators of the first batch.
+        ///
+        pub fn new(batches: Vec<V2Batch>) -> V2Result<Self> {
+            let (separators, encoding_characters) = match batches.first() {
+                Some(batch) => (
+                    batch.separators.clone(),
+                    get_batch_encoding_characters(batch)?,
+                ),
+                None => (
+                    V2ParserCharacters::new(),
+                    V2_DEFAULT_ENCODING_CHARACTERS.to_rumstring(),
+                ),
+            };
+            let header = build_header(V2_FILE_HEADER_PATTERN, &encoding_characters, &separators)?;
+            let trailer = build_trailer(V2_FILE_TRAILER_PATTERN, batches.len(), &separators)?;
+            Ok(V2File {
+                separators,
+                header: Some(header),
+                batches,
+                trailer: Some(trailer),
+                errors: Vec::new(),
+            })
+        }
+
+        pub fn try_from_str(raw_file: &str) -> V2Result<Self> {
+            Self::try_from_str_with_mode(raw_file, V2ParseMode::Strict)
+        }
+
+        ///
+        /// Parses a batch file. Messages that fail to parse, count mismatches, and misplaced
+        /// segments are recorded in [V2File::errors] instead of failing the whole file. We only
+        /// fail when the input has no header segment to take the separators from or a header or
+        /// trailer segment cannot be parsed.
+        ///
+        pub fn try_from_str_with_mode(raw_file: &str, mode: V2ParseMode) -> V2Result<Self> {
+            let clean_file = V2Message::sanitize(raw_file);
+            let tokens: Vec<&str> = V2Message::tokenize_segments(clean_file.as_str())
+                .into_iter()
+                .filter(|token| !token.is_empty())
+                .collect();
+            let separators = match tokens.first() {
+                Some(token) => V2ParserCharacters::from_header(token)?,
+                None => return Err(format_compact!("Batch file is empty!")),
+            };
+            let mut parser = V2BatchParser {
+                mode,
+                file: V2File {
+                    separators,
+                    header: None,
+                    batches: Vec::new(),
+                    trailer: None,
+                    errors: Vec::new(),
+                },
+                batch: None,
+                message: Vec::new(),
+            };
+            for (indx, token) in tokens.iter().enumerate() {
+                parser.parse_segment(token, indx + 1)?;
+            }
+            parser.finish(tokens.len())
+        }
+
+        pub fn header(&self) -> Option<&V2Segment> {
+            self.header.as_ref()
+        }
+
+        pub fn header_mut(&mut self) -> Option<&mut V2Segment> {
+            self.header.as_mut()
+        }
+
+        pub fn trailer(&self) -> Option<&V2Segment> {
+            self.trailer.as_ref()
+        }
+
+        pub fn trailer_mut(&mut self) -> Option<&mut V2Segment> {
+            self.trailer.as_mut()
+        }
+
+        pub fn separators(&self) -> &V2ParserCharacters {
+            &self.separators
+        }
+
+        pub fn batches(&self) -> &Vec<V2Batch> {
+            &self.batches
+        }
+
+        ///
+        /// Every message in the file, in input order.
+        ///
+        pub fn messages(&self) -> impl Iterator<Item = &V2Message> {
+            self.batches.iter().flat_map(|batch| batch.messages.iter())
+        }
+
+        pub fn len(&self) -> usize {
+            self.batches.len()
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.batches.is_empty()
+        }
+
+        ///
+        /// Errors of the file and of all of its batches.
+        ///
+        pub fn errors(&self) -> Vec<&V2BatchError> {
+            let mut errors: Vec<&V2BatchError> = self.errors.iter().collect();
+            for batch in self.batches.iter() {
+                errors.extend(batch.errors.iter());
+            }
+            errors
+        }
+
+        pub fn is_valid(&self) -> bool {
+            self.errors.is_empty() && self.batches.iter().all(|batch| batch.is_valid())
+        }
+
+        ///
+        /// Adds a batch and updates FTS-1 if the file has a trailer.
+        ///
+        pub fn push(&mut self, batch: V2Batch) -> V2Result<()> {
+            self.batches.push(batch);
+            if let Some(trailer) = self.trailer.as_mut() {
+                set_count(trailer, self.batches.len(), &self.separators)?;
+            }
+            Ok(())
+        }
+
+        ///
+        /// FTS-1 if present and valued.
+        ///
+        pub fn declared_count(&self) -> V2Result<Option<usize>> {
+            match &self.trailer {
+                Some(trailer) => get_count(trailer),
+                None => Ok(None),
+            }
+        }
+
+        pub fn to_string(&self) -> V2String {
+            let mut file = Vec::with_capacity(self.batches.len() + 2);
+            if let Some(header) = &self.header {
+                file.push(header.to_string(&self.separators));
+            }
+            for batch in self.batches.iter() {
+                file.push(batch.to_string());
+            }
+            if let Some(trailer) = &self.trailer {
+                file.push(trailer.to_string(&self.separators));
+            }
+            file.join_compact(self.separators.segment_terminator.as_str())
+        }
+
+        ///
+        /// Generates the file with a different set of encoding characters and segment
+        /// terminator, e.g. `\r\n` for tools that expect one segment per line. See
+        /// [V2Message::to_string_with].
+        ///
+        pub fn to_string_with(&self, separators: &V2ParserCharacters) -> V2Result<V2String> {
+            separators.validate()?;
+            let mut file = Vec::with_capacity(self.batches.len() + 2);
+            if let Some(header) = &self.header {
+                file.push(header.to_string_with(&self.separators, separators));
+            }
+            for batch in self.batches.iter() {
+                file.push(batch.to_string_with(separators)?);
+            }
+            if let Some(trailer) = &self.trailer {
+                file.push(trailer.to_string_with(&self.separators, separators));
+            }
+            Ok(file.join_compact(separators.segment_terminator.as_str()))
+        }
+    }
+
+    ///
+    /// State kept while walking the segments of a batch file.
+    ///
+    struct V2BatchParser<'a> {
+        mode: V2ParseMode,
+        file: V2File,
+        batch: Option<V2Batch>,
+        message: Vec<&'a str>,
+    }
+
+    impl<'a> V2BatchParser<'a> {
+        fn parse_segment(&mut self, token: &'a str, position: usize) -> V2Result<()> {
+            let name = token.get(0..3).unwrap_or(token).to_ascii_uppercase();
+            if self.file.trailer.is_some() {
+                self.structure_error(
+                    position,
+                    format_compact!("Segment {} found after the file trailer!", name),
+                );
+                return Ok(());
+            }
+            match name.as_str() {
+                V2_FILE_HEADER_PATTERN => {
+                    if position != 1 {
+                        self.structure_error(
+                            position,
+                            format_compact!("File header must be the first segment in the file!"),
+                        );
+                        return Ok(());
+                    }
+                    self.file.header = Some(V2Segment::from_str(token, &self.file.separators)?);
+                }
+                V2_BATCH_HEADER_PATTERN => {
+                    self.flush_message();
+                    self.close_batch(position);
+                    let separators = V2ParserCharacters::from_header(token)?;
+                    let header = V2Segment::from_str(token, &separators)?;
+                    self.batch = Some(V2Batch::from_header(Some(header), separators));
+                }
+                V2_MSHEADER_PATTERN => {
+                    self.flush_message();
+                    self.message.push(token);
+                }
+                V2_BATCH_TRAILER_PATTERN => {
+                    self.flush_message();
+                    let batch = self.current_batch();
+                    batch.trailer = Some(V2Segment::from_str(token, &batch.separators)?);
+                    self.close_batch(position);
+                }
+                V2_FILE_TRAILER_PATTERN => {
+                    self.flush_message();
+                    self.close_batch(position);
+                    self.file.trailer = Some(V2Segment::from_str(token, &self.file.separators)?);
+                }
+                _ if self.message.is_empty() => self.structure_error(
+                    position,
+                    format_compact!("Segment {} is not part of any message!", name),
+                ),
+                _ => self.message.push(token),
+            }
+            Ok(())
+        }
+
+        fn finish(mut self, position: usize) -> V2Result<V2File> {
+            self.flush_message();
+            self.close_batch(position);
+            let found = self.file.batches.len();
+            match self.file.declared_count() {
+                Ok(Some(declared)) if declared != found => {
+                    self.file.errors.push(V2BatchError::Count {
+                        segment: V2_FILE_TRAILER_PATTERN.to_rumstring(),
+                        declared,
+                        found,
+                    })
+                }
+                Err(error) => self.structure_error(position, error),
+                _ => {}
+            }
+            Ok(self.file)
+        }
+
+        ///
+        /// Returns the open batch. Messages outside of a BHS/BTS pair belong to an implicit batch
+        /// without header.
+        ///
+        fn current_batch(&mut self) -> &mut V2Batch {
+            let separators = &self.file.separators;
+            self.batch
+                .get_or_insert_with(|| V2Batch::from_header(None, separators.clone()))
+        }
+
+        fn flush_message(&mut self) {
+            if self.message.is_empty() {
+                return;
+            }
+            let raw_message = self
+                .message
+                .join(self.file.separators.segment_terminator.as_str());
+            self.message.clear();
+            let mode = self.mode;
+            let batch_number = self.file.batches.len() + 1;
+            let batch = self.current_batch();
+            batch.message_count += 1;
+            match V2Message::try_from_str_with_mode(&raw_message, mode) {
+                Ok(message) => batch.messages.push(message),
+                Err(error) => batch.errors.push(V2BatchError::Message {
+                    batch: batch_number,
+                    message: batch.message_count,
+                    error,
+                }),
+            }
+        }
+
+        fn close_batch(&mut self, position: usize) {
+            if let Some(mut batch) = self.batch.take() {
+                batch.validate_count(position);
+                self.file.batches.push(batch);
+            }
+        }
+
+        fn structure_error(&mut self, segment: usize, error: RUMString) {
+            self.file
+                .errors
+                .push(V2BatchError::Structure { segment, error });
+        }
+    }
+
+    /**************************** Helpers ***************************************/
+
+    fn build_header(
+        name: &str,
+        encoding_characters: &str,
+        separators: &V2ParserCharacters,
+    ) -> V2Result<V2Segment> {
+        V2Segment::from_str(
+            &format_compact!(
+                "{}{}{}",
+                name,
+                separators.field_separator,
+                encoding_characters
+            ),
+            separators,
+        )
+    }
+
+    fn build_trailer(
+        name: &str,
+        count: usize,
+        separators: &V2ParserCharacters,
+    ) -> V2Result<V2Segment> {
+        V2Segment::from_str(
+            &format_compact!("{}{}{}", name, separators.field_separator, count),
+            separators,
+        )
+    }
+
+    fn get_count(trailer: &V2Segment) -> V2Result<Option<usize>> {
+        if trailer.is_empty() {
+            return Ok(None);
+        }
+        let count = match trailer.get(1)?.first() {
+            Some(field) => field.get(1)?.as_str().trim(),
+            None => return Ok(None),
+        };
+        if count.is_empty() {
+            return Ok(None);
+        }
+        match count.parse::<usize>() {
+            Ok(count) => Ok(Some(count)),
+            Err(_) => Err(format_compact!(
+                "{}-1 has an invalid count {}!",
+                trailer.name(),
+                count
+            )),
+        }
+    }
+
+    fn set_count(
+        trailer: &mut V2Segment,
+        count: usize,
+        separators: &V2ParserCharacters,
+    ) -> V2Result<()> {
+        if trailer.is_empty() {
+            *trailer = build_trailer(trailer.name(), count, separators)?;
+            return Ok(());
+        }
+        match trailer.get_mut(1)?.first_mut() {
+            Some(field) => field
+                .get_mut(1)?
+                .get_mut(1)?
+                .set(&format_compact!("{}", count)),
+            None => *trailer = build_trailer(trailer.name(), count, separators)?,
+        }
+        Ok(())
+    }
+
+    fn get_header_encoding_characters(header: &V2Segment) -> V2Result<V2String> {
+        match header.get(1)?.first() {
+            Some(field) => Ok(field.get(1)?.as_str().to_rumstring()),
+            None => Err(format_compact!(
+                "{} is missing its encoding characters!",
+                header.name()
+            )),
+        }
+    }
+
+    fn get_message_encoding_characters(message: &V2Message) -> V2Result<V2String> {
+        let msh = message.get(&V2_SEGMENT_IDS[V2_MSHEADER_PATTERN], 1)?;
+        get_header_encoding_characters(msh)
+    }
+
+    fn get_batch_encoding_characters(batch: &V2Batch) -> V2Result<V2String> {
+        if let Some(header) = &batch.header {
+            return get_header_encoding_characters(header);
+        }
+        match batch.messages.first() {
+            Some(message) => get_message_encoding_characters(message),
+            None => Ok(V2_DEFAULT_ENCODING_CHARACTERS.to_rumstring()),
+        }
+    }
+}
@@ -0,0 +1,338 @@
+/*
+ * rumtk attempts to implement HL7 and medical protocols for interoperability in medicine.
+ * This toolkit aims to be reliable, simple, performant, and standards compliant.
+ * Copyright (C) 2024  Luis M. Santos, M.D.
+ *
+ * This library is free software; you can redistribute it and/or
+ * modify it under the terms of the GNU Lesser General Public
+ * License as published by the Free Software Foundation; either
+ * version 2.1 of the License, or (at your option) any later version.
+ *
+ * This library is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+ * Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public
+ * License along with this library; if not, write to the Free Software
+ * Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301  USA
+ */
+
+///
+/// Programmatic construction of outbound messages. Useful when the data lives in domain objects
+/// and formatting an ER7 string by hand would be error prone.
+///
+pub mod v2_builder {
+    use crate::hl7_v2_base_types::v2_base_types::{
+        V2ParseMode, V2ParserCharacters, V2Result, V2SearchIndex, V2SegmentIds,
+    };
+    use crate::hl7_v2_constants::{V2_EMPTY_STRING, V2_MSHEADER_PATTERN};
+    use crate::hl7_v2_field_descriptors::v2_field_descriptor::V2_FIELD_DESCRIPTORS;
+    use crate::hl7_v2_lengths::v2_lengths::V2LengthPolicy;
+    use crate::hl7_v2_parser::v2_parser::{
+        SegmentMap, SegmentOrder, V2Component, V2Field, V2Message, V2Segment,
+    };
+    use rumtk_core::strings::{format_compact, RUMString, RUMStringConversions};
+
+    /**************************** Types *****************************************/
+
+    ///
+    /// Fluent builder for [V2Message].
+    ///
+    /// The builder starts with an MSH segment carrying the encoding characters of the chosen
+    /// [V2ParserCharacters]. Every call after [V2MessageBuilder::segment] targets the segment
+    /// that was appended last. Values are plain text. They are escaped per Section 2.7.1 so the
+    /// generated message is always well-formed.
+    ///
+    /// Positions follow the same numbering as [V2Segment::get] and search patterns. For header
+    /// segments, field 1 holds the encoding characters and cannot be set. Use
+    /// [V2MessageBuilder::with_separators] to change them.
+    ///
+    /// The first error is kept and reported by [V2MessageBuilder::build] so calls can be chained
+    /// without checking each one.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use rumtk_hl7_v2::hl7_v2_builder::v2_builder::V2MessageBuilder;
+    ///
+    /// let message = V2MessageBuilder::new()
+    ///     .component(8, 1, "ADT")
+    ///     .component(8, 2, "A01")
+    ///     .segment("PID")
+    ///     .component(5, 1, "EVERYMAN")
+    ///     .named_component(11, "AD", "city", "GREENSBORO")
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     message.to_string(),
+    ///     "MSH|^~\\&|||||||ADT^A01\rPID|||||EVERYMAN||||||^^GREENSBORO"
+    /// );
+    /// ```
+    ///
+    pub struct V2MessageBuilder {
+        separators: V2ParserCharacters,
+        segments: Vec<(u8, V2Segment)>,
+        segment_ids: V2SegmentIds,
+        length_policy: V2LengthPolicy,
+        error: Option<RUMString>,
+    }
+
+    impl Default for V2MessageBuilder {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl V2MessageBuilder {
+        pub fn new() -> Self {
+            Self::with_separators(V2ParserCharacters::new())
+        }
+
+        ///
+        /// Starts a message that uses custom separators. MSH-2 is generated from them.
+        ///
+        pub fn with_separators(separators: V2ParserCharacters) -> Self {
+            let raw_msh = format_compact!(
+                "{}{}{}",
+                V2_MSHEADER_PATTERN,
+                &separators.field_separator,
+                separators.encoding_characters()
+            );
+            let mut builder = V2MessageBuilder {
+                separators,
+                segments: Vec::new(),
+                segment_ids: V2SegmentIds::new(),
+                length_policy: V2LengthPolicy::Ignore,
+                error: None,
+            };
+            builder.push_segment(&raw_msh);
+            builder
+        }
+
+        ///
+        /// Sets how values longer than their definition allows are handled by
+        /// [V2MessageBuilder::build]. Lengths are looked up for the version in MSH-12. See
+        /// [V2Message::enforce_lengths].
+        ///
+        pub fn with_length_policy(mut self, policy: V2LengthPolicy) -> Self {
+            self.length_policy = policy;
+            self
+        }
+
+        ///
+        /// Appends an empty segment. Subsequent calls target this segment.
+        ///
+        pub fn segment(mut self, name: &str) -> Self {
+            self.push_segment(name);
+            self
+        }
+
+        ///
+        /// Replaces the whole field (all repetitions) with a single value.
+        ///
+        pub fn field(self, field: usize, value: &str) -> Self {
+            self.repetition(field, 1, value).try_set(|builder| {
+                builder.current_field_group(field)?.truncate(1);
+                Ok(())
+            })
+        }
+
+        ///
+        /// Replaces one repetition of a field with a single value. Missing repetitions are
+        /// added as empty ones.
+        ///
+        pub fn repetition(self, field: usize, repetition: usize, value: &str) -> Self {
+            self.try_set(|builder| {
+                let separators = builder.separators.clone();
+                let target = builder.current_repetition(field, repetition)?;
+                *target = V2Field::new();
+                target
+                    .get_or_insert_mut(1)?
+                    .get_or_insert_mut(1)?
+                    .set_decoded(value, &separators);
+                Ok(())
+            })
+        }
+
+        ///
+        /// Replaces a component of the first repetition of a field.
+        ///
+        pub fn component(self, field: usize, component: usize, value: &str) -> Self {
+            self.try_set(|builder| {
+                let separators = builder.separators.clone();
+                let target = builder
+                    .current_repetition(field, 1)?
+                    .get_or_insert_mut(component)?;
+                *target = V2Component::with_raw_str(V2_EMPTY_STRING);
+                target.get_or_insert_mut(1)?.set_decoded(value, &separators);
+                Ok(())
+            })
+        }
+
+        ///
+        /// Sets a subcomponent of the first repetition of a field.
+        ///
+        pub fn subcomponent(
+            self,
+            field: usize,
+            component: usize,
+            subcomponent: usize,
+            value: &str,
+        ) -> Self {
+            self.try_set(|builder| {
+                let separators = builder.separators.clone();
+                let target = builder
+                    .current_repetition(field, 1)?
+                    .get_or_insert_mut(component)?;
+                target.set_subcomponent_separator(&separators.subcomponent_separator);
+                target
+                    .get_or_insert_mut(subcomponent)?
+                    .set_decoded(value, &separators);
+                Ok(())
+            })
+        }
+
+        ///
+        /// Same as [V2MessageBuilder::component] but the component is picked by its name in the
+        /// descriptor of the field's data type. For example, `("AD", "city")` or
+        /// `("CNN", "family_name")`. See [V2_FIELD_DESCRIPTORS].
+        ///
+        pub fn named_component(
+            self,
+            field: usize,
+            data_type: &str,
+            component_name: &str,
+            value: &str,
+        ) -> Self {
+            let descriptor = match V2_FIELD_DESCRIPTORS.get(data_type) {
+                Some(descriptor) => descriptor,
+                None => {
+                    return self.fail(format_compact!(
+                        "No descriptor found for data type {}!",
+                        data_type
+                    ))
+                }
+            };
+            match descriptor.iter().find(|item| item.name == component_name) {
+                Some(item) => self.component(field, item.seq as usize, value),
+                None => self.fail(format_compact!(
+                    "Data type {} has no component named {}!",
+                    data_type,
+                    component_name
+                )),
+            }
+        }
+
+        ///
+        /// Sets the subcomponent addressed by a search pattern (e.g. `OBX(2)5[2].1`) in any of
+        /// the segments appended so far. Omitted indices default to 1.
+        ///
+        pub fn set(self, search_pattern: &str, value: &str) -> Self {
+            self.try_set(|builder| {
+                let index = V2SearchIndex::from(search_pattern);
+                let separators = builder.separators.clone();
+                let segment_id = builder.segment_ids.find_indexed(&index);
+                let segment = match builder
+                    .segments
+                    .iter_mut()
+                    .filter(|(id, _)| *id == segment_id)
+                    .nth((index.segment_group as usize).wrapping_sub(1))
+                {
+                    Some((_, segment)) => segment,
+                    None => {
+                        return Err(format_compact!(
+                            "No segment matches {} in the message being built!",
+                            search_pattern
+                        ))
+                    }
+                };
+                segment
+                    .find_subcomponent_or_insert_mut(&index, &separators)?
+                    .set_decoded(value, &separators);
+                Ok(())
+            })
+        }
+
+        ///
+        /// Finalizes the message. Fails with the first error found while building it, or with
+        /// the first value the length policy rejects.
+        ///
+        pub fn build(self) -> V2Result<V2Message> {
+            if let Some(error) = self.error {
+                return Err(error);
+            }
+            let mut segment_groups = SegmentMap::new();
+            let mut segment_order = SegmentOrder::with_capacity(self.segments.len());
+            for (key, segment) in self.segments {
+                let segment_group = segment_groups.entry(key).or_default();
+                segment_order.push((key, segment_group.len()));
+                segment_group.push(segment);
+            }
+            let mut message = V2Message::from_parts(
+                self.separators,
+                segment_groups,
+                segment_order,
+                self.segment_ids,
+            );
+            message.enforce_lengths(self.length_policy)?;
+            Ok(message)
+        }
+
+        fn push_segment(&mut self, raw_segment: &str) {
+            let result = V2Segment::from_str(raw_segment, &self.separators).and_then(|segment| {
+                let key = self
+                    .segment_ids
+                    .get_or_assign(segment.name(), V2ParseMode::Strict)?;
+                Ok((key, segment))
+            });
+            match result {
+                Ok(segment) => self.segments.push(segment),
+                Err(e) => self.record_error(e),
+            }
+        }
+
+        fn current_field_group(&mut self, field: usize) -> V2Result<&mut Vec<V2Field>> {
+            match self.segments.last_mut() {
+                Some((_, segment)) => segment.get_writable_field_mut(field as isize),
+                None => Err("No segment to write to!".to_rumstring()),
+            }
+        }
+
+        fn current_repetition(
+            &mut self,
+            field: usize,
+            repetition: usize,
+        ) -> V2Result<&mut V2Field> {
+            match self.segments.last_mut() {
+                Some((_, segment)) => {
+                    segment.get_or_insert_repetition_mut(field as isize, repetition)
+                }
+                None => Err("No segment to write to!".to_rumstring()),
+            }
+        }
+
+        fn try_set<F>(mut self, setter: F) -> Self
+        where
+            F: FnOnce(&mut Self) -> V2Result<()>,
+        {
+            if self.error.is_none() {
+                if let Err(e) = setter(&mut self) {
+                    self.record_error(e);
+                }
+            }
+            self
+        }
+
+        fn fail(mut self, error: RUMString) -> Self {
+            self.record_error(error);
+            self
+        }
+
+        fn record_error(&mut self, error: RUMString) {
+            if self.error.is_none() {
+                self.error = Some(error);
+            }
+        }
+    }
+}
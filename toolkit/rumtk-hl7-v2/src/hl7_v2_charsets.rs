@@ -0,0 +1,685 @@
+/*
+ * rumtk attempts to implement HL7 and medical protocols for interoperability in medicine.
+ * This toolkit aims to be reliable, simple, performant, and standards compliant.
+ * Copyright (C) 2024  Luis M. Santos, M.D.
+ *
+ * This library is free software; you can redistribute it and/or
+ * modify it under the terms of the GNU Lesser General Public
+ * License as published by the Free Software Foundation; either
+ * version 2.1 of the License, or (at your option) any later version.
+ *
+ * This library is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+ * Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public
+ * License along with this library; if not, write to the Free Software
+ * Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301  USA
+ */
+
+///
+/// Decoding of raw messages per the character sets declared in MSH-18 and MSH-20, and encoding
+/// of outbound messages the same way. Characters the default character set cannot represent are
+/// written in the alternate character sets of MSH-18, switching with the escape sequences of the
+/// scheme in MSH-20.
+///
+/// ## Per Section 2.14.9.18 (MSH-18 Character Set)
+/// ```text
+/// This field contains the character set for the entire message. Refer to HL7 Table 0211 -
+/// Alternate character sets for valid values. If the field is not valued, the default
+/// single-byte character set (ASCII (ISO IR6)) should be assumed. No other character sets are
+/// allowed in the message. If the field repeats, the first repetition is the default character
+/// set and the remaining ones are the alternate character sets that may be used in the message.
+/// ```
+///
+/// ## Per Section 2.14.9.20 (MSH-20 Alternate Character Set Handling Scheme)
+/// ```text
+/// ISO 2022-1994   This standard is titled "Information Technology - Character Code Structure
+///                 and Extension Technique". This standard specifies an escape sequence.
+/// 2.3             The character set switching mode specified in HL7 2.5, section 2.7.2,
+///                 "Escape sequences supporting multiple character sets" and section 2.A.46,
+///                 "XPN - extended person name".
+/// <null>          This is the default, indicating that there is no character set switching
+///                 occurring in this message.
+/// ```
+///
+pub mod v2_charsets {
+    use crate::hl7_v2_base_types::v2_base_types::{V2Result, V2String};
+    use crate::hl7_v2_constants::{
+        V2_MSHEADER_PATTERN, V2_MSH_ALTERNATE_CHARSET_HANDLING, V2_MSH_CHARSET, V2_SEGMENT_IDS,
+    };
+    use crate::hl7_v2_parser::v2_parser::V2Message;
+    use encoding_rs::{
+        Encoding, BIG5, EUC_KR, GB18030, ISO_2022_JP, ISO_8859_15, ISO_8859_2, ISO_8859_3,
+        ISO_8859_4, ISO_8859_5, ISO_8859_6, ISO_8859_7, ISO_8859_8, SHIFT_JIS, UTF_16BE, UTF_16LE,
+        UTF_8, WINDOWS_1252, WINDOWS_1254,
+    };
+    use rumtk_core::strings::{format_compact, try_decode, RUMString};
+
+    /**************************** Constants**************************************/
+
+    const ESC: u8 = 0x1b;
+    const ISO_2022_ASCII: &[u8] = b"(B";
+
+    /**************************** Types *****************************************/
+
+    ///
+    /// How a message switches between the character sets listed in MSH-18. See MSH-20.
+    ///
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+    pub enum V2CharsetSwitching {
+        ///
+        /// The whole message is in the default character set.
+        ///
+        #[default]
+        None,
+        ///
+        /// `ISO 2022-1994`. The data carries the ISO 2022 escape sequences (ESC ...).
+        ///
+        Iso2022,
+        ///
+        /// `2.3`. The data carries the `\Cxxyy\` and `\Mxxyyzz\` escape sequences of Section
+        /// 2.7.2, where the hexadecimal digits are the bytes that follow ESC in ISO 2022.
+        ///
+        EscapeSequences,
+    }
+
+    impl V2CharsetSwitching {
+        #[allow(clippy::should_implement_trait)]
+        pub fn from_str(scheme: &str) -> V2Result<V2CharsetSwitching> {
+            match scheme.trim() {
+                "" => Ok(V2CharsetSwitching::None),
+                "ISO 2022-1994" => Ok(V2CharsetSwitching::Iso2022),
+                "2.3" => Ok(V2CharsetSwitching::EscapeSequences),
+                other => Err(format_compact!(
+                    "Unknown alternate character set handling scheme {} in MSH-20!",
+                    other
+                )),
+            }
+        }
+    }
+
+    /****************************** API *****************************************/
+
+    ///
+    /// Returns the encoding for a character set in HL7 Table 0211 (e.g. `8859/1` or
+    /// `UNICODE UTF-8`). Names outside the table are looked up as WHATWG labels, so senders
+    /// declaring `Shift_JIS` or `windows-1252` are understood too.
+    ///
+    /// ISO 8859-1 is decoded as windows-1252, which only differs in the C1 control codes.
+    ///
+    pub fn find_charset(name: &str) -> V2Result<&'static Encoding> {
+        let name = name.trim();
+        let encoding = match name.to_uppercase().as_str() {
+            "" | "ASCII" | "ISO IR6" | "8859/1" | "ISO IR100" => WINDOWS_1252,
+            "8859/2" => ISO_8859_2,
+            "8859/3" => ISO_8859_3,
+            "8859/4" => ISO_8859_4,
+            "8859/5" => ISO_8859_5,
+            "8859/6" => ISO_8859_6,
+            "8859/7" => ISO_8859_7,
+            "8859/8" => ISO_8859_8,
+            "8859/9" => WINDOWS_1254,
+            "8859/15" => ISO_8859_15,
+            "ISO IR14" | "ISO IR13" => SHIFT_JIS,
+            "ISO IR87" => ISO_2022_JP,
+            "GB 18030-2000" => GB18030,
+            "KS X 1001" => EUC_KR,
+            "BIG-5" => BIG5,
+            "UNICODE" | "UNICODE UTF-16" => UTF_16LE,
+            "UNICODE UTF-8" => UTF_8,
+            "ISO IR159" | "CNS 11643-1992" | "UNICODE UTF-32" => {
+                return Err(format_compact!("Character set {} is not supported!", name))
+            }
+            _ => match Encoding::for_label(name.as_bytes()) {
+                Some(encoding) => encoding,
+                None => return Err(format_compact!("Unknown character set {}!", name)),
+            },
+        };
+        Ok(encoding)
+    }
+
+    ///
+    /// Decodes a raw message to UTF-8 using the character set declared in MSH-18 and the
+    /// switching scheme in MSH-20. The escape sequences that switch character sets are consumed.
+    ///
+    /// Messages starting with a byte order mark, or in UTF-16 without one, are decoded as
+    /// Unicode. If MSH-18 is not valued, the message is read as UTF-8 when it is valid UTF-8
+    /// (ASCII being a subset) and the encoding is guessed otherwise.
+    ///
+    pub fn decode_message(bytes: &[u8]) -> V2Result<V2String> {
+        if let Some((encoding, bom_length)) = Encoding::for_bom(bytes) {
+            return decode_with(&bytes[bom_length..], encoding);
+        }
+        match bytes {
+            [0, b'M', ..] => return decode_with(bytes, UTF_16BE),
+            [b'M', 0, ..] => return decode_with(bytes, UTF_16LE),
+            _ => {}
+        }
+        let header = match find_header(bytes) {
+            Some(header) => header,
+            None => return Ok(guess_decode(bytes)),
+        };
+        let charsets = header_field(header, V2_MSH_CHARSET);
+        let default_charset = charsets
+            .split(|b| Some(b) == header.get(5))
+            .next()
+            .unwrap_or_default();
+        let scheme = V2CharsetSwitching::from_str(&String::from_utf8_lossy(header_field(
+            header,
+            V2_MSH_ALTERNATE_CHARSET_HANDLING,
+        )))?;
+        if default_charset.is_empty() && scheme == V2CharsetSwitching::None {
+            return Ok(guess_decode(bytes));
+        }
+        let default = find_charset(&String::from_utf8_lossy(default_charset))?;
+        match scheme {
+            V2CharsetSwitching::None => decode_with(bytes, default),
+            V2CharsetSwitching::Iso2022 => decode_switching(bytes, default, None),
+            V2CharsetSwitching::EscapeSequences => match header.get(6) {
+                Some(escape_character) => decode_switching(bytes, default, Some(*escape_character)),
+                None => Err("MSH-2 has no escape character!".into()),
+            },
+        }
+    }
+
+    ///
+    /// Encodes a message in a character set of HL7 Table 0211. Fails if the message has
+    /// characters the character set cannot represent. UTF-16 output starts with a byte order
+    /// mark.
+    ///
+    pub fn encode_message(message: &str, charset: &str) -> V2Result<Vec<u8>> {
+        let encoding = find_charset(charset)?;
+        if is_ascii_charset(charset) && !message.is_ascii() {
+            return Err(
+                "The message has characters outside of ASCII! Declare a character set in MSH-18."
+                    .into(),
+            );
+        }
+        if encoding == UTF_16LE || encoding == UTF_16BE {
+            let mut encoded = Vec::with_capacity(message.len() * 2 + 2);
+            for unit in std::iter::once(0xfeff).chain(message.encode_utf16()) {
+                match encoding == UTF_16LE {
+                    true => encoded.extend(unit.to_le_bytes()),
+                    false => encoded.extend(unit.to_be_bytes()),
+                }
+            }
+            return Ok(encoded);
+        }
+        let (encoded, _, had_errors) = encoding.encode(message);
+        match had_errors {
+            true => Err(format_compact!(
+                "The message has characters that cannot be encoded in {}!",
+                charset
+            )),
+            false => Ok(encoded.into_owned()),
+        }
+    }
+
+    ///
+    /// Encodes a message in the first character set of `charsets` (MSH-18), switching to the
+    /// others for characters the first one cannot represent. Switches are written as ISO 2022
+    /// escape sequences or as `\Cxxyy\`/`\Mxxyyzz\` depending on `scheme` (MSH-20), and every
+    /// segment starts over in the default character set, which is how [decode_message] reads
+    /// them back. Fails if a character is in none of the character sets.
+    ///
+    pub fn encode_message_switching(
+        message: &str,
+        charsets: &[V2String],
+        scheme: V2CharsetSwitching,
+        escape_character: &str,
+    ) -> V2Result<Vec<u8>> {
+        let default_charset = charsets.first().map(|c| c.as_str()).unwrap_or_default();
+        if scheme == V2CharsetSwitching::None || charsets.len() < 2 {
+            return encode_message(message, default_charset);
+        }
+        let default = find_charset(default_charset)?;
+        if default == UTF_8 || default == UTF_16LE || default == UTF_16BE {
+            // Unicode has every character, so there is nothing to switch to.
+            return encode_message(message, default_charset);
+        }
+        let ascii_only = is_ascii_charset(default_charset);
+        let mut alternates = Vec::with_capacity(charsets.len() - 1);
+        for charset in &charsets[1..] {
+            if !charset.trim().is_empty() {
+                alternates.push(charset_designation(charset)?);
+            }
+        }
+        let switch_bytes = |designation: &[u8]| -> V2Result<Vec<u8>> {
+            match scheme {
+                V2CharsetSwitching::EscapeSequences => {
+                    let kind = match designation.first() {
+                        Some(b'$') => 'M',
+                        _ => 'C',
+                    };
+                    let hex: String = designation.iter().map(|b| format!("{:02X}", b)).collect();
+                    match escape_character.is_empty() {
+                        true => Err("MSH-2 has no escape character!".into()),
+                        false => Ok(format!(
+                            "{}{}{}{}",
+                            escape_character, kind, hex, escape_character
+                        )
+                        .into_bytes()),
+                    }
+                }
+                _ => {
+                    let mut sequence = vec![ESC];
+                    sequence.extend(designation);
+                    Ok(sequence)
+                }
+            }
+        };
+
+        let in_charset = |c: char, designation: &[u8]| {
+            let mut buffer = [0u8; 4];
+            (designation != ISO_2022_ASCII || !ascii_only || c.is_ascii())
+                && encode_run(c.encode_utf8(&mut buffer), designation, default).is_ok()
+        };
+
+        let chars: Vec<char> = message.chars().collect();
+        let mut encoded = Vec::with_capacity(message.len());
+        let mut designation: &[u8] = ISO_2022_ASCII;
+        let mut run = String::new();
+        for (indx, c) in chars.iter().enumerate() {
+            if matches!(c, '\r' | '\n') {
+                encoded.extend(encode_run(&run, designation, default)?);
+                run.clear();
+                encoded.push(*c as u8);
+                designation = ISO_2022_ASCII;
+                continue;
+            }
+            let target = if in_charset(*c, ISO_2022_ASCII) {
+                ISO_2022_ASCII
+            } else if designation != ISO_2022_ASCII && in_charset(*c, designation) {
+                designation
+            } else {
+                // Several alternates may have the character (e.g. Greek is in JIS X 0208 too), so
+                // pick the one that covers the most of the text the default cannot represent.
+                let upcoming: Vec<char> = chars[indx..]
+                    .iter()
+                    .take_while(|c| !matches!(c, '\r' | '\n') && !in_charset(**c, ISO_2022_ASCII))
+                    .copied()
+                    .collect();
+                let mut best: (usize, &[u8]) = (0, ISO_2022_ASCII);
+                for alternate in &alternates {
+                    let covered = upcoming
+                        .iter()
+                        .take_while(|c| in_charset(**c, alternate))
+                        .count();
+                    if covered > best.0 {
+                        best = (covered, alternate);
+                    }
+                }
+                match best {
+                    (0, _) => {
+                        return Err(format_compact!(
+                            "Character {:?} is not in any of the character sets in MSH-18!",
+                            c
+                        ))
+                    }
+                    (_, alternate) => alternate,
+                }
+            };
+            if target != designation {
+                encoded.extend(encode_run(&run, designation, default)?);
+                run.clear();
+                encoded.extend(switch_bytes(target)?);
+                designation = target;
+            }
+            run.push(*c);
+        }
+        encoded.extend(encode_run(&run, designation, default)?);
+        Ok(encoded)
+    }
+
+    /**************************** Helpers ***************************************/
+
+    fn is_ascii_charset(charset: &str) -> bool {
+        matches!(
+            charset.trim().to_uppercase().as_str(),
+            "" | "ASCII" | "ISO IR6"
+        )
+    }
+
+    ///
+    /// ISO 2022 designation of a character set of HL7 Table 0211. Reverse of [decode_run]. Only
+    /// character sets we can switch to have one.
+    ///
+    fn charset_designation(charset: &str) -> V2Result<&'static [u8]> {
+        let designation: &'static [u8] = match charset.trim().to_uppercase().as_str() {
+            "ASCII" | "ISO IR6" => b"(B",
+            "8859/1" | "ISO IR100" => b"-A",
+            "8859/2" => b"-B",
+            "8859/3" => b"-C",
+            "8859/4" => b"-D",
+            "8859/5" => b"-L",
+            "8859/6" => b"-G",
+            "8859/7" => b"-F",
+            "8859/8" => b"-H",
+            "8859/9" => b"-M",
+            "8859/15" => b"-b",
+            "ISO IR87" => b"$B",
+            "KS X 1001" => b"$)C",
+            "GB 18030-2000" => b"$)A",
+            _ => {
+                return Err(format_compact!(
+                    "Cannot switch to character set {}! It has no escape sequence we support.",
+                    charset.trim()
+                ))
+            }
+        };
+        Ok(designation)
+    }
+
+    ///
+    /// Encodes a run of text in the character set selected by an ISO 2022 designation. Runs in
+    /// JIS X 0208 are returned without the escape sequences the ISO-2022-JP encoder wraps them in.
+    ///
+    fn encode_run(run: &str, designation: &[u8], default: &'static Encoding) -> V2Result<Vec<u8>> {
+        let encoding = match designation {
+            b"(B" => default,
+            b"$B" => ISO_2022_JP,
+            b"-A" => WINDOWS_1252,
+            b"-B" => ISO_8859_2,
+            b"-C" => ISO_8859_3,
+            b"-D" => ISO_8859_4,
+            b"-L" => ISO_8859_5,
+            b"-G" => ISO_8859_6,
+            b"-F" => ISO_8859_7,
+            b"-H" => ISO_8859_8,
+            b"-M" => WINDOWS_1254,
+            b"-b" => ISO_8859_15,
+            b"$)C" => EUC_KR,
+            b"$)A" => GB18030,
+            _ => {
+                return Err(format_compact!(
+                    "Character set ESC {} is not supported!",
+                    String::from_utf8_lossy(designation)
+                ))
+            }
+        };
+        if run.is_empty() {
+            return Ok(Vec::new());
+        }
+        let (encoded, _, had_errors) = encoding.encode(run);
+        if had_errors {
+            return Err(format_compact!(
+                "Text {:?} cannot be encoded in {}!",
+                run,
+                encoding.name()
+            ));
+        }
+        if encoding != ISO_2022_JP {
+            return Ok(encoded.into_owned());
+        }
+        let jis = encoded
+            .strip_prefix(b"\x1b$B".as_slice())
+            .and_then(|jis| jis.strip_suffix(b"\x1b(B".as_slice()));
+        match jis {
+            Some(jis) if !jis.contains(&ESC) => Ok(jis.to_vec()),
+            _ => Err(format_compact!("Text {:?} is not all in JIS X 0208!", run)),
+        }
+    }
+
+    fn decode_with(bytes: &[u8], encoding: &'static Encoding) -> V2Result<V2String> {
+        match encoding.decode_without_bom_handling_and_without_replacement(bytes) {
+            Some(decoded) => Ok(RUMString::from(decoded)),
+            None => Err(format_compact!(
+                "The message is not valid {}!",
+                encoding.name()
+            )),
+        }
+    }
+
+    fn guess_decode(bytes: &[u8]) -> V2String {
+        match std::str::from_utf8(bytes) {
+            Ok(decoded) => RUMString::from(decoded),
+            Err(_) => try_decode(bytes),
+        }
+    }
+
+    ///
+    /// Returns the MSH segment up to its terminator. MSH may be preceded by FHS and BHS.
+    ///
+    fn find_header(bytes: &[u8]) -> Option<&[u8]> {
+        let pattern = V2_MSHEADER_PATTERN.as_bytes();
+        let start = (0..bytes.len()).find(|&i| {
+            bytes[i..].starts_with(pattern) && (i == 0 || matches!(bytes[i - 1], b'\r' | b'\n'))
+        })?;
+        let header = &bytes[start..];
+        let end = header
+            .iter()
+            .position(|b| matches!(b, b'\r' | b'\n'))
+            .unwrap_or(header.len());
+        match end > pattern.len() + 4 {
+            true => Some(&header[..end]),
+            false => None,
+        }
+    }
+
+    ///
+    /// Returns an MSH field by position (see [V2_MSH_CHARSET]) from the raw header.
+    ///
+    fn header_field(header: &[u8], position: isize) -> &[u8] {
+        let field_separator = header[V2_MSHEADER_PATTERN.len()];
+        header
+            .split(|b| *b == field_separator)
+            .nth(position as usize)
+            .unwrap_or_default()
+    }
+
+    ///
+    /// Splits the message on the escape sequences that switch character sets and decodes each
+    /// run with the character set it selects. Runs go back to the default character set at the
+    /// end of each segment. `escape_character` selects the `2.3` scheme, otherwise the data
+    /// carries ISO 2022 escape sequences.
+    ///
+    fn decode_switching(
+        bytes: &[u8],
+        default: &'static Encoding,
+        escape_character: Option<u8>,
+    ) -> V2Result<V2String> {
+        let mut decoded = V2String::with_capacity(bytes.len());
+        let mut designation: &[u8] = ISO_2022_ASCII;
+        let mut run_start = 0;
+        let mut i = 0;
+        while i < bytes.len() {
+            let (sequence, length) = match escape_character {
+                Some(escape_character) => match_hl7_escape(&bytes[i..], escape_character),
+                None => match_iso_2022_escape(&bytes[i..]),
+            };
+            if length > 0 {
+                decoded.push_str(&decode_run(&bytes[run_start..i], designation, default)?);
+                designation = sequence;
+                i += length;
+                run_start = i;
+            } else if matches!(bytes[i], b'\r' | b'\n') {
+                decoded.push_str(&decode_run(&bytes[run_start..=i], designation, default)?);
+                designation = ISO_2022_ASCII;
+                i += 1;
+                run_start = i;
+            } else {
+                i += 1;
+            }
+        }
+        decoded.push_str(&decode_run(&bytes[run_start..], designation, default)?);
+        Ok(decoded)
+    }
+
+    ///
+    /// Matches an ISO 2022 escape sequence at the start of the input. Returns the bytes after
+    /// ESC and the length of the whole sequence, which is 0 if there is none.
+    ///
+    fn match_iso_2022_escape(bytes: &[u8]) -> (&[u8], usize) {
+        if bytes.first() != Some(&ESC) {
+            return (&[], 0);
+        }
+        // Intermediate bytes are 0x20-0x2F and the sequence ends with a final byte 0x30-0x7E.
+        match bytes[1..].iter().position(|b| (0x30..=0x7e).contains(b)) {
+            Some(end) if end > 0 => (&bytes[1..end + 2], end + 2),
+            _ => (&[], 0),
+        }
+    }
+
+    ///
+    /// Matches a `\Cxxyy\` or `\Mxxyyzz\` escape sequence at the start of the input. Returns the
+    /// bytes they stand for and the length of the sequence, which is 0 if there is none.
+    /// Sequences that are not valid hex or do not designate a character set we support are not
+    /// escapes, so they are left in the data as is.
+    ///
+    fn match_hl7_escape(bytes: &[u8], escape_character: u8) -> (&'static [u8], usize) {
+        let digits = match bytes {
+            [e, b'C', ..] if *e == escape_character => 4,
+            [e, b'M', ..] if *e == escape_character => match bytes.get(6) {
+                Some(b) if *b == escape_character => 4,
+                _ => 6,
+            },
+            _ => return (&[], 0),
+        };
+        let length = digits + 3;
+        let hex = match bytes.get(2..2 + digits) {
+            Some(hex) if bytes.get(length - 1) == Some(&escape_character) => hex,
+            _ => return (&[], 0),
+        };
+        let sequence: Option<Vec<u8>> = hex
+            .chunks(2)
+            .map(|pair| {
+                std::str::from_utf8(pair)
+                    .ok()
+                    .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+            })
+            .collect();
+        match sequence.as_deref().and_then(designation_bytes) {
+            Some(designation) => (designation, length),
+            None => (&[], 0),
+        }
+    }
+
+    ///
+    /// Maps the bytes of a designation back to a static slice so runs can keep it around.
+    ///
+    fn designation_bytes(sequence: &[u8]) -> Option<&'static [u8]> {
+        const DESIGNATIONS: [&[u8]; 18] = [
+            b"(B", b"(J", b"(I", b"$@", b"$B", b"$(D", b"-A", b"-B", b"-C", b"-D", b"-L", b"-G",
+            b"-F", b"-H", b"-M", b"-b", b"$)C", b"$)A",
+        ];
+        DESIGNATIONS.iter().find(|d| **d == sequence).copied()
+    }
+
+    ///
+    /// Decodes a run of bytes in the character set selected by an ISO 2022 designation.
+    ///
+    fn decode_run(
+        run: &[u8],
+        designation: &[u8],
+        default: &'static Encoding,
+    ) -> V2Result<V2String> {
+        let encoding = match designation {
+            b"(B" => default,
+            b"(J" => SHIFT_JIS,
+            b"(I" | b"$@" | b"$B" => {
+                // The ISO-2022-JP decoder needs the designation in the data itself.
+                let mut designated = Vec::with_capacity(run.len() + designation.len() + 4);
+                designated.push(ESC);
+                designated.extend(designation);
+                designated.extend(run);
+                designated.push(ESC);
+                designated.extend(ISO_2022_ASCII);
+                return decode_with(&designated, ISO_2022_JP);
+            }
+            b"-A" => WINDOWS_1252,
+            b"-B" => ISO_8859_2,
+            b"-C" => ISO_8859_3,
+            b"-D" => ISO_8859_4,
+            b"-L" => ISO_8859_5,
+            b"-G" => ISO_8859_6,
+            b"-F" => ISO_8859_7,
+            b"-H" => ISO_8859_8,
+            b"-M" => WINDOWS_1254,
+            b"-b" => ISO_8859_15,
+            b"$)C" => EUC_KR,
+            b"$)A" => GB18030,
+            _ => {
+                return Err(format_compact!(
+                    "Character set ESC {} is not supported!",
+                    String::from_utf8_lossy(designation)
+                ))
+            }
+        };
+        decode_with(run, encoding)
+    }
+
+    impl V2Message {
+        ///
+        /// Parses a raw message, decoding it per MSH-18 and MSH-20. See [decode_message].
+        ///
+        pub fn from_bytes(bytes: &[u8]) -> V2Result<V2Message> {
+            V2Message::try_from_str(&decode_message(bytes)?)
+        }
+
+        ///
+        /// Default character set of the message (first repetition of MSH-18). Empty if not
+        /// valued, which means ASCII.
+        ///
+        pub fn charset(&self) -> V2String {
+            self.find_component(&format_compact!("MSH{}", V2_MSH_CHARSET))
+                .map(|charset| charset.to_string())
+                .unwrap_or_default()
+        }
+
+        ///
+        /// Declares the character set the message is sent in by setting MSH-18. Fails for
+        /// character sets we cannot encode in.
+        ///
+        pub fn set_charset(&mut self, charset: &str) -> V2Result<()> {
+            encode_message("", charset)?;
+            self.set(&format_compact!("MSH{}", V2_MSH_CHARSET), charset)
+        }
+
+        ///
+        /// Generates the message and encodes it in the character sets declared in MSH-18,
+        /// switching between them the way MSH-20 says. See [encode_message_switching]. Fails if
+        /// the message has characters that need a character set the header does not declare.
+        ///
+        pub fn to_bytes(&self) -> V2Result<Vec<u8>> {
+            let scheme = V2CharsetSwitching::from_str(
+                &self
+                    .find_component(&format_compact!("MSH{}", V2_MSH_ALTERNATE_CHARSET_HANDLING))
+                    .map(|scheme| scheme.to_string())
+                    .unwrap_or_default(),
+            )?;
+            let charsets: Vec<V2String> = match self
+                .get(&V2_SEGMENT_IDS[V2_MSHEADER_PATTERN], 1)
+                .and_then(|msh| msh.get(V2_MSH_CHARSET))
+            {
+                Ok(field_group) => field_group
+                    .iter()
+                    .map(|field| {
+                        field
+                            .get(1)
+                            .and_then(|component| component.get(1))
+                            .map(|charset| charset.to_string())
+                            .unwrap_or_default()
+                    })
+                    .collect(),
+                Err(_) => Vec::new(),
+            };
+            let message = self.to_string();
+            match encode_message_switching(
+                &message,
+                &charsets,
+                scheme,
+                &self.separators().escape_character,
+            ) {
+                Err(e) if scheme == V2CharsetSwitching::None || charsets.len() < 2 => {
+                    Err(format_compact!(
+                        "{} List alternate character sets in MSH-18 and a switching scheme in MSH-20 to send it.",
+                        e
+                    ))
+                }
+                result => result,
+            }
+        }
+    }
+}
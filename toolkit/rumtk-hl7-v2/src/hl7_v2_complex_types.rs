@@ -21,16 +21,17 @@
 pub mod hl7_v2_complex_types {
     use crate::hl7_v2_base_types::v2_primitives::*;
     use crate::hl7_v2_field_descriptors::v2_field_descriptor::*;
+    use crate::hl7_v2_versions::v2_versions::{
+        version_data_type, version_type_descriptor, V2Version, V2_DEFAULT_VERSION,
+    };
     use rumtk_core::strings::format_compact;
 
-    type V2StrField<'a> = Vec<&'a str>;
-
     ///
     /// Interface for ensuring we get a vector of strings instead of components.
     /// This ensures we keep this module independent of the parser module.
     ///
     pub trait V2FieldToString: Sized {
-        fn to_component_list(&self) -> V2StrField;
+        fn to_component_list(&self) -> V2ComponentList<'_>;
     }
 
     #[derive(Debug)]
@@ -47,21 +48,283 @@ pub mod hl7_v2_complex_types {
         V2ST(V2Result<V2ST>),
         V2Text(V2Result<V2TX>),
         V2SI(V2Result<V2SI>),
+        V2Complex(V2ComplexType, Vec<V2TypedComponent>),
+        Empty,
         Err(V2String),
     }
 
+    ///
+    /// Casted component of a complex type. Keeps the component name from the data type tables so
+    /// the structure can be walked by name.
+    ///
+    #[derive(Debug)]
+    pub struct V2TypedComponent {
+        pub name: &'static str,
+        pub seq: u16,
+        pub value: V2Type,
+    }
+
+    impl V2Type {
+        ///
+        /// Returns the component named `name` of a complex value. `None` for primitives or if
+        /// the complex type has no such component.
+        ///
+        pub fn component(&self, name: &str) -> Option<&V2Type> {
+            match self {
+                V2Type::V2Complex(_, components) => components
+                    .iter()
+                    .find(|component| component.name == name)
+                    .map(|component| &component.value),
+                _ => None,
+            }
+        }
+
+        pub fn is_empty(&self) -> bool {
+            matches!(self, V2Type::Empty)
+        }
+
+        ///
+        /// Returns the error of this value if it failed to cast. Complex values report errors
+        /// through [V2Type::errors].
+        ///
+        pub fn error(&self) -> Option<&V2String> {
+            match self {
+                V2Type::V2String(Err(e))
+                | V2Type::V2DateTime(Err(e))
+                | V2Type::V2Date(Err(e))
+                | V2Type::V2Time(Err(e))
+                | V2Type::V2FT(Err(e))
+                | V2Type::V2SNM(Err(e))
+                | V2Type::V2NM(Err(e))
+                | V2Type::V2ID(Err(e))
+                | V2Type::V2IS(Err(e))
+                | V2Type::V2ST(Err(e))
+                | V2Type::V2Text(Err(e))
+                | V2Type::V2SI(Err(e))
+                | V2Type::Err(e) => Some(e),
+                _ => None,
+            }
+        }
+
+        ///
+        /// Collects every error in this value, including those of nested components. Errors of
+        /// components are prefixed with the path to the component, e.g. `family_name.surname`.
+        ///
+        pub fn errors(&self) -> Vec<V2String> {
+            match self {
+                V2Type::V2Complex(_, components) => {
+                    let mut errors = Vec::new();
+                    for component in components {
+                        for error in component.value.errors() {
+                            let separator = match component.value {
+                                V2Type::V2Complex(_, _) => ".",
+                                _ => ": ",
+                            };
+                            errors.push(format_compact!(
+                                "{}{}{}",
+                                component.name,
+                                separator,
+                                error
+                            ));
+                        }
+                    }
+                    errors
+                }
+                _ => self.error().into_iter().cloned().collect(),
+            }
+        }
+    }
+
+    fn is_missing(component: &[&str]) -> bool {
+        component.iter().all(|c| c.is_empty())
+    }
+
+    ///
+    /// Returns the descriptor of component `seq` (1-indexed) of a complex type in `version`.
+    ///
+    pub fn complex_type_descriptor(
+        complex: &V2ComplexType,
+        seq: usize,
+        version: V2Version,
+    ) -> V2Result<&'static V2ComponentTypeDescriptor> {
+        let type_name = complex_type_to_str(complex);
+        let descriptor = match version_type_descriptor(complex, version) {
+            Some(descriptor) => descriptor,
+            None => return Err(format_compact!("No descriptor for type {}!", type_name)),
+        };
+        match seq.checked_sub(1).and_then(|indx| descriptor.get(indx)) {
+            Some(component) => Ok(*component),
+            None => Err(format_compact!(
+                "Type {} has no component {}!",
+                type_name,
+                seq
+            )),
+        }
+    }
+
+    ///
+    /// Data type of a component of `parent` in `version`. See [version_data_type]. The time of a
+    /// TS is a DTM in every version.
+    ///
+    pub fn component_data_type(
+        parent: &V2ComplexType,
+        component_type: &V2ComponentTypeDescriptor,
+        version: V2Version,
+    ) -> V2ComponentType {
+        match parent {
+            V2ComplexType::TS => component_type.data_type,
+            _ => version_data_type(&component_type.data_type, version),
+        }
+    }
+
+    pub fn cast_primitive(
+        component: &str,
+        primitive: &V2PrimitiveType,
+        characters: &V2ParserCharacters,
+    ) -> V2Type {
+        let c = component;
+        match primitive {
+            V2PrimitiveType::DateTime => V2Type::V2DateTime(c.to_v2datetime()),
+            V2PrimitiveType::Date => V2Type::V2Date(c.to_v2date()),
+            V2PrimitiveType::Time => V2Type::V2Time(c.to_v2time()),
+            V2PrimitiveType::FT => {
+                V2Type::V2FT(c.to_v2formattedtext(&characters.repetition_separator))
+            }
+            V2PrimitiveType::Text => V2Type::V2Text(c.to_v2text(&characters.repetition_separator)),
+            V2PrimitiveType::String => V2Type::V2String(c.to_v2string()),
+            V2PrimitiveType::SNM => V2Type::V2SNM(c.to_v2telephonestring()),
+            V2PrimitiveType::ID => V2Type::V2ID(c.to_v2id()),
+            V2PrimitiveType::IS => V2Type::V2IS(c.to_v2is()),
+            V2PrimitiveType::NM => V2Type::V2NM(c.to_v2number()),
+            V2PrimitiveType::ST => V2Type::V2ST(c.to_v2stringdata()),
+            V2PrimitiveType::SI => V2Type::V2SI(c.to_v2sequenceid()),
+        }
+    }
+
+    ///
+    /// Casts the components of a complex type following its descriptor in [V2_FIELD_DESCRIPTORS]
+    /// (or its override for `version`). Each component carries its own result so one bad
+    /// component does not hide the others. Components past the ones defined for the type are
+    /// ignored.
+    ///
+    pub fn cast_complex(
+        components: &V2ComponentList,
+        complex: &V2ComplexType,
+        characters: &V2ParserCharacters,
+        version: V2Version,
+    ) -> V2Type {
+        let type_name = complex_type_to_str(complex);
+        let descriptor = match version_type_descriptor(complex, version) {
+            Some(descriptor) => descriptor,
+            None => return V2Type::Err(format_compact!("No descriptor for type {}!", type_name)),
+        };
+        let context = V2ConditionContext::new(components).with_version(version);
+        let mut typed_components = Vec::with_capacity(descriptor.len());
+        for component_type in descriptor.iter() {
+            let component = components
+                .get(component_type.seq as usize - 1)
+                .cloned()
+                .unwrap_or_default();
+            let value = if is_missing(&component)
+                && component_type
+                    .optionality
+                    .is_conditionally_required(&context)
+            {
+                V2Type::Err(format_compact!(
+                    "Conditionally required data in seq {} is missing!",
+                    component_type.seq
+                ))
+            } else {
+                let data_type = component_data_type(complex, component_type, version);
+                cast_component_as(component, component_type, &data_type, characters, version)
+            };
+            typed_components.push(V2TypedComponent {
+                name: component_type.name,
+                seq: component_type.seq,
+                value,
+            });
+        }
+        V2Type::V2Complex(*complex, typed_components)
+    }
+
+    ///
+    /// Casts a whole field (one repetition) to `data_type`. Primitive fields only use the first
+    /// component.
+    ///
+    pub fn cast_field(
+        field: &V2ComponentList,
+        data_type: &V2ComponentType,
+        characters: &V2ParserCharacters,
+        version: V2Version,
+    ) -> V2Type {
+        match data_type {
+            V2ComponentType::Primitive(primitive) => {
+                if field.iter().skip(1).any(|component| !is_missing(component)) {
+                    return V2Type::Err(format_compact!(
+                        "Field of primitive type {:?} has more than one component. Got [{:?}]",
+                        primitive,
+                        field
+                    ));
+                }
+                match field.first() {
+                    Some(component) if !is_missing(component) => {
+                        if component.len() > 1 {
+                            return V2Type::Err(format_compact!(
+                                "Field of primitive type {:?} has subcomponents. Got [{:?}]",
+                                primitive,
+                                component
+                            ));
+                        }
+                        cast_primitive(component[0], primitive, characters)
+                    }
+                    _ => V2Type::Empty,
+                }
+            }
+            V2ComponentType::Complex(complex) => match field.iter().all(|c| is_missing(c)) {
+                true => V2Type::Empty,
+                false => cast_complex(field, complex, characters, version),
+            },
+            V2ComponentType::Varies => V2Type::Err(format_compact!(
+                "The data type of the field varies! Resolve it before casting."
+            )),
+        }
+    }
+
     pub fn cast_component(
         component: Vec<&str>,
         component_type: &V2ComponentTypeDescriptor,
         characters: &V2ParserCharacters,
     ) -> V2Type {
-        if component_type.optionality.is_required() && component.len() == 0 {
-            return V2Type::Err(format_compact!(
-                "Required data in seq {} is missing!",
-                component_type.seq
-            ));
+        cast_component_as(
+            component,
+            component_type,
+            &component_type.data_type,
+            characters,
+            V2_DEFAULT_VERSION,
+        )
+    }
+
+    ///
+    /// Same as [cast_component] but casts to `data_type` instead of the type in the descriptor.
+    /// Complex types nested in the component are casted per `version`.
+    ///
+    pub fn cast_component_as(
+        component: Vec<&str>,
+        component_type: &V2ComponentTypeDescriptor,
+        data_type: &V2ComponentType,
+        characters: &V2ParserCharacters,
+        version: V2Version,
+    ) -> V2Type {
+        if is_missing(&component) {
+            return match component_type.optionality.is_required() {
+                true => V2Type::Err(format_compact!(
+                    "Required data in seq {} is missing!",
+                    component_type.seq
+                )),
+                false => V2Type::Empty,
+            };
         }
-        match &component_type.data_type {
+        match data_type {
             V2ComponentType::Primitive(primitive) => {
                 if component.len() > 1 {
                     V2Type::Err(
@@ -71,30 +334,21 @@ pub mod hl7_v2_complex_types {
                         )
                     )
                 } else {
-                    let c = component[0];
-                    match primitive {
-                        V2PrimitiveType::DateTime => V2Type::V2DateTime(c.to_v2datetime()),
-                        V2PrimitiveType::Date => V2Type::V2Date(c.to_v2date()),
-                        V2PrimitiveType::Time => V2Type::V2Time(c.to_v2time()),
-                        V2PrimitiveType::FT => {
-                            V2Type::V2FT(c.to_v2formattedtext(&characters.repetition_separator))
-                        }
-                        V2PrimitiveType::Text => {
-                            V2Type::V2Text(c.to_v2text(&characters.repetition_separator))
-                        }
-                        V2PrimitiveType::String => V2Type::V2String(c.to_v2string()),
-                        V2PrimitiveType::SNM => V2Type::V2SNM(c.to_v2telephonestring()),
-                        V2PrimitiveType::ID => V2Type::V2ID(c.to_v2id()),
-                        V2PrimitiveType::IS => V2Type::V2IS(c.to_v2is()),
-                        V2PrimitiveType::NM => V2Type::V2NM(c.to_v2number()),
-                        V2PrimitiveType::ST => V2Type::V2ST(c.to_v2stringdata()),
-                        V2PrimitiveType::SI => V2Type::V2SI(c.to_v2sequenceid()),
-                    }
+                    cast_primitive(component[0], primitive, characters)
                 }
             }
-            V2ComponentType::Complex(complex) => match complex {
-                _ => V2Type::Err(format_compact!("Unknown requested type!")),
-            },
+            V2ComponentType::Complex(complex) => {
+                // Components of a complex type nested in a component are its subcomponents.
+                let subcomponents: V2ComponentList = component
+                    .iter()
+                    .map(|subcomponent| vec![*subcomponent])
+                    .collect();
+                cast_complex(&subcomponents, complex, characters, version)
+            }
+            V2ComponentType::Varies => V2Type::Err(format_compact!(
+                "The data type of seq {} varies! Resolve it before casting.",
+                component_type.seq
+            )),
         }
     }
 }
@@ -18,11 +18,22 @@
  * Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301  USA
  */
 
-
 use ::phf::Map;
 use ::phf_macros::phf_map;
 
 pub const V2_MSHEADER_PATTERN: &str = "MSH";
+pub const V2_FILE_HEADER_PATTERN: &str = "FHS";
+pub const V2_FILE_TRAILER_PATTERN: &str = "FTS";
+pub const V2_BATCH_HEADER_PATTERN: &str = "BHS";
+pub const V2_BATCH_TRAILER_PATTERN: &str = "BTS";
+///
+/// Segments that start with the field separator and the encoding characters.
+///
+pub const V2_HEADER_SEGMENTS: [&str; 3] = [
+    V2_MSHEADER_PATTERN,
+    V2_FILE_HEADER_PATTERN,
+    V2_BATCH_HEADER_PATTERN,
+];
 pub const V2_DELETE_FIELD: &str = "\"\"";
 pub const V2_SEGMENT_TERMINATOR: char = '\r';
 pub const V2_TRUNCATION_CHARACTER: char = '#';
@@ -30,6 +41,31 @@ pub const V2_TRUNCATION_CHARACTER: char = '#';
 pub const V2_DATETIME_THOUSAND_TICK: u16 = 1000;
 pub const V2_DATETIME_MICRO_LENGTH: u8 = 4;
 pub const V2_EMPTY_STRING: &str = "";
+pub const V2_CUSTOM_SEGMENT_PREFIX: char = 'Z';
+pub const V2_CUSTOM_SEGMENT_PATTERN: &str = "Z**";
+///
+/// First id handed out to segments not present in [V2_SEGMENT_IDS]. Ids from here up to
+/// [u8::MAX] are assigned per message, so they are only meaningful within the message that
+/// assigned them.
+///
+pub const V2_CUSTOM_SEGMENT_ID_START: u8 = 194;
+///
+/// Id used by searches that reference a segment missing from [V2_SEGMENT_IDS]. The segment is
+/// then looked up by name in the message being searched. No segment in a message ever uses it.
+///
+pub const V2_UNKNOWN_SEGMENT_ID: u8 = 0;
+
+///
+/// MSH field positions as used by [V2Segment::get](crate::hl7_v2_parser::v2_parser::V2Segment::get).
+/// The parser stores the encoding characters (MSH-2) as the first field because MSH-1 is the
+/// field separator itself, so every MSH position is one less than its number in the standard.
+///
+pub const V2_MSH_DATETIME: isize = 6;
+pub const V2_MSH_MESSAGE_TYPE: isize = 8;
+pub const V2_MSH_CONTROL_ID: isize = 9;
+pub const V2_MSH_VERSION: isize = 11;
+pub const V2_MSH_CHARSET: isize = 17;
+pub const V2_MSH_ALTERNATE_CHARSET_HANDLING: isize = 19;
 
 pub type ElementDesc = Map<&'static str, &'static str>;
 pub type ElementID = Map<&'static str, u8>;
@@ -740,6 +776,21 @@ pub static V2_MESSAGE_DESC: ElementDesc = phf_map! {
     "VXU" => "Vaccine Update"
 };
 
+///
+/// Maps message type and trigger event pairs (MSH-9.1 and MSH-9.2) to the message structure
+/// (MSH-9.3) they use. Only needed when a sender leaves MSH-9.3 empty.
+///
+pub static V2_MESSAGE_STRUCTURE_BY_EVENT: ElementDesc = phf_map! {
+    "ADT_A01" => "ADT_A01",
+    "ADT_A04" => "ADT_A01",
+    "ADT_A08" => "ADT_A01",
+    "ADT_A13" => "ADT_A01",
+    "OML_O21" => "OML_O21",
+    "ORU_R01" => "ORU_R01",
+    "VXU_V04" => "VXU_V04"
+};
+
 pub enum V2_SEARCH_EXPR_TYPE {
-    V2_DEFAULT
+    V2_DEFAULT,
+    V2_QUERY,
 }
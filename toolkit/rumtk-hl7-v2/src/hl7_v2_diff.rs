@@ -0,0 +1,434 @@
+/*
+ * rumtk attempts to implement HL7 and medical protocols for interoperability in medicine.
+ * This toolkit aims to be reliable, simple, performant, and standards compliant.
+ * Copyright (C) 2024  Luis M. Santos, M.D.
+ *
+ * This library is free software; you can redistribute it and/or
+ * modify it under the terms of the GNU Lesser General Public
+ * License as published by the Free Software Foundation; either
+ * version 2.1 of the License, or (at your option) any later version.
+ *
+ * This library is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+ * Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public
+ * License along with this library; if not, write to the Free Software
+ * Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301  USA
+ */
+
+///
+/// Structural comparison of two messages. Segments are paired by name and occurrence (the
+/// second OBX of one message is compared with the second OBX of the other), then compared
+/// field by field, repetition by repetition, and component by component. Differences are
+/// reported at the deepest level that has structure on both sides, so a changed family name
+/// shows up as `PID(1)5[1].1` rather than as the whole of PID-5.
+///
+/// Positions use the same numbering as [V2Message::find_component], so they can be handed back
+/// to it. Values are compared with their escape sequences interpreted, so messages that only
+/// differ in their encoding characters are equal. The encoding characters themselves (MSH-2)
+/// are not compared.
+///
+/// ## Example
+///
+/// ```text
+/// ~ PID(1)5[1].1: EVERYMAN -> EVERYWOMAN
+/// + PID(1)13[2]: ^NET^Internet^adam@example.com
+/// - NK1(1): NK1|1|NUCLEAR^NELDA^W|SPO^SPOUSE
+/// ```
+///
+pub mod v2_diff {
+    use crate::hl7_v2_base_types::v2_base_types::{is_header_segment_name, V2String};
+    use crate::hl7_v2_constants::{V2_MSHEADER_PATTERN, V2_MSH_CONTROL_ID, V2_MSH_DATETIME};
+    use crate::hl7_v2_parser::v2_parser::{
+        V2Component, V2Field, V2Message, V2ParserCharacters, V2Segment,
+    };
+    use rumtk_core::cache::AHashMap;
+    use rumtk_core::strings::{RUMString, RUMStringConversions};
+    use std::fmt::Display;
+
+    /**************************** Types *****************************************/
+
+    ///
+    /// Location of a change. Levels below the one the change was found at are `None`.
+    ///
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct V2DiffPosition {
+        pub segment: RUMString,
+        pub segment_group: usize,
+        pub field: Option<usize>,
+        pub repetition: Option<usize>,
+        pub component: Option<usize>,
+        pub subcomponent: Option<usize>,
+    }
+
+    impl Display for V2DiffPosition {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{}({})", &self.segment, self.segment_group)?;
+            if let Some(field) = self.field {
+                write!(f, "{}", field)?;
+            }
+            if let Some(repetition) = self.repetition {
+                write!(f, "[{}]", repetition)?;
+            }
+            if let Some(component) = self.component {
+                write!(f, ".{}", component)?;
+            }
+            if let Some(subcomponent) = self.subcomponent {
+                write!(f, ".{}", subcomponent)?;
+            }
+            Ok(())
+        }
+    }
+
+    ///
+    /// A single difference between two messages. Values of whole segments, repetitions, or
+    /// components are in the wire format of the message they come from.
+    ///
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum V2Change {
+        Added {
+            position: V2DiffPosition,
+            value: V2String,
+        },
+        Removed {
+            position: V2DiffPosition,
+            value: V2String,
+        },
+        Modified {
+            position: V2DiffPosition,
+            old: V2String,
+            new: V2String,
+        },
+    }
+
+    impl V2Change {
+        pub fn position(&self) -> &V2DiffPosition {
+            match self {
+                V2Change::Added { position, .. }
+                | V2Change::Removed { position, .. }
+                | V2Change::Modified { position, .. } => position,
+            }
+        }
+    }
+
+    impl Display for V2Change {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                V2Change::Added { position, value } => write!(f, "+ {}: {}", position, value),
+                V2Change::Removed { position, value } => write!(f, "- {}: {}", position, value),
+                V2Change::Modified { position, old, new } => {
+                    write!(f, "~ {}: {} -> {}", position, old, new)
+                }
+            }
+        }
+    }
+
+    ///
+    /// What to leave out of a comparison. Fields are given by their position as used by
+    /// [V2Segment::get], e.g. [V2_MSH_DATETIME] for MSH-7.
+    ///
+    #[derive(Debug, Default, Clone, PartialEq, Eq)]
+    pub struct V2DiffRules {
+        ignored_segments: Vec<RUMString>,
+        ignored_fields: Vec<(RUMString, usize)>,
+    }
+
+    impl V2DiffRules {
+        pub fn new() -> V2DiffRules {
+            V2DiffRules::default()
+        }
+
+        ///
+        /// Ignores the fields that change every time a message is sent: the date/time of the
+        /// message (MSH-7) and the message control ID (MSH-10).
+        ///
+        pub fn volatile() -> V2DiffRules {
+            V2DiffRules::new()
+                .ignore_field(V2_MSHEADER_PATTERN, V2_MSH_DATETIME as usize)
+                .ignore_field(V2_MSHEADER_PATTERN, V2_MSH_CONTROL_ID as usize)
+        }
+
+        pub fn ignore_segment(mut self, segment: &str) -> V2DiffRules {
+            self.ignored_segments.push(segment.to_rumstring());
+            self
+        }
+
+        pub fn ignore_field(mut self, segment: &str, field: usize) -> V2DiffRules {
+            self.ignored_fields.push((segment.to_rumstring(), field));
+            self
+        }
+
+        pub fn is_segment_ignored(&self, segment: &str) -> bool {
+            self.ignored_segments.iter().any(|s| s == segment)
+        }
+
+        pub fn is_field_ignored(&self, segment: &str, field: usize) -> bool {
+            self.ignored_fields
+                .iter()
+                .any(|(s, f)| s == segment && *f == field)
+        }
+    }
+
+    ///
+    /// Result of [V2Message::diff]. Changes are listed in the order of the segments of the old
+    /// message, followed by the segments only found in the new one.
+    ///
+    #[derive(Debug, Default, Clone, PartialEq, Eq)]
+    pub struct V2Diff {
+        changes: Vec<V2Change>,
+    }
+
+    impl V2Diff {
+        pub fn changes(&self) -> &Vec<V2Change> {
+            &self.changes
+        }
+
+        pub fn len(&self) -> usize {
+            self.changes.len()
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.changes.is_empty()
+        }
+
+        ///
+        /// Readable report with one change per line. `+` marks additions, `-` removals, and `~`
+        /// modifications.
+        ///
+        pub fn report(&self) -> V2String {
+            let lines: Vec<String> = self.changes.iter().map(|c| c.to_string()).collect();
+            lines.join("\n").to_rumstring()
+        }
+    }
+
+    impl Display for V2Diff {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{}", self.report())
+        }
+    }
+
+    /**************************** Helpers ***************************************/
+
+    ///
+    /// Walks both messages and collects their differences.
+    ///
+    struct V2Differ<'a> {
+        old_chars: &'a V2ParserCharacters,
+        new_chars: &'a V2ParserCharacters,
+        rules: &'a V2DiffRules,
+        changes: Vec<V2Change>,
+    }
+
+    impl<'a> V2Differ<'a> {
+        fn diff_segment(
+            &mut self,
+            position: V2DiffPosition,
+            old: Option<&V2Segment>,
+            new: Option<&V2Segment>,
+        ) {
+            let (old, new) = match (old, new) {
+                (Some(old), Some(new)) => (old, new),
+                (Some(old), None) => {
+                    let value = old.to_string(self.old_chars);
+                    return self.changes.push(V2Change::Removed { position, value });
+                }
+                (None, Some(new)) => {
+                    let value = new.to_string(self.new_chars);
+                    return self.changes.push(V2Change::Added { position, value });
+                }
+                (None, None) => return,
+            };
+            // The encoding characters of header segments are not data.
+            let first = match is_header_segment_name(old.name()) {
+                true => 2,
+                false => 1,
+            };
+            for field in first..=old.len().max(new.len()) {
+                if self.rules.is_field_ignored(old.name(), field) {
+                    continue;
+                }
+                let old_field = old
+                    .get(field as isize)
+                    .ok()
+                    .map(|f| f.as_slice())
+                    .unwrap_or(&[]);
+                let new_field = new
+                    .get(field as isize)
+                    .ok()
+                    .map(|f| f.as_slice())
+                    .unwrap_or(&[]);
+                let position = V2DiffPosition {
+                    field: Some(field),
+                    ..position.clone()
+                };
+                self.diff_field(position, old_field, new_field);
+            }
+        }
+
+        fn diff_field(&mut self, position: V2DiffPosition, old: &[V2Field], new: &[V2Field]) {
+            for repetition in 1..=old.len().max(new.len()) {
+                let position = V2DiffPosition {
+                    repetition: Some(repetition),
+                    ..position.clone()
+                };
+                let old = old.get(repetition - 1).filter(|f| !f.is_empty());
+                let new = new.get(repetition - 1).filter(|f| !f.is_empty());
+                match (old, new) {
+                    (Some(old), Some(new)) if old.len() <= 1 && new.len() <= 1 => {
+                        self.diff_component(position, old.get(1).ok(), new.get(1).ok())
+                    }
+                    (Some(old), Some(new)) => {
+                        for component in 1..=old.len().max(new.len()) {
+                            let position = V2DiffPosition {
+                                component: Some(component),
+                                ..position.clone()
+                            };
+                            self.diff_component(
+                                position,
+                                old.get(component as isize).ok(),
+                                new.get(component as isize).ok(),
+                            );
+                        }
+                    }
+                    (Some(old), None) => self.changes.push(V2Change::Removed {
+                        position,
+                        value: old.to_string(self.old_chars),
+                    }),
+                    (None, Some(new)) => self.changes.push(V2Change::Added {
+                        position,
+                        value: new.to_string(self.new_chars),
+                    }),
+                    (None, None) => {}
+                }
+            }
+        }
+
+        fn diff_component(
+            &mut self,
+            position: V2DiffPosition,
+            old: Option<&V2Component>,
+            new: Option<&V2Component>,
+        ) {
+            let old = old.filter(|c| !c.is_empty());
+            let new = new.filter(|c| !c.is_empty());
+            match (old, new) {
+                (Some(old), Some(new)) if old.len() <= 1 && new.len() <= 1 => {
+                    self.diff_value(position, old, new, None)
+                }
+                (Some(old), Some(new)) => {
+                    for subcomponent in 1..=old.len().max(new.len()) {
+                        let position = V2DiffPosition {
+                            subcomponent: Some(subcomponent),
+                            ..position.clone()
+                        };
+                        self.diff_value(position, old, new, Some(subcomponent));
+                    }
+                }
+                (Some(old), None) => self.changes.push(V2Change::Removed {
+                    position,
+                    value: old.to_string(),
+                }),
+                (None, Some(new)) => self.changes.push(V2Change::Added {
+                    position,
+                    value: new.to_string(),
+                }),
+                (None, None) => {}
+            }
+        }
+
+        fn diff_value(
+            &mut self,
+            position: V2DiffPosition,
+            old: &V2Component,
+            new: &V2Component,
+            subcomponent: Option<usize>,
+        ) {
+            let decode = |component: &V2Component, chars: &V2ParserCharacters| -> V2String {
+                let subcomponent = component.get(subcomponent.unwrap_or(1) as isize);
+                match subcomponent {
+                    Ok(subcomponent) => subcomponent
+                        .decoded(chars)
+                        .unwrap_or_else(|_| subcomponent.to_string()),
+                    Err(_) => V2String::default(),
+                }
+            };
+            let old = decode(old, self.old_chars);
+            let new = decode(new, self.new_chars);
+            let change = match (old.is_empty(), new.is_empty()) {
+                _ if old == new => return,
+                (true, false) => V2Change::Added {
+                    position,
+                    value: new,
+                },
+                (false, true) => V2Change::Removed {
+                    position,
+                    value: old,
+                },
+                _ => V2Change::Modified { position, old, new },
+            };
+            self.changes.push(change);
+        }
+    }
+
+    /**************************** API *******************************************/
+
+    impl V2Message {
+        ///
+        /// Compares this message (the old one) with `other` (the new one). See the module
+        /// documentation for how segments are paired and where changes are reported.
+        ///
+        pub fn diff(&self, other: &V2Message, rules: &V2DiffRules) -> V2Diff {
+            let old_segments = number_segments(self);
+            let new_segments = number_segments(other);
+            let mut differ = V2Differ {
+                old_chars: self.separators(),
+                new_chars: other.separators(),
+                rules,
+                changes: Vec::new(),
+            };
+            let paired = old_segments.iter().map(|(key, old)| {
+                let new = new_segments.iter().find(|(k, _)| k == key).map(|(_, s)| *s);
+                (key, Some(*old), new)
+            });
+            let added = new_segments
+                .iter()
+                .filter(|(key, _)| !old_segments.iter().any(|(k, _)| k == key))
+                .map(|(key, new)| (key, None, Some(*new)));
+            for ((name, segment_group), old, new) in paired.chain(added) {
+                if rules.is_segment_ignored(name) {
+                    continue;
+                }
+                let position = V2DiffPosition {
+                    segment: name.clone(),
+                    segment_group: *segment_group,
+                    field: None,
+                    repetition: None,
+                    component: None,
+                    subcomponent: None,
+                };
+                differ.diff_segment(position, old, new);
+            }
+            V2Diff {
+                changes: differ.changes,
+            }
+        }
+    }
+
+    ///
+    /// Pairs each segment with its name and occurrence (1-indexed) in message order.
+    ///
+    fn number_segments(message: &V2Message) -> Vec<((RUMString, usize), &V2Segment)> {
+        let mut counts: AHashMap<RUMString, usize> = AHashMap::default();
+        message
+            .segments()
+            .map(|segment| {
+                let name = segment.name().to_rumstring();
+                let count = counts.entry(name.clone()).or_insert(0);
+                *count += 1;
+                ((name, *count), segment)
+            })
+            .collect()
+    }
+}
@@ -21,16 +21,16 @@
 pub mod v2_field_descriptor {
     use crate::hl7_v2_base_types::v2_primitives::V2PrimitiveType;
     pub use crate::hl7_v2_optionality_rules::*;
-    pub use once_cell::unsync::Lazy;
     use ::phf::Map;
     use ::phf_macros::phf_map;
+    pub use once_cell::unsync::Lazy;
 
     ///
     /// Enumerator listing every complex type we have defined so far. Complex type definitions here
     /// will be used to guide type casting of the string components of a field into the proper primitive
     /// component types and overall field structure.
     ///
-    #[derive(Debug)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
     pub enum V2ComplexType {
         ///
         /// # 2A.3.1AD - address
@@ -877,20 +877,1125 @@ pub mod v2_field_descriptor {
         ///
         CQ,
         ///
-        ///
+        /// # 2A.3.12 CSU - channel sensitivity and units
+        ///
+        /// **Definition:** This data type defines the channel sensitivity (gain) and the units in which it
+        /// is measured in a waveform result. The unit of measure components follow the CWE layout.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Channel Sensitivity (NM)
+        ///     2. Unit of Measure Identifier (ST)
+        ///     3. Unit of Measure Description (ST)
+        ///     4. Unit of Measure Coding System (ID)
+        ///     5. Alternate Unit of Measure Identifier (ST)
+        ///     6. Alternate Unit of Measure Description (ST)
+        ///     7. Alternate Unit of Measure Coding System (ID)
+        ///     8. Unit of Measure Coding System Version ID (ST)
+        ///     9. Alternate Unit of Measure Coding System Version ID (ST)
+        ///     10. Original Text (ST)
+        ///     11. Second Alternate Unit of Measure Identifier (ST)
+        ///     12. Second Alternate Unit of Measure Text (ST)
+        ///     13. Name of Second Alternate Unit of Measure Coding System (ID)
+        ///     14. Second Alternate Unit of Measure Coding System Version ID (ST)
+        ///     15. Unit of Measure Coding System OID (ST)
+        ///     16. Unit of Measure Value Set OID (ST)
+        ///     17. Unit of Measure Value Set Version ID (DTM)
+        ///     18. Alternate Unit of Measure Coding System OID (ST)
+        ///     19. Alternate Unit of Measure Value Set OID (ST)
+        ///     20. Alternate Unit of Measure Value Set Version ID (DTM)
+        ///     21. Second Alternate Unit of Measure Coding System OID (ST)
+        ///     22. Second Alternate Unit of Measure Value Set OID (ST)
+        ///     23. Second Alternate Unit of Measure Value Set Version ID (DTM)
+        /// ```
         ///
         CSU,
+        ///
+        /// # 2A.3.13 CWE - coded with exceptions
+        ///
+        /// **Definition:** Specifies a coded element and its associated detail. The CWE data type is used
+        /// when more than one table may be applicable, the specified HL7 or externally defined table may be
+        /// extended with local values, or when text is in place, the code may be omitted.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Identifier (ST)
+        ///     2. Text (ST)
+        ///     3. Name of Coding System (ID)
+        ///     4. Alternate Identifier (ST)
+        ///     5. Alternate Text (ST)
+        ///     6. Name of Alternate Coding System (ID)
+        ///     7. Coding System Version ID (ST)
+        ///     8. Alternate Coding System Version ID (ST)
+        ///     9. Original Text (ST)
+        ///     10. Second Alternate Identifier (ST)
+        ///     11. Second Alternate Text (ST)
+        ///     12. Name of Second Alternate Coding System (ID)
+        ///     13. Second Alternate Coding System Version ID (ST)
+        ///     14. Coding System OID (ST)
+        ///     15. Value Set OID (ST)
+        ///     16. Value Set Version ID (DTM)
+        ///     17. Alternate Coding System OID (ST)
+        ///     18. Alternate Value Set OID (ST)
+        ///     19. Alternate Value Set Version ID (DTM)
+        ///     20. Second Alternate Coding System OID (ST)
+        ///     21. Second Alternate Value Set OID (ST)
+        ///     22. Second Alternate Value Set Version ID (DTM)
+        /// ```
+        ///
         CWE,
+        ///
+        /// # 2A.3.14 CX - extended composite ID with check digit
+        ///
+        /// **Definition:** This data type is used for specifying an identifier with its associated
+        /// administrative detail.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. ID Number (ST)
+        ///     2. Identifier Check Digit (ST)
+        ///     3. Check Digit Scheme (ID)
+        ///     4. Assigning Authority (HD)
+        ///     5. Identifier Type Code (ID)
+        ///     6. Assigning Facility (HD)
+        ///     7. Effective Date (DT)
+        ///     8. Expiration Date (DT)
+        ///     9. Assigning Jurisdiction (CWE)
+        ///     10. Assigning Agency or Department (CWE)
+        ///     11. Security Check (ST)
+        ///     12. Security Check Scheme (ID)
+        /// ```
+        ///
+        CX,
+        ///
+        /// # 2A.3.15 DDI - daily deductible information
+        ///
+        /// **Definition:** Specifies the detail information for the daily deductible.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Delay Days (NM)
+        ///     2. Monetary Amount (MO)
+        ///     3. Number of Days (NM)
+        /// ```
+        ///
+        DDI,
+        ///
+        /// # 2A.3.16 DIN - date and institution name
+        ///
+        /// **Definition:** Specifies the date and the institution that relate to an activity or event.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Date (DTM)
+        ///     2. Institution Name (CWE)
+        /// ```
+        ///
+        DIN,
+        ///
+        /// # 2A.3.17 DLD - discharge to location and date
+        ///
+        /// **Definition:** Specifies the healthcare facility to which the patient was discharged and the
+        /// date.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Discharge to Location (CWE)
+        ///     2. Effective Date (DTM)
+        /// ```
+        ///
+        DLD,
+        ///
+        /// # 2A.3.18 DLN - driver's license number
+        ///
+        /// **Definition:** This data type gives the driver's license information.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. License Number (ST)
+        ///     2. Issuing State, Province, Country (CWE)
+        ///     3. Expiration Date (DT)
+        /// ```
+        ///
+        DLN,
+        ///
+        /// # 2A.3.19 DLT - delta
+        ///
+        /// **Definition:** Describes the information that controls delta check warnings.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Normal Range (NR)
+        ///     2. Numeric Threshold (NM)
+        ///     3. Change Computation (ID)
+        ///     4. Days Retained (NM)
+        /// ```
+        ///
+        DLT,
+        ///
+        /// # 2A.3.20 DR - date/time range
+        ///
+        /// **Definition:** Specifies an interval between two date/time values. Either bound may be omitted
+        /// to leave the range open ended.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Range Start Date/Time (DTM)
+        ///     2. Range End Date/Time (DTM)
+        /// ```
+        ///
+        DR,
+        ///
+        /// # 2A.3.23 DTN - day type and number
+        ///
+        /// **Definition:** Indicates a type of day and the number of days that apply to it, e.g. days
+        /// approved for a stay.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Day Type (IS)
+        ///     2. Number of Days (NM)
+        /// ```
+        ///
+        DTN,
+        ///
+        /// # 2A.3.24 ED - encapsulated data
+        ///
+        /// **Definition:** This data type transmits encapsulated data from a source system to a destination
+        /// system. It contains the identity of the source system, the type of data, the encoding method of
+        /// the data, and the data itself.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Source Application (HD)
+        ///     2. Type of Data (ID)
+        ///     3. Data Subtype (ID)
+        ///     4. Encoding (ID)
+        ///     5. Data (TX)
+        /// ```
+        ///
+        ED,
+        ///
+        /// # 2A.3.25 EI - entity identifier
+        ///
+        /// **Definition:** The entity identifier defines a given entity within a specified series of
+        /// identifiers.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Entity Identifier (ST)
+        ///     2. Namespace ID (IS)
+        ///     3. Universal ID (ST)
+        ///     4. Universal ID Type (ID)
+        /// ```
+        ///
+        EI,
+        ///
+        /// # 2A.3.26 EIP - entity identifier pair
+        ///
+        /// **Definition:** Specifies an identifier assigned to an entity by either the placer or the filler
+        /// system. If both components are populated the identifiers must refer to the same entity.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Placer Assigned Identifier (EI)
+        ///     2. Filler Assigned Identifier (EI)
+        /// ```
+        ///
+        EIP,
+        ///
+        /// # WITHDRAWN (ELD - error location and description)
+        ///
+        /// **Attention: The ELD data type was retained for backward compatibility only as of v2.5 and
+        /// withdrawn as of v2.7.** It is used in ERR-1 and replaced by the ERL data type in ERR-2.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Segment ID (ST)
+        ///     2. Segment Sequence (NM)
+        ///     3. Field Position (NM)
+        ///     4. Code Identifying Error (CE)
+        /// ```
+        ///
+        ELD,
+        ///
+        /// # 2A.3.27 ERL - error location
+        ///
+        /// **Definition:** This data type identifies the segment and its constituent where an error has
+        /// occurred.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Segment ID (ST)
+        ///     2. Segment Sequence (NM)
+        ///     3. Field Position (NM)
+        ///     4. Field Repetition (NM)
+        ///     5. Component Number (NM)
+        ///     6. Sub-Component Number (NM)
+        /// ```
+        ///
+        ERL,
+        ///
+        /// # 2A.3.28 FC - financial class
+        ///
+        /// **Definition:** Specifies the financial class assigned to a person and its effective date.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Financial Class Code (CWE)
+        ///     2. Effective Date (DTM)
+        /// ```
+        ///
+        FC,
+        ///
+        /// # 2A.3.29 FN - family name
+        ///
+        /// **Definition:** This data type allows full specification of the surname of a person. Where
+        /// appropriate, it differentiates the person's own surname from that of the person's partner or
+        /// spouse, in cases where the person's name may contain elements from either name.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Surname (ST)
+        ///     2. Own Surname Prefix (ST)
+        ///     3. Own Surname (ST)
+        ///     4. Surname Prefix From Partner/Spouse (ST)
+        ///     5. Surname From Partner/Spouse (ST)
+        /// ```
+        ///
+        FN,
+        ///
+        /// # 2A.3.32 HD - hierarchic designator
+        ///
+        /// **Definition:** The basic definition of the HD is that it identifies an (HL7) application,
+        /// system, location or organization. It is either a local namespace, a universal identifier with
+        /// its type, or both.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Namespace ID (IS)
+        ///     2. Universal ID (ST)
+        ///     3. Universal ID Type (ID)
+        /// ```
+        ///
+        HD,
+        ///
+        /// # 2A.3.33 ICD - insurance certification definition
+        ///
+        /// **Definition:** Specifies whether a certification is required for a patient type and when.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Certification Patient Type (IS)
+        ///     2. Certification Required (ID)
+        ///     3. Date/Time Certification Required (DTM)
+        /// ```
+        ///
+        ICD,
+        ///
+        /// # 2A.3.36 JCC - job code/class
+        ///
+        /// **Definition:** Specifies a code that identifies a person's job code and employee
+        /// classification.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Job Code (IS)
+        ///     2. Job Class (IS)
+        ///     3. Job Description Text (TX)
+        /// ```
+        ///
+        JCC,
+        ///
+        /// # 2A.3.37 LA1 - location with address variation 1
+        ///
+        /// **Definition:** Specifies a location and its address. Retained for backward compatibility.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Point of Care (IS)
+        ///     2. Room (IS)
+        ///     3. Bed (IS)
+        ///     4. Facility (HD)
+        ///     5. Location Status (IS)
+        ///     6. Patient Location Type (IS)
+        ///     7. Building (IS)
+        ///     8. Floor (IS)
+        ///     9. Address (AD)
+        /// ```
+        ///
+        LA1,
+        ///
+        /// # 2A.3.38 LA2 - location with address variation 2
+        ///
+        /// **Definition:** Specifies a location and its address with the address components flattened.
+        /// Retained for backward compatibility.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Point of Care (IS)
+        ///     2. Room (IS)
+        ///     3. Bed (IS)
+        ///     4. Facility (HD)
+        ///     5. Location Status (IS)
+        ///     6. Patient Location Type (IS)
+        ///     7. Building (IS)
+        ///     8. Floor (IS)
+        ///     9. Street Address (ST)
+        ///     10. Other Designation (ST)
+        ///     11. City (ST)
+        ///     12. State or Province (ST)
+        ///     13. Zip or Postal Code (ST)
+        ///     14. Country (ID)
+        ///     15. Address Type (ID)
+        ///     16. Other Geographic Designation (ST)
+        /// ```
+        ///
+        LA2,
+        ///
+        /// # 2A.3.40 MO - money
+        ///
+        /// **Definition:** Specifies an amount of money and the denomination in which it is expressed.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Quantity (NM)
+        ///     2. Denomination (ID)
+        /// ```
+        ///
         MO,
+        ///
+        /// # 2A.3.41 MOC - money and code
+        ///
+        /// **Definition:** Transmits a monetary amount and an associated charge code.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Monetary Amount (MO)
+        ///     2. Charge Code (CWE)
+        /// ```
+        ///
+        MOC,
+        ///
+        /// # 2A.3.42 MOP - money or percentage
+        ///
+        /// **Definition:** Transmits an amount that is either a monetary value or a percentage.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Money or Percentage Indicator (ID)
+        ///     2. Money or Percentage Quantity (NM)
+        ///     3. Monetary Denomination (ID)
+        /// ```
+        ///
+        MOP,
+        ///
+        /// # 2A.3.43 MSG - message type
+        ///
+        /// **Definition:** This field contains the message type, trigger event, and the message structure
+        /// ID for the message.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Message Code (ID)
+        ///     2. Trigger Event (ID)
+        ///     3. Message Structure (ID)
+        /// ```
+        ///
+        MSG,
+        ///
+        /// # 2A.3.45 NDL - name with date and location
+        ///
+        /// **Definition:** Specifies the name of the person performing a service, when the person performed
+        /// the service and where the person performed the service.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Name (CNN)
+        ///     2. Start Date/time (DTM)
+        ///     3. End Date/time (DTM)
+        ///     4. Point of Care (IS)
+        ///     5. Room (IS)
+        ///     6. Bed (IS)
+        ///     7. Facility (HD)
+        ///     8. Location Status (IS)
+        ///     9. Patient Location Type (IS)
+        ///     10. Building (IS)
+        ///     11. Floor (IS)
+        /// ```
+        ///
+        NDL,
+        ///
+        /// # 2A.3.47 NR - numeric range
+        ///
+        /// **Definition:** Specifies the interval between the lowest and the highest values in a series of
+        /// data. Either bound may be omitted.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Low Value (NM)
+        ///     2. High Value (NM)
+        /// ```
+        ///
         NR,
+        ///
+        /// # 2A.3.48 OCD - occurrence code and date
+        ///
+        /// **Definition:** Specifies an occurrence code and the date it applies to.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Occurrence Code (CNE)
+        ///     2. Occurrence Date (DT)
+        /// ```
+        ///
+        OCD,
+        ///
+        /// # 2A.3.49 OSD - order sequence definition
+        ///
+        /// **Definition:** This data type specifies a fully coded version for forming a relationship
+        /// between an order and one or more other orders. Retained for backward compatibility.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Sequence/Results Flag (ID)
+        ///     2. Placer Order Number: Entity Identifier (ST)
+        ///     3. Placer Order Number: Namespace ID (IS)
+        ///     4. Filler Order Number: Entity Identifier (ST)
+        ///     5. Filler Order Number: Namespace ID (IS)
+        ///     6. Sequence Condition Value (ST)
+        ///     7. Maximum Number of Repeats (NM)
+        ///     8. Placer Order Number: Universal ID (ST)
+        ///     9. Placer Order Number: Universal ID Type (ID)
+        ///     10. Filler Order Number: Universal ID (ST)
+        ///     11. Filler Order Number: Universal ID Type (ID)
+        /// ```
+        ///
+        OSD,
+        ///
+        /// # 2A.3.50 OSP - occurrence span code and date
+        ///
+        /// **Definition:** Specifies an occurrence span code and the dates it spans.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Occurrence Span Code (CNE)
+        ///     2. Occurrence Span Start Date (DT)
+        ///     3. Occurrence Span Stop Date (DT)
+        /// ```
+        ///
+        OSP,
+        ///
+        /// # 2A.3.51 PIP - practitioner institutional privileges
+        ///
+        /// **Definition:** Specifies the privileges a practitioner has been granted at an institution.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Privilege (CWE)
+        ///     2. Privilege Class (CWE)
+        ///     3. Expiration Date (DT)
+        ///     4. Activation Date (DT)
+        ///     5. Facility (EI)
+        /// ```
+        ///
+        PIP,
+        ///
+        /// # 2A.3.52 PL - person location
+        ///
+        /// **Definition:** This data type is used to specify a patient location within a healthcare
+        /// institution. Which components are valued depends on the needs of the site.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Point of Care (HD)
+        ///     2. Room (HD)
+        ///     3. Bed (HD)
+        ///     4. Facility (HD)
+        ///     5. Location Status (IS)
+        ///     6. Person Location Type (IS)
+        ///     7. Building (HD)
+        ///     8. Floor (HD)
+        ///     9. Location Description (ST)
+        ///     10. Comprehensive Location Identifier (EI)
+        ///     11. Assigning Authority for Location (HD)
+        /// ```
+        ///
+        PL,
+        ///
+        /// # 2A.3.53 PLN - practitioner license or other ID number
+        ///
+        /// **Definition:** This data type specifies a practitioner's license number, or other ID number
+        /// such as UPIN, Medicare and Medicaid number, and associated detail.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. ID Number (ST)
+        ///     2. Type of ID Number (CWE)
+        ///     3. State/other Qualifying Information (ST)
+        ///     4. Expiration Date (DT)
+        /// ```
+        ///
+        PLN,
+        ///
+        /// # 2A.3.54 PPN - performing person time stamp
+        ///
+        /// **Definition:** This data type is the equivalent of an XCN data type joined with a DTM data
+        /// type. It identifies who performed an action and when.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Person Identifier (ST)
+        ///     2. Family Name (FN)
+        ///     3. Given Name (ST)
+        ///     4. Second and Further Given Names or Initials Thereof (ST)
+        ///     5. Suffix (e.g. JR or III) (ST)
+        ///     6. Prefix (e.g. DR) (ST)
+        ///     7. Degree (e.g. MD) (IS)
+        ///     8. Source Table (CWE)
+        ///     9. Assigning Authority (HD)
+        ///     10. Name Type Code (ID)
+        ///     11. Identifier Check Digit (ST)
+        ///     12. Check Digit Scheme (ID)
+        ///     13. Identifier Type Code (ID)
+        ///     14. Assigning Facility (HD)
+        ///     15. Date/Time Action Performed (DTM)
+        ///     16. Name Representation Code (ID)
+        ///     17. Name Context (CWE)
+        ///     18. Name Validity Range (DR)
+        ///     19. Name Assembly Order (ID)
+        ///     20. Effective Date (DTM)
+        ///     21. Expiration Date (DTM)
+        ///     22. Professional Suffix (ST)
+        ///     23. Assigning Jurisdiction (CWE)
+        ///     24. Assigning Agency or Department (CWE)
+        ///     25. Security Check (ST)
+        ///     26. Security Check Scheme (ID)
+        /// ```
+        ///
+        PPN,
+        ///
+        /// # 2A.3.55 PRL - parent result link
+        ///
+        /// **Definition:** This data type is used to construct a link to a parent result.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Parent Observation Identifier (CWE)
+        ///     2. Parent Observation Sub-identifier (ST)
+        ///     3. Parent Observation Value Descriptor (TX)
+        /// ```
+        ///
+        PRL,
+        ///
+        /// # 2A.3.56 PT - processing type
+        ///
+        /// **Definition:** This data type indicates whether to process a message as defined in HL7
+        /// Application (level 7) Processing rules.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Processing ID (ID)
+        ///     2. Processing Mode (ID)
+        /// ```
+        ///
+        PT,
+        ///
+        /// # 2A.3.57 PTA - policy type and amount
+        ///
+        /// **Definition:** Specifies the policy type and the amount covered by the insurance.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Policy Type (IS)
+        ///     2. Amount Class (IS)
+        ///     3. Money or Percentage Quantity (NM)
+        ///     4. Money or Percentage (MOP)
+        /// ```
+        ///
+        PTA,
+        ///
+        /// # 2A.3.58 QIP - query input parameter list
+        ///
+        /// **Definition:** This data type is used to specify the segment field name and the values to
+        /// search for in a query.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Segment Field Name (ST)
+        ///     2. Values (ST)
+        /// ```
+        ///
+        QIP,
+        ///
+        /// # 2A.3.59 QSC - query selection criteria
+        ///
+        /// **Definition:** This data type is used to specify the selection criteria of a query.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Segment Field Name (ST)
+        ///     2. Relational Operator (ID)
+        ///     3. Value (ST)
+        ///     4. Relational Conjunction (ID)
+        /// ```
+        ///
+        QSC,
+        ///
+        /// # 2A.3.60 RCD - row column definition
+        ///
+        /// **Definition:** Specifies the columns of a tabular query response.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Segment Field Name (ST)
+        ///     2. HL7 Data Type (ID)
+        ///     3. Maximum Column Width (NM)
+        /// ```
+        ///
+        RCD,
+        ///
+        /// # 2A.3.61 RFR - reference range
+        ///
+        /// **Definition:** This data type specifies the reference range of a numeric observation and the
+        /// population it applies to.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Numeric Range (NR)
+        ///     2. Administrative Sex (CWE)
+        ///     3. Age Range (NR)
+        ///     4. Gestational Age Range (NR)
+        ///     5. Species (ST)
+        ///     6. Race/subspecies (ST)
+        ///     7. Conditions (TX)
+        /// ```
+        ///
+        RFR,
+        ///
+        /// # 2A.3.62 RI - repeat interval
+        ///
+        /// **Definition:** Contains the interval between repeated services.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Repeat Pattern (CWE)
+        ///     2. Explicit Time Interval (ST)
+        /// ```
+        ///
+        RI,
+        ///
+        /// # 2A.3.63 RMC - room coverage
+        ///
+        /// **Definition:** Specifies the room coverage provided by an insurance policy.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Room Type (CWE)
+        ///     2. Amount Type (CWE)
+        ///     3. Coverage Amount (NM)
+        ///     4. Money or Percentage (MOP)
+        /// ```
+        ///
+        RMC,
+        ///
+        /// # 2A.3.64 RP - reference pointer
+        ///
+        /// **Definition:** This data type transmits information about data stored on another system. It
+        /// contains a reference pointer that uniquely identifies the data on the other system, the identity
+        /// of the other system, and the type of data.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Pointer (ST)
+        ///     2. Application ID (HD)
+        ///     3. Type of Data (ID)
+        ///     4. Subtype (ID)
+        /// ```
+        ///
+        RP,
+        ///
+        /// # 2A.3.65 RPT - repeat pattern
+        ///
+        /// **Definition:** This data type is used to define the repeat pattern of a service, including any
+        /// calendar alignment and offsets.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Repeat Pattern Code (CWE)
+        ///     2. Calendar Alignment (ID)
+        ///     3. Phase Range Begin Value (NM)
+        ///     4. Phase Range End Value (NM)
+        ///     5. Period Quantity (NM)
+        ///     6. Period Units (CWE)
+        ///     7. Institution Specified Time (ID)
+        ///     8. Event (ID)
+        ///     9. Event Offset Quantity (NM)
+        ///     10. Event Offset Units (CWE)
+        ///     11. General Timing Specification (GTS)
+        /// ```
+        ///
+        RPT,
+        ///
+        /// # 2A.3.66 SAD - street address
+        ///
+        /// **Definition:** This data type specifies an entity's street address and associated detail.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Street or Mailing Address (ST)
+        ///     2. Street Name (ST)
+        ///     3. Dwelling Number (ST)
+        /// ```
+        ///
+        SAD,
+        ///
+        /// # 2A.3.67 SCV - scheduling class value pair
+        ///
+        /// **Definition:** This data type is used to communicate parameters and preferences to the filler
+        /// application regarding the selection of an appropriate time slot, resource, location, or filler
+        /// override criterion for an appointment.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Parameter Class (CWE)
+        ///     2. Parameter Value (ST)
+        /// ```
+        ///
+        SCV,
+        ///
+        /// # 2A.3.69 SN - structured numeric
+        ///
+        /// **Definition:** The structured numeric data type is used to unambiguously express numeric
+        /// clinical results along with qualifications. This enables receiving systems to store the
+        /// components separately, and facilitates the use of numeric database queries.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Comparator (ST)
+        ///     2. Num1 (NM)
+        ///     3. Separator/Suffix (ST)
+        ///     4. Num2 (NM)
+        /// ```
+        ///
+        SN,
+        ///
+        /// # 2A.3.71 SPD - specialty description
+        ///
+        /// **Definition:** This data type specifies a practitioner's specialty and the board that certifies
+        /// it.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Specialty Name (ST)
+        ///     2. Governing Board (ST)
+        ///     3. Eligible or Certified (ID)
+        ///     4. Date of Certification (DT)
+        /// ```
+        ///
+        SPD,
+        ///
+        /// # 2A.3.72 SPS - specimen source
+        ///
+        /// **Definition:** This data type identifies the site where the specimen should be obtained or
+        /// where the service should be performed. Retained for backward compatibility.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Specimen Source Name or Code (CWE)
+        ///     2. Additives (CWE)
+        ///     3. Specimen Collection Method (TX)
+        ///     4. Body Site (CWE)
+        ///     5. Site Modifier (CWE)
+        ///     6. Collection Method Modifier Code (CWE)
+        ///     7. Specimen Role (CWE)
+        /// ```
+        ///
+        SPS,
+        ///
+        /// # 2A.3.73 SRT - sort order
+        ///
+        /// **Definition:** Specifies those parameters by which a response is to be sorted and by which
+        /// method.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Sort-by Field (ST)
+        ///     2. Sequencing (ID)
+        /// ```
+        ///
+        SRT,
+        ///
+        /// # 2A.3.76 TQ - timing quantity
+        ///
+        /// **Definition:** Describes when a service should be performed and how frequently. Retained for
+        /// backward compatibility. Replaced by the TQ1 and TQ2 segments as of v2.5.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Quantity (CQ)
+        ///     2. Interval (RI)
+        ///     3. Duration (ST)
+        ///     4. Start Date/Time (DTM)
+        ///     5. End Date/Time (DTM)
+        ///     6. Priority (ST)
+        ///     7. Condition (ST)
+        ///     8. Text (TX)
+        ///     9. Conjunction (ID)
+        ///     10. Order Sequencing (OSD)
+        ///     11. Occurrence Duration (CWE)
+        ///     12. Total Occurrences (NM)
+        /// ```
+        ///
+        TQ,
+        ///
+        /// # 2A.3.77 TS - time stamp
+        ///
+        /// **Definition:** Contains the exact time of an event, including the date and time. Retained for
+        /// backward compatibility. Replaced by the DTM data type as of v2.6.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Time (DTM)
+        ///     2. Degree of Precision (ID)
+        /// ```
+        ///
+        TS,
+        ///
+        /// # 2A.3.79 UVC - UB value code and amount
+        ///
+        /// **Definition:** Specifies a value code and an associated amount, as used in UB-92/UB-04 claims.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Value Code (CWE)
+        ///     2. Value Amount (MO)
+        ///     3. Non-Monetary Value Amount / Quantity (NM)
+        ///     4. Non-Monetary Value Amount / Units (CWE)
+        /// ```
+        ///
+        UVC,
+        ///
+        /// # 2A.3.80 VH - visiting hours
+        ///
+        /// **Definition:** Specifies the day range and the hour range during which visits are allowed.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Start Day Range (ID)
+        ///     2. End Day Range (ID)
+        ///     3. Start Hour Range (TM)
+        ///     4. End Hour Range (TM)
+        /// ```
+        ///
+        VH,
+        ///
+        /// # 2A.3.81 VID - version identifier
+        ///
+        /// **Definition:** This specifies the HL7 version and any localization of it.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Version ID (ID)
+        ///     2. Internationalization Code (CWE)
+        ///     3. International Version ID (CWE)
+        /// ```
+        ///
+        VID,
+        ///
+        /// # 2A.3.82 VR - value range
+        ///
+        /// **Definition:** Specifies the first and last data code values of a range.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. First Data Code Value (ST)
+        ///     2. Last Data Code Value (ST)
+        /// ```
+        ///
+        VR,
+        ///
+        /// # 2A.3.83 WVI - channel identifier
+        ///
+        /// **Definition:** This data type specifies the number and name of the recording channel where
+        /// waveform data is transmitted.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Channel Number (NM)
+        ///     2. Channel Name (ST)
+        /// ```
+        ///
         WVI,
+        ///
+        /// # 2A.3.84 WVS - waveform source
+        ///
+        /// **Definition:** This data type identifies the source of the waveform connected to a channel.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Source One Name (ST)
+        ///     2. Source Two Name (ST)
+        /// ```
+        ///
         WVS,
+        ///
+        /// # 2A.3.85 XAD - extended address
+        ///
+        /// **Definition:** This data type specifies the address of a person, place or organization plus
+        /// associated information.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Street Address (SAD)
+        ///     2. Other Designation (ST)
+        ///     3. City (ST)
+        ///     4. State or Province (ST)
+        ///     5. Zip or Postal Code (ST)
+        ///     6. Country (ID)
+        ///     7. Address Type (ID)
+        ///     8. Other Geographic Designation (ST)
+        ///     9. County/Parish Code (CWE)
+        ///     10. Census Tract (CWE)
+        ///     11. Address Representation Code (ID)
+        ///     12. Address Validity Range (DR)
+        ///     13. Effective Date (DTM)
+        ///     14. Expiration Date (DTM)
+        ///     15. Expiration Reason (CWE)
+        ///     16. Temporary Indicator (ID)
+        ///     17. Bad Address Indicator (ID)
+        ///     18. Address Usage (ID)
+        ///     19. Addressee (ST)
+        ///     20. Comment (ST)
+        ///     21. Preference Order (NM)
+        ///     22. Protection Code (CWE)
+        ///     23. Address Identifier (EI)
+        /// ```
+        ///
+        XAD,
+        ///
+        /// # 2A.3.86 XCN - extended composite ID number and name for persons
+        ///
+        /// **Definition:** This data type identifies a person using an ID number and name, plus the
+        /// administrative detail needed to resolve them.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Person Identifier (ST)
+        ///     2. Family Name (FN)
+        ///     3. Given Name (ST)
+        ///     4. Second and Further Given Names or Initials Thereof (ST)
+        ///     5. Suffix (e.g. JR or III) (ST)
+        ///     6. Prefix (e.g. DR) (ST)
+        ///     7. Degree (e.g. MD) (IS)
+        ///     8. Source Table (CWE)
+        ///     9. Assigning Authority (HD)
+        ///     10. Name Type Code (ID)
+        ///     11. Identifier Check Digit (ST)
+        ///     12. Check Digit Scheme (ID)
+        ///     13. Identifier Type Code (ID)
+        ///     14. Assigning Facility (HD)
+        ///     15. Name Representation Code (ID)
+        ///     16. Name Context (CWE)
+        ///     17. Name Validity Range (DR)
+        ///     18. Name Assembly Order (ID)
+        ///     19. Effective Date (DTM)
+        ///     20. Expiration Date (DTM)
+        ///     21. Professional Suffix (ST)
+        ///     22. Assigning Jurisdiction (CWE)
+        ///     23. Assigning Agency or Department (CWE)
+        ///     24. Security Check (ST)
+        ///     25. Security Check Scheme (ID)
+        /// ```
+        ///
+        XCN,
+        ///
+        /// # 2A.3.87 XON - extended composite name and identification number for organizations
+        ///
+        /// **Definition:** This data type identifies an organization (legal entity) by name and identifier,
+        /// plus the administrative detail needed to resolve them.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Organization Name (ST)
+        ///     2. Organization Name Type Code (CWE)
+        ///     3. ID Number (NM)
+        ///     4. Identifier Check Digit (NM)
+        ///     5. Check Digit Scheme (ID)
+        ///     6. Assigning Authority (HD)
+        ///     7. Identifier Type Code (ID)
+        ///     8. Assigning Facility (HD)
+        ///     9. Name Representation Code (ID)
+        ///     10. Organization Identifier (ST)
+        /// ```
+        ///
+        XON,
+        ///
+        /// # 2A.3.88 XPN - extended person name
+        ///
+        /// **Definition:** This data type specifies the name of a person plus associated information such
+        /// as the name type, context and validity.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Family Name (FN)
+        ///     2. Given Name (ST)
+        ///     3. Second and Further Given Names or Initials Thereof (ST)
+        ///     4. Suffix (e.g. JR or III) (ST)
+        ///     5. Prefix (e.g. DR) (ST)
+        ///     6. Degree (e.g. MD) (IS)
+        ///     7. Name Type Code (ID)
+        ///     8. Name Representation Code (ID)
+        ///     9. Name Context (CWE)
+        ///     10. Name Validity Range (DR)
+        ///     11. Name Assembly Order (ID)
+        ///     12. Effective Date (DTM)
+        ///     13. Expiration Date (DTM)
+        ///     14. Professional Suffix (ST)
+        ///     15. Called By (ST)
+        /// ```
+        ///
+        XPN,
+        ///
+        /// # 2A.3.89 XTN - extended telecommunication number
+        ///
+        /// **Definition:** Contains the extended telephone number or other telecommunication address (e.g.
+        /// email) of a person, place or organization.
+        ///
+        /// ## Components
+        /// ```text
+        ///     1. Telephone Number (ST)
+        ///     2. Telecommunication Use Code (ID)
+        ///     3. Telecommunication Equipment Type (ID)
+        ///     4. Communication Address (ST)
+        ///     5. Country Code (SNM)
+        ///     6. Area/City Code (SNM)
+        ///     7. Local Number (SNM)
+        ///     8. Extension (SNM)
+        ///     9. Any Text (ST)
+        ///     10. Extension Prefix (ST)
+        ///     11. Speed Dial Code (ST)
+        ///     12. Unformatted Telephone number (ST)
+        ///     13. Effective Start Date (DTM)
+        ///     14. Expiration Date (DTM)
+        ///     15. Expiration Reason (CWE)
+        ///     16. Protection Code (CWE)
+        ///     17. Shared Telecommunication Identifier (EI)
+        ///     18. Preference Order (NM)
+        /// ```
+        ///
+        XTN,
     }
 
-    #[derive(Debug)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
     pub enum V2ComponentType {
         Primitive(V2PrimitiveType),
         Complex(V2ComplexType),
+        ///
+        /// The data type is carried by another field of the segment. For example, the type of
+        /// OBX-5 is given by OBX-2.
+        ///
+        Varies,
     }
 
     #[derive(Debug)]
@@ -906,6 +2011,7 @@ pub mod v2_field_descriptor {
     }
 
     impl V2ComponentTypeDescriptor {
+        #[allow(clippy::too_many_arguments)]
         pub const fn new(
             name: &'static str,
             description: &'static str,
@@ -939,14 +2045,14 @@ pub mod v2_field_descriptor {
     /// * `name` - String representing the field name.
     /// * `description` - String describing the component as given in the data type table.
     /// * `data_type` - Appropriate [`V2PrimitiveType`] enumerator item describing the type we should
-    ///     target when casting the field
+    ///   target when casting the field
     /// * `max_input_len` - Length to truncate value of field if `truncate` is True
     /// * `seq` - Number of field/sequence in segment.
     /// * `valid_table` - Validation table used for additional validation of input. It's a number now,
-    ///     but may be changed to an enumerator in the future.
+    ///   but may be changed to an enumerator in the future.
     /// * `optionality` - Option allowing marking the field as required. If a required field is missing,
-    ///     emit error. If a field is flagged as conditional, we expect an Option passed to be passed
-    ///     with None or a tuple of conditions.
+    ///   emit error. If a field is flagged as conditional, we expect an Option passed to be passed
+    ///   with None or a tuple of conditions.
     /// * `truncate` - Boolean flag for marking the component as one that needs to be truncated to `max_input_len`.
     ///
     #[macro_export]
@@ -990,8 +2096,8 @@ pub mod v2_field_descriptor {
             v2_component_descriptor!("source", "Source", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 3, 0, Optionality::O, true)
         ],
         "CCD" => &[
-            v2_component_descriptor!("event", "Invocation Event", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 1, 0, Optionality::R, false),
-            v2_component_descriptor!("date", "Date/time", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 2, 100, Optionality::O, false)
+            v2_component_descriptor!("event", "Invocation Event", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 1, 100, Optionality::R, false),
+            v2_component_descriptor!("date", "Date/time", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 2, 0, Optionality::O, false)
         ],
         "CCP" => &[
             v2_component_descriptor!("cc_factor", "Channel Calibration Sensitivity Correction Factor", V2ComponentType::Primitive(V2PrimitiveType::NM), 6, 1, 0, Optionality::O, true),
@@ -1006,30 +2112,37 @@ pub mod v2_field_descriptor {
             v2_component_descriptor!("channel_sampling_frequency", "Channel Sampling Frequency", V2ComponentType::Primitive(V2PrimitiveType::NM), 6, 5, 0, Optionality::O, true),
             v2_component_descriptor!("min_max_values", "Minimum and Maximum Data Values", V2ComponentType::Complex(V2ComplexType::NR), 0, 6, 0, Optionality::O, false)
         ],
-        "CE" => &[  ],
+        "CE" => &[
+            v2_component_descriptor!("id", "Identifier", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 1, 0, Optionality::O, false),
+            v2_component_descriptor!("text", "Text", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 2, 0, Optionality::O, true),
+            v2_component_descriptor!("coding_system", "Name of Coding System", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 3, 396, Optionality::O, false),
+            v2_component_descriptor!("alt_id", "Alternate Identifier", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 4, 0, Optionality::O, false),
+            v2_component_descriptor!("alt_text", "Alternate Text", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 5, 0, Optionality::O, true),
+            v2_component_descriptor!("alt_coding_system", "Name of Alternate Coding System", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 6, 396, Optionality::O, false)
+        ],
         "CF" => &[
             v2_component_descriptor!("id", "Identifier", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 1, 0, Optionality::O, false),
             v2_component_descriptor!("formatted_text", "Formatted Text", V2ComponentType::Primitive(V2PrimitiveType::FT), 0, 2, 0, Optionality::O, false),
-            v2_component_descriptor!("coding_system", "Name of Coding System", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 3, 396, Optionality::C(CONDITION_CF1), false),
+            v2_component_descriptor!("coding_system", "Name of Coding System", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 3, 396, Optionality::C(Some(CONDITION_CF1)), false),
             v2_component_descriptor!("alt_id", "Alternate Identifier", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 4, 0, Optionality::O, false),
             v2_component_descriptor!("alt_formatted_text", "Alternate Formatted Text", V2ComponentType::Primitive(V2PrimitiveType::FT), 0, 5, 0, Optionality::O, false),
-            v2_component_descriptor!("alt_coding_system", "Name of Alternate Coding System", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 6, 396, Optionality::C(CONDITION_CF2), false),
-            v2_component_descriptor!("version_id", "Coding System Version ID", V2ComponentType::Primitive(V2PrimitiveType::ST), 10, 7, 0, Optionality::C(CONDITION_CF3), false),
+            v2_component_descriptor!("alt_coding_system", "Name of Alternate Coding System", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 6, 396, Optionality::C(Some(CONDITION_CF2)), false),
+            v2_component_descriptor!("version_id", "Coding System Version ID", V2ComponentType::Primitive(V2PrimitiveType::ST), 10, 7, 0, Optionality::C(Some(CONDITION_CF3)), false),
             v2_component_descriptor!("alt_version_id", "Alternate Coding System Version ID", V2ComponentType::Primitive(V2PrimitiveType::ST), 10, 8, 0, Optionality::O, false),
             v2_component_descriptor!("original_text", "Original Text", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 9, 0, Optionality::O, false),
             v2_component_descriptor!("second_alt_id", "Second Alternate Identifier", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 10, 0, Optionality::O, false),
             v2_component_descriptor!("second_alt_formatted_text", "Second Alternate Formatted Text", V2ComponentType::Primitive(V2PrimitiveType::FT), 199, 11, 0, Optionality::O, false),
             v2_component_descriptor!("second_alt_coding_system", "Name of Second Alternate Coding System", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 12, 396, Optionality::O, false),
-            v2_component_descriptor!("second_alt_version_id", "Second Alternate Coding System Version ID", V2ComponentType::Primitive(V2PrimitiveType::ST), 10, 13, 0, Optionality::C(CONDITION_CF4), false),
-            v2_component_descriptor!("coding_system_oid", "Coding System OID", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 14, 0, Optionality::C(CONDITION_CF5), false),
+            v2_component_descriptor!("second_alt_version_id", "Second Alternate Coding System Version ID", V2ComponentType::Primitive(V2PrimitiveType::ST), 10, 13, 0, Optionality::C(Some(CONDITION_CF4)), false),
+            v2_component_descriptor!("coding_system_oid", "Coding System OID", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 14, 0, Optionality::C(Some(CONDITION_CF5)), false),
             v2_component_descriptor!("valueset_oid", "Value Set OID", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 15, 0, Optionality::O, false),
-            v2_component_descriptor!("valueset_version_id", "Value Set Version ID", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 8, 16, 0, Optionality::C(CONDITION_CF6), false),
-            v2_component_descriptor!("alt_coding_system_oid", "Alternate Coding System OID", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 17, 0, Optionality::C(CONDITION_CF7), false),
+            v2_component_descriptor!("valueset_version_id", "Value Set Version ID", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 8, 16, 0, Optionality::C(Some(CONDITION_CF6)), false),
+            v2_component_descriptor!("alt_coding_system_oid", "Alternate Coding System OID", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 17, 0, Optionality::C(Some(CONDITION_CF7)), false),
             v2_component_descriptor!("alt_valueset_oid", "Alternate Value Set OID", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 18, 0, Optionality::O, false),
-            v2_component_descriptor!("alt_valueset_version_id", "Alternate Value Set Version ID", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 8, 19, 0, Optionality::C(CONDITION_CF8), false),
-            v2_component_descriptor!("second_alt_coding_system_oid", "Second Alternate Coding System OID", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 20, 0, Optionality::C(CONDITION_CF9), false),
+            v2_component_descriptor!("alt_valueset_version_id", "Alternate Value Set Version ID", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 8, 19, 0, Optionality::C(Some(CONDITION_CF8)), false),
+            v2_component_descriptor!("second_alt_coding_system_oid", "Second Alternate Coding System OID", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 20, 0, Optionality::C(Some(CONDITION_CF9)), false),
             v2_component_descriptor!("second_alt_valueset_oid", "Second Alternate Value Set OID", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 21, 0, Optionality::O, false),
-            v2_component_descriptor!("second_alt_valueset_version_id", "Second Alternate Value Set Version ID", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 8, 22, 0, Optionality::C(CONDITION_CF10), false)
+            v2_component_descriptor!("second_alt_valueset_version_id", "Second Alternate Value Set Version ID", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 8, 22, 0, Optionality::C(Some(CONDITION_CF10)), false)
         ],
         "CNE" => &[
             v2_component_descriptor!("id", "Identifier", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 1, 0, Optionality::R, false),
@@ -1038,22 +2151,22 @@ pub mod v2_field_descriptor {
             v2_component_descriptor!("alt_id", "Alternate Identifier", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 4, 0, Optionality::O, false),
             v2_component_descriptor!("alt_text", "Alternate Text", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 5, 0, Optionality::O, true),
             v2_component_descriptor!("alt_coding_system", "Name of Alternate Coding System", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 6, 396, Optionality::O, false),
-            v2_component_descriptor!("version_id", "Coding System Version ID", V2ComponentType::Primitive(V2PrimitiveType::ST), 10, 7, 0, Optionality::C(CONDITION_CNE1), false),
+            v2_component_descriptor!("version_id", "Coding System Version ID", V2ComponentType::Primitive(V2PrimitiveType::ST), 10, 7, 0, Optionality::C(Some(CONDITION_CNE1)), false),
             v2_component_descriptor!("alt_version_id", "Alternate Coding System Version ID", V2ComponentType::Primitive(V2PrimitiveType::ST), 10, 8, 0, Optionality::O, false),
             v2_component_descriptor!("original_text", "Original Text", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 9, 0, Optionality::O, true),
             v2_component_descriptor!("second_alt_id", "Second Alternate Identifier", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 10, 0, Optionality::O, false),
             v2_component_descriptor!("second_alt_text", "Second Alternate Text", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 11, 0, Optionality::O, true),
             v2_component_descriptor!("second_alt_coding_system", "Name of Second Alternate Coding System", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 12, 396, Optionality::O, false),
-            v2_component_descriptor!("second_alt_version_id", "Second Alternate Coding System Version ID", V2ComponentType::Primitive(V2PrimitiveType::ST), 10, 13, 0, Optionality::C(CONDITION_CNE2), false),
-            v2_component_descriptor!("coding_system_oid", "Coding System OID", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 14, 0, Optionality::C(CONDITION_CNE3), false),
+            v2_component_descriptor!("second_alt_version_id", "Second Alternate Coding System Version ID", V2ComponentType::Primitive(V2PrimitiveType::ST), 10, 13, 0, Optionality::C(Some(CONDITION_CNE2)), false),
+            v2_component_descriptor!("coding_system_oid", "Coding System OID", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 14, 0, Optionality::C(Some(CONDITION_CNE3)), false),
             v2_component_descriptor!("valueset_oid", "Value Set OID", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 15, 0, Optionality::O, false),
-            v2_component_descriptor!("valueset_version_id", "Value Set Version ID", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 8, 16, 0, Optionality::C(CONDITION_CNE4), false),
-            v2_component_descriptor!("alt_coding_system_oid", "Alternate Coding System OID", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 17, 0, Optionality::C(CONDITION_CNE5), false),
+            v2_component_descriptor!("valueset_version_id", "Value Set Version ID", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 8, 16, 0, Optionality::C(Some(CONDITION_CNE4)), false),
+            v2_component_descriptor!("alt_coding_system_oid", "Alternate Coding System OID", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 17, 0, Optionality::C(Some(CONDITION_CNE5)), false),
             v2_component_descriptor!("alt_valueset_oid", "Alternate Value Set OID", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 18, 0, Optionality::O, false),
-            v2_component_descriptor!("alt_valueset_version_id", "Alternate Value Set Version ID", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 8, 19, 0, Optionality::C(CONDITION_CNE6), false),
-            v2_component_descriptor!("second_alt_coding_system_oid", "Second Alternate Coding System OID", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 20, 0, Optionality::C(CONDITION_CNE7), false),
+            v2_component_descriptor!("alt_valueset_version_id", "Alternate Value Set Version ID", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 8, 19, 0, Optionality::C(Some(CONDITION_CNE6)), false),
+            v2_component_descriptor!("second_alt_coding_system_oid", "Second Alternate Coding System OID", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 20, 0, Optionality::C(Some(CONDITION_CNE7)), false),
             v2_component_descriptor!("second_alt_valueset_oid", "Second Alternate Value Set OID", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 21, 0, Optionality::O, false),
-            v2_component_descriptor!("second_alt_valueset_version_id", "Second Alternate Value Set Version ID", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 8, 22, 0, Optionality::C(CONDITION_CNE8), false)
+            v2_component_descriptor!("second_alt_valueset_version_id", "Second Alternate Value Set Version ID", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 8, 22, 0, Optionality::C(Some(CONDITION_CNE8)), false)
         ],
         "CNN" => &[
             v2_component_descriptor!("id", "ID Number", V2ComponentType::Primitive(V2PrimitiveType::ST), 15, 1, 0, Optionality::O, false),
@@ -1063,17 +2176,17 @@ pub mod v2_field_descriptor {
             v2_component_descriptor!("suffix", "Suffix (e.g. JR or III)", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 5, 0, Optionality::O, true),
             v2_component_descriptor!("prefix", "Prefix (e.g. DR)", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 6, 0, Optionality::O, true),
             v2_component_descriptor!("degree", "Degree (e.g. MD)", V2ComponentType::Primitive(V2PrimitiveType::IS), 6, 7, 360, Optionality::O, false),
-            v2_component_descriptor!("source_table", "Source Table", V2ComponentType::Primitive(V2PrimitiveType::IS), 4, 8, 297, Optionality::C(CONDITION_CNN1), false),
-            v2_component_descriptor!("aa_namespace_id", "Assigning Authority - Namespace ID", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 9, 363, Optionality::C(CONDITION_CNN2), false),
-            v2_component_descriptor!("aa_universal_id", "Assigning Authority - Universal ID", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 10, 0, Optionality::C(CONDITION_CNN3), false),
-            v2_component_descriptor!("aa_universal_id_type", "Assigning Authority - Universal ID Type", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 11, 301, Optionality::C(CONDITION_CNN4), false)
+            v2_component_descriptor!("source_table", "Source Table", V2ComponentType::Primitive(V2PrimitiveType::IS), 4, 8, 297, Optionality::C(Some(CONDITION_CNN1)), false),
+            v2_component_descriptor!("aa_namespace_id", "Assigning Authority - Namespace ID", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 9, 363, Optionality::C(Some(CONDITION_CNN2)), false),
+            v2_component_descriptor!("aa_universal_id", "Assigning Authority - Universal ID", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 10, 0, Optionality::C(Some(CONDITION_CNN3)), false),
+            v2_component_descriptor!("aa_universal_id_type", "Assigning Authority - Universal ID Type", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 11, 301, Optionality::C(Some(CONDITION_CNN4)), false)
         ],
         "CP" => &[
             v2_component_descriptor!("price", "Price", V2ComponentType::Complex(V2ComplexType::MO), 0, 1, 0, Optionality::R, false),
             v2_component_descriptor!("price_type", "Price Type", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 2, 205, Optionality::O, false),
             v2_component_descriptor!("from_value", "From Value", V2ComponentType::Primitive(V2PrimitiveType::NM), 0, 3, 0, Optionality::O, false),
             v2_component_descriptor!("to_value", "To Value", V2ComponentType::Primitive(V2PrimitiveType::NM), 0, 4, 0, Optionality::O, false),
-            v2_component_descriptor!("range_units", "Range Units", V2ComponentType::Complex(V2ComplexType::CWE), 0, 5, 0, Optionality::C(CONDITION_CP), false),
+            v2_component_descriptor!("range_units", "Range Units", V2ComponentType::Complex(V2ComplexType::CWE), 0, 5, 0, Optionality::C(Some(CONDITION_CP)), false),
             v2_component_descriptor!("range_type", "Range Type", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 6, 298, Optionality::O, false)
         ],
         "CQ" => &[
@@ -1081,8 +2194,546 @@ pub mod v2_field_descriptor {
             v2_component_descriptor!("units", "Units", V2ComponentType::Complex(V2ComplexType::CWE), 0, 2, 794, Optionality::O, false)
         ],
         "CSU" => &[
-            v2_component_descriptor!("quantity", "Quantity", V2ComponentType::Primitive(V2PrimitiveType::NM), 0, 1, 0, Optionality::O, false),
-            v2_component_descriptor!("units", "Units", V2ComponentType::Complex(V2ComplexType::CWE), 0, 2, 794, Optionality::O, false)
+            v2_component_descriptor!("channel_sensitivity", "Channel Sensitivity", V2ComponentType::Primitive(V2PrimitiveType::NM), 0, 1, 0, Optionality::R, false),
+            v2_component_descriptor!("unit_id", "Unit of Measure Identifier", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 2, 0, Optionality::O, false),
+            v2_component_descriptor!("unit_text", "Unit of Measure Description", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 3, 0, Optionality::O, true),
+            v2_component_descriptor!("unit_coding_system", "Unit of Measure Coding System", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 4, 396, Optionality::C(Some(CONDITION_CSU1)), false),
+            v2_component_descriptor!("alt_unit_id", "Alternate Unit of Measure Identifier", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 5, 0, Optionality::O, false),
+            v2_component_descriptor!("alt_unit_text", "Alternate Unit of Measure Description", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 6, 0, Optionality::O, true),
+            v2_component_descriptor!("alt_unit_coding_system", "Alternate Unit of Measure Coding System", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 7, 396, Optionality::C(Some(CONDITION_CSU2)), false),
+            v2_component_descriptor!("unit_version_id", "Unit of Measure Coding System Version ID", V2ComponentType::Primitive(V2PrimitiveType::ST), 10, 8, 0, Optionality::O, false),
+            v2_component_descriptor!("alt_unit_version_id", "Alternate Unit of Measure Coding System Version ID", V2ComponentType::Primitive(V2PrimitiveType::ST), 10, 9, 0, Optionality::O, false),
+            v2_component_descriptor!("original_text", "Original Text", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 10, 0, Optionality::O, true),
+            v2_component_descriptor!("second_alt_unit_id", "Second Alternate Unit of Measure Identifier", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 11, 0, Optionality::O, false),
+            v2_component_descriptor!("second_alt_unit_text", "Second Alternate Unit of Measure Text", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 12, 0, Optionality::O, true),
+            v2_component_descriptor!("second_alt_unit_coding_system", "Name of Second Alternate Unit of Measure Coding System", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 13, 396, Optionality::O, false),
+            v2_component_descriptor!("second_alt_unit_version_id", "Second Alternate Unit of Measure Coding System Version ID", V2ComponentType::Primitive(V2PrimitiveType::ST), 10, 14, 0, Optionality::O, false),
+            v2_component_descriptor!("unit_coding_system_oid", "Unit of Measure Coding System OID", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 15, 0, Optionality::O, false),
+            v2_component_descriptor!("unit_valueset_oid", "Unit of Measure Value Set OID", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 16, 0, Optionality::O, false),
+            v2_component_descriptor!("unit_valueset_version_id", "Unit of Measure Value Set Version ID", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 8, 17, 0, Optionality::O, false),
+            v2_component_descriptor!("alt_unit_coding_system_oid", "Alternate Unit of Measure Coding System OID", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 18, 0, Optionality::O, false),
+            v2_component_descriptor!("alt_unit_valueset_oid", "Alternate Unit of Measure Value Set OID", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 19, 0, Optionality::O, false),
+            v2_component_descriptor!("alt_unit_valueset_version_id", "Alternate Unit of Measure Value Set Version ID", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 8, 20, 0, Optionality::O, false),
+            v2_component_descriptor!("second_alt_unit_coding_system_oid", "Second Alternate Unit of Measure Coding System OID", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 21, 0, Optionality::O, false),
+            v2_component_descriptor!("second_alt_unit_valueset_oid", "Second Alternate Unit of Measure Value Set OID", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 22, 0, Optionality::O, false),
+            v2_component_descriptor!("second_alt_unit_valueset_version_id", "Second Alternate Unit of Measure Value Set Version ID", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 8, 23, 0, Optionality::O, false)
+        ],
+        "CWE" => &[
+            v2_component_descriptor!("id", "Identifier", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 1, 0, Optionality::O, false),
+            v2_component_descriptor!("text", "Text", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 2, 0, Optionality::O, true),
+            v2_component_descriptor!("coding_system", "Name of Coding System", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 3, 396, Optionality::C(Some(CONDITION_CWE1)), false),
+            v2_component_descriptor!("alt_id", "Alternate Identifier", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 4, 0, Optionality::O, false),
+            v2_component_descriptor!("alt_text", "Alternate Text", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 5, 0, Optionality::O, true),
+            v2_component_descriptor!("alt_coding_system", "Name of Alternate Coding System", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 6, 396, Optionality::C(Some(CONDITION_CWE2)), false),
+            v2_component_descriptor!("version_id", "Coding System Version ID", V2ComponentType::Primitive(V2PrimitiveType::ST), 10, 7, 0, Optionality::C(Some(CONDITION_CWE3)), false),
+            v2_component_descriptor!("alt_version_id", "Alternate Coding System Version ID", V2ComponentType::Primitive(V2PrimitiveType::ST), 10, 8, 0, Optionality::O, false),
+            v2_component_descriptor!("original_text", "Original Text", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 9, 0, Optionality::O, true),
+            v2_component_descriptor!("second_alt_id", "Second Alternate Identifier", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 10, 0, Optionality::O, false),
+            v2_component_descriptor!("second_alt_text", "Second Alternate Text", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 11, 0, Optionality::O, true),
+            v2_component_descriptor!("second_alt_coding_system", "Name of Second Alternate Coding System", V2ComponentType::Primitive(V2PrimitiveType::ID), 0, 12, 396, Optionality::C(Some(CONDITION_CWE4)), false),
+            v2_component_descriptor!("second_alt_version_id", "Second Alternate Coding System Version ID", V2ComponentType::Primitive(V2PrimitiveType::ST), 10, 13, 0, Optionality::C(Some(CONDITION_CWE5)), false),
+            v2_component_descriptor!("coding_system_oid", "Coding System OID", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 14, 0, Optionality::C(Some(CONDITION_CWE6)), false),
+            v2_component_descriptor!("valueset_oid", "Value Set OID", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 15, 0, Optionality::O, false),
+            v2_component_descriptor!("valueset_version_id", "Value Set Version ID", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 8, 16, 0, Optionality::C(Some(CONDITION_CWE7)), false),
+            v2_component_descriptor!("alt_coding_system_oid", "Alternate Coding System OID", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 17, 0, Optionality::C(Some(CONDITION_CWE8)), false),
+            v2_component_descriptor!("alt_valueset_oid", "Alternate Value Set OID", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 18, 0, Optionality::O, false),
+            v2_component_descriptor!("alt_valueset_version_id", "Alternate Value Set Version ID", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 8, 19, 0, Optionality::C(Some(CONDITION_CWE9)), false),
+            v2_component_descriptor!("second_alt_coding_system_oid", "Second Alternate Coding System OID", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 20, 0, Optionality::C(Some(CONDITION_CWE10)), false),
+            v2_component_descriptor!("second_alt_valueset_oid", "Second Alternate Value Set OID", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 21, 0, Optionality::O, false),
+            v2_component_descriptor!("second_alt_valueset_version_id", "Second Alternate Value Set Version ID", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 8, 22, 0, Optionality::C(Some(CONDITION_CWE11)), false)
+        ],
+        "CX" => &[
+            v2_component_descriptor!("id", "ID Number", V2ComponentType::Primitive(V2PrimitiveType::ST), 15, 1, 0, Optionality::R, false),
+            v2_component_descriptor!("check_digit", "Identifier Check Digit", V2ComponentType::Primitive(V2PrimitiveType::ST), 4, 2, 0, Optionality::B, false),
+            v2_component_descriptor!("check_digit_scheme", "Check Digit Scheme", V2ComponentType::Primitive(V2PrimitiveType::ID), 3, 3, 61, Optionality::B, false),
+            v2_component_descriptor!("assigning_authority", "Assigning Authority", V2ComponentType::Complex(V2ComplexType::HD), 0, 4, 363, Optionality::C(Some(CONDITION_CX1)), false),
+            v2_component_descriptor!("id_type", "Identifier Type Code", V2ComponentType::Primitive(V2PrimitiveType::ID), 5, 5, 203, Optionality::R, false),
+            v2_component_descriptor!("assigning_facility", "Assigning Facility", V2ComponentType::Complex(V2ComplexType::HD), 0, 6, 0, Optionality::O, false),
+            v2_component_descriptor!("effective_date", "Effective Date", V2ComponentType::Primitive(V2PrimitiveType::Date), 8, 7, 0, Optionality::O, false),
+            v2_component_descriptor!("expiration_date", "Expiration Date", V2ComponentType::Primitive(V2PrimitiveType::Date), 8, 8, 0, Optionality::O, false),
+            v2_component_descriptor!("assigning_jurisdiction", "Assigning Jurisdiction", V2ComponentType::Complex(V2ComplexType::CWE), 0, 9, 0, Optionality::C(Some(CONDITION_CX2)), false),
+            v2_component_descriptor!("assigning_agency", "Assigning Agency or Department", V2ComponentType::Complex(V2ComplexType::CWE), 0, 10, 0, Optionality::C(Some(CONDITION_CX3)), false),
+            v2_component_descriptor!("security_check", "Security Check", V2ComponentType::Primitive(V2PrimitiveType::ST), 4, 11, 0, Optionality::O, false),
+            v2_component_descriptor!("security_check_scheme", "Security Check Scheme", V2ComponentType::Primitive(V2PrimitiveType::ID), 3, 12, 904, Optionality::O, false)
+        ],
+        "DDI" => &[
+            v2_component_descriptor!("delay_days", "Delay Days", V2ComponentType::Primitive(V2PrimitiveType::NM), 3, 1, 0, Optionality::O, false),
+            v2_component_descriptor!("monetary_amount", "Monetary Amount", V2ComponentType::Complex(V2ComplexType::MO), 0, 2, 0, Optionality::R, false),
+            v2_component_descriptor!("number_of_days", "Number of Days", V2ComponentType::Primitive(V2PrimitiveType::NM), 4, 3, 0, Optionality::O, false)
+        ],
+        "DIN" => &[
+            v2_component_descriptor!("date", "Date", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 1, 0, Optionality::O, false),
+            v2_component_descriptor!("institution_name", "Institution Name", V2ComponentType::Complex(V2ComplexType::CWE), 0, 2, 531, Optionality::O, false)
+        ],
+        "DLD" => &[
+            v2_component_descriptor!("discharge_location", "Discharge to Location", V2ComponentType::Complex(V2ComplexType::CWE), 0, 1, 113, Optionality::R, false),
+            v2_component_descriptor!("effective_date", "Effective Date", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 2, 0, Optionality::O, false)
+        ],
+        "DLN" => &[
+            v2_component_descriptor!("license_number", "License Number", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 1, 0, Optionality::R, false),
+            v2_component_descriptor!("issuing_state", "Issuing State, Province, Country", V2ComponentType::Complex(V2ComplexType::CWE), 0, 2, 333, Optionality::O, false),
+            v2_component_descriptor!("expiration_date", "Expiration Date", V2ComponentType::Primitive(V2PrimitiveType::Date), 8, 3, 0, Optionality::O, false)
+        ],
+        "DLT" => &[
+            v2_component_descriptor!("normal_range", "Normal Range", V2ComponentType::Complex(V2ComplexType::NR), 0, 1, 0, Optionality::O, false),
+            v2_component_descriptor!("numeric_threshold", "Numeric Threshold", V2ComponentType::Primitive(V2PrimitiveType::NM), 0, 2, 0, Optionality::O, false),
+            v2_component_descriptor!("change_computation", "Change Computation", V2ComponentType::Primitive(V2PrimitiveType::ID), 1, 3, 523, Optionality::O, false),
+            v2_component_descriptor!("days_retained", "Days Retained", V2ComponentType::Primitive(V2PrimitiveType::NM), 0, 4, 0, Optionality::O, false)
+        ],
+        "DR" => &[
+            v2_component_descriptor!("range_start", "Range Start Date/Time", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 1, 0, Optionality::O, false),
+            v2_component_descriptor!("range_end", "Range End Date/Time", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 2, 0, Optionality::O, false)
+        ],
+        "DTN" => &[
+            v2_component_descriptor!("day_type", "Day Type", V2ComponentType::Primitive(V2PrimitiveType::IS), 1, 1, 149, Optionality::R, false),
+            v2_component_descriptor!("number_of_days", "Number of Days", V2ComponentType::Primitive(V2PrimitiveType::NM), 3, 2, 0, Optionality::R, false)
+        ],
+        "ED" => &[
+            v2_component_descriptor!("source_application", "Source Application", V2ComponentType::Complex(V2ComplexType::HD), 0, 1, 0, Optionality::O, false),
+            v2_component_descriptor!("data_type", "Type of Data", V2ComponentType::Primitive(V2PrimitiveType::ID), 9, 2, 191, Optionality::R, false),
+            v2_component_descriptor!("data_subtype", "Data Subtype", V2ComponentType::Primitive(V2PrimitiveType::ID), 18, 3, 291, Optionality::O, false),
+            v2_component_descriptor!("encoding", "Encoding", V2ComponentType::Primitive(V2PrimitiveType::ID), 6, 4, 299, Optionality::R, false),
+            v2_component_descriptor!("data", "Data", V2ComponentType::Primitive(V2PrimitiveType::Text), 0, 5, 0, Optionality::R, false)
+        ],
+        "EI" => &[
+            v2_component_descriptor!("entity_id", "Entity Identifier", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 1, 0, Optionality::O, false),
+            v2_component_descriptor!("namespace_id", "Namespace ID", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 2, 363, Optionality::C(Some(CONDITION_EI1)), false),
+            v2_component_descriptor!("universal_id", "Universal ID", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 3, 0, Optionality::C(Some(CONDITION_EI2)), false),
+            v2_component_descriptor!("universal_id_type", "Universal ID Type", V2ComponentType::Primitive(V2PrimitiveType::ID), 6, 4, 301, Optionality::C(Some(CONDITION_EI3)), false)
+        ],
+        "EIP" => &[
+            v2_component_descriptor!("placer_id", "Placer Assigned Identifier", V2ComponentType::Complex(V2ComplexType::EI), 0, 1, 0, Optionality::O, false),
+            v2_component_descriptor!("filler_id", "Filler Assigned Identifier", V2ComponentType::Complex(V2ComplexType::EI), 0, 2, 0, Optionality::O, false)
+        ],
+        "ELD" => &[
+            v2_component_descriptor!("segment_id", "Segment ID", V2ComponentType::Primitive(V2PrimitiveType::ST), 3, 1, 0, Optionality::O, false),
+            v2_component_descriptor!("segment_sequence", "Segment Sequence", V2ComponentType::Primitive(V2PrimitiveType::NM), 2, 2, 0, Optionality::O, false),
+            v2_component_descriptor!("field_position", "Field Position", V2ComponentType::Primitive(V2PrimitiveType::NM), 2, 3, 0, Optionality::O, false),
+            v2_component_descriptor!("error_code", "Code Identifying Error", V2ComponentType::Complex(V2ComplexType::CE), 0, 4, 357, Optionality::O, false)
+        ],
+        "ERL" => &[
+            v2_component_descriptor!("segment_id", "Segment ID", V2ComponentType::Primitive(V2PrimitiveType::ST), 3, 1, 0, Optionality::R, false),
+            v2_component_descriptor!("segment_sequence", "Segment Sequence", V2ComponentType::Primitive(V2PrimitiveType::NM), 2, 2, 0, Optionality::R, false),
+            v2_component_descriptor!("field_position", "Field Position", V2ComponentType::Primitive(V2PrimitiveType::NM), 2, 3, 0, Optionality::O, false),
+            v2_component_descriptor!("field_repetition", "Field Repetition", V2ComponentType::Primitive(V2PrimitiveType::NM), 2, 4, 0, Optionality::O, false),
+            v2_component_descriptor!("component_number", "Component Number", V2ComponentType::Primitive(V2PrimitiveType::NM), 2, 5, 0, Optionality::O, false),
+            v2_component_descriptor!("subcomponent_number", "Sub-Component Number", V2ComponentType::Primitive(V2PrimitiveType::NM), 2, 6, 0, Optionality::O, false)
+        ],
+        "FC" => &[
+            v2_component_descriptor!("financial_class", "Financial Class Code", V2ComponentType::Complex(V2ComplexType::CWE), 0, 1, 64, Optionality::R, false),
+            v2_component_descriptor!("effective_date", "Effective Date", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 2, 0, Optionality::O, false)
+        ],
+        "FN" => &[
+            v2_component_descriptor!("surname", "Surname", V2ComponentType::Primitive(V2PrimitiveType::ST), 50, 1, 0, Optionality::R, true),
+            v2_component_descriptor!("own_surname_prefix", "Own Surname Prefix", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 2, 0, Optionality::O, true),
+            v2_component_descriptor!("own_surname", "Own Surname", V2ComponentType::Primitive(V2PrimitiveType::ST), 50, 3, 0, Optionality::O, true),
+            v2_component_descriptor!("partner_surname_prefix", "Surname Prefix From Partner/Spouse", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 4, 0, Optionality::O, true),
+            v2_component_descriptor!("partner_surname", "Surname From Partner/Spouse", V2ComponentType::Primitive(V2PrimitiveType::ST), 50, 5, 0, Optionality::O, true)
+        ],
+        "HD" => &[
+            v2_component_descriptor!("namespace_id", "Namespace ID", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 1, 300, Optionality::C(Some(CONDITION_HD1)), false),
+            v2_component_descriptor!("universal_id", "Universal ID", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 2, 0, Optionality::C(Some(CONDITION_HD2)), false),
+            v2_component_descriptor!("universal_id_type", "Universal ID Type", V2ComponentType::Primitive(V2PrimitiveType::ID), 6, 3, 301, Optionality::C(Some(CONDITION_HD3)), false)
+        ],
+        "ICD" => &[
+            v2_component_descriptor!("certification_patient_type", "Certification Patient Type", V2ComponentType::Primitive(V2PrimitiveType::IS), 1, 1, 150, Optionality::O, false),
+            v2_component_descriptor!("certification_required", "Certification Required", V2ComponentType::Primitive(V2PrimitiveType::ID), 1, 2, 136, Optionality::R, false),
+            v2_component_descriptor!("certification_required_date", "Date/Time Certification Required", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 3, 0, Optionality::O, false)
+        ],
+        "JCC" => &[
+            v2_component_descriptor!("job_code", "Job Code", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 1, 327, Optionality::O, false),
+            v2_component_descriptor!("job_class", "Job Class", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 2, 328, Optionality::O, false),
+            v2_component_descriptor!("job_description", "Job Description Text", V2ComponentType::Primitive(V2PrimitiveType::Text), 250, 3, 0, Optionality::O, false)
+        ],
+        "LA1" => &[
+            v2_component_descriptor!("point_of_care", "Point of Care", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 1, 302, Optionality::O, false),
+            v2_component_descriptor!("room", "Room", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 2, 303, Optionality::O, false),
+            v2_component_descriptor!("bed", "Bed", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 3, 304, Optionality::O, false),
+            v2_component_descriptor!("facility", "Facility", V2ComponentType::Complex(V2ComplexType::HD), 0, 4, 0, Optionality::O, false),
+            v2_component_descriptor!("location_status", "Location Status", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 5, 306, Optionality::O, false),
+            v2_component_descriptor!("patient_location_type", "Patient Location Type", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 6, 305, Optionality::O, false),
+            v2_component_descriptor!("building", "Building", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 7, 307, Optionality::O, false),
+            v2_component_descriptor!("floor", "Floor", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 8, 308, Optionality::O, false),
+            v2_component_descriptor!("address", "Address", V2ComponentType::Complex(V2ComplexType::AD), 0, 9, 0, Optionality::O, false)
+        ],
+        "LA2" => &[
+            v2_component_descriptor!("point_of_care", "Point of Care", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 1, 302, Optionality::O, false),
+            v2_component_descriptor!("room", "Room", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 2, 303, Optionality::O, false),
+            v2_component_descriptor!("bed", "Bed", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 3, 304, Optionality::O, false),
+            v2_component_descriptor!("facility", "Facility", V2ComponentType::Complex(V2ComplexType::HD), 0, 4, 0, Optionality::O, false),
+            v2_component_descriptor!("location_status", "Location Status", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 5, 306, Optionality::O, false),
+            v2_component_descriptor!("patient_location_type", "Patient Location Type", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 6, 305, Optionality::O, false),
+            v2_component_descriptor!("building", "Building", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 7, 307, Optionality::O, false),
+            v2_component_descriptor!("floor", "Floor", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 8, 308, Optionality::O, false),
+            v2_component_descriptor!("street_address", "Street Address", V2ComponentType::Primitive(V2PrimitiveType::ST), 120, 9, 0, Optionality::O, true),
+            v2_component_descriptor!("second_address", "Other Designation", V2ComponentType::Primitive(V2PrimitiveType::ST), 120, 10, 0, Optionality::O, true),
+            v2_component_descriptor!("city", "City", V2ComponentType::Primitive(V2PrimitiveType::ST), 50, 11, 0, Optionality::O, true),
+            v2_component_descriptor!("state", "State or Province", V2ComponentType::Primitive(V2PrimitiveType::ST), 50, 12, 0, Optionality::O, true),
+            v2_component_descriptor!("zip", "Zip or Postal Code", V2ComponentType::Primitive(V2PrimitiveType::ST), 12, 13, 0, Optionality::O, false),
+            v2_component_descriptor!("country", "Country", V2ComponentType::Primitive(V2PrimitiveType::ID), 3, 14, 399, Optionality::O, false),
+            v2_component_descriptor!("address_type", "Address Type", V2ComponentType::Primitive(V2PrimitiveType::ID), 3, 15, 190, Optionality::O, false),
+            v2_component_descriptor!("county", "Other Geographic Designation", V2ComponentType::Primitive(V2PrimitiveType::ST), 50, 16, 0, Optionality::O, true)
+        ],
+        "MO" => &[
+            v2_component_descriptor!("quantity", "Quantity", V2ComponentType::Primitive(V2PrimitiveType::NM), 16, 1, 0, Optionality::O, false),
+            v2_component_descriptor!("denomination", "Denomination", V2ComponentType::Primitive(V2PrimitiveType::ID), 3, 2, 913, Optionality::O, false)
+        ],
+        "MOC" => &[
+            v2_component_descriptor!("monetary_amount", "Monetary Amount", V2ComponentType::Complex(V2ComplexType::MO), 0, 1, 0, Optionality::O, false),
+            v2_component_descriptor!("charge_code", "Charge Code", V2ComponentType::Complex(V2ComplexType::CWE), 0, 2, 132, Optionality::O, false)
+        ],
+        "MOP" => &[
+            v2_component_descriptor!("indicator", "Money or Percentage Indicator", V2ComponentType::Primitive(V2PrimitiveType::ID), 2, 1, 148, Optionality::R, false),
+            v2_component_descriptor!("quantity", "Money or Percentage Quantity", V2ComponentType::Primitive(V2PrimitiveType::NM), 16, 2, 0, Optionality::R, false),
+            v2_component_descriptor!("denomination", "Monetary Denomination", V2ComponentType::Primitive(V2PrimitiveType::ID), 3, 3, 913, Optionality::O, false)
+        ],
+        "MSG" => &[
+            v2_component_descriptor!("message_code", "Message Code", V2ComponentType::Primitive(V2PrimitiveType::ID), 3, 1, 76, Optionality::R, false),
+            v2_component_descriptor!("trigger_event", "Trigger Event", V2ComponentType::Primitive(V2PrimitiveType::ID), 3, 2, 3, Optionality::R, false),
+            v2_component_descriptor!("message_structure", "Message Structure", V2ComponentType::Primitive(V2PrimitiveType::ID), 7, 3, 354, Optionality::R, false)
+        ],
+        "NDL" => &[
+            v2_component_descriptor!("name", "Name", V2ComponentType::Complex(V2ComplexType::CNN), 0, 1, 0, Optionality::O, false),
+            v2_component_descriptor!("start_date", "Start Date/time", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 2, 0, Optionality::O, false),
+            v2_component_descriptor!("end_date", "End Date/time", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 3, 0, Optionality::O, false),
+            v2_component_descriptor!("point_of_care", "Point of Care", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 4, 302, Optionality::O, false),
+            v2_component_descriptor!("room", "Room", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 5, 303, Optionality::O, false),
+            v2_component_descriptor!("bed", "Bed", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 6, 304, Optionality::O, false),
+            v2_component_descriptor!("facility", "Facility", V2ComponentType::Complex(V2ComplexType::HD), 0, 7, 0, Optionality::O, false),
+            v2_component_descriptor!("location_status", "Location Status", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 8, 306, Optionality::O, false),
+            v2_component_descriptor!("patient_location_type", "Patient Location Type", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 9, 305, Optionality::O, false),
+            v2_component_descriptor!("building", "Building", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 10, 307, Optionality::O, false),
+            v2_component_descriptor!("floor", "Floor", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 11, 308, Optionality::O, false)
+        ],
+        "NR" => &[
+            v2_component_descriptor!("low_value", "Low Value", V2ComponentType::Primitive(V2PrimitiveType::NM), 0, 1, 0, Optionality::O, false),
+            v2_component_descriptor!("high_value", "High Value", V2ComponentType::Primitive(V2PrimitiveType::NM), 0, 2, 0, Optionality::O, false)
+        ],
+        "OCD" => &[
+            v2_component_descriptor!("occurrence_code", "Occurrence Code", V2ComponentType::Complex(V2ComplexType::CNE), 0, 1, 350, Optionality::R, false),
+            v2_component_descriptor!("occurrence_date", "Occurrence Date", V2ComponentType::Primitive(V2PrimitiveType::Date), 8, 2, 0, Optionality::R, false)
+        ],
+        "OSD" => &[
+            v2_component_descriptor!("sequence_results_flag", "Sequence/Results Flag", V2ComponentType::Primitive(V2PrimitiveType::ID), 1, 1, 524, Optionality::R, false),
+            v2_component_descriptor!("placer_entity_id", "Placer Order Number: Entity Identifier", V2ComponentType::Primitive(V2PrimitiveType::ST), 15, 2, 0, Optionality::R, false),
+            v2_component_descriptor!("placer_namespace_id", "Placer Order Number: Namespace ID", V2ComponentType::Primitive(V2PrimitiveType::IS), 6, 3, 363, Optionality::O, false),
+            v2_component_descriptor!("filler_entity_id", "Filler Order Number: Entity Identifier", V2ComponentType::Primitive(V2PrimitiveType::ST), 15, 4, 0, Optionality::R, false),
+            v2_component_descriptor!("filler_namespace_id", "Filler Order Number: Namespace ID", V2ComponentType::Primitive(V2PrimitiveType::IS), 6, 5, 363, Optionality::O, false),
+            v2_component_descriptor!("sequence_condition_value", "Sequence Condition Value", V2ComponentType::Primitive(V2PrimitiveType::ST), 12, 6, 0, Optionality::O, false),
+            v2_component_descriptor!("maximum_repeats", "Maximum Number of Repeats", V2ComponentType::Primitive(V2PrimitiveType::NM), 3, 7, 0, Optionality::O, false),
+            v2_component_descriptor!("placer_universal_id", "Placer Order Number: Universal ID", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 8, 0, Optionality::R, false),
+            v2_component_descriptor!("placer_universal_id_type", "Placer Order Number: Universal ID Type", V2ComponentType::Primitive(V2PrimitiveType::ID), 6, 9, 301, Optionality::O, false),
+            v2_component_descriptor!("filler_universal_id", "Filler Order Number: Universal ID", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 10, 0, Optionality::R, false),
+            v2_component_descriptor!("filler_universal_id_type", "Filler Order Number: Universal ID Type", V2ComponentType::Primitive(V2PrimitiveType::ID), 6, 11, 301, Optionality::O, false)
+        ],
+        "OSP" => &[
+            v2_component_descriptor!("occurrence_span_code", "Occurrence Span Code", V2ComponentType::Complex(V2ComplexType::CNE), 0, 1, 351, Optionality::R, false),
+            v2_component_descriptor!("start_date", "Occurrence Span Start Date", V2ComponentType::Primitive(V2PrimitiveType::Date), 8, 2, 0, Optionality::O, false),
+            v2_component_descriptor!("stop_date", "Occurrence Span Stop Date", V2ComponentType::Primitive(V2PrimitiveType::Date), 8, 3, 0, Optionality::O, false)
+        ],
+        "PIP" => &[
+            v2_component_descriptor!("privilege", "Privilege", V2ComponentType::Complex(V2ComplexType::CWE), 0, 1, 525, Optionality::R, false),
+            v2_component_descriptor!("privilege_class", "Privilege Class", V2ComponentType::Complex(V2ComplexType::CWE), 0, 2, 526, Optionality::O, false),
+            v2_component_descriptor!("expiration_date", "Expiration Date", V2ComponentType::Primitive(V2PrimitiveType::Date), 8, 3, 0, Optionality::O, false),
+            v2_component_descriptor!("activation_date", "Activation Date", V2ComponentType::Primitive(V2PrimitiveType::Date), 8, 4, 0, Optionality::O, false),
+            v2_component_descriptor!("facility", "Facility", V2ComponentType::Complex(V2ComplexType::EI), 0, 5, 0, Optionality::O, false)
+        ],
+        "PL" => &[
+            v2_component_descriptor!("point_of_care", "Point of Care", V2ComponentType::Complex(V2ComplexType::HD), 0, 1, 302, Optionality::O, false),
+            v2_component_descriptor!("room", "Room", V2ComponentType::Complex(V2ComplexType::HD), 0, 2, 303, Optionality::O, false),
+            v2_component_descriptor!("bed", "Bed", V2ComponentType::Complex(V2ComplexType::HD), 0, 3, 304, Optionality::O, false),
+            v2_component_descriptor!("facility", "Facility", V2ComponentType::Complex(V2ComplexType::HD), 0, 4, 0, Optionality::O, false),
+            v2_component_descriptor!("location_status", "Location Status", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 5, 306, Optionality::O, false),
+            v2_component_descriptor!("person_location_type", "Person Location Type", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 6, 305, Optionality::O, false),
+            v2_component_descriptor!("building", "Building", V2ComponentType::Complex(V2ComplexType::HD), 0, 7, 307, Optionality::O, false),
+            v2_component_descriptor!("floor", "Floor", V2ComponentType::Complex(V2ComplexType::HD), 0, 8, 308, Optionality::O, false),
+            v2_component_descriptor!("location_description", "Location Description", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 9, 0, Optionality::O, true),
+            v2_component_descriptor!("location_id", "Comprehensive Location Identifier", V2ComponentType::Complex(V2ComplexType::EI), 0, 10, 0, Optionality::O, false),
+            v2_component_descriptor!("location_assigning_authority", "Assigning Authority for Location", V2ComponentType::Complex(V2ComplexType::HD), 0, 11, 363, Optionality::O, false)
+        ],
+        "PLN" => &[
+            v2_component_descriptor!("id", "ID Number", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 1, 0, Optionality::R, false),
+            v2_component_descriptor!("id_type", "Type of ID Number", V2ComponentType::Complex(V2ComplexType::CWE), 0, 2, 338, Optionality::R, false),
+            v2_component_descriptor!("qualifying_info", "State/other Qualifying Information", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 3, 0, Optionality::O, false),
+            v2_component_descriptor!("expiration_date", "Expiration Date", V2ComponentType::Primitive(V2PrimitiveType::Date), 8, 4, 0, Optionality::O, false)
+        ],
+        "PPN" => &[
+            v2_component_descriptor!("person_id", "Person Identifier", V2ComponentType::Primitive(V2PrimitiveType::ST), 15, 1, 0, Optionality::O, false),
+            v2_component_descriptor!("family_name", "Family Name", V2ComponentType::Complex(V2ComplexType::FN), 0, 2, 0, Optionality::O, false),
+            v2_component_descriptor!("given_name", "Given Name", V2ComponentType::Primitive(V2PrimitiveType::ST), 30, 3, 0, Optionality::O, true),
+            v2_component_descriptor!("second_given_name", "Second and Further Given Names or Initials Thereof", V2ComponentType::Primitive(V2PrimitiveType::ST), 30, 4, 0, Optionality::O, true),
+            v2_component_descriptor!("suffix", "Suffix (e.g. JR or III)", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 5, 0, Optionality::O, true),
+            v2_component_descriptor!("prefix", "Prefix (e.g. DR)", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 6, 0, Optionality::O, true),
+            v2_component_descriptor!("degree", "Degree (e.g. MD)", V2ComponentType::Primitive(V2PrimitiveType::IS), 6, 7, 360, Optionality::B, false),
+            v2_component_descriptor!("source_table", "Source Table", V2ComponentType::Complex(V2ComplexType::CWE), 0, 8, 297, Optionality::C(Some(CONDITION_PPN1)), false),
+            v2_component_descriptor!("assigning_authority", "Assigning Authority", V2ComponentType::Complex(V2ComplexType::HD), 0, 9, 363, Optionality::C(Some(CONDITION_PPN2)), false),
+            v2_component_descriptor!("name_type", "Name Type Code", V2ComponentType::Primitive(V2PrimitiveType::ID), 1, 10, 200, Optionality::O, false),
+            v2_component_descriptor!("check_digit", "Identifier Check Digit", V2ComponentType::Primitive(V2PrimitiveType::ST), 4, 11, 0, Optionality::O, false),
+            v2_component_descriptor!("check_digit_scheme", "Check Digit Scheme", V2ComponentType::Primitive(V2PrimitiveType::ID), 3, 12, 61, Optionality::C(Some(CONDITION_PPN3)), false),
+            v2_component_descriptor!("id_type", "Identifier Type Code", V2ComponentType::Primitive(V2PrimitiveType::ID), 5, 13, 203, Optionality::O, false),
+            v2_component_descriptor!("assigning_facility", "Assigning Facility", V2ComponentType::Complex(V2ComplexType::HD), 0, 14, 0, Optionality::O, false),
+            v2_component_descriptor!("action_performed_date", "Date/Time Action Performed", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 15, 0, Optionality::O, false),
+            v2_component_descriptor!("name_representation", "Name Representation Code", V2ComponentType::Primitive(V2PrimitiveType::ID), 1, 16, 4465, Optionality::O, false),
+            v2_component_descriptor!("name_context", "Name Context", V2ComponentType::Complex(V2ComplexType::CWE), 0, 17, 448, Optionality::O, false),
+            v2_component_descriptor!("name_validity_range", "Name Validity Range", V2ComponentType::Complex(V2ComplexType::DR), 0, 18, 0, Optionality::B, false),
+            v2_component_descriptor!("name_assembly_order", "Name Assembly Order", V2ComponentType::Primitive(V2PrimitiveType::ID), 1, 19, 444, Optionality::O, false),
+            v2_component_descriptor!("effective_date", "Effective Date", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 20, 0, Optionality::O, false),
+            v2_component_descriptor!("expiration_date", "Expiration Date", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 21, 0, Optionality::O, false),
+            v2_component_descriptor!("professional_suffix", "Professional Suffix", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 22, 0, Optionality::O, true),
+            v2_component_descriptor!("assigning_jurisdiction", "Assigning Jurisdiction", V2ComponentType::Complex(V2ComplexType::CWE), 0, 23, 0, Optionality::C(Some(CONDITION_PPN4)), false),
+            v2_component_descriptor!("assigning_agency", "Assigning Agency or Department", V2ComponentType::Complex(V2ComplexType::CWE), 0, 24, 0, Optionality::C(Some(CONDITION_PPN4)), false),
+            v2_component_descriptor!("security_check", "Security Check", V2ComponentType::Primitive(V2PrimitiveType::ST), 4, 25, 0, Optionality::O, false),
+            v2_component_descriptor!("security_check_scheme", "Security Check Scheme", V2ComponentType::Primitive(V2PrimitiveType::ID), 3, 26, 904, Optionality::O, false)
+        ],
+        "PRL" => &[
+            v2_component_descriptor!("parent_observation_id", "Parent Observation Identifier", V2ComponentType::Complex(V2ComplexType::CWE), 0, 1, 0, Optionality::R, false),
+            v2_component_descriptor!("parent_observation_sub_id", "Parent Observation Sub-identifier", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 2, 0, Optionality::O, false),
+            v2_component_descriptor!("parent_observation_value", "Parent Observation Value Descriptor", V2ComponentType::Primitive(V2PrimitiveType::Text), 250, 3, 0, Optionality::O, false)
+        ],
+        "PT" => &[
+            v2_component_descriptor!("processing_id", "Processing ID", V2ComponentType::Primitive(V2PrimitiveType::ID), 1, 1, 103, Optionality::R, false),
+            v2_component_descriptor!("processing_mode", "Processing Mode", V2ComponentType::Primitive(V2PrimitiveType::ID), 1, 2, 207, Optionality::O, false)
+        ],
+        "PTA" => &[
+            v2_component_descriptor!("policy_type", "Policy Type", V2ComponentType::Primitive(V2PrimitiveType::IS), 5, 1, 147, Optionality::R, false),
+            v2_component_descriptor!("amount_class", "Amount Class", V2ComponentType::Primitive(V2PrimitiveType::IS), 9, 2, 193, Optionality::O, false),
+            v2_component_descriptor!("quantity", "Money or Percentage Quantity", V2ComponentType::Primitive(V2PrimitiveType::NM), 16, 3, 0, Optionality::B, false),
+            v2_component_descriptor!("money_or_percentage", "Money or Percentage", V2ComponentType::Complex(V2ComplexType::MOP), 0, 4, 0, Optionality::R, false)
+        ],
+        "QIP" => &[
+            v2_component_descriptor!("segment_field_name", "Segment Field Name", V2ComponentType::Primitive(V2PrimitiveType::ST), 12, 1, 0, Optionality::R, false),
+            v2_component_descriptor!("values", "Values", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 2, 0, Optionality::O, false)
+        ],
+        "QSC" => &[
+            v2_component_descriptor!("segment_field_name", "Segment Field Name", V2ComponentType::Primitive(V2PrimitiveType::ST), 12, 1, 0, Optionality::R, false),
+            v2_component_descriptor!("relational_operator", "Relational Operator", V2ComponentType::Primitive(V2PrimitiveType::ID), 2, 2, 209, Optionality::O, false),
+            v2_component_descriptor!("value", "Value", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 3, 0, Optionality::O, false),
+            v2_component_descriptor!("relational_conjunction", "Relational Conjunction", V2ComponentType::Primitive(V2PrimitiveType::ID), 3, 4, 210, Optionality::O, false)
+        ],
+        "RCD" => &[
+            v2_component_descriptor!("segment_field_name", "Segment Field Name", V2ComponentType::Primitive(V2PrimitiveType::ST), 12, 1, 0, Optionality::O, false),
+            v2_component_descriptor!("hl7_data_type", "HL7 Data Type", V2ComponentType::Primitive(V2PrimitiveType::ID), 3, 2, 440, Optionality::O, false),
+            v2_component_descriptor!("maximum_column_width", "Maximum Column Width", V2ComponentType::Primitive(V2PrimitiveType::NM), 3, 3, 0, Optionality::O, false)
+        ],
+        "RFR" => &[
+            v2_component_descriptor!("numeric_range", "Numeric Range", V2ComponentType::Complex(V2ComplexType::NR), 0, 1, 0, Optionality::R, false),
+            v2_component_descriptor!("administrative_sex", "Administrative Sex", V2ComponentType::Complex(V2ComplexType::CWE), 0, 2, 1, Optionality::O, false),
+            v2_component_descriptor!("age_range", "Age Range", V2ComponentType::Complex(V2ComplexType::NR), 0, 3, 0, Optionality::O, false),
+            v2_component_descriptor!("gestational_age_range", "Gestational Age Range", V2ComponentType::Complex(V2ComplexType::NR), 0, 4, 0, Optionality::O, false),
+            v2_component_descriptor!("species", "Species", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 5, 0, Optionality::O, false),
+            v2_component_descriptor!("race_subspecies", "Race/subspecies", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 6, 0, Optionality::O, false),
+            v2_component_descriptor!("conditions", "Conditions", V2ComponentType::Primitive(V2PrimitiveType::Text), 0, 7, 0, Optionality::O, false)
+        ],
+        "RI" => &[
+            v2_component_descriptor!("repeat_pattern", "Repeat Pattern", V2ComponentType::Complex(V2ComplexType::CWE), 0, 1, 335, Optionality::O, false),
+            v2_component_descriptor!("explicit_time_interval", "Explicit Time Interval", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 2, 0, Optionality::O, false)
+        ],
+        "RMC" => &[
+            v2_component_descriptor!("room_type", "Room Type", V2ComponentType::Complex(V2ComplexType::CWE), 0, 1, 145, Optionality::R, false),
+            v2_component_descriptor!("amount_type", "Amount Type", V2ComponentType::Complex(V2ComplexType::CWE), 0, 2, 146, Optionality::O, false),
+            v2_component_descriptor!("coverage_amount", "Coverage Amount", V2ComponentType::Primitive(V2PrimitiveType::NM), 16, 3, 0, Optionality::B, false),
+            v2_component_descriptor!("money_or_percentage", "Money or Percentage", V2ComponentType::Complex(V2ComplexType::MOP), 0, 4, 0, Optionality::R, false)
+        ],
+        "RP" => &[
+            v2_component_descriptor!("pointer", "Pointer", V2ComponentType::Primitive(V2PrimitiveType::ST), 15, 1, 0, Optionality::O, false),
+            v2_component_descriptor!("application_id", "Application ID", V2ComponentType::Complex(V2ComplexType::HD), 0, 2, 0, Optionality::O, false),
+            v2_component_descriptor!("data_type", "Type of Data", V2ComponentType::Primitive(V2PrimitiveType::ID), 9, 3, 834, Optionality::O, false),
+            v2_component_descriptor!("subtype", "Subtype", V2ComponentType::Primitive(V2PrimitiveType::ID), 19, 4, 291, Optionality::O, false)
+        ],
+        "RPT" => &[
+            v2_component_descriptor!("repeat_pattern_code", "Repeat Pattern Code", V2ComponentType::Complex(V2ComplexType::CWE), 0, 1, 335, Optionality::R, false),
+            v2_component_descriptor!("calendar_alignment", "Calendar Alignment", V2ComponentType::Primitive(V2PrimitiveType::ID), 2, 2, 527, Optionality::O, false),
+            v2_component_descriptor!("phase_range_begin", "Phase Range Begin Value", V2ComponentType::Primitive(V2PrimitiveType::NM), 10, 3, 0, Optionality::O, false),
+            v2_component_descriptor!("phase_range_end", "Phase Range End Value", V2ComponentType::Primitive(V2PrimitiveType::NM), 10, 4, 0, Optionality::O, false),
+            v2_component_descriptor!("period_quantity", "Period Quantity", V2ComponentType::Primitive(V2PrimitiveType::NM), 10, 5, 0, Optionality::O, false),
+            v2_component_descriptor!("period_units", "Period Units", V2ComponentType::Complex(V2ComplexType::CWE), 0, 6, 0, Optionality::C(Some(CONDITION_RPT1)), false),
+            v2_component_descriptor!("institution_specified_time", "Institution Specified Time", V2ComponentType::Primitive(V2PrimitiveType::ID), 1, 7, 136, Optionality::O, false),
+            v2_component_descriptor!("event", "Event", V2ComponentType::Primitive(V2PrimitiveType::ID), 6, 8, 528, Optionality::O, false),
+            v2_component_descriptor!("event_offset_quantity", "Event Offset Quantity", V2ComponentType::Primitive(V2PrimitiveType::NM), 10, 9, 0, Optionality::O, false),
+            v2_component_descriptor!("event_offset_units", "Event Offset Units", V2ComponentType::Complex(V2ComplexType::CWE), 0, 10, 0, Optionality::C(Some(CONDITION_RPT2)), false),
+            v2_component_descriptor!("general_timing_specification", "General Timing Specification", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 11, 0, Optionality::O, false)
+        ],
+        "SAD" => &[
+            v2_component_descriptor!("street_address", "Street or Mailing Address", V2ComponentType::Primitive(V2PrimitiveType::ST), 120, 1, 0, Optionality::O, true),
+            v2_component_descriptor!("street_name", "Street Name", V2ComponentType::Primitive(V2PrimitiveType::ST), 50, 2, 0, Optionality::O, true),
+            v2_component_descriptor!("dwelling_number", "Dwelling Number", V2ComponentType::Primitive(V2PrimitiveType::ST), 12, 3, 0, Optionality::O, false)
+        ],
+        "SCV" => &[
+            v2_component_descriptor!("parameter_class", "Parameter Class", V2ComponentType::Complex(V2ComplexType::CWE), 0, 1, 294, Optionality::O, false),
+            v2_component_descriptor!("parameter_value", "Parameter Value", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 2, 0, Optionality::O, false)
+        ],
+        "SN" => &[
+            v2_component_descriptor!("comparator", "Comparator", V2ComponentType::Primitive(V2PrimitiveType::ST), 2, 1, 0, Optionality::O, false),
+            v2_component_descriptor!("num1", "Num1", V2ComponentType::Primitive(V2PrimitiveType::NM), 15, 2, 0, Optionality::O, false),
+            v2_component_descriptor!("separator_suffix", "Separator/Suffix", V2ComponentType::Primitive(V2PrimitiveType::ST), 1, 3, 0, Optionality::O, false),
+            v2_component_descriptor!("num2", "Num2", V2ComponentType::Primitive(V2PrimitiveType::NM), 15, 4, 0, Optionality::O, false)
+        ],
+        "SPD" => &[
+            v2_component_descriptor!("specialty_name", "Specialty Name", V2ComponentType::Primitive(V2PrimitiveType::ST), 50, 1, 0, Optionality::R, false),
+            v2_component_descriptor!("governing_board", "Governing Board", V2ComponentType::Primitive(V2PrimitiveType::ST), 50, 2, 0, Optionality::O, false),
+            v2_component_descriptor!("eligible_or_certified", "Eligible or Certified", V2ComponentType::Primitive(V2PrimitiveType::ID), 1, 3, 337, Optionality::O, false),
+            v2_component_descriptor!("certification_date", "Date of Certification", V2ComponentType::Primitive(V2PrimitiveType::Date), 8, 4, 0, Optionality::O, false)
+        ],
+        "SPS" => &[
+            v2_component_descriptor!("specimen_source", "Specimen Source Name or Code", V2ComponentType::Complex(V2ComplexType::CWE), 0, 1, 70, Optionality::O, false),
+            v2_component_descriptor!("additives", "Additives", V2ComponentType::Complex(V2ComplexType::CWE), 0, 2, 371, Optionality::O, false),
+            v2_component_descriptor!("collection_method", "Specimen Collection Method", V2ComponentType::Primitive(V2PrimitiveType::Text), 0, 3, 0, Optionality::O, false),
+            v2_component_descriptor!("body_site", "Body Site", V2ComponentType::Complex(V2ComplexType::CWE), 0, 4, 163, Optionality::O, false),
+            v2_component_descriptor!("site_modifier", "Site Modifier", V2ComponentType::Complex(V2ComplexType::CWE), 0, 5, 495, Optionality::O, false),
+            v2_component_descriptor!("collection_method_modifier", "Collection Method Modifier Code", V2ComponentType::Complex(V2ComplexType::CWE), 0, 6, 0, Optionality::O, false),
+            v2_component_descriptor!("specimen_role", "Specimen Role", V2ComponentType::Complex(V2ComplexType::CWE), 0, 7, 369, Optionality::O, false)
+        ],
+        "SRT" => &[
+            v2_component_descriptor!("sort_by_field", "Sort-by Field", V2ComponentType::Primitive(V2PrimitiveType::ST), 12, 1, 0, Optionality::R, false),
+            v2_component_descriptor!("sequencing", "Sequencing", V2ComponentType::Primitive(V2PrimitiveType::ID), 2, 2, 397, Optionality::O, false)
+        ],
+        "TQ" => &[
+            v2_component_descriptor!("quantity", "Quantity", V2ComponentType::Complex(V2ComplexType::CQ), 0, 1, 0, Optionality::O, false),
+            v2_component_descriptor!("interval", "Interval", V2ComponentType::Complex(V2ComplexType::RI), 0, 2, 0, Optionality::O, false),
+            v2_component_descriptor!("duration", "Duration", V2ComponentType::Primitive(V2PrimitiveType::ST), 6, 3, 0, Optionality::O, false),
+            v2_component_descriptor!("start_date", "Start Date/Time", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 4, 0, Optionality::O, false),
+            v2_component_descriptor!("end_date", "End Date/Time", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 5, 0, Optionality::O, false),
+            v2_component_descriptor!("priority", "Priority", V2ComponentType::Primitive(V2PrimitiveType::ST), 6, 6, 0, Optionality::O, false),
+            v2_component_descriptor!("condition", "Condition", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 7, 0, Optionality::O, false),
+            v2_component_descriptor!("text", "Text", V2ComponentType::Primitive(V2PrimitiveType::Text), 200, 8, 0, Optionality::O, false),
+            v2_component_descriptor!("conjunction", "Conjunction", V2ComponentType::Primitive(V2PrimitiveType::ID), 1, 9, 472, Optionality::O, false),
+            v2_component_descriptor!("order_sequencing", "Order Sequencing", V2ComponentType::Complex(V2ComplexType::OSD), 0, 10, 0, Optionality::O, false),
+            v2_component_descriptor!("occurrence_duration", "Occurrence Duration", V2ComponentType::Complex(V2ComplexType::CWE), 0, 11, 0, Optionality::O, false),
+            v2_component_descriptor!("total_occurrences", "Total Occurrences", V2ComponentType::Primitive(V2PrimitiveType::NM), 4, 12, 0, Optionality::O, false)
+        ],
+        "TS" => &[
+            v2_component_descriptor!("time", "Time", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 1, 0, Optionality::R, false),
+            v2_component_descriptor!("degree_of_precision", "Degree of Precision", V2ComponentType::Primitive(V2PrimitiveType::ID), 1, 2, 529, Optionality::B, false)
+        ],
+        "UVC" => &[
+            v2_component_descriptor!("value_code", "Value Code", V2ComponentType::Complex(V2ComplexType::CWE), 0, 1, 153, Optionality::R, false),
+            v2_component_descriptor!("value_amount", "Value Amount", V2ComponentType::Complex(V2ComplexType::MO), 0, 2, 0, Optionality::O, false),
+            v2_component_descriptor!("non_monetary_quantity", "Non-Monetary Value Amount / Quantity", V2ComponentType::Primitive(V2PrimitiveType::NM), 0, 3, 0, Optionality::O, false),
+            v2_component_descriptor!("non_monetary_units", "Non-Monetary Value Amount / Units", V2ComponentType::Complex(V2ComplexType::CWE), 0, 4, 0, Optionality::O, false)
+        ],
+        "VH" => &[
+            v2_component_descriptor!("start_day", "Start Day Range", V2ComponentType::Primitive(V2PrimitiveType::ID), 3, 1, 267, Optionality::O, false),
+            v2_component_descriptor!("end_day", "End Day Range", V2ComponentType::Primitive(V2PrimitiveType::ID), 3, 2, 267, Optionality::O, false),
+            v2_component_descriptor!("start_hour", "Start Hour Range", V2ComponentType::Primitive(V2PrimitiveType::Time), 0, 3, 0, Optionality::O, false),
+            v2_component_descriptor!("end_hour", "End Hour Range", V2ComponentType::Primitive(V2PrimitiveType::Time), 0, 4, 0, Optionality::O, false)
+        ],
+        "VID" => &[
+            v2_component_descriptor!("version_id", "Version ID", V2ComponentType::Primitive(V2PrimitiveType::ID), 5, 1, 104, Optionality::O, false),
+            v2_component_descriptor!("internationalization_code", "Internationalization Code", V2ComponentType::Complex(V2ComplexType::CWE), 0, 2, 399, Optionality::O, false),
+            v2_component_descriptor!("international_version_id", "International Version ID", V2ComponentType::Complex(V2ComplexType::CWE), 0, 3, 0, Optionality::O, false)
+        ],
+        "VR" => &[
+            v2_component_descriptor!("first_value", "First Data Code Value", V2ComponentType::Primitive(V2PrimitiveType::ST), 6, 1, 0, Optionality::O, false),
+            v2_component_descriptor!("last_value", "Last Data Code Value", V2ComponentType::Primitive(V2PrimitiveType::ST), 6, 2, 0, Optionality::O, false)
+        ],
+        "WVI" => &[
+            v2_component_descriptor!("channel_number", "Channel Number", V2ComponentType::Primitive(V2PrimitiveType::NM), 4, 1, 0, Optionality::R, false),
+            v2_component_descriptor!("channel_name", "Channel Name", V2ComponentType::Primitive(V2PrimitiveType::ST), 17, 2, 0, Optionality::O, false)
+        ],
+        "WVS" => &[
+            v2_component_descriptor!("source_one", "Source One Name", V2ComponentType::Primitive(V2PrimitiveType::ST), 8, 1, 0, Optionality::R, false),
+            v2_component_descriptor!("source_two", "Source Two Name", V2ComponentType::Primitive(V2PrimitiveType::ST), 8, 2, 0, Optionality::O, false)
+        ],
+        "XAD" => &[
+            v2_component_descriptor!("street_address", "Street Address", V2ComponentType::Complex(V2ComplexType::SAD), 0, 1, 0, Optionality::O, false),
+            v2_component_descriptor!("second_address", "Other Designation", V2ComponentType::Primitive(V2PrimitiveType::ST), 120, 2, 0, Optionality::O, true),
+            v2_component_descriptor!("city", "City", V2ComponentType::Primitive(V2PrimitiveType::ST), 50, 3, 0, Optionality::O, true),
+            v2_component_descriptor!("state", "State or Province", V2ComponentType::Primitive(V2PrimitiveType::ST), 50, 4, 0, Optionality::O, true),
+            v2_component_descriptor!("zip", "Zip or Postal Code", V2ComponentType::Primitive(V2PrimitiveType::ST), 12, 5, 0, Optionality::O, false),
+            v2_component_descriptor!("country", "Country", V2ComponentType::Primitive(V2PrimitiveType::ID), 3, 6, 399, Optionality::O, false),
+            v2_component_descriptor!("address_type", "Address Type", V2ComponentType::Primitive(V2PrimitiveType::ID), 3, 7, 190, Optionality::O, false),
+            v2_component_descriptor!("county", "Other Geographic Designation", V2ComponentType::Primitive(V2PrimitiveType::ST), 50, 8, 0, Optionality::O, true),
+            v2_component_descriptor!("county_code", "County/Parish Code", V2ComponentType::Complex(V2ComplexType::CWE), 0, 9, 289, Optionality::O, false),
+            v2_component_descriptor!("census_tract", "Census Tract", V2ComponentType::Complex(V2ComplexType::CWE), 0, 10, 288, Optionality::O, false),
+            v2_component_descriptor!("address_representation", "Address Representation Code", V2ComponentType::Primitive(V2PrimitiveType::ID), 1, 11, 4465, Optionality::O, false),
+            v2_component_descriptor!("address_validity_range", "Address Validity Range", V2ComponentType::Complex(V2ComplexType::DR), 0, 12, 0, Optionality::B, false),
+            v2_component_descriptor!("effective_date", "Effective Date", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 13, 0, Optionality::O, false),
+            v2_component_descriptor!("expiration_date", "Expiration Date", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 14, 0, Optionality::O, false),
+            v2_component_descriptor!("expiration_reason", "Expiration Reason", V2ComponentType::Complex(V2ComplexType::CWE), 0, 15, 616, Optionality::O, false),
+            v2_component_descriptor!("temporary_indicator", "Temporary Indicator", V2ComponentType::Primitive(V2PrimitiveType::ID), 1, 16, 136, Optionality::O, false),
+            v2_component_descriptor!("bad_address_indicator", "Bad Address Indicator", V2ComponentType::Primitive(V2PrimitiveType::ID), 1, 17, 136, Optionality::O, false),
+            v2_component_descriptor!("address_usage", "Address Usage", V2ComponentType::Primitive(V2PrimitiveType::ID), 2, 18, 617, Optionality::O, false),
+            v2_component_descriptor!("addressee", "Addressee", V2ComponentType::Primitive(V2PrimitiveType::ST), 50, 19, 0, Optionality::O, true),
+            v2_component_descriptor!("comment", "Comment", V2ComponentType::Primitive(V2PrimitiveType::ST), 120, 20, 0, Optionality::O, true),
+            v2_component_descriptor!("preference_order", "Preference Order", V2ComponentType::Primitive(V2PrimitiveType::NM), 2, 21, 0, Optionality::O, false),
+            v2_component_descriptor!("protection_code", "Protection Code", V2ComponentType::Complex(V2ComplexType::CWE), 0, 22, 618, Optionality::O, false),
+            v2_component_descriptor!("address_id", "Address Identifier", V2ComponentType::Complex(V2ComplexType::EI), 0, 23, 0, Optionality::O, false)
+        ],
+        "XCN" => &[
+            v2_component_descriptor!("person_id", "Person Identifier", V2ComponentType::Primitive(V2PrimitiveType::ST), 15, 1, 0, Optionality::O, false),
+            v2_component_descriptor!("family_name", "Family Name", V2ComponentType::Complex(V2ComplexType::FN), 0, 2, 0, Optionality::O, false),
+            v2_component_descriptor!("given_name", "Given Name", V2ComponentType::Primitive(V2PrimitiveType::ST), 30, 3, 0, Optionality::O, true),
+            v2_component_descriptor!("second_given_name", "Second and Further Given Names or Initials Thereof", V2ComponentType::Primitive(V2PrimitiveType::ST), 30, 4, 0, Optionality::O, true),
+            v2_component_descriptor!("suffix", "Suffix (e.g. JR or III)", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 5, 0, Optionality::O, true),
+            v2_component_descriptor!("prefix", "Prefix (e.g. DR)", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 6, 0, Optionality::O, true),
+            v2_component_descriptor!("degree", "Degree (e.g. MD)", V2ComponentType::Primitive(V2PrimitiveType::IS), 6, 7, 360, Optionality::B, false),
+            v2_component_descriptor!("source_table", "Source Table", V2ComponentType::Complex(V2ComplexType::CWE), 0, 8, 297, Optionality::C(Some(CONDITION_XCN1)), false),
+            v2_component_descriptor!("assigning_authority", "Assigning Authority", V2ComponentType::Complex(V2ComplexType::HD), 0, 9, 363, Optionality::C(Some(CONDITION_XCN2)), false),
+            v2_component_descriptor!("name_type", "Name Type Code", V2ComponentType::Primitive(V2PrimitiveType::ID), 1, 10, 200, Optionality::O, false),
+            v2_component_descriptor!("check_digit", "Identifier Check Digit", V2ComponentType::Primitive(V2PrimitiveType::ST), 4, 11, 0, Optionality::O, false),
+            v2_component_descriptor!("check_digit_scheme", "Check Digit Scheme", V2ComponentType::Primitive(V2PrimitiveType::ID), 3, 12, 61, Optionality::C(Some(CONDITION_XCN3)), false),
+            v2_component_descriptor!("id_type", "Identifier Type Code", V2ComponentType::Primitive(V2PrimitiveType::ID), 5, 13, 203, Optionality::O, false),
+            v2_component_descriptor!("assigning_facility", "Assigning Facility", V2ComponentType::Complex(V2ComplexType::HD), 0, 14, 0, Optionality::O, false),
+            v2_component_descriptor!("name_representation", "Name Representation Code", V2ComponentType::Primitive(V2PrimitiveType::ID), 1, 15, 4465, Optionality::O, false),
+            v2_component_descriptor!("name_context", "Name Context", V2ComponentType::Complex(V2ComplexType::CWE), 0, 16, 448, Optionality::O, false),
+            v2_component_descriptor!("name_validity_range", "Name Validity Range", V2ComponentType::Complex(V2ComplexType::DR), 0, 17, 0, Optionality::B, false),
+            v2_component_descriptor!("name_assembly_order", "Name Assembly Order", V2ComponentType::Primitive(V2PrimitiveType::ID), 1, 18, 444, Optionality::O, false),
+            v2_component_descriptor!("effective_date", "Effective Date", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 19, 0, Optionality::O, false),
+            v2_component_descriptor!("expiration_date", "Expiration Date", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 20, 0, Optionality::O, false),
+            v2_component_descriptor!("professional_suffix", "Professional Suffix", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 21, 0, Optionality::O, true),
+            v2_component_descriptor!("assigning_jurisdiction", "Assigning Jurisdiction", V2ComponentType::Complex(V2ComplexType::CWE), 0, 22, 0, Optionality::C(Some(CONDITION_XCN4)), false),
+            v2_component_descriptor!("assigning_agency", "Assigning Agency or Department", V2ComponentType::Complex(V2ComplexType::CWE), 0, 23, 0, Optionality::C(Some(CONDITION_XCN4)), false),
+            v2_component_descriptor!("security_check", "Security Check", V2ComponentType::Primitive(V2PrimitiveType::ST), 4, 24, 0, Optionality::O, false),
+            v2_component_descriptor!("security_check_scheme", "Security Check Scheme", V2ComponentType::Primitive(V2PrimitiveType::ID), 3, 25, 904, Optionality::O, false)
+        ],
+        "XON" => &[
+            v2_component_descriptor!("organization_name", "Organization Name", V2ComponentType::Primitive(V2PrimitiveType::ST), 50, 1, 0, Optionality::C(Some(CONDITION_XON1)), true),
+            v2_component_descriptor!("organization_name_type", "Organization Name Type Code", V2ComponentType::Complex(V2ComplexType::CWE), 0, 2, 204, Optionality::O, false),
+            v2_component_descriptor!("id", "ID Number", V2ComponentType::Primitive(V2PrimitiveType::NM), 4, 3, 0, Optionality::B, false),
+            v2_component_descriptor!("check_digit", "Identifier Check Digit", V2ComponentType::Primitive(V2PrimitiveType::NM), 4, 4, 0, Optionality::B, false),
+            v2_component_descriptor!("check_digit_scheme", "Check Digit Scheme", V2ComponentType::Primitive(V2PrimitiveType::ID), 3, 5, 61, Optionality::B, false),
+            v2_component_descriptor!("assigning_authority", "Assigning Authority", V2ComponentType::Complex(V2ComplexType::HD), 0, 6, 363, Optionality::C(Some(CONDITION_XON2)), false),
+            v2_component_descriptor!("id_type", "Identifier Type Code", V2ComponentType::Primitive(V2PrimitiveType::ID), 5, 7, 203, Optionality::C(Some(CONDITION_XON2)), false),
+            v2_component_descriptor!("assigning_facility", "Assigning Facility", V2ComponentType::Complex(V2ComplexType::HD), 0, 8, 0, Optionality::O, false),
+            v2_component_descriptor!("name_representation", "Name Representation Code", V2ComponentType::Primitive(V2PrimitiveType::ID), 1, 9, 4465, Optionality::O, false),
+            v2_component_descriptor!("organization_id", "Organization Identifier", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 10, 0, Optionality::C(Some(CONDITION_XON3)), false)
+        ],
+        "XPN" => &[
+            v2_component_descriptor!("family_name", "Family Name", V2ComponentType::Complex(V2ComplexType::FN), 0, 1, 0, Optionality::O, false),
+            v2_component_descriptor!("given_name", "Given Name", V2ComponentType::Primitive(V2PrimitiveType::ST), 30, 2, 0, Optionality::O, true),
+            v2_component_descriptor!("second_given_name", "Second and Further Given Names or Initials Thereof", V2ComponentType::Primitive(V2PrimitiveType::ST), 30, 3, 0, Optionality::O, true),
+            v2_component_descriptor!("suffix", "Suffix (e.g. JR or III)", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 4, 0, Optionality::O, true),
+            v2_component_descriptor!("prefix", "Prefix (e.g. DR)", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 5, 0, Optionality::O, true),
+            v2_component_descriptor!("degree", "Degree (e.g. MD)", V2ComponentType::Primitive(V2PrimitiveType::IS), 6, 6, 360, Optionality::B, false),
+            v2_component_descriptor!("name_type", "Name Type Code", V2ComponentType::Primitive(V2PrimitiveType::ID), 1, 7, 200, Optionality::O, false),
+            v2_component_descriptor!("name_representation", "Name Representation Code", V2ComponentType::Primitive(V2PrimitiveType::ID), 1, 8, 4465, Optionality::O, false),
+            v2_component_descriptor!("name_context", "Name Context", V2ComponentType::Complex(V2ComplexType::CWE), 0, 9, 448, Optionality::O, false),
+            v2_component_descriptor!("name_validity_range", "Name Validity Range", V2ComponentType::Complex(V2ComplexType::DR), 0, 10, 0, Optionality::B, false),
+            v2_component_descriptor!("name_assembly_order", "Name Assembly Order", V2ComponentType::Primitive(V2PrimitiveType::ID), 1, 11, 444, Optionality::O, false),
+            v2_component_descriptor!("effective_date", "Effective Date", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 12, 0, Optionality::O, false),
+            v2_component_descriptor!("expiration_date", "Expiration Date", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 13, 0, Optionality::O, false),
+            v2_component_descriptor!("professional_suffix", "Professional Suffix", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 14, 0, Optionality::O, true),
+            v2_component_descriptor!("called_by", "Called By", V2ComponentType::Primitive(V2PrimitiveType::ST), 30, 15, 0, Optionality::O, true)
+        ],
+        "XTN" => &[
+            v2_component_descriptor!("telephone_number", "Telephone Number", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 1, 0, Optionality::B, false),
+            v2_component_descriptor!("use_code", "Telecommunication Use Code", V2ComponentType::Primitive(V2PrimitiveType::ID), 3, 2, 201, Optionality::O, false),
+            v2_component_descriptor!("equipment_type", "Telecommunication Equipment Type", V2ComponentType::Primitive(V2PrimitiveType::ID), 8, 3, 202, Optionality::O, false),
+            v2_component_descriptor!("communication_address", "Communication Address", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 4, 0, Optionality::C(Some(CONDITION_XTN1)), false),
+            v2_component_descriptor!("country_code", "Country Code", V2ComponentType::Primitive(V2PrimitiveType::SNM), 3, 5, 0, Optionality::O, false),
+            v2_component_descriptor!("area_code", "Area/City Code", V2ComponentType::Primitive(V2PrimitiveType::SNM), 5, 6, 0, Optionality::C(Some(CONDITION_XTN2)), false),
+            v2_component_descriptor!("local_number", "Local Number", V2ComponentType::Primitive(V2PrimitiveType::SNM), 9, 7, 0, Optionality::C(Some(CONDITION_XTN2)), false),
+            v2_component_descriptor!("extension", "Extension", V2ComponentType::Primitive(V2PrimitiveType::SNM), 5, 8, 0, Optionality::O, false),
+            v2_component_descriptor!("any_text", "Any Text", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 9, 0, Optionality::O, true),
+            v2_component_descriptor!("extension_prefix", "Extension Prefix", V2ComponentType::Primitive(V2PrimitiveType::ST), 4, 10, 0, Optionality::O, false),
+            v2_component_descriptor!("speed_dial_code", "Speed Dial Code", V2ComponentType::Primitive(V2PrimitiveType::ST), 6, 11, 0, Optionality::O, false),
+            v2_component_descriptor!("unformatted_number", "Unformatted Telephone number", V2ComponentType::Primitive(V2PrimitiveType::ST), 199, 12, 0, Optionality::C(Some(CONDITION_XTN3)), false),
+            v2_component_descriptor!("effective_start_date", "Effective Start Date", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 13, 0, Optionality::O, false),
+            v2_component_descriptor!("expiration_date", "Expiration Date", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 0, 14, 0, Optionality::O, false),
+            v2_component_descriptor!("expiration_reason", "Expiration Reason", V2ComponentType::Complex(V2ComplexType::CWE), 0, 15, 868, Optionality::O, false),
+            v2_component_descriptor!("protection_code", "Protection Code", V2ComponentType::Complex(V2ComplexType::CWE), 0, 16, 618, Optionality::O, false),
+            v2_component_descriptor!("shared_telecommunication_id", "Shared Telecommunication Identifier", V2ComponentType::Complex(V2ComplexType::EI), 0, 17, 0, Optionality::O, false),
+            v2_component_descriptor!("preference_order", "Preference Order", V2ComponentType::Primitive(V2PrimitiveType::NM), 2, 18, 0, Optionality::O, false)
         ]
     };
 
@@ -1103,7 +2754,177 @@ pub mod v2_field_descriptor {
             V2ComplexType::CP => "CP",
             V2ComplexType::CQ => "CQ",
             V2ComplexType::CSU => "CSU",
-            _ => "Error",
+            V2ComplexType::CWE => "CWE",
+            V2ComplexType::CX => "CX",
+            V2ComplexType::DDI => "DDI",
+            V2ComplexType::DIN => "DIN",
+            V2ComplexType::DLD => "DLD",
+            V2ComplexType::DLN => "DLN",
+            V2ComplexType::DLT => "DLT",
+            V2ComplexType::DR => "DR",
+            V2ComplexType::DTN => "DTN",
+            V2ComplexType::ED => "ED",
+            V2ComplexType::EI => "EI",
+            V2ComplexType::EIP => "EIP",
+            V2ComplexType::ELD => "ELD",
+            V2ComplexType::ERL => "ERL",
+            V2ComplexType::FC => "FC",
+            V2ComplexType::FN => "FN",
+            V2ComplexType::HD => "HD",
+            V2ComplexType::ICD => "ICD",
+            V2ComplexType::JCC => "JCC",
+            V2ComplexType::LA1 => "LA1",
+            V2ComplexType::LA2 => "LA2",
+            V2ComplexType::MO => "MO",
+            V2ComplexType::MOC => "MOC",
+            V2ComplexType::MOP => "MOP",
+            V2ComplexType::MSG => "MSG",
+            V2ComplexType::NDL => "NDL",
+            V2ComplexType::NR => "NR",
+            V2ComplexType::OCD => "OCD",
+            V2ComplexType::OSD => "OSD",
+            V2ComplexType::OSP => "OSP",
+            V2ComplexType::PIP => "PIP",
+            V2ComplexType::PL => "PL",
+            V2ComplexType::PLN => "PLN",
+            V2ComplexType::PPN => "PPN",
+            V2ComplexType::PRL => "PRL",
+            V2ComplexType::PT => "PT",
+            V2ComplexType::PTA => "PTA",
+            V2ComplexType::QIP => "QIP",
+            V2ComplexType::QSC => "QSC",
+            V2ComplexType::RCD => "RCD",
+            V2ComplexType::RFR => "RFR",
+            V2ComplexType::RI => "RI",
+            V2ComplexType::RMC => "RMC",
+            V2ComplexType::RP => "RP",
+            V2ComplexType::RPT => "RPT",
+            V2ComplexType::SAD => "SAD",
+            V2ComplexType::SCV => "SCV",
+            V2ComplexType::SN => "SN",
+            V2ComplexType::SPD => "SPD",
+            V2ComplexType::SPS => "SPS",
+            V2ComplexType::SRT => "SRT",
+            V2ComplexType::TQ => "TQ",
+            V2ComplexType::TS => "TS",
+            V2ComplexType::UVC => "UVC",
+            V2ComplexType::VH => "VH",
+            V2ComplexType::VID => "VID",
+            V2ComplexType::VR => "VR",
+            V2ComplexType::WVI => "WVI",
+            V2ComplexType::WVS => "WVS",
+            V2ComplexType::XAD => "XAD",
+            V2ComplexType::XCN => "XCN",
+            V2ComplexType::XON => "XON",
+            V2ComplexType::XPN => "XPN",
+            V2ComplexType::XTN => "XTN",
+        }
+    }
+
+    ///
+    /// Return enumerator key corresponding to string key.
+    ///
+    pub fn complex_type_from_str(complex_type: &str) -> Option<V2ComplexType> {
+        match complex_type {
+            "AD" => Some(V2ComplexType::AD),
+            "AUI" => Some(V2ComplexType::AUI),
+            "CCD" => Some(V2ComplexType::CCD),
+            "CCP" => Some(V2ComplexType::CCP),
+            "CD" => Some(V2ComplexType::CD),
+            "CE" => Some(V2ComplexType::CE),
+            "CF" => Some(V2ComplexType::CF),
+            "CNE" => Some(V2ComplexType::CNE),
+            "CNN" => Some(V2ComplexType::CNN),
+            "CP" => Some(V2ComplexType::CP),
+            "CQ" => Some(V2ComplexType::CQ),
+            "CSU" => Some(V2ComplexType::CSU),
+            "CWE" => Some(V2ComplexType::CWE),
+            "CX" => Some(V2ComplexType::CX),
+            "DDI" => Some(V2ComplexType::DDI),
+            "DIN" => Some(V2ComplexType::DIN),
+            "DLD" => Some(V2ComplexType::DLD),
+            "DLN" => Some(V2ComplexType::DLN),
+            "DLT" => Some(V2ComplexType::DLT),
+            "DR" => Some(V2ComplexType::DR),
+            "DTN" => Some(V2ComplexType::DTN),
+            "ED" => Some(V2ComplexType::ED),
+            "EI" => Some(V2ComplexType::EI),
+            "EIP" => Some(V2ComplexType::EIP),
+            "ELD" => Some(V2ComplexType::ELD),
+            "ERL" => Some(V2ComplexType::ERL),
+            "FC" => Some(V2ComplexType::FC),
+            "FN" => Some(V2ComplexType::FN),
+            "HD" => Some(V2ComplexType::HD),
+            "ICD" => Some(V2ComplexType::ICD),
+            "JCC" => Some(V2ComplexType::JCC),
+            "LA1" => Some(V2ComplexType::LA1),
+            "LA2" => Some(V2ComplexType::LA2),
+            "MO" => Some(V2ComplexType::MO),
+            "MOC" => Some(V2ComplexType::MOC),
+            "MOP" => Some(V2ComplexType::MOP),
+            "MSG" => Some(V2ComplexType::MSG),
+            "NDL" => Some(V2ComplexType::NDL),
+            "NR" => Some(V2ComplexType::NR),
+            "OCD" => Some(V2ComplexType::OCD),
+            "OSD" => Some(V2ComplexType::OSD),
+            "OSP" => Some(V2ComplexType::OSP),
+            "PIP" => Some(V2ComplexType::PIP),
+            "PL" => Some(V2ComplexType::PL),
+            "PLN" => Some(V2ComplexType::PLN),
+            "PPN" => Some(V2ComplexType::PPN),
+            "PRL" => Some(V2ComplexType::PRL),
+            "PT" => Some(V2ComplexType::PT),
+            "PTA" => Some(V2ComplexType::PTA),
+            "QIP" => Some(V2ComplexType::QIP),
+            "QSC" => Some(V2ComplexType::QSC),
+            "RCD" => Some(V2ComplexType::RCD),
+            "RFR" => Some(V2ComplexType::RFR),
+            "RI" => Some(V2ComplexType::RI),
+            "RMC" => Some(V2ComplexType::RMC),
+            "RP" => Some(V2ComplexType::RP),
+            "RPT" => Some(V2ComplexType::RPT),
+            "SAD" => Some(V2ComplexType::SAD),
+            "SCV" => Some(V2ComplexType::SCV),
+            "SN" => Some(V2ComplexType::SN),
+            "SPD" => Some(V2ComplexType::SPD),
+            "SPS" => Some(V2ComplexType::SPS),
+            "SRT" => Some(V2ComplexType::SRT),
+            "TQ" => Some(V2ComplexType::TQ),
+            "TS" => Some(V2ComplexType::TS),
+            "UVC" => Some(V2ComplexType::UVC),
+            "VH" => Some(V2ComplexType::VH),
+            "VID" => Some(V2ComplexType::VID),
+            "VR" => Some(V2ComplexType::VR),
+            "WVI" => Some(V2ComplexType::WVI),
+            "WVS" => Some(V2ComplexType::WVS),
+            "XAD" => Some(V2ComplexType::XAD),
+            "XCN" => Some(V2ComplexType::XCN),
+            "XON" => Some(V2ComplexType::XON),
+            "XPN" => Some(V2ComplexType::XPN),
+            "XTN" => Some(V2ComplexType::XTN),
+            _ => None,
         }
     }
+
+    ///
+    /// Maps an HL7 data type code (e.g. `ST`, `DTM`, `XPN`) to the component type we cast it to.
+    /// Used to resolve fields whose type varies, like OBX-5, from the code in OBX-2.
+    ///
+    pub fn data_type_from_str(data_type: &str) -> Option<V2ComponentType> {
+        let primitive = match data_type {
+            "ST" | "GTS" | "TN" => V2PrimitiveType::ST,
+            "ID" => V2PrimitiveType::ID,
+            "IS" => V2PrimitiveType::IS,
+            "NM" => V2PrimitiveType::NM,
+            "SI" => V2PrimitiveType::SI,
+            "FT" => V2PrimitiveType::FT,
+            "TX" => V2PrimitiveType::Text,
+            "DT" => V2PrimitiveType::Date,
+            "DTM" => V2PrimitiveType::DateTime,
+            "TM" => V2PrimitiveType::Time,
+            "SNM" => V2PrimitiveType::SNM,
+            _ => return complex_type_from_str(data_type).map(V2ComponentType::Complex),
+        };
+        Some(V2ComponentType::Primitive(primitive))
+    }
 }
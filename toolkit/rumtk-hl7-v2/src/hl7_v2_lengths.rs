@@ -0,0 +1,411 @@
+/*
+ * rumtk attempts to implement HL7 and medical protocols for interoperability in medicine.
+ * This toolkit aims to be reliable, simple, performant, and standards compliant.
+ * Copyright (C) 2024  Luis M. Santos, M.D.
+ *
+ * This library is free software; you can redistribute it and/or
+ * modify it under the terms of the GNU Lesser General Public
+ * License as published by the Free Software Foundation; either
+ * version 2.1 of the License, or (at your option) any later version.
+ *
+ * This library is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+ * Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public
+ * License along with this library; if not, write to the Free Software
+ * Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301  USA
+ */
+
+///
+/// Maximum length enforcement for generated messages and detection of values truncated by the
+/// sender.
+///
+/// ## Per Section 2.5.5.2
+/// ```text
+/// If the value is longer than the maximum length, the value SHALL be truncated to the maximum
+/// length minus one, and the truncation character appended, so that the value is the maximum
+/// length. The truncation character is only used where the data type allows truncation.
+/// ```
+///
+/// The truncation character only has that meaning if MSH-2 declares it as the 5th encoding
+/// character. Otherwise, a trailing `#` is ordinary data and values are cut without a marker.
+///
+pub mod v2_lengths {
+    use crate::hl7_v2_base_types::v2_base_types::{
+        is_header_segment_name, V2Diagnostic, V2DiagnosticSeverity, V2Diagnostics,
+        V2ParserCharacters, V2Result,
+    };
+    use crate::hl7_v2_base_types::v2_primitives::V2PrimitiveType;
+    use crate::hl7_v2_complex_types::hl7_v2_complex_types::component_data_type;
+    use crate::hl7_v2_field_descriptors::v2_field_descriptor::V2ComponentType;
+    use crate::hl7_v2_parser::v2_parser::{V2Component, V2Message, V2Segment, V2SubComponent};
+    use crate::hl7_v2_segment_descriptors::v2_segment_descriptor::seq_to_position;
+    use crate::hl7_v2_versions::v2_versions::{
+        version_segment_descriptor, version_type_descriptor, V2Version,
+    };
+    use rumtk_core::strings::{format_compact, RUMString};
+
+    /**************************** Types *****************************************/
+
+    ///
+    /// What to do with values longer than the maximum length in their definition when a
+    /// message is generated.
+    ///
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+    pub enum V2LengthPolicy {
+        ///
+        /// Values are generated as given.
+        ///
+        #[default]
+        Ignore,
+        ///
+        /// Values whose data type allows it are truncated. As of v2.7, the last character kept is
+        /// replaced by the truncation character if the message declares one. Values that cannot
+        /// be truncated are an error.
+        ///
+        Truncate,
+        ///
+        /// Any value longer than its maximum length is an error.
+        ///
+        Reject,
+    }
+
+    /**************************** Helpers ***************************************/
+
+    ///
+    /// Only text types may be truncated. Identifiers, codes, numbers, dates, and times have their
+    /// own truncation rules (or none) and never carry the truncation character.
+    ///
+    fn can_truncate(primitive: &V2PrimitiveType) -> bool {
+        matches!(
+            primitive,
+            V2PrimitiveType::String
+                | V2PrimitiveType::ST
+                | V2PrimitiveType::Text
+                | V2PrimitiveType::FT
+        )
+    }
+
+    fn uses_truncation_character(version: V2Version, characters: &V2ParserCharacters) -> bool {
+        version >= V2Version::V2_7 && characters.declares_truncation()
+    }
+
+    ///
+    /// Applies `policy` to one value. Lengths are counted on the decoded value so escape
+    /// sequences count as the character they stand for. Yields whether the value was truncated.
+    ///
+    #[allow(clippy::too_many_arguments)]
+    fn enforce_length<F>(
+        value: &mut V2SubComponent,
+        max_len: u32,
+        truncatable: bool,
+        policy: V2LengthPolicy,
+        characters: &V2ParserCharacters,
+        version: V2Version,
+        location: F,
+    ) -> V2Result<bool>
+    where
+        F: Fn() -> RUMString,
+    {
+        if max_len == 0 || policy == V2LengthPolicy::Ignore {
+            return Ok(false);
+        }
+        let decoded = value.decoded(characters)?;
+        let length = decoded.chars().count();
+        if length <= max_len as usize {
+            return Ok(false);
+        }
+        if policy == V2LengthPolicy::Reject || !truncatable {
+            return Err(format_compact!(
+                "{} is {} characters long but at most {} are allowed!",
+                location(),
+                length,
+                max_len
+            ));
+        }
+        match uses_truncation_character(version, characters) {
+            true => {
+                let kept: RUMString = decoded.chars().take(max_len as usize - 1).collect();
+                value.set_decoded(&kept, characters);
+                let truncated =
+                    format_compact!("{}{}", value.to_string(), &characters.truncation_character);
+                value.set(&truncated);
+            }
+            false => {
+                let kept: RUMString = decoded.chars().take(max_len as usize).collect();
+                value.set_decoded(&kept, characters);
+            }
+        }
+        Ok(true)
+    }
+
+    ///
+    /// Applies `policy` to the subcomponents of a component whose data type is `data_type`.
+    /// Components of primitive type only hold their first subcomponent.
+    ///
+    #[allow(clippy::too_many_arguments)]
+    fn enforce_component_lengths(
+        component: &mut V2Component,
+        data_type: &V2ComponentType,
+        max_len: u32,
+        truncatable: bool,
+        policy: V2LengthPolicy,
+        characters: &V2ParserCharacters,
+        version: V2Version,
+        location: &str,
+    ) -> V2Result<usize> {
+        match data_type {
+            V2ComponentType::Primitive(primitive) => {
+                if component.is_empty() {
+                    return Ok(0);
+                }
+                let truncated = enforce_length(
+                    component.get_mut(1)?,
+                    max_len,
+                    truncatable && can_truncate(primitive),
+                    policy,
+                    characters,
+                    version,
+                    || RUMString::from(location),
+                )?;
+                Ok(truncated as usize)
+            }
+            V2ComponentType::Complex(complex) => {
+                let descriptor = match version_type_descriptor(complex, version) {
+                    Some(descriptor) => descriptor,
+                    None => return Ok(0),
+                };
+                let mut count = 0;
+                for subcomponent_type in descriptor.iter() {
+                    let seq = subcomponent_type.seq as usize;
+                    if seq > component.len() {
+                        break;
+                    }
+                    let primitive = match component_data_type(complex, subcomponent_type, version) {
+                        V2ComponentType::Primitive(primitive) => primitive,
+                        _ => continue,
+                    };
+                    let truncated = enforce_length(
+                        component.get_mut(seq as isize)?,
+                        subcomponent_type.max_input_len,
+                        subcomponent_type.truncate && can_truncate(&primitive),
+                        policy,
+                        characters,
+                        version,
+                        || format_compact!("{}.{}", location, seq),
+                    )?;
+                    count += truncated as usize;
+                }
+                Ok(count)
+            }
+            V2ComponentType::Varies => Ok(0),
+        }
+    }
+
+    /**************************** API *******************************************/
+
+    impl V2SubComponent {
+        ///
+        /// Checks if the value ends with the truncation character, meaning the sender cut it to
+        /// the maximum length. Only meaningful as of v2.7 and only if MSH-2 declares the
+        /// truncation character. Otherwise, a trailing `#` is just data.
+        ///
+        pub fn is_truncated(&self, parser_chars: &V2ParserCharacters) -> bool {
+            parser_chars.declares_truncation()
+                && self
+                    .to_string()
+                    .ends_with(parser_chars.truncation_character.as_str())
+        }
+    }
+
+    impl V2Segment {
+        ///
+        /// Applies `policy` to every field of the segment with a definition in `version`. Fields
+        /// of primitive type are checked against the length of the field. Fields of complex type
+        /// are checked component by component, and only components flagged as truncatable in the
+        /// data type tables are truncated. `location` is used as the prefix of errors (e.g.
+        /// `PID(1)`). Yields how many values were truncated. The segment is left untouched if a
+        /// value is too long and cannot be truncated.
+        ///
+        pub fn enforce_lengths(
+            &mut self,
+            policy: V2LengthPolicy,
+            characters: &V2ParserCharacters,
+            version: V2Version,
+            location: &str,
+        ) -> V2Result<usize> {
+            if policy == V2LengthPolicy::Ignore {
+                return Ok(0);
+            }
+            let mut enforced = self.clone();
+            let count = enforced.enforce_lengths_in_place(policy, characters, version, location)?;
+            if count > 0 {
+                *self = enforced;
+            }
+            Ok(count)
+        }
+
+        fn enforce_lengths_in_place(
+            &mut self,
+            policy: V2LengthPolicy,
+            characters: &V2ParserCharacters,
+            version: V2Version,
+            location: &str,
+        ) -> V2Result<usize> {
+            let mut count = 0;
+            for descriptor in version_segment_descriptor(self.name(), version) {
+                // MSH-1 and MSH-2 are the delimiters.
+                if is_header_segment_name(self.name()) && descriptor.seq <= 2 {
+                    continue;
+                }
+                let position = seq_to_position(self.name(), descriptor.seq as isize);
+                if position < 1 || position > self.len() as isize {
+                    continue;
+                }
+                let data_type = match self.field_data_type_for(position, version) {
+                    Ok(data_type) => data_type,
+                    Err(_) => continue,
+                };
+                for (indx, field) in self.get_mut(position)?.iter_mut().enumerate() {
+                    let field_location = format_compact!("{}{}[{}]", location, position, indx + 1);
+                    match data_type {
+                        V2ComponentType::Primitive(_) => {
+                            if field.is_empty() {
+                                continue;
+                            }
+                            count += enforce_component_lengths(
+                                field.get_mut(1)?,
+                                &data_type,
+                                descriptor.max_input_len,
+                                true,
+                                policy,
+                                characters,
+                                version,
+                                &field_location,
+                            )?;
+                        }
+                        V2ComponentType::Complex(complex) => {
+                            let type_descriptor = match version_type_descriptor(&complex, version) {
+                                Some(type_descriptor) => type_descriptor,
+                                None => continue,
+                            };
+                            for component_type in type_descriptor.iter() {
+                                let seq = component_type.seq as usize;
+                                if seq > field.len() {
+                                    break;
+                                }
+                                count += enforce_component_lengths(
+                                    field.get_mut(seq as isize)?,
+                                    &component_data_type(&complex, component_type, version),
+                                    component_type.max_input_len,
+                                    component_type.truncate,
+                                    policy,
+                                    characters,
+                                    version,
+                                    &format_compact!("{}.{}", field_location, seq),
+                                )?;
+                            }
+                        }
+                        V2ComponentType::Varies => {}
+                    }
+                }
+            }
+            Ok(count)
+        }
+    }
+
+    impl V2Message {
+        ///
+        /// Applies `policy` to every segment of the message, using the definitions of the
+        /// message version. Stops at the first value that is too long and cannot be truncated, in
+        /// which case the message is left untouched. Yields how many values were truncated.
+        ///
+        /// ## Example
+        /// ```
+        /// use rumtk_hl7_v2::hl7_v2_lengths::v2_lengths::V2LengthPolicy;
+        /// use rumtk_hl7_v2::hl7_v2_parser::v2_parser::V2Message;
+        ///
+        /// let mut message = V2Message::try_from_str("MSH|^~\\&#|||||||ADT^A01|1|P|2.7\rPID|1||123||DOE^ALEXANDRIAXXXXXXXXXXXXXXXXXXXXX").unwrap();
+        /// assert_eq!(message.enforce_lengths(V2LengthPolicy::Truncate).unwrap(), 1);
+        /// assert_eq!(message.find_component(&"PID5.2".into()).unwrap().to_string(), "ALEXANDRIAXXXXXXXXXXXXXXXXXXX#");
+        /// ```
+        ///
+        pub fn enforce_lengths(&mut self, policy: V2LengthPolicy) -> V2Result<usize> {
+            if policy == V2LengthPolicy::Ignore {
+                return Ok(0);
+            }
+            let version = self.version();
+            let characters = self.separators().clone();
+            let order = self.segment_order().clone();
+            let mut count = 0;
+            // Truncates copies of the segments and only swaps them in once every segment passed.
+            let mut enforced = Vec::new();
+            for (key, indx) in order {
+                let mut segment = self.get(&key, indx + 1)?.clone();
+                let location = format_compact!("{}({})", segment.name(), indx + 1);
+                let truncated =
+                    segment.enforce_lengths_in_place(policy, &characters, version, &location)?;
+                if truncated > 0 {
+                    enforced.push((key, indx, segment));
+                    count += truncated;
+                }
+            }
+            for (key, indx, segment) in enforced {
+                *self.get_mut(&key, indx + 1)? = segment;
+            }
+            Ok(count)
+        }
+
+        ///
+        /// Flags every value that ends with the truncation character. Messages before v2.7, or
+        /// whose MSH-2 does not declare a truncation character, have none, so nothing is
+        /// flagged for them.
+        ///
+        pub fn truncated_values(&self) -> V2Diagnostics {
+            let mut diagnostics = V2Diagnostics::new();
+            if !uses_truncation_character(self.version(), self.separators()) {
+                return diagnostics;
+            }
+            let characters = self.separators();
+            for (indx, segment) in self.segments().enumerate() {
+                let first = match is_header_segment_name(segment.name()) {
+                    // Skips MSH-2, which holds the truncation character itself.
+                    true => 2,
+                    false => 1,
+                };
+                for position in first..=segment.len() as isize {
+                    let field_group = match segment.get(position) {
+                        Ok(field_group) => field_group,
+                        Err(_) => continue,
+                    };
+                    for field in field_group {
+                        for component in 1..=field.len() as isize {
+                            let truncated = match field.get(component) {
+                                Ok(component) => (1..=component.len() as isize).any(|sub| {
+                                    component
+                                        .get(sub)
+                                        .is_ok_and(|sub| sub.is_truncated(characters))
+                                }),
+                                Err(_) => false,
+                            };
+                            if truncated {
+                                diagnostics.push(
+                                    V2Diagnostic::new(
+                                        V2DiagnosticSeverity::Warning,
+                                        0,
+                                        "Value was truncated by the sender!",
+                                    )
+                                    .at_segment(indx + 1, segment.name())
+                                    .at_field(position, Some(component)),
+                                );
+                            }
+                        }
+                    }
+                }
+            }
+            diagnostics
+        }
+    }
+}
@@ -0,0 +1,491 @@
+/*
+ * rumtk attempts to implement HL7 and medical protocols for interoperability in medicine.
+ * This toolkit aims to be reliable, simple, performant, and standards compliant.
+ * Copyright (C) 2024  Luis M. Santos, M.D.
+ *
+ * This library is free software; you can redistribute it and/or
+ * modify it under the terms of the GNU Lesser General Public
+ * License as published by the Free Software Foundation; either
+ * version 2.1 of the License, or (at your option) any later version.
+ *
+ * This library is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+ * Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public
+ * License along with this library; if not, write to the Free Software
+ * Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301  USA
+ */
+
+///
+/// Borrowed, zero-copy view of an HL7 v2 message.
+///
+/// [V2Message](crate::hl7_v2_parser::v2_parser::V2Message) copies every subcomponent into its own
+/// string. That is convenient when editing a message but costly on busy feeds where we only read a
+/// handful of values out of each message. [V2MessageRef] instead keeps a reference to the original
+/// buffer and only records where each segment and field starts and ends. Repetitions, components,
+/// and subcomponents are located on demand by scanning the field with memchr.
+///
+/// Values returned by this module are the raw slices found in the buffer, so escape sequences are
+/// left as they are. Use [V2ParserCharacters::unescape] when you need the decoded text. When you
+/// need to modify the message, promote it with [V2MessageRef::to_owned_message].
+///
+pub mod v2_message_ref {
+    use crate::hl7_v2_base_types::v2_base_types::{
+        V2ParseMode, V2ParserCharacters, V2Result, V2SearchIndex, V2SegmentIds,
+    };
+    use crate::hl7_v2_constants::{V2_HEADER_SEGMENTS, V2_SEARCH_EXPR_TYPE, V2_SEGMENT_TERMINATOR};
+    use crate::hl7_v2_parser::v2_parser::V2Message;
+    use crate::hl7_v2_search::compile_search_pattern;
+    use memchr::{memchr2_iter, memchr_iter};
+    use rumtk_core::core::clamp_index;
+    use rumtk_core::strings::{format_compact, RUMString};
+
+    /**************************** Constants**************************************/
+
+    ///
+    /// Sanitization in [V2Message] turns every `\n` into a segment terminator, so we scan for both.
+    ///
+    const V2_LINE_FEED: u8 = b'\n';
+
+    /**************************** Helpers ***************************************/
+
+    fn get_delimiter_byte(delimiter: &str, name: &str) -> V2Result<u8> {
+        match delimiter.as_bytes() {
+            [byte] if byte.is_ascii() => Ok(*byte),
+            _ => Err(format_compact!(
+                "The {} {:?} is not a single ASCII character! Borrowed parsing requires single byte delimiters.",
+                name,
+                delimiter
+            )),
+        }
+    }
+
+    ///
+    /// Returns the token at the 1-indexed position `indx` (negative counts from the end) after
+    /// splitting `input` on `delimiter`. Indexing follows the same rules as the owned types.
+    ///
+    fn nth_token<'a>(input: &'a str, delimiter: u8, indx: isize, item: &str) -> V2Result<&'a str> {
+        let bytes = input.as_bytes();
+        let token_count = memchr_iter(delimiter, bytes).count() + 1;
+        let token_indx = match clamp_index(&indx, &(token_count as isize)) {
+            Ok(token_indx) => token_indx - 1,
+            Err(_) => return Err(format_compact!("{} at index {} not found!", item, indx)),
+        };
+        let start = match token_indx {
+            0 => 0,
+            _ => memchr_iter(delimiter, bytes).nth(token_indx - 1).unwrap() + 1,
+        };
+        let end = match memchr_iter(delimiter, &bytes[start..]).next() {
+            Some(offset) => start + offset,
+            None => bytes.len(),
+        };
+        Ok(&input[start..end])
+    }
+
+    /**************************** Types *****************************************/
+
+    ///
+    /// Delimiters of a message as bytes so we can hand them to memchr.
+    ///
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct V2Delimiters {
+        pub field_separator: u8,
+        pub component_separator: u8,
+        pub repetition_separator: u8,
+        pub subcomponent_separator: u8,
+    }
+
+    impl V2Delimiters {
+        pub fn from_parser_chars(parser_chars: &V2ParserCharacters) -> V2Result<Self> {
+            Ok(V2Delimiters {
+                field_separator: get_delimiter_byte(
+                    &parser_chars.field_separator,
+                    "field separator",
+                )?,
+                component_separator: get_delimiter_byte(
+                    &parser_chars.component_separator,
+                    "component separator",
+                )?,
+                repetition_separator: get_delimiter_byte(
+                    &parser_chars.repetition_separator,
+                    "repetition separator",
+                )?,
+                subcomponent_separator: get_delimiter_byte(
+                    &parser_chars.subcomponent_separator,
+                    "subcomponent separator",
+                )?,
+            })
+        }
+    }
+
+    ///
+    /// Borrowed segment. Field boundaries are computed once when the segment is indexed. The first
+    /// entry of the field index is the segment name, so field numbers line up with
+    /// [V2Segment::get](crate::hl7_v2_parser::v2_parser::V2Segment::get).
+    ///
+    #[derive(Debug, PartialEq)]
+    pub struct V2SegmentRef<'a> {
+        id: u8,
+        raw: &'a str,
+        fields: Vec<&'a str>,
+        delimiters: V2Delimiters,
+    }
+
+    impl<'a> V2SegmentRef<'a> {
+        ///
+        /// Indexes a segment. Custom segments get their id from `segment_ids`, the table of the
+        /// message the segment belongs to.
+        ///
+        pub fn from_str(
+            raw_segment: &'a str,
+            delimiters: &V2Delimiters,
+            mode: V2ParseMode,
+            segment_ids: &mut V2SegmentIds,
+        ) -> V2Result<Self> {
+            let bytes = raw_segment.as_bytes();
+            let mut fields = Vec::with_capacity(32);
+            let mut start = 0;
+            for end in memchr_iter(delimiters.field_separator, bytes) {
+                fields.push(&raw_segment[start..end]);
+                start = end + 1;
+            }
+            fields.push(&raw_segment[start..]);
+
+            let name = fields[0];
+            let id = match name.bytes().all(|c| !c.is_ascii_lowercase()) {
+                true => segment_ids.get_or_assign(name, mode)?,
+                false => segment_ids.get_or_assign(&name.to_uppercase(), mode)?,
+            };
+
+            Ok(V2SegmentRef {
+                id,
+                raw: raw_segment,
+                fields,
+                delimiters: *delimiters,
+            })
+        }
+
+        pub fn id(&self) -> u8 {
+            self.id
+        }
+
+        ///
+        /// Segment name exactly as it appears in the buffer.
+        ///
+        pub fn name(&self) -> &'a str {
+            self.fields[0]
+        }
+
+        pub fn raw(&self) -> &'a str {
+            self.raw
+        }
+
+        pub fn len(&self) -> usize {
+            self.fields.len() - 1
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.len() == 0
+        }
+
+        ///
+        /// Returns the whole field, repetitions included.
+        ///
+        pub fn get(&self, indx: isize) -> V2Result<&'a str> {
+            let field_indx = match clamp_index(&indx, &(self.len() as isize)) {
+                Ok(field_indx) => field_indx,
+                Err(_) => return Err(format_compact!("Field number {} not found!", indx)),
+            };
+            Ok(self.fields[field_indx])
+        }
+
+        ///
+        /// Returns a 1-indexed repetition of a field.
+        ///
+        pub fn get_repetition(&self, indx: isize, repetition: usize) -> V2Result<&'a str> {
+            let field = self.get(indx)?;
+            if self.is_encoding_field(indx) {
+                return match repetition {
+                    1 => Ok(field),
+                    _ => Err(format_compact!("Subfield provided is not 1 indexed or out of bounds. Did you give us a 0 when you meant 1? Got {}!", repetition)),
+                };
+            }
+            if repetition == 0 {
+                return Err(format_compact!("Subfield provided is not 1 indexed or out of bounds. Did you give us a 0 when you meant 1? Got {}!", repetition));
+            }
+            let bytes = field.as_bytes();
+            let mut start = 0;
+            let mut separators = memchr_iter(self.delimiters.repetition_separator, bytes);
+            for _ in 1..repetition {
+                start = match separators.next() {
+                    Some(end) => end + 1,
+                    None => return Err(format_compact!("Subfield provided is not 1 indexed or out of bounds. Did you give us a 0 when you meant 1? Got {}!", repetition)),
+                };
+            }
+            let end = match separators.next() {
+                Some(end) => end,
+                None => bytes.len(),
+            };
+            Ok(&field[start..end])
+        }
+
+        ///
+        /// Resolves the field, repetition, and component of a search index within this segment.
+        /// Same as
+        /// [V2Segment::find_component](crate::hl7_v2_parser::v2_parser::V2Segment::find_component).
+        ///
+        pub fn find_component(&self, index: &V2SearchIndex) -> V2Result<&'a str> {
+            let field_indx = index.field as isize;
+            let field = self.get_repetition(field_indx, index.field_group as usize)?;
+            if self.is_encoding_field(field_indx) {
+                // MSH-2 holds the encoding characters, so it is a single component.
+                return match clamp_index(&(index.component as isize), &1) {
+                    Ok(_) => Ok(field),
+                    Err(_) => Err(format_compact!(
+                        "Component at index {} not found!",
+                        index.component
+                    )),
+                };
+            }
+            nth_token(
+                field,
+                self.delimiters.component_separator,
+                index.component as isize,
+                "Component",
+            )
+        }
+
+        ///
+        /// Same as [V2SegmentRef::find_component] but goes down to the subcomponent of the index.
+        ///
+        pub fn find_subcomponent(&self, index: &V2SearchIndex) -> V2Result<&'a str> {
+            let component = self.find_component(index)?;
+            if self.is_encoding_field(index.field as isize) {
+                // The encoding characters are never split into subcomponents.
+                return match clamp_index(&(index.subcomponent as isize), &1) {
+                    Ok(_) => Ok(component),
+                    Err(_) => Err(format_compact!(
+                        "Subcomponent at index {} not found!",
+                        index.subcomponent
+                    )),
+                };
+            }
+            nth_token(
+                component,
+                self.delimiters.subcomponent_separator,
+                index.subcomponent as isize,
+                "Subcomponent",
+            )
+        }
+
+        fn is_encoding_field(&self, indx: isize) -> bool {
+            let field_indx = clamp_index(&indx, &(self.len() as isize)).unwrap_or(0);
+            field_indx == 1
+                && V2_HEADER_SEGMENTS
+                    .iter()
+                    .any(|header| header.eq_ignore_ascii_case(self.name()))
+        }
+    }
+
+    ///
+    /// Borrowed view of a message. See the [module documentation](self) for the trade-offs.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use rumtk_hl7_v2::hl7_v2_message_ref::v2_message_ref::V2MessageRef;
+    /// use rumtk_core::strings::RUMString;
+    /// let raw = "MSH|^~\\&|SENDER|FAC|||20240102||ORU^R01^ORU_R01|1|P|2.5.1\rPID|1||123^^^LAB^MR||DOE^JANE";
+    /// let message = V2MessageRef::try_from_str(raw).unwrap();
+    /// let name = message.find_component(&RUMString::from("PID5.2")).unwrap();
+    /// assert_eq!(name, "JANE");
+    /// let owned = message.to_owned_message().unwrap();
+    /// ```
+    ///
+    #[derive(Debug, PartialEq)]
+    pub struct V2MessageRef<'a> {
+        raw: &'a str,
+        separators: V2ParserCharacters,
+        mode: V2ParseMode,
+        segments: Vec<V2SegmentRef<'a>>,
+        segment_ids: V2SegmentIds,
+    }
+
+    impl<'a> V2MessageRef<'a> {
+        pub fn try_from_str(raw_msg: &'a str) -> V2Result<Self> {
+            Self::try_from_str_with_mode(raw_msg, V2ParseMode::Strict)
+        }
+
+        ///
+        /// Indexes a message without copying it. Segments are split the same way
+        /// [V2Message::try_from_str_with_mode] splits them after sanitizing the message.
+        ///
+        pub fn try_from_str_with_mode(raw_msg: &'a str, mode: V2ParseMode) -> V2Result<Self> {
+            let segment_tokens = V2MessageRef::tokenize_segments(raw_msg);
+            let msh_segment = V2Message::find_msh(&segment_tokens)?;
+            let separators = V2ParserCharacters::from_msh(msh_segment)?;
+            let delimiters = V2Delimiters::from_parser_chars(&separators)?;
+            let mut segments = Vec::with_capacity(segment_tokens.len());
+            let mut segment_ids = V2SegmentIds::new();
+            for segment_str in segment_tokens {
+                segments.push(V2SegmentRef::from_str(
+                    segment_str,
+                    &delimiters,
+                    mode,
+                    &mut segment_ids,
+                )?);
+            }
+
+            Ok(V2MessageRef {
+                raw: raw_msg,
+                separators,
+                mode,
+                segments,
+                segment_ids,
+            })
+        }
+
+        ///
+        /// Splits the buffer on segment terminators and line feeds, trimming each segment and
+        /// skipping empty ones.
+        ///
+        pub fn tokenize_segments(raw_message: &'a str) -> Vec<&'a str> {
+            let bytes = raw_message.as_bytes();
+            let mut tokens = Vec::with_capacity(16);
+            let mut start = 0;
+            let terminators = memchr2_iter(V2_SEGMENT_TERMINATOR as u8, V2_LINE_FEED, bytes);
+            for end in terminators.chain(std::iter::once(bytes.len())) {
+                let token = raw_message[start..end].trim();
+                if !token.is_empty() {
+                    tokens.push(token);
+                }
+                start = end + 1;
+            }
+            tokens
+        }
+
+        pub fn raw(&self) -> &'a str {
+            self.raw
+        }
+
+        pub fn separators(&self) -> &V2ParserCharacters {
+            &self.separators
+        }
+
+        ///
+        /// Ids of the segments in this message. Same as [V2Message::segment_ids].
+        ///
+        pub fn segment_ids(&self) -> &V2SegmentIds {
+            &self.segment_ids
+        }
+
+        pub fn len(&self) -> usize {
+            self.segments.len()
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.segments.is_empty()
+        }
+
+        ///
+        /// Segments in the order they appear in the buffer.
+        ///
+        pub fn segments(&self) -> impl Iterator<Item = &V2SegmentRef<'a>> {
+            self.segments.iter()
+        }
+
+        ///
+        /// Returns the nth (1-indexed) segment with the given segment id. Same as
+        /// [V2Message::get].
+        ///
+        pub fn get(&self, segment_index: &u8, sub_segment: usize) -> V2Result<&V2SegmentRef<'a>> {
+            let mut segments = self
+                .segments
+                .iter()
+                .filter(|segment| segment.id == *segment_index);
+            match sub_segment {
+                0 => None,
+                _ => segments.nth(sub_segment - 1),
+            }
+            .ok_or_else(|| {
+                format_compact!(
+                    "Subsegment {} was not found in segment group {}!",
+                    sub_segment,
+                    segment_index
+                )
+            })
+        }
+
+        ///
+        /// Resolves a search pattern the same way [V2Message::find_component] does, sharing its
+        /// compiled pattern cache. The returned slice points into the original buffer.
+        ///
+        /// Only plain paths such as `PID5[1].2` or `PID(1)5.1` are supported. Query expressions
+        /// (wildcards, ranges, or `WHERE` clauses) are rejected with an error. Promote the message
+        /// with [V2MessageRef::to_owned_message] to run those.
+        ///
+        pub fn find_component(&self, search_pattern: &RUMString) -> V2Result<&'a str> {
+            let (segment, index) = self.find_search_pattern(search_pattern)?;
+            segment.find_component(index)
+        }
+
+        ///
+        /// Resolves a search pattern the same way [V2Message::find_subcomponent] does. The
+        /// returned slice points into the original buffer.
+        ///
+        pub fn find_subcomponent(&self, search_pattern: &RUMString) -> V2Result<&'a str> {
+            let (segment, index) = self.find_search_pattern(search_pattern)?;
+            segment.find_subcomponent(index)
+        }
+
+        fn find_search_pattern(
+            &self,
+            search_pattern: &RUMString,
+        ) -> V2Result<(&V2SegmentRef<'a>, &'static V2SearchIndex)> {
+            if let V2_SEARCH_EXPR_TYPE::V2_QUERY = V2SearchIndex::expr_type(search_pattern) {
+                return Err(format_compact!(
+                    "Query {} is not supported on borrowed messages! Use V2Message instead.",
+                    search_pattern
+                ));
+            }
+            let index = compile_search_pattern(search_pattern);
+            let segment = self.get(
+                &self.segment_ids.find_indexed(index),
+                index.segment_group as usize,
+            )?;
+            Ok((segment, index))
+        }
+
+        ///
+        /// Builds the owned [V2Message] for this buffer. Useful when a value needs to be edited or
+        /// the message has to outlive the buffer.
+        ///
+        pub fn to_owned_message(&self) -> V2Result<V2Message> {
+            let raw_segments: Vec<&str> = self.segments.iter().map(|s| s.raw).collect();
+            let (segment_groups, segment_order, segment_ids) =
+                V2Message::extract_ordered_segments(&raw_segments, &self.separators, self.mode)?;
+            Ok(V2Message::from_parts(
+                self.separators.clone(),
+                segment_groups,
+                segment_order,
+                segment_ids,
+            ))
+        }
+    }
+
+    impl<'a> TryFrom<&'a str> for V2MessageRef<'a> {
+        type Error = RUMString;
+        fn try_from(input: &'a str) -> V2Result<Self> {
+            V2MessageRef::try_from_str(input)
+        }
+    }
+
+    impl TryFrom<&V2MessageRef<'_>> for V2Message {
+        type Error = RUMString;
+        fn try_from(input: &V2MessageRef<'_>) -> V2Result<Self> {
+            input.to_owned_message()
+        }
+    }
+}
@@ -0,0 +1,1066 @@
+/*
+ * rumtk attempts to implement HL7 and medical protocols for interoperability in medicine.
+ * This toolkit aims to be reliable, simple, performant, and standards compliant.
+ * Copyright (C) 2024  Luis M. Santos, M.D.
+ *
+ * This library is free software; you can redistribute it and/or
+ * modify it under the terms of the GNU Lesser General Public
+ * License as published by the Free Software Foundation; either
+ * version 2.1 of the License, or (at your option) any later version.
+ *
+ * This library is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+ * Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public
+ * License along with this library; if not, write to the Free Software
+ * Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301  USA
+ */
+
+///
+/// Message structure definitions and the group tree we build from them.
+///
+/// A [V2Message] is a flat list of segments. The standard, however, organizes the segments of
+/// each message structure into (possibly nested and repeating) segment groups. The definitions
+/// here describe those groups so we can rebuild the hierarchy of a parsed message and answer
+/// questions like "which OBX segments belong to the second OBR".
+///
+pub mod v2_message_structures {
+    use crate::hl7_v2_base_types::v2_base_types::{V2Result, V2SearchIndex, V2String};
+    use crate::hl7_v2_constants::{
+        V2_MESSAGE_STRUCTURE_BY_EVENT, V2_MSHEADER_PATTERN, V2_MSH_MESSAGE_TYPE, V2_SEGMENT_IDS,
+    };
+    use crate::hl7_v2_parser::v2_parser::{
+        V2Component, V2Message, V2Segment, V2SegmentPosition, V2SubComponent,
+    };
+    use crate::{v2_structure_group, v2_structure_segment};
+    use ::phf::Map;
+    use ::phf_macros::phf_map;
+    use rumtk_core::strings::{format_compact, AsStr, RUMString, RUMStringConversions};
+
+    /**************************** Constants**************************************/
+    pub const V2_REQUIRED: bool = false;
+    pub const V2_OPTIONAL: bool = true;
+    pub const V2_SINGLE: bool = false;
+    pub const V2_REPEATING: bool = true;
+    const V2_STRUCTURE_PATH_SEPARATOR: char = '/';
+
+    /**************************** Types *****************************************/
+
+    ///
+    /// An item in the abstract message syntax of a message structure.
+    ///
+    /// ## Per Section 2.12.1
+    ///
+    /// ```text
+    /// The abstract message is defined in special notation that lists the segment names in the order
+    /// they would appear in the message. Braces, {...}, indicate one or more repetitions of the
+    /// enclosed group of segments. Brackets, [...], indicate that the enclosed group of segments is
+    /// optional.
+    /// ```
+    ///
+    #[derive(Debug)]
+    pub enum V2StructureElement {
+        Segment {
+            name: &'static str,
+            optional: bool,
+            repeating: bool,
+        },
+        Group {
+            name: &'static str,
+            optional: bool,
+            repeating: bool,
+            elements: &'static [V2StructureElement],
+        },
+    }
+
+    impl V2StructureElement {
+        pub fn name(&self) -> &'static str {
+            match self {
+                V2StructureElement::Segment { name, .. } => name,
+                V2StructureElement::Group { name, .. } => name,
+            }
+        }
+
+        pub fn is_optional(&self) -> bool {
+            match self {
+                V2StructureElement::Segment { optional, .. } => *optional,
+                V2StructureElement::Group { optional, .. } => *optional,
+            }
+        }
+
+        pub fn is_repeating(&self) -> bool {
+            match self {
+                V2StructureElement::Segment { repeating, .. } => *repeating,
+                V2StructureElement::Group { repeating, .. } => *repeating,
+            }
+        }
+
+        ///
+        /// Returns true if a segment with the given name can be the first segment of this
+        /// element. For groups, we look through the leading optional elements up to and including
+        /// the first required one (the anchor segment in groups defined after v2.5).
+        ///
+        pub fn can_start_with(&self, segment_name: &str) -> bool {
+            match self {
+                V2StructureElement::Segment { name, .. } => *name == segment_name,
+                V2StructureElement::Group { elements, .. } => {
+                    for element in elements.iter() {
+                        if element.can_start_with(segment_name) {
+                            return true;
+                        }
+                        if !element.is_optional() {
+                            break;
+                        }
+                    }
+                    false
+                }
+            }
+        }
+
+        fn contains_segment(&self, segment_name: &str) -> bool {
+            match self {
+                V2StructureElement::Segment { name, .. } => *name == segment_name,
+                V2StructureElement::Group { elements, .. } => elements
+                    .iter()
+                    .any(|element| element.contains_segment(segment_name)),
+            }
+        }
+    }
+
+    ///
+    /// Definition of a message structure such as ADT_A01 (MSH-9.3).
+    ///
+    #[derive(Debug)]
+    pub struct V2MessageStructure {
+        pub name: &'static str,
+        pub description: &'static str,
+        pub elements: &'static [V2StructureElement],
+    }
+
+    impl V2MessageStructure {
+        ///
+        /// Returns true if the segment appears anywhere in this message structure.
+        ///
+        pub fn contains_segment(&self, segment_name: &str) -> bool {
+            self.elements
+                .iter()
+                .any(|element| element.contains_segment(segment_name))
+        }
+    }
+
+    pub type V2MessageStructures = Map<&'static str, &'static V2MessageStructure>;
+
+    ///
+    /// A node in the group tree of a message. Segment nodes point back into the [V2Message].
+    ///
+    #[derive(Debug, PartialEq)]
+    pub enum V2StructureNode {
+        Segment(V2SegmentNode),
+        Group(V2GroupNode),
+    }
+
+    impl V2StructureNode {
+        pub fn name(&self) -> &str {
+            match self {
+                V2StructureNode::Segment(segment) => segment.name.as_str(),
+                V2StructureNode::Group(group) => group.name.as_str(),
+            }
+        }
+    }
+
+    #[derive(Debug, PartialEq)]
+    pub struct V2SegmentNode {
+        pub name: RUMString,
+        ///
+        /// Position of the segment in [V2Message::segment_order].
+        ///
+        pub order: usize,
+        pub position: V2SegmentPosition,
+    }
+
+    #[derive(Debug, PartialEq)]
+    pub struct V2GroupNode {
+        pub name: RUMString,
+        pub children: Vec<V2StructureNode>,
+    }
+
+    impl V2GroupNode {
+        fn new(name: &str) -> V2GroupNode {
+            V2GroupNode {
+                name: name.to_rumstring(),
+                children: Vec::new(),
+            }
+        }
+
+        ///
+        /// Finds the nth (1-indexed) node with the given name. Direct children and their
+        /// descendants are visited in message order.
+        ///
+        pub fn find(&self, name: &str, nth: usize) -> Option<&V2StructureNode> {
+            let mut count = 0;
+            self.find_nth(name, nth, &mut count)
+        }
+
+        fn find_nth<'a>(
+            &'a self,
+            name: &str,
+            nth: usize,
+            count: &mut usize,
+        ) -> Option<&'a V2StructureNode> {
+            for child in self.children.iter() {
+                if child.name() == name {
+                    *count += 1;
+                    if *count == nth {
+                        return Some(child);
+                    }
+                }
+                if let V2StructureNode::Group(group) = child {
+                    if let Some(node) = group.find_nth(name, nth, count) {
+                        return Some(node);
+                    }
+                }
+            }
+            None
+        }
+
+        ///
+        /// Collects every segment node with the given name found under this group.
+        ///
+        pub fn segments_named(&self, name: &str) -> Vec<&V2SegmentNode> {
+            let mut segments = Vec::new();
+            self.collect_segments(name, &mut segments);
+            segments
+        }
+
+        fn collect_segments<'a>(&'a self, name: &str, segments: &mut Vec<&'a V2SegmentNode>) {
+            for child in self.children.iter() {
+                match child {
+                    V2StructureNode::Segment(segment) if segment.name == name => {
+                        segments.push(segment)
+                    }
+                    V2StructureNode::Group(group) => group.collect_segments(name, segments),
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    ///
+    /// Problems found while matching a message against its structure definition.
+    ///
+    #[derive(Debug, PartialEq)]
+    pub enum V2StructureIssue {
+        ///
+        /// A segment that does not fit the structure at the position it was found in.
+        ///
+        UnexpectedSegment { name: RUMString, order: usize },
+        ///
+        /// A required segment is missing from the group named in `group`.
+        ///
+        MissingSegment { name: RUMString, group: RUMString },
+        ///
+        /// A required group is missing from the group named in `group`.
+        ///
+        MissingGroup { name: RUMString, group: RUMString },
+    }
+
+    ///
+    /// Hierarchical view of a [V2Message] built from its message structure definition.
+    ///
+    /// Paths used for navigation are made of `/` separated steps. Each step is the name of a
+    /// group or segment with an optional 1-indexed repetition in parenthesis. A step matches
+    /// the nth node with that name under the previous step, even if it is nested inside other
+    /// groups. The last step may carry a field path using the default search syntax.
+    ///
+    /// ## Example
+    ///
+    /// - `ORDER_OBSERVATION(2)` => the second ORDER_OBSERVATION group.
+    /// - `ORDER_OBSERVATION(2)/OBX(3)` => the third OBX segment in that group.
+    /// - `ORDER_OBSERVATION(2)/OBX(3)5` => OBX-5 of that segment.
+    ///
+    #[derive(Debug)]
+    pub struct V2MessageTree<'a> {
+        message: &'a V2Message,
+        structure: &'static V2MessageStructure,
+        root: V2GroupNode,
+        issues: Vec<V2StructureIssue>,
+    }
+
+    impl<'a> V2MessageTree<'a> {
+        ///
+        /// Builds the group tree of a message using the structure named in MSH-9.3. When MSH-9.3 is
+        /// empty, we derive the structure from the message type and trigger event (MSH-9.1 and
+        /// MSH-9.2).
+        ///
+        pub fn from_message(message: &'a V2Message) -> V2Result<V2MessageTree<'a>> {
+            let structure_id = Self::get_structure_id(message)?;
+            let structure = match V2_MESSAGE_STRUCTURES.get(structure_id.as_str()) {
+                Some(structure) => *structure,
+                None => {
+                    return Err(format_compact!(
+                        "No message structure definition found for {}!",
+                        structure_id
+                    ))
+                }
+            };
+            Ok(Self::from_structure(message, structure))
+        }
+
+        ///
+        /// Builds the group tree of a message using the given structure definition.
+        ///
+        pub fn from_structure(
+            message: &'a V2Message,
+            structure: &'static V2MessageStructure,
+        ) -> V2MessageTree<'a> {
+            let segments: Vec<(&str, V2SegmentPosition)> = message
+                .segment_order()
+                .iter()
+                .zip(message.segments())
+                .map(|(position, segment)| (segment.name(), *position))
+                .collect();
+            let mut matcher = V2StructureMatcher {
+                structure,
+                segments: &segments,
+                cursor: 0,
+                issues: Vec::new(),
+            };
+            let mut root = V2GroupNode::new(structure.name);
+            matcher.match_elements(structure.elements, &mut root);
+            while matcher.cursor < segments.len() {
+                matcher.skip_unexpected();
+            }
+
+            V2MessageTree {
+                message,
+                structure,
+                root,
+                issues: matcher.issues,
+            }
+        }
+
+        pub fn structure(&self) -> &'static V2MessageStructure {
+            self.structure
+        }
+
+        pub fn root(&self) -> &V2GroupNode {
+            &self.root
+        }
+
+        pub fn issues(&self) -> &Vec<V2StructureIssue> {
+            &self.issues
+        }
+
+        ///
+        /// True when the message matched its structure without any issues.
+        ///
+        pub fn is_valid(&self) -> bool {
+            self.issues.is_empty()
+        }
+
+        ///
+        /// Returns the group found at the given path.
+        ///
+        pub fn find_group(&self, path: &str) -> V2Result<&V2GroupNode> {
+            match self.find_node(path)? {
+                V2StructureNode::Group(group) => Ok(group),
+                V2StructureNode::Segment(segment) => Err(format_compact!(
+                    "Path {} points to segment {}, not a group!",
+                    path,
+                    segment.name
+                )),
+            }
+        }
+
+        ///
+        /// Returns the segment found at the given path. Any field path in the last step is
+        /// ignored.
+        ///
+        pub fn find_segment(&self, path: &str) -> V2Result<&'a V2Segment> {
+            let (node_path, _) = Self::split_field_path(path);
+            match self.find_node(node_path)? {
+                V2StructureNode::Segment(segment) => self.get_segment(segment),
+                V2StructureNode::Group(group) => Err(format_compact!(
+                    "Path {} points to group {}, not a segment!",
+                    path,
+                    group.name
+                )),
+            }
+        }
+
+        ///
+        /// Resolves a path ending in a field path, e.g. `ORDER_OBSERVATION(2)/OBX(3)5.1`.
+        ///
+        pub fn find_component(&self, path: &str) -> V2Result<&'a V2Component> {
+            let (segment, index) = self.find_field_path(path)?;
+            segment.find_component(&index)
+        }
+
+        ///
+        /// Same as [V2MessageTree::find_component] but goes down to the subcomponent, e.g.
+        /// `PATIENT/PID3.4.2`.
+        ///
+        pub fn find_subcomponent(&self, path: &str) -> V2Result<&'a V2SubComponent> {
+            let (segment, index) = self.find_field_path(path)?;
+            segment.find_subcomponent(&index)
+        }
+
+        fn find_field_path(&self, path: &str) -> V2Result<(&'a V2Segment, V2SearchIndex)> {
+            let (_, field_path) = Self::split_field_path(path);
+            let segment = self.find_segment(path)?;
+            let index = V2SearchIndex::from(&format_compact!("{}{}", segment.name(), field_path));
+            Ok((segment, index))
+        }
+
+        ///
+        /// Returns every segment with the given name inside the group at `group_path`, in
+        /// message order. An empty group path means the whole message.
+        ///
+        pub fn group_segments(
+            &self,
+            group_path: &str,
+            segment_name: &str,
+        ) -> V2Result<Vec<&'a V2Segment>> {
+            let group = match group_path.is_empty() {
+                true => &self.root,
+                false => self.find_group(group_path)?,
+            };
+            let mut segments = Vec::new();
+            for node in group.segments_named(segment_name) {
+                segments.push(self.get_segment(node)?);
+            }
+            Ok(segments)
+        }
+
+        pub fn get_segment(&self, node: &V2SegmentNode) -> V2Result<&'a V2Segment> {
+            let (key, indx) = node.position;
+            self.message.get(&key, indx + 1)
+        }
+
+        fn find_node(&self, path: &str) -> V2Result<&V2StructureNode> {
+            let mut group = &self.root;
+            let mut node: Option<&V2StructureNode> = None;
+            for step in path.split(V2_STRUCTURE_PATH_SEPARATOR) {
+                if let Some(V2StructureNode::Segment(segment)) = node {
+                    return Err(format_compact!(
+                        "Cannot descend into segment {} while resolving {}!",
+                        segment.name,
+                        path
+                    ));
+                }
+                let (name, nth) = Self::parse_step(step)?;
+                node = match group.find(name, nth) {
+                    Some(found) => Some(found),
+                    None => {
+                        return Err(format_compact!(
+                            "No {}({}) found in {} while resolving {}!",
+                            name,
+                            nth,
+                            group.name,
+                            path
+                        ))
+                    }
+                };
+                if let Some(V2StructureNode::Group(next_group)) = node {
+                    group = next_group;
+                }
+            }
+            match node {
+                Some(node) => Ok(node),
+                None => Err(format_compact!("Empty structure path!")),
+            }
+        }
+
+        fn parse_step(step: &str) -> V2Result<(&str, usize)> {
+            let step = step.trim();
+            match step.find('(') {
+                Some(open) => {
+                    let close = match step.rfind(')') {
+                        Some(close) if close > open => close,
+                        _ => {
+                            return Err(format_compact!(
+                                "Malformed repetition in path step {}!",
+                                step
+                            ))
+                        }
+                    };
+                    match step[open + 1..close].parse::<usize>() {
+                        Ok(nth) if nth > 0 => Ok((&step[..open], nth)),
+                        _ => Err(format_compact!(
+                            "Repetitions in path step {} must be 1 indexed numbers!",
+                            step
+                        )),
+                    }
+                }
+                None => Ok((step, 1)),
+            }
+        }
+
+        fn split_field_path(path: &str) -> (&str, &str) {
+            let last_step_start = match path.rfind(V2_STRUCTURE_PATH_SEPARATOR) {
+                Some(indx) => indx + 1,
+                None => 0,
+            };
+            let last_step = &path[last_step_start..];
+            let name_end = match last_step.find(')') {
+                Some(indx) => indx + 1,
+                None => match last_step.as_bytes().get(3) {
+                    Some(c) if c.is_ascii_digit() || b"-.[".contains(c) => 3,
+                    _ => last_step.len(),
+                },
+            };
+            path.split_at(last_step_start + name_end)
+        }
+
+        fn get_structure_id(message: &V2Message) -> V2Result<V2String> {
+            let msh = message.get(&V2_SEGMENT_IDS[V2_MSHEADER_PATTERN], 1)?;
+            let message_type = match msh.get(V2_MSH_MESSAGE_TYPE)?.first() {
+                Some(field) => field,
+                None => return Err(format_compact!("MSH-9 is missing from the message!")),
+            };
+            let component = |indx: isize| -> &str {
+                match message_type.get(indx) {
+                    Ok(component) => component.as_str(),
+                    Err(_) => "",
+                }
+            };
+            let structure = component(3);
+            if !structure.is_empty() {
+                return Ok(structure.to_rumstring());
+            }
+            let event = format_compact!("{}_{}", component(1), component(2));
+            match V2_MESSAGE_STRUCTURE_BY_EVENT.get(event.as_str()) {
+                Some(structure) => Ok(structure.to_rumstring()),
+                None => Ok(event),
+            }
+        }
+    }
+
+    ///
+    /// Greedy matcher walking the segments of a message through the abstract message syntax.
+    /// Segments that are not part of the structure at all (e.g. Z-segments) are reported and
+    /// skipped in place. Segments that are part of the structure but cannot go where they were
+    /// found end the current group and, if nothing above can take them, are reported too.
+    ///
+    struct V2StructureMatcher<'s> {
+        structure: &'static V2MessageStructure,
+        segments: &'s Vec<(&'s str, V2SegmentPosition)>,
+        cursor: usize,
+        issues: Vec<V2StructureIssue>,
+    }
+
+    impl<'s> V2StructureMatcher<'s> {
+        fn current(&self) -> Option<&'s str> {
+            self.segments.get(self.cursor).map(|(name, _)| *name)
+        }
+
+        fn skip_unexpected(&mut self) {
+            if let Some((name, _)) = self.segments.get(self.cursor) {
+                self.issues.push(V2StructureIssue::UnexpectedSegment {
+                    name: name.to_rumstring(),
+                    order: self.cursor,
+                });
+                self.cursor += 1;
+            }
+        }
+
+        fn skip_foreign_segments(&mut self) {
+            while let Some(name) = self.current() {
+                if self.structure.contains_segment(name) {
+                    break;
+                }
+                self.skip_unexpected();
+            }
+        }
+
+        fn match_elements(
+            &mut self,
+            elements: &'static [V2StructureElement],
+            group: &mut V2GroupNode,
+        ) {
+            for element in elements.iter() {
+                let mut count = 0;
+                loop {
+                    self.skip_foreign_segments();
+                    let name = match self.current() {
+                        Some(name) => name,
+                        None => break,
+                    };
+                    if !element.can_start_with(name) || (count > 0 && !element.is_repeating()) {
+                        break;
+                    }
+                    match element {
+                        V2StructureElement::Segment { .. } => {
+                            let (name, position) = self.segments[self.cursor];
+                            group.children.push(V2StructureNode::Segment(V2SegmentNode {
+                                name: name.to_rumstring(),
+                                order: self.cursor,
+                                position,
+                            }));
+                            self.cursor += 1;
+                        }
+                        V2StructureElement::Group { name, elements, .. } => {
+                            let mut child = V2GroupNode::new(name);
+                            self.match_elements(elements, &mut child);
+                            group.children.push(V2StructureNode::Group(child));
+                        }
+                    }
+                    count += 1;
+                }
+
+                if count == 0 && !element.is_optional() {
+                    let issue = match element {
+                        V2StructureElement::Segment { name, .. } => {
+                            V2StructureIssue::MissingSegment {
+                                name: name.to_rumstring(),
+                                group: group.name.clone(),
+                            }
+                        }
+                        V2StructureElement::Group { name, .. } => V2StructureIssue::MissingGroup {
+                            name: name.to_rumstring(),
+                            group: group.name.clone(),
+                        },
+                    };
+                    self.issues.push(issue);
+                }
+            }
+        }
+    }
+
+    /**************************** Macros ***************************************/
+
+    ///
+    /// Declares a segment in a message structure definition.
+    ///
+    /// # Arguments
+    /// * `name` - Segment id.
+    /// * `optional` - [V2_OPTIONAL] or [V2_REQUIRED]. Brackets in the abstract message syntax.
+    /// * `repeating` - [V2_REPEATING] or [V2_SINGLE]. Braces in the abstract message syntax.
+    ///
+    #[macro_export]
+    macro_rules! v2_structure_segment {
+        ( $name:expr, $optional:expr, $repeating:expr ) => {
+            $crate::hl7_v2_message_structures::v2_message_structures::V2StructureElement::Segment {
+                name: $name,
+                optional: $optional,
+                repeating: $repeating,
+            }
+        };
+    }
+
+    ///
+    /// Declares a segment group in a message structure definition. The group's elements are
+    /// listed in brackets after the repetition flag.
+    ///
+    #[macro_export]
+    macro_rules! v2_structure_group {
+        ( $name:expr, $optional:expr, $repeating:expr, [ $( $element:expr ),* $(,)? ] ) => {
+            $crate::hl7_v2_message_structures::v2_message_structures::V2StructureElement::Group {
+                name: $name,
+                optional: $optional,
+                repeating: $repeating,
+                elements: &[ $( $element ),* ],
+            }
+        };
+    }
+
+    /**************************** Globals ***************************************/
+
+    ///
+    /// ## ADT^A01^ADT_A01 Admit/Visit Notification (Section 3.3.1)
+    ///
+    pub static ADT_A01: V2MessageStructure = V2MessageStructure {
+        name: "ADT_A01",
+        description: "Admit/Visit Notification",
+        elements: &[
+            v2_structure_segment!("MSH", V2_REQUIRED, V2_SINGLE),
+            v2_structure_segment!("SFT", V2_OPTIONAL, V2_REPEATING),
+            v2_structure_segment!("UAC", V2_OPTIONAL, V2_SINGLE),
+            v2_structure_segment!("EVN", V2_REQUIRED, V2_SINGLE),
+            v2_structure_segment!("PID", V2_REQUIRED, V2_SINGLE),
+            v2_structure_segment!("PD1", V2_OPTIONAL, V2_SINGLE),
+            v2_structure_segment!("ARV", V2_OPTIONAL, V2_REPEATING),
+            v2_structure_segment!("ROL", V2_OPTIONAL, V2_REPEATING),
+            v2_structure_segment!("NK1", V2_OPTIONAL, V2_REPEATING),
+            v2_structure_segment!("PV1", V2_REQUIRED, V2_SINGLE),
+            v2_structure_segment!("PV2", V2_OPTIONAL, V2_SINGLE),
+            v2_structure_segment!("ARV", V2_OPTIONAL, V2_REPEATING),
+            v2_structure_segment!("ROL", V2_OPTIONAL, V2_REPEATING),
+            v2_structure_segment!("DB1", V2_OPTIONAL, V2_REPEATING),
+            v2_structure_segment!("OBX", V2_OPTIONAL, V2_REPEATING),
+            v2_structure_segment!("AL1", V2_OPTIONAL, V2_REPEATING),
+            v2_structure_segment!("DG1", V2_OPTIONAL, V2_REPEATING),
+            v2_structure_segment!("DRG", V2_OPTIONAL, V2_SINGLE),
+            v2_structure_group!(
+                "PROCEDURE",
+                V2_OPTIONAL,
+                V2_REPEATING,
+                [
+                    v2_structure_segment!("PR1", V2_REQUIRED, V2_SINGLE),
+                    v2_structure_segment!("ROL", V2_OPTIONAL, V2_REPEATING),
+                ]
+            ),
+            v2_structure_segment!("GT1", V2_OPTIONAL, V2_REPEATING),
+            v2_structure_group!(
+                "INSURANCE",
+                V2_OPTIONAL,
+                V2_REPEATING,
+                [
+                    v2_structure_segment!("IN1", V2_REQUIRED, V2_SINGLE),
+                    v2_structure_segment!("IN2", V2_OPTIONAL, V2_SINGLE),
+                    v2_structure_segment!("IN3", V2_OPTIONAL, V2_REPEATING),
+                    v2_structure_segment!("ROL", V2_OPTIONAL, V2_REPEATING),
+                ]
+            ),
+            v2_structure_segment!("ACC", V2_OPTIONAL, V2_SINGLE),
+            v2_structure_segment!("UB1", V2_OPTIONAL, V2_SINGLE),
+            v2_structure_segment!("UB2", V2_OPTIONAL, V2_SINGLE),
+            v2_structure_segment!("PDA", V2_OPTIONAL, V2_SINGLE),
+        ],
+    };
+
+    ///
+    /// ## ORU^R01^ORU_R01 Unsolicited Transmission of an Observation Message (Section 7.3.1)
+    ///
+    pub static ORU_R01: V2MessageStructure = V2MessageStructure {
+        name: "ORU_R01",
+        description: "Unsolicited Transmission of an Observation Message",
+        elements: &[
+            v2_structure_segment!("MSH", V2_REQUIRED, V2_SINGLE),
+            v2_structure_segment!("SFT", V2_OPTIONAL, V2_REPEATING),
+            v2_structure_segment!("UAC", V2_OPTIONAL, V2_SINGLE),
+            v2_structure_group!(
+                "PATIENT_RESULT",
+                V2_REQUIRED,
+                V2_REPEATING,
+                [
+                    v2_structure_group!(
+                        "PATIENT",
+                        V2_OPTIONAL,
+                        V2_SINGLE,
+                        [
+                            v2_structure_segment!("PID", V2_REQUIRED, V2_SINGLE),
+                            v2_structure_segment!("PD1", V2_OPTIONAL, V2_SINGLE),
+                            v2_structure_segment!("NTE", V2_OPTIONAL, V2_REPEATING),
+                            v2_structure_segment!("NK1", V2_OPTIONAL, V2_REPEATING),
+                            v2_structure_group!(
+                                "VISIT",
+                                V2_OPTIONAL,
+                                V2_SINGLE,
+                                [
+                                    v2_structure_segment!("PV1", V2_REQUIRED, V2_SINGLE),
+                                    v2_structure_segment!("PV2", V2_OPTIONAL, V2_SINGLE),
+                                ]
+                            ),
+                        ]
+                    ),
+                    v2_structure_group!(
+                        "ORDER_OBSERVATION",
+                        V2_REQUIRED,
+                        V2_REPEATING,
+                        [
+                            v2_structure_segment!("ORC", V2_OPTIONAL, V2_SINGLE),
+                            v2_structure_segment!("OBR", V2_REQUIRED, V2_SINGLE),
+                            v2_structure_segment!("NTE", V2_OPTIONAL, V2_REPEATING),
+                            v2_structure_group!(
+                                "TIMING_QTY",
+                                V2_OPTIONAL,
+                                V2_REPEATING,
+                                [
+                                    v2_structure_segment!("TQ1", V2_REQUIRED, V2_SINGLE),
+                                    v2_structure_segment!("TQ2", V2_OPTIONAL, V2_REPEATING),
+                                ]
+                            ),
+                            v2_structure_segment!("CTD", V2_OPTIONAL, V2_SINGLE),
+                            v2_structure_group!(
+                                "OBSERVATION",
+                                V2_OPTIONAL,
+                                V2_REPEATING,
+                                [
+                                    v2_structure_segment!("OBX", V2_REQUIRED, V2_SINGLE),
+                                    v2_structure_segment!("NTE", V2_OPTIONAL, V2_REPEATING),
+                                ]
+                            ),
+                            v2_structure_segment!("FT1", V2_OPTIONAL, V2_REPEATING),
+                            v2_structure_segment!("CTI", V2_OPTIONAL, V2_REPEATING),
+                            v2_structure_group!(
+                                "SPECIMEN",
+                                V2_OPTIONAL,
+                                V2_REPEATING,
+                                [
+                                    v2_structure_segment!("SPM", V2_REQUIRED, V2_SINGLE),
+                                    v2_structure_segment!("OBX", V2_OPTIONAL, V2_REPEATING),
+                                ]
+                            ),
+                        ]
+                    ),
+                ]
+            ),
+            v2_structure_segment!("DSC", V2_OPTIONAL, V2_SINGLE),
+        ],
+    };
+
+    ///
+    /// ## OML^O21^OML_O21 Laboratory Order Message (Section 4.4.6)
+    ///
+    pub static OML_O21: V2MessageStructure = V2MessageStructure {
+        name: "OML_O21",
+        description: "Laboratory Order Message",
+        elements: &[
+            v2_structure_segment!("MSH", V2_REQUIRED, V2_SINGLE),
+            v2_structure_segment!("SFT", V2_OPTIONAL, V2_REPEATING),
+            v2_structure_segment!("UAC", V2_OPTIONAL, V2_SINGLE),
+            v2_structure_segment!("NTE", V2_OPTIONAL, V2_REPEATING),
+            v2_structure_group!(
+                "PATIENT",
+                V2_OPTIONAL,
+                V2_SINGLE,
+                [
+                    v2_structure_segment!("PID", V2_REQUIRED, V2_SINGLE),
+                    v2_structure_segment!("PD1", V2_OPTIONAL, V2_SINGLE),
+                    v2_structure_segment!("NTE", V2_OPTIONAL, V2_REPEATING),
+                    v2_structure_segment!("NK1", V2_OPTIONAL, V2_REPEATING),
+                    v2_structure_group!(
+                        "PATIENT_VISIT",
+                        V2_OPTIONAL,
+                        V2_SINGLE,
+                        [
+                            v2_structure_segment!("PV1", V2_REQUIRED, V2_SINGLE),
+                            v2_structure_segment!("PV2", V2_OPTIONAL, V2_SINGLE),
+                        ]
+                    ),
+                    v2_structure_group!(
+                        "INSURANCE",
+                        V2_OPTIONAL,
+                        V2_REPEATING,
+                        [
+                            v2_structure_segment!("IN1", V2_REQUIRED, V2_SINGLE),
+                            v2_structure_segment!("IN2", V2_OPTIONAL, V2_SINGLE),
+                            v2_structure_segment!("IN3", V2_OPTIONAL, V2_SINGLE),
+                        ]
+                    ),
+                    v2_structure_segment!("GT1", V2_OPTIONAL, V2_SINGLE),
+                    v2_structure_segment!("AL1", V2_OPTIONAL, V2_REPEATING),
+                ]
+            ),
+            v2_structure_group!(
+                "ORDER",
+                V2_REQUIRED,
+                V2_REPEATING,
+                [
+                    v2_structure_segment!("ORC", V2_REQUIRED, V2_SINGLE),
+                    v2_structure_group!(
+                        "TIMING",
+                        V2_OPTIONAL,
+                        V2_REPEATING,
+                        [
+                            v2_structure_segment!("TQ1", V2_REQUIRED, V2_SINGLE),
+                            v2_structure_segment!("TQ2", V2_OPTIONAL, V2_REPEATING),
+                        ]
+                    ),
+                    v2_structure_group!(
+                        "OBSERVATION_REQUEST",
+                        V2_OPTIONAL,
+                        V2_SINGLE,
+                        [
+                            v2_structure_segment!("OBR", V2_REQUIRED, V2_SINGLE),
+                            v2_structure_segment!("TCD", V2_OPTIONAL, V2_SINGLE),
+                            v2_structure_segment!("NTE", V2_OPTIONAL, V2_REPEATING),
+                            v2_structure_segment!("DG1", V2_OPTIONAL, V2_REPEATING),
+                            v2_structure_group!(
+                                "OBSERVATION",
+                                V2_OPTIONAL,
+                                V2_REPEATING,
+                                [
+                                    v2_structure_segment!("OBX", V2_REQUIRED, V2_SINGLE),
+                                    v2_structure_segment!("TCD", V2_OPTIONAL, V2_SINGLE),
+                                    v2_structure_segment!("NTE", V2_OPTIONAL, V2_REPEATING),
+                                ]
+                            ),
+                            v2_structure_group!(
+                                "SPECIMEN",
+                                V2_OPTIONAL,
+                                V2_REPEATING,
+                                [
+                                    v2_structure_segment!("SPM", V2_REQUIRED, V2_SINGLE),
+                                    v2_structure_segment!("OBX", V2_OPTIONAL, V2_REPEATING),
+                                    v2_structure_group!(
+                                        "CONTAINER",
+                                        V2_OPTIONAL,
+                                        V2_REPEATING,
+                                        [
+                                            v2_structure_segment!("SAC", V2_REQUIRED, V2_SINGLE),
+                                            v2_structure_segment!("OBX", V2_OPTIONAL, V2_REPEATING),
+                                        ]
+                                    ),
+                                ]
+                            ),
+                            v2_structure_group!(
+                                "PRIOR_RESULT",
+                                V2_OPTIONAL,
+                                V2_REPEATING,
+                                [
+                                    v2_structure_group!(
+                                        "PATIENT_PRIOR",
+                                        V2_OPTIONAL,
+                                        V2_SINGLE,
+                                        [
+                                            v2_structure_segment!("PID", V2_REQUIRED, V2_SINGLE),
+                                            v2_structure_segment!("PD1", V2_OPTIONAL, V2_SINGLE),
+                                        ]
+                                    ),
+                                    v2_structure_group!(
+                                        "PATIENT_VISIT_PRIOR",
+                                        V2_OPTIONAL,
+                                        V2_SINGLE,
+                                        [
+                                            v2_structure_segment!("PV1", V2_REQUIRED, V2_SINGLE),
+                                            v2_structure_segment!("PV2", V2_OPTIONAL, V2_SINGLE),
+                                        ]
+                                    ),
+                                    v2_structure_segment!("AL1", V2_OPTIONAL, V2_REPEATING),
+                                    v2_structure_group!(
+                                        "ORDER_PRIOR",
+                                        V2_REQUIRED,
+                                        V2_REPEATING,
+                                        [
+                                            v2_structure_segment!("ORC", V2_OPTIONAL, V2_SINGLE),
+                                            v2_structure_segment!("OBR", V2_REQUIRED, V2_SINGLE),
+                                            v2_structure_segment!("NTE", V2_OPTIONAL, V2_REPEATING),
+                                            v2_structure_group!(
+                                                "TIMING_PRIOR",
+                                                V2_OPTIONAL,
+                                                V2_REPEATING,
+                                                [
+                                                    v2_structure_segment!(
+                                                        "TQ1",
+                                                        V2_REQUIRED,
+                                                        V2_SINGLE
+                                                    ),
+                                                    v2_structure_segment!(
+                                                        "TQ2",
+                                                        V2_OPTIONAL,
+                                                        V2_REPEATING
+                                                    ),
+                                                ]
+                                            ),
+                                            v2_structure_group!(
+                                                "OBSERVATION_PRIOR",
+                                                V2_REQUIRED,
+                                                V2_REPEATING,
+                                                [
+                                                    v2_structure_segment!(
+                                                        "OBX",
+                                                        V2_REQUIRED,
+                                                        V2_SINGLE
+                                                    ),
+                                                    v2_structure_segment!(
+                                                        "NTE",
+                                                        V2_OPTIONAL,
+                                                        V2_REPEATING
+                                                    ),
+                                                ]
+                                            ),
+                                        ]
+                                    ),
+                                ]
+                            ),
+                        ]
+                    ),
+                    v2_structure_segment!("FT1", V2_OPTIONAL, V2_REPEATING),
+                    v2_structure_segment!("CTI", V2_OPTIONAL, V2_REPEATING),
+                    v2_structure_segment!("BLG", V2_OPTIONAL, V2_SINGLE),
+                ]
+            ),
+        ],
+    };
+
+    ///
+    /// ## VXU^V04^VXU_V04 Unsolicited Vaccination Record Update (Section 4A.3.8)
+    ///
+    pub static VXU_V04: V2MessageStructure = V2MessageStructure {
+        name: "VXU_V04",
+        description: "Unsolicited Vaccination Record Update",
+        elements: &[
+            v2_structure_segment!("MSH", V2_REQUIRED, V2_SINGLE),
+            v2_structure_segment!("SFT", V2_OPTIONAL, V2_REPEATING),
+            v2_structure_segment!("UAC", V2_OPTIONAL, V2_SINGLE),
+            v2_structure_segment!("PID", V2_REQUIRED, V2_SINGLE),
+            v2_structure_segment!("PD1", V2_OPTIONAL, V2_SINGLE),
+            v2_structure_segment!("NK1", V2_OPTIONAL, V2_REPEATING),
+            v2_structure_group!(
+                "PATIENT",
+                V2_OPTIONAL,
+                V2_SINGLE,
+                [
+                    v2_structure_segment!("PV1", V2_REQUIRED, V2_SINGLE),
+                    v2_structure_segment!("PV2", V2_OPTIONAL, V2_SINGLE),
+                ]
+            ),
+            v2_structure_segment!("GT1", V2_OPTIONAL, V2_REPEATING),
+            v2_structure_group!(
+                "INSURANCE",
+                V2_OPTIONAL,
+                V2_REPEATING,
+                [
+                    v2_structure_segment!("IN1", V2_REQUIRED, V2_SINGLE),
+                    v2_structure_segment!("IN2", V2_OPTIONAL, V2_SINGLE),
+                    v2_structure_segment!("IN3", V2_OPTIONAL, V2_SINGLE),
+                ]
+            ),
+            v2_structure_group!(
+                "ORDER",
+                V2_OPTIONAL,
+                V2_REPEATING,
+                [
+                    v2_structure_segment!("ORC", V2_REQUIRED, V2_SINGLE),
+                    v2_structure_group!(
+                        "TIMING",
+                        V2_OPTIONAL,
+                        V2_REPEATING,
+                        [
+                            v2_structure_segment!("TQ1", V2_REQUIRED, V2_SINGLE),
+                            v2_structure_segment!("TQ2", V2_OPTIONAL, V2_REPEATING),
+                        ]
+                    ),
+                    v2_structure_segment!("RXA", V2_REQUIRED, V2_SINGLE),
+                    v2_structure_segment!("RXR", V2_OPTIONAL, V2_SINGLE),
+                    v2_structure_group!(
+                        "OBSERVATION",
+                        V2_OPTIONAL,
+                        V2_REPEATING,
+                        [
+                            v2_structure_segment!("OBX", V2_REQUIRED, V2_SINGLE),
+                            v2_structure_segment!("NTE", V2_OPTIONAL, V2_REPEATING),
+                        ]
+                    ),
+                ]
+            ),
+        ],
+    };
+
+    ///
+    /// Message structure definitions keyed by message structure id (MSH-9.3). Use
+    /// [V2_MESSAGE_STRUCTURE_BY_EVENT] to find the structure of a message type and trigger event
+    /// pair.
+    ///
+    pub static V2_MESSAGE_STRUCTURES: V2MessageStructures = phf_map! {
+        "ADT_A01" => &ADT_A01,
+        "OML_O21" => &OML_O21,
+        "ORU_R01" => &ORU_R01,
+        "VXU_V04" => &VXU_V04,
+    };
+
+    /**************************** API ******************************************/
+
+    impl V2Message {
+        ///
+        /// Builds the group tree of this message. See [V2MessageTree].
+        ///
+        pub fn structure(&self) -> V2Result<V2MessageTree<'_>> {
+            V2MessageTree::from_message(self)
+        }
+    }
+}
@@ -189,6 +189,7 @@ pub mod mllp_v2 {
     //!     <SB><NAK><EB><CR>
     //! ```
 
+    use crate::hl7_v2_charsets::v2_charsets::{decode_message, encode_message};
     use crate::hl7_v2_parser::v2_parser::format_compact;
     use rumtk_core::core::RUMResult;
     pub use rumtk_core::net::tcp::{
@@ -197,7 +198,7 @@ pub mod mllp_v2 {
     };
     use rumtk_core::net::tcp::{AsyncRwLock, RUMClient, RUMServer, SafeClient, SafeServer};
     use rumtk_core::strings::{
-        basic_escape, filter_non_printable_ascii, try_decode, RUMArrayConversions, RUMString,
+        basic_escape, filter_non_printable_ascii, RUMArrayConversions, RUMString,
         RUMStringConversions, ToCompactString,
     };
     use rumtk_core::threading::thread_primitives::SafeTaskArgs;
@@ -267,9 +268,9 @@ pub mod mllp_v2 {
 
     ///
     /// Opposite of [mllp_encode]. Strips the incoming message off the \<[SB]\>, \<[EB]\>, and \<[CR]\>.
-    /// The remaining data is considered an ASCII or UTF-8 payload. However, to be on the safe side,
-    /// we use the [try_decode] function from the strings module to attempt auto-detection of encoding
-    /// and forcing the output to be in UTF-8.
+    /// The remaining data is decoded to UTF-8 per the character set declared in MSH-18 (see
+    /// [decode_message]). If the message does not declare one, we use the [try_decode](rumtk_core::strings::try_decode) function
+    /// from the strings module to attempt auto-detection of encoding.
     ///
     /// # Steps
     ///
@@ -308,7 +309,7 @@ pub mod mllp_v2 {
         if contents.len() == 1 {
             Ok(contents.to_vec().to_rumstring())
         } else {
-            Ok(try_decode(&contents))
+            decode_message(contents)
         }
     }
 
@@ -490,6 +491,7 @@ pub mod mllp_v2 {
         filter_policy: MLLP_FILTER_POLICY,
         server_handle: ServerRunner,
         server: bool,
+        charset: Option<RUMString>,
     }
 
     impl AsyncMLLP {
@@ -532,9 +534,20 @@ pub mod mllp_v2 {
                 filter_policy,
                 server_handle,
                 server,
+                charset: None,
             })
         }
 
+        ///
+        /// Encodes outbound messages in a character set of HL7 Table 0211 instead of UTF-8. The
+        /// messages should declare the same character set in MSH-18.
+        ///
+        pub fn set_charset(&mut self, charset: &str) -> RUMResult<()> {
+            encode_message("", charset)?;
+            self.charset = Some(RUMString::from(charset));
+            Ok(())
+        }
+
         async fn next_layer(&self) -> GuardedLowerLayer {
             self.transport_layer.lock().await
         }
@@ -604,7 +617,10 @@ pub mod mllp_v2 {
 
         pub async fn send(&mut self, message: &str, endpoint: &RUMString) -> RUMResult<()> {
             let filtered = mllp_filter_message(message, &self.filter_policy)?;
-            let encoded = mllp_encode(&filtered);
+            let encoded = match &self.charset {
+                Some(charset) => mllp_encode_bytes(&encode_message(&filtered, charset)?),
+                None => mllp_encode(&filtered),
+            };
             self.next_layer()
                 .await
                 .send_message(&encoded, endpoint)
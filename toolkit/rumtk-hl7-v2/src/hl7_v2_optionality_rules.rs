@@ -18,21 +18,163 @@
  * Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301  USA
  */
 
-use crate::hl7_v2_base_types::v2_primitives::V2ComponentList;
+use crate::hl7_v2_base_types::v2_base_types::find_segment_id;
+use crate::hl7_v2_base_types::v2_primitives::{V2ComponentList, V2Result};
+use crate::hl7_v2_complex_types::hl7_v2_complex_types::V2FieldToString;
+use crate::hl7_v2_constants::V2_MSH_MESSAGE_TYPE;
+use crate::hl7_v2_parser::v2_parser::{V2Message, V2Segment};
+use crate::hl7_v2_segment_descriptors::v2_segment_descriptor::seq_to_position;
+use crate::hl7_v2_versions::v2_versions::{V2Version, V2_DEFAULT_VERSION};
+use rumtk_core::strings::{format_compact, AsStr};
 
-pub type V2ComponentConditionFn = fn(field: &V2ComponentList) -> bool;
+pub type V2ComponentConditionFn = fn(context: &V2ConditionContext) -> bool;
 
 ///
+/// Data a condition predicate can look at. The field (or complex component) being checked is
+/// always given as its component list. The segment and message holding it are only known when
+/// the condition is evaluated while walking a message, so predicates that need them evaluate to
+/// not required without them. The version is the one in MSH-12, or [V2_DEFAULT_VERSION].
 ///
+/// ## Per Section 2.5.2
+/// ```text
+///     C(a/b) - Conditional. This usage has an associated condition predicate. If the predicate is
+///     satisfied, the usage is "a", otherwise it is "b". The predicate may refer to other elements
+///     of the segment or message, or to the message version.
+/// ```
 ///
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
+pub struct V2ConditionContext<'a> {
+    pub field: &'a V2ComponentList<'a>,
+    pub segment: Option<&'a V2Segment>,
+    pub message: Option<&'a V2Message>,
+    pub version: V2Version,
+}
+
+impl<'a> V2ConditionContext<'a> {
+    pub fn new(field: &'a V2ComponentList<'a>) -> V2ConditionContext<'a> {
+        V2ConditionContext {
+            field,
+            segment: None,
+            message: None,
+            version: V2_DEFAULT_VERSION,
+        }
+    }
+
+    pub fn with_segment(mut self, segment: &'a V2Segment) -> V2ConditionContext<'a> {
+        self.segment = Some(segment);
+        self
+    }
+
+    ///
+    /// Adds the message and takes the version from its MSH-12.
+    ///
+    pub fn with_message(mut self, message: &'a V2Message) -> V2ConditionContext<'a> {
+        self.message = Some(message);
+        self.version = message.version();
+        self
+    }
+
+    pub fn with_version(mut self, version: V2Version) -> V2ConditionContext<'a> {
+        self.version = version;
+        self
+    }
+
+    ///
+    /// Checks if any repetition of field `seq` of the segment holds data. Always false when the
+    /// segment is not known.
+    ///
+    pub fn is_segment_field_valued(&self, seq: isize) -> bool {
+        let segment = match self.segment {
+            Some(segment) => segment,
+            None => return false,
+        };
+        let position = seq_to_position(segment.name(), seq);
+        if position < 1 || position > segment.len() as isize {
+            return false;
+        }
+        match segment.get(position) {
+            Ok(group) => group.iter().any(|field| {
+                field
+                    .to_component_list()
+                    .iter()
+                    .flatten()
+                    .any(|subcomponent| !subcomponent.is_empty())
+            }),
+            Err(_) => false,
+        }
+    }
+
+    ///
+    /// Returns the first component of the first repetition of field `seq` of the segment, or an
+    /// empty string.
+    ///
+    pub fn segment_field_value(&self, seq: isize) -> &'a str {
+        match self.segment {
+            Some(segment) => field_value(segment, seq),
+            None => "",
+        }
+    }
+
+    ///
+    /// Returns the segments of the message in the same group as the segment, meaning the ones
+    /// found between the closest segments named in `boundaries` around it (e.g. the OBX under the
+    /// same OBR). Empty when the segment or the message is not known.
+    ///
+    pub fn group_segments(&self, boundaries: &[&str]) -> Vec<&'a V2Segment> {
+        let (segment, message) = match (self.segment, self.message) {
+            (Some(segment), Some(message)) => (segment, message),
+            _ => return Vec::new(),
+        };
+        let mut group = Vec::new();
+        let mut found = false;
+        for candidate in message.segments() {
+            if boundaries.contains(&candidate.name()) {
+                if found {
+                    break;
+                }
+                group.clear();
+            }
+            found |= std::ptr::eq(candidate, segment);
+            group.push(candidate);
+        }
+        match found {
+            true => group,
+            false => Vec::new(),
+        }
+    }
+
+    ///
+    /// Returns the message code in MSH-9.1 (e.g. `ORU`), or an empty string when the message is
+    /// not known.
+    ///
+    pub fn message_code(&self) -> &'a str {
+        let message = match self.message {
+            Some(message) => message,
+            None => return "",
+        };
+        find_segment_id("MSH")
+            .ok_or_else(|| "No MSH segment id!".into())
+            .and_then(|msh_id| message.get(&msh_id, 1))
+            .and_then(|msh| msh.get(V2_MSH_MESSAGE_TYPE))
+            .and_then(|field| match field.first() {
+                Some(field) => Ok(field.get(1)?.get(1)?.as_str()),
+                None => Err("MSH-9 is empty!".into()),
+            })
+            .unwrap_or_default()
+    }
+}
+
+///
+///
+///
+#[derive(Debug, Clone, Copy)]
 pub enum Optionality {
     /// Required
     R,
     /// Required but may be empty
     RE,
-    /// Undeclared Conditional if None, Declared Conditional if filled vector (C(a|b)).
-    C(V2ComponentConditionFn),
+    /// Undeclared Conditional if None, Declared Conditional if it holds a predicate (C(a|b)).
+    C(Option<V2ComponentConditionFn>),
     /// Not supported
     X,
     /// Optional
@@ -56,19 +198,117 @@ impl Optionality {
     ///
     /// Executes contained function in conditional if any and returns result.
     /// Otherwise, returns false. Meaning, this method will always succeed for
-    /// non-conditional components. Yields whether component is required.
+    /// non-conditional components and undeclared conditionals. Yields whether component is
+    /// required.
     ///
-    pub fn is_conditionally_required(&self, field: &V2ComponentList) -> bool {
+    pub fn is_conditionally_required(&self, context: &V2ConditionContext) -> bool {
         match &self {
-            Optionality::C(f) => f(&field),
+            Optionality::C(Some(f)) => f(context),
             _ => false,
         }
     }
+
+    ///
+    /// Resolves a conditional usage against `context`. Yields [Optionality::R] if the predicate
+    /// holds and [Optionality::O] otherwise. Other usages, including undeclared conditionals
+    /// which cannot be evaluated, are returned as is.
+    ///
+    pub fn evaluate(&self, context: &V2ConditionContext) -> Optionality {
+        match self {
+            Optionality::C(Some(f)) => match f(context) {
+                true => Optionality::R,
+                false => Optionality::O,
+            },
+            _ => *self,
+        }
+    }
+
+    ///
+    /// Parses a usage code as found in conformance profiles. Conditional usages (`C` and `CE`)
+    /// are undeclared since profiles state their predicates in prose. `W`
+    /// (withdrawn) is treated as backwards compatible.
+    ///
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(usage: &str) -> V2Result<Optionality> {
+        match usage.trim() {
+            "R" => Ok(Optionality::R),
+            "RE" => Ok(Optionality::RE),
+            "C" | "CE" => Ok(Optionality::C(None)),
+            "X" => Ok(Optionality::X),
+            "O" => Ok(Optionality::O),
+            "B" | "W" => Ok(Optionality::B),
+            _ => Err(format_compact!("Unknown usage code {}!", usage)),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Optionality::R => "R",
+            Optionality::RE => "RE",
+            Optionality::C(_) => "C",
+            Optionality::X => "X",
+            Optionality::O => "O",
+            Optionality::B => "B",
+        }
+    }
 }
 
 /******************************* Conditions ********************************/
 
-const CONDITION_NOOP: V2ComponentConditionFn = |c: &V2ComponentList| false;
+/******************************* Helpers ***********************************/
+
+///
+/// Returns the first component of the first repetition of field `seq` of `segment`, or an empty
+/// string.
+///
+fn field_value(segment: &V2Segment, seq: isize) -> &str {
+    let position = seq_to_position(segment.name(), seq);
+    if position < 1 || position > segment.len() as isize {
+        return "";
+    }
+    segment
+        .get(position)
+        .and_then(|group| match group.first() {
+            Some(field) => Ok(field.get(1)?.get(1)?.as_str()),
+            None => Err("Field is empty!".into()),
+        })
+        .unwrap_or_default()
+}
+
+///
+/// Checks if the component at `indx` (0-indexed) exists and holds any data. Components missing at
+/// the end of a field are treated as not valued.
+///
+fn is_valued(c: &V2ConditionContext, indx: usize) -> bool {
+    match c.field.get(indx) {
+        Some(component) => component
+            .iter()
+            .any(|subcomponent| !subcomponent.is_empty()),
+        None => false,
+    }
+}
+
+///
+/// Returns the first subcomponent of the component at `indx` (0-indexed) or an empty string.
+///
+fn component_value<'a>(c: &V2ConditionContext<'a>, indx: usize) -> &'a str {
+    match c.field.get(indx).and_then(|component| component.first()) {
+        Some(value) => value,
+        None => "",
+    }
+}
+
+///
+/// Checks if the coding system at `indx` (0-indexed) is valued with something other than an HL7
+/// table (HL7nnnn), in which case its version ID must be valued.
+///
+fn needs_version_id(c: &V2ConditionContext, indx: usize) -> bool {
+    let coding_system = component_value(c, indx);
+    let is_hl7_table = coding_system.len() == 7
+        && coding_system.starts_with("HL7")
+        && coding_system[3..].chars().all(|c| c.is_ascii_digit());
+    !coding_system.is_empty() && !is_hl7_table
+}
 
 /***************CF Conditions**************/
 ///
@@ -79,7 +319,7 @@ const CONDITION_NOOP: V2ComponentConditionFn = |c: &V2ComponentList| false;
 /// CF.3 and/or CF.14, the Coding System component or the Coding System OID, for the tuple.
 ///
 pub const CONDITION_CF1: V2ComponentConditionFn =
-    |c: &V2ComponentList| c[0].len() > 0 && !c[13].len() > 0;
+    |c: &V2ConditionContext| is_valued(c, 0) && !is_valued(c, 13);
 ///
 /// As of v2.7 this component is required when CF.4 is populated and CF.17 is not populated. Both
 /// CF.6 and CF.17 may be populated. Receivers should not identify a code based on its position
@@ -88,7 +328,7 @@ pub const CONDITION_CF1: V2ComponentConditionFn =
 /// CF.6 and/or CF.17, the Coding System component or the Coding System OID, for the tuple.
 ///
 pub const CONDITION_CF2: V2ComponentConditionFn =
-    |c: &V2ComponentList| c[3].len() > 0 && !c[16].len() > 0;
+    |c: &V2ConditionContext| is_valued(c, 3) && !is_valued(c, 16);
 ///
 /// Definition: This component carries the version for the coding system identified by components 1-
 /// 3. If CF.3 is populated with a value other than HL7nnnn or is of table type user-defined, version
@@ -99,44 +339,44 @@ pub const CONDITION_CF2: V2ComponentConditionFn =
 ///
 /// ## Note
 /// ```text
-///     ??? What. Not quiet implementable or worth the hassle of aborting validation.
-///     Leaving this for a more global rule.
+///     HL7nnnn tables of type user-defined cannot be told apart from HL7 tables without a table
+///     registry, so every HL7nnnn coding system is treated as an HL7 table.
 /// ```
-pub const CONDITION_CF3: V2ComponentConditionFn = CONDITION_NOOP;
+pub const CONDITION_CF3: V2ComponentConditionFn = |c: &V2ConditionContext| needs_version_id(c, 2);
 ///
-/// ??? No real condition? Why even mark field as conditionally required?
+/// Same rule as [CONDITION_CF3] for the second alternate coding system in CF.12.
 ///
-pub const CONDITION_CF4: V2ComponentConditionFn = CONDITION_NOOP;
+pub const CONDITION_CF4: V2ComponentConditionFn = |c: &V2ConditionContext| needs_version_id(c, 11);
 ///
 /// This component is required when CF.1 is populated and CF.3 is not populated. Both CF.3 and
 /// CF.14 may be populated.
 ///
 pub const CONDITION_CF5: V2ComponentConditionFn =
-    |c: &V2ComponentList| c[0].len() > 0 && !c[2].len() > 0;
+    |c: &V2ConditionContext| is_valued(c, 0) && !is_valued(c, 2);
 ///
 /// Value set version ID is required if CF.15 is populated.
 ///
-pub const CONDITION_CF6: V2ComponentConditionFn = |c: &V2ComponentList| c[14].len() > 0;
+pub const CONDITION_CF6: V2ComponentConditionFn = |c: &V2ConditionContext| is_valued(c, 14);
 ///
 /// This component is required when CF.4 is populated and CF.6 is not populated. Both CF.6 and
 /// CF.17 may be populated.
 ///
 pub const CONDITION_CF7: V2ComponentConditionFn =
-    |c: &V2ComponentList| c[3].len() > 0 && !c[5].len() > 0;
+    |c: &V2ConditionContext| is_valued(c, 3) && !is_valued(c, 5);
 ///
 /// Value set version ID is required if CF.18 is populated.
 ///
-pub const CONDITION_CF8: V2ComponentConditionFn = |c: &V2ComponentList| c[17].len() > 0;
+pub const CONDITION_CF8: V2ComponentConditionFn = |c: &V2ConditionContext| is_valued(c, 17);
 ///
 /// This component is required when CF.10 is populated and CF.12 is not populated. Both CF.12 and
 /// CF.20 may be populated.
 ///
 pub const CONDITION_CF9: V2ComponentConditionFn =
-    |c: &V2ComponentList| c[9].len() > 0 && !c[11].len() > 0;
+    |c: &V2ConditionContext| is_valued(c, 9) && !is_valued(c, 11);
 ///
 /// Value set version ID is required if CF.21 is populated.
 ///
-pub const CONDITION_CF10: V2ComponentConditionFn = |c: &V2ComponentList| c[20].len() > 0;
+pub const CONDITION_CF10: V2ComponentConditionFn = |c: &V2ConditionContext| is_valued(c, 20);
 
 /***************CNE Conditions*************/
 ///
@@ -147,73 +387,367 @@ pub const CONDITION_CF10: V2ComponentConditionFn = |c: &V2ComponentList| c[20].l
 /// optional but its use should be encouraged since it makes messages easier to review for accuracy,
 /// especially during interface testing and debugging.
 ///
-/// ## Note
-/// ```text
-///     ???? Not sure how to implement this one without a lot of global state which I am trying to
-///     avoid. For now, defaulting to a noop.
-/// ```
-pub const CONDITION_CNE1: V2ComponentConditionFn = CONDITION_NOOP;
+/// Applies to the coding system in CNE.3. See [CONDITION_CF3].
+///
+pub const CONDITION_CNE1: V2ComponentConditionFn = |c: &V2ConditionContext| needs_version_id(c, 2);
 ///
-/// See [CONDITION_CNE1]
+/// Same rule as [CONDITION_CNE1] for the second alternate coding system in CNE.12.
 ///
-pub const CONDITION_CNE2: V2ComponentConditionFn = CONDITION_NOOP;
+pub const CONDITION_CNE2: V2ComponentConditionFn = |c: &V2ConditionContext| needs_version_id(c, 11);
 ///
 /// This component is required when CNE.1 is populated and CNE.3 is not populated. Both CNE.3
 /// and CNE.14 may be populated.
 ///
 pub const CONDITION_CNE3: V2ComponentConditionFn =
-    |c: &V2ComponentList| c[0].len() > 0 && !(c[2].len() > 0);
+    |c: &V2ConditionContext| is_valued(c, 0) && !is_valued(c, 2);
 ///
 /// Value set version ID is required if CNE.15 is populated.
 ///
-pub const CONDITION_CNE4: V2ComponentConditionFn = |c: &V2ComponentList| c[14].len() > 0;
+pub const CONDITION_CNE4: V2ComponentConditionFn = |c: &V2ConditionContext| is_valued(c, 14);
 ///
 /// This component is required when CNE.4 is populated and CNE.6 is not populated. Both CNE.6
 /// and CNE.17 may be populated.
 ///
 pub const CONDITION_CNE5: V2ComponentConditionFn =
-    |c: &V2ComponentList| c[3].len() > 0 && !(c[5].len() > 0);
+    |c: &V2ConditionContext| is_valued(c, 3) && !is_valued(c, 5);
 ///
 /// Value set version ID is required if CNE.18 is populated.
 ///
-pub const CONDITION_CNE6: V2ComponentConditionFn = |c: &V2ComponentList| c[17].len() > 0;
+pub const CONDITION_CNE6: V2ComponentConditionFn = |c: &V2ConditionContext| is_valued(c, 17);
 ///
 /// This component is required when CNE.4 is populated and neither CNE.6 nor CNE.18 is populated.
 /// In short either the CNE.6 or the CNE.14 or CNE.17 must be populated when CNE.4 is populated.
 ///
 pub const CONDITION_CNE7: V2ComponentConditionFn =
-    |c: &V2ComponentList| c[3].len() > 0 && !(c[5].len() > 0 || c[17].len() > 0);
+    |c: &V2ConditionContext| is_valued(c, 3) && !(is_valued(c, 5) || is_valued(c, 17));
 ///
 /// Value set version ID is required if CNE.21 is populated.
 ///
-pub const CONDITION_CNE8: V2ComponentConditionFn = |c: &V2ComponentList| c[20].len() > 0;
+pub const CONDITION_CNE8: V2ComponentConditionFn = |c: &V2ConditionContext| is_valued(c, 20);
 
 /***************CNN Conditions*************/
 
 ///
 /// If component 1 is valued, either CNN.8 or CNN.9, or both CNN.10 and CNN.11, must be valued.
+/// CNN.8 is required when none of the alternatives are present.
 ///
-pub const CONDITION_CNN1: V2ComponentConditionFn =
-    |c: &V2ComponentList| (c[0].len() > 0 && c[8].len() > 0) || !CONDITION_CNN2(&c);
+pub const CONDITION_CNN1: V2ComponentConditionFn = |c: &V2ConditionContext| {
+    is_valued(c, 0) && !(is_valued(c, 8) || (is_valued(c, 9) && is_valued(c, 10)))
+};
 
 ///
 /// If component 1 is valued, either CNN.8 or CNN.9, or both CNN.10 and CNN.11, must be valued.
+/// CNN.9 is required when none of the alternatives are present.
 ///
-pub const CONDITION_CNN2: V2ComponentConditionFn =
-    |c: &V2ComponentList| (c[0].len() > 0 && c[7].len() > 0) || !CONDITION_CNN1(&c);
+pub const CONDITION_CNN2: V2ComponentConditionFn = |c: &V2ConditionContext| {
+    is_valued(c, 0) && !(is_valued(c, 7) || (is_valued(c, 9) && is_valued(c, 10)))
+};
 
 ///
 /// If CNN.11 is valued, this component must be valued
 ///
-pub const CONDITION_CNN3: V2ComponentConditionFn = |c: &V2ComponentList| c[10].len() > 0;
+pub const CONDITION_CNN3: V2ComponentConditionFn = |c: &V2ConditionContext| is_valued(c, 10);
 
 ///
 /// If CNN.10 is valued, this component must be valued.
 ///
-pub const CONDITION_CNN4: V2ComponentConditionFn = |c: &V2ComponentList| c[9].len() > 0;
+pub const CONDITION_CNN4: V2ComponentConditionFn = |c: &V2ConditionContext| is_valued(c, 9);
 
 /***************CP Conditions**************/
 ///
 /// This component is required if CP.3 From Value and/or CP.4 To Value are present.
 ///
-pub const CONDITION_CP: V2ComponentConditionFn = |c: &V2ComponentList| c[2].len() > 0;
+pub const CONDITION_CP: V2ComponentConditionFn =
+    |c: &V2ConditionContext| is_valued(c, 2) || is_valued(c, 3);
+
+/***************CSU Conditions*************/
+///
+/// The unit of measure coding system is required when CSU.2 is populated.
+///
+pub const CONDITION_CSU1: V2ComponentConditionFn = |c: &V2ConditionContext| is_valued(c, 1);
+///
+/// The alternate unit of measure coding system is required when CSU.5 is populated.
+///
+pub const CONDITION_CSU2: V2ComponentConditionFn = |c: &V2ConditionContext| is_valued(c, 4);
+
+/***************CWE Conditions*************/
+///
+/// This component is required when CWE.1 is populated and CWE.14 is not populated. Both CWE.3
+/// and CWE.14 may be populated.
+///
+pub const CONDITION_CWE1: V2ComponentConditionFn =
+    |c: &V2ConditionContext| is_valued(c, 0) && !is_valued(c, 13);
+///
+/// This component is required when CWE.4 is populated and CWE.17 is not populated. Both CWE.6
+/// and CWE.17 may be populated.
+///
+pub const CONDITION_CWE2: V2ComponentConditionFn =
+    |c: &V2ConditionContext| is_valued(c, 3) && !is_valued(c, 16);
+///
+/// Version ID of the coding system in CWE.3. See [CONDITION_CF3].
+///
+pub const CONDITION_CWE3: V2ComponentConditionFn = |c: &V2ConditionContext| needs_version_id(c, 2);
+///
+/// This component is required when CWE.10 is populated and CWE.20 is not populated. Both CWE.12
+/// and CWE.20 may be populated.
+///
+pub const CONDITION_CWE4: V2ComponentConditionFn =
+    |c: &V2ConditionContext| is_valued(c, 9) && !is_valued(c, 19);
+///
+/// Version ID of the coding system in CWE.12. See [CONDITION_CF3].
+///
+pub const CONDITION_CWE5: V2ComponentConditionFn = |c: &V2ConditionContext| needs_version_id(c, 11);
+///
+/// This component is required when CWE.1 is populated and CWE.3 is not populated. Both CWE.3
+/// and CWE.14 may be populated.
+///
+pub const CONDITION_CWE6: V2ComponentConditionFn =
+    |c: &V2ConditionContext| is_valued(c, 0) && !is_valued(c, 2);
+///
+/// Value set version ID is required if CWE.15 is populated.
+///
+pub const CONDITION_CWE7: V2ComponentConditionFn = |c: &V2ConditionContext| is_valued(c, 14);
+///
+/// This component is required when CWE.4 is populated and CWE.6 is not populated. Both CWE.6
+/// and CWE.17 may be populated.
+///
+pub const CONDITION_CWE8: V2ComponentConditionFn =
+    |c: &V2ConditionContext| is_valued(c, 3) && !is_valued(c, 5);
+///
+/// Value set version ID is required if CWE.18 is populated.
+///
+pub const CONDITION_CWE9: V2ComponentConditionFn = |c: &V2ConditionContext| is_valued(c, 17);
+///
+/// This component is required when CWE.10 is populated and CWE.12 is not populated. Both CWE.12
+/// and CWE.20 may be populated.
+///
+pub const CONDITION_CWE10: V2ComponentConditionFn =
+    |c: &V2ConditionContext| is_valued(c, 9) && !is_valued(c, 11);
+///
+/// Value set version ID is required if CWE.21 is populated.
+///
+pub const CONDITION_CWE11: V2ComponentConditionFn = |c: &V2ConditionContext| is_valued(c, 20);
+
+/***************CX Conditions**************/
+///
+/// The assigning authority is required if neither CX.9 nor CX.10 are populated.
+///
+pub const CONDITION_CX1: V2ComponentConditionFn =
+    |c: &V2ConditionContext| !(is_valued(c, 8) || is_valued(c, 9));
+///
+/// The assigning jurisdiction is required if neither CX.4 nor CX.10 are populated.
+///
+pub const CONDITION_CX2: V2ComponentConditionFn =
+    |c: &V2ConditionContext| !(is_valued(c, 3) || is_valued(c, 9));
+///
+/// The assigning agency or department is required if neither CX.4 nor CX.9 are populated.
+///
+pub const CONDITION_CX3: V2ComponentConditionFn =
+    |c: &V2ConditionContext| !(is_valued(c, 3) || is_valued(c, 8));
+
+/***************EI Conditions**************/
+///
+/// If EI.1 is populated, either EI.2 or both EI.3 and EI.4 must be populated.
+///
+pub const CONDITION_EI1: V2ComponentConditionFn =
+    |c: &V2ConditionContext| is_valued(c, 0) && !is_valued(c, 2);
+///
+/// EI.3 and EI.4 must either both be valued or both be empty. EI.3 is also required when EI.1 is
+/// populated and EI.2 is not.
+///
+pub const CONDITION_EI2: V2ComponentConditionFn =
+    |c: &V2ConditionContext| is_valued(c, 3) || (is_valued(c, 0) && !is_valued(c, 1));
+///
+/// EI.3 and EI.4 must either both be valued or both be empty.
+///
+pub const CONDITION_EI3: V2ComponentConditionFn = |c: &V2ConditionContext| is_valued(c, 2);
+
+/***************HD Conditions**************/
+///
+/// The namespace ID is required if HD.2 is not populated.
+///
+pub const CONDITION_HD1: V2ComponentConditionFn = |c: &V2ConditionContext| !is_valued(c, 1);
+///
+/// HD.2 and HD.3 must either both be valued or both be empty. HD.2 is also required when HD.1 is
+/// not populated.
+///
+pub const CONDITION_HD2: V2ComponentConditionFn =
+    |c: &V2ConditionContext| is_valued(c, 2) || !is_valued(c, 0);
+///
+/// HD.2 and HD.3 must either both be valued or both be empty.
+///
+pub const CONDITION_HD3: V2ComponentConditionFn = |c: &V2ConditionContext| is_valued(c, 1);
+
+/***************PPN Conditions*************/
+///
+/// If component 1 is valued, either PPN.8 or PPN.9, or both PPN.23 and PPN.24, must be valued.
+/// PPN.8 is required when none of the alternatives are present.
+///
+pub const CONDITION_PPN1: V2ComponentConditionFn = |c: &V2ConditionContext| {
+    is_valued(c, 0) && !(is_valued(c, 8) || (is_valued(c, 22) && is_valued(c, 23)))
+};
+///
+/// If component 1 is valued, either PPN.8 or PPN.9, or both PPN.23 and PPN.24, must be valued.
+/// PPN.9 is required when none of the alternatives are present.
+///
+pub const CONDITION_PPN2: V2ComponentConditionFn = |c: &V2ConditionContext| {
+    is_valued(c, 0) && !(is_valued(c, 7) || (is_valued(c, 22) && is_valued(c, 23)))
+};
+///
+/// The check digit scheme is required if PPN.11 is valued.
+///
+pub const CONDITION_PPN3: V2ComponentConditionFn = |c: &V2ConditionContext| is_valued(c, 10);
+///
+/// If component 1 is valued, either PPN.8 or PPN.9, or both PPN.23 and PPN.24, must be valued.
+/// PPN.23 and PPN.24 are required when neither PPN.8 nor PPN.9 are present.
+///
+pub const CONDITION_PPN4: V2ComponentConditionFn =
+    |c: &V2ConditionContext| is_valued(c, 0) && !(is_valued(c, 7) || is_valued(c, 8));
+
+/***************RPT Conditions*************/
+///
+/// The period units are required if RPT.5 is valued.
+///
+pub const CONDITION_RPT1: V2ComponentConditionFn = |c: &V2ConditionContext| is_valued(c, 4);
+///
+/// The event offset units are required if RPT.9 is valued.
+///
+pub const CONDITION_RPT2: V2ComponentConditionFn = |c: &V2ConditionContext| is_valued(c, 8);
+
+/***************XCN Conditions*************/
+///
+/// If component 1 is valued, either XCN.8 or XCN.9, or both XCN.22 and XCN.23, must be valued.
+/// XCN.8 is required when none of the alternatives are present.
+///
+pub const CONDITION_XCN1: V2ComponentConditionFn = |c: &V2ConditionContext| {
+    is_valued(c, 0) && !(is_valued(c, 8) || (is_valued(c, 21) && is_valued(c, 22)))
+};
+///
+/// If component 1 is valued, either XCN.8 or XCN.9, or both XCN.22 and XCN.23, must be valued.
+/// XCN.9 is required when none of the alternatives are present.
+///
+pub const CONDITION_XCN2: V2ComponentConditionFn = |c: &V2ConditionContext| {
+    is_valued(c, 0) && !(is_valued(c, 7) || (is_valued(c, 21) && is_valued(c, 22)))
+};
+///
+/// The check digit scheme is required if XCN.11 is valued.
+///
+pub const CONDITION_XCN3: V2ComponentConditionFn = |c: &V2ConditionContext| is_valued(c, 10);
+///
+/// If component 1 is valued, either XCN.8 or XCN.9, or both XCN.22 and XCN.23, must be valued.
+/// XCN.22 and XCN.23 are required when neither XCN.8 nor XCN.9 are present.
+///
+pub const CONDITION_XCN4: V2ComponentConditionFn =
+    |c: &V2ConditionContext| is_valued(c, 0) && !(is_valued(c, 7) || is_valued(c, 8));
+
+/***************XON Conditions*************/
+///
+/// The organization name is required if XON.10 is not valued.
+///
+pub const CONDITION_XON1: V2ComponentConditionFn = |c: &V2ConditionContext| !is_valued(c, 9);
+///
+/// The assigning authority and the identifier type code are required if XON.10 is valued.
+///
+pub const CONDITION_XON2: V2ComponentConditionFn = |c: &V2ConditionContext| is_valued(c, 9);
+///
+/// The organization identifier is required if XON.1 is not valued.
+///
+pub const CONDITION_XON3: V2ComponentConditionFn = |c: &V2ConditionContext| !is_valued(c, 0);
+
+/***************XTN Conditions*************/
+///
+/// Checks if the equipment type in XTN.3 is a network address instead of a telephone.
+///
+fn is_network_address(c: &V2ConditionContext) -> bool {
+    matches!(component_value(c, 2), "Internet" | "X.400")
+}
+///
+/// The communication address is required when XTN.3 is Internet or X.400.
+///
+pub const CONDITION_XTN1: V2ComponentConditionFn = |c: &V2ConditionContext| is_network_address(c);
+///
+/// The area code and local number are required when XTN.3 is a telephone type and XTN.12 is not
+/// populated.
+///
+pub const CONDITION_XTN2: V2ComponentConditionFn =
+    |c: &V2ConditionContext| is_valued(c, 2) && !is_network_address(c) && !is_valued(c, 11);
+///
+/// The unformatted telephone number is required when XTN.3 is a telephone type and XTN.7 is not
+/// populated.
+///
+pub const CONDITION_XTN3: V2ComponentConditionFn =
+    |c: &V2ConditionContext| is_valued(c, 2) && !is_network_address(c) && !is_valued(c, 6);
+
+/************************ Segment Field Conditions *************************/
+///
+/// Checks if the message is a result message (ORU or OUL), which is what the result fields of
+/// OBR are conditioned on.
+///
+fn is_result_message(c: &V2ConditionContext) -> bool {
+    matches!(c.message_code(), "ORU" | "OUL")
+}
+
+///
+/// Placer order number of ORC-2 and OBR-2. Either the placer or the filler order number must be
+/// valued, so it is required when the filler order number in the same segment is not.
+///
+pub const CONDITION_PLACER_ORDER_NUMBER: V2ComponentConditionFn =
+    |c: &V2ConditionContext| c.segment.is_some() && !c.is_segment_field_valued(3);
+///
+/// Filler order number of ORC-3 and OBR-3. It is required in result messages, which are sent by
+/// the filler, and when the placer order number in the same segment is not valued.
+///
+pub const CONDITION_FILLER_ORDER_NUMBER: V2ComponentConditionFn = |c: &V2ConditionContext| {
+    is_result_message(c) || (c.segment.is_some() && !c.is_segment_field_valued(2))
+};
+///
+/// OBR-7, OBR-22, and OBR-25 are required when the OBR is transmitted as part of a result message.
+///
+pub const CONDITION_OBR_RESULT: V2ComponentConditionFn =
+    |c: &V2ConditionContext| is_result_message(c);
+///
+/// The value type in OBX-2 must be valued unless OBX-11 (observation result status) is X, meaning
+/// the result could not be obtained.
+///
+pub const CONDITION_OBX_VALUE_TYPE: V2ComponentConditionFn =
+    |c: &V2ConditionContext| c.segment.is_some() && c.segment_field_value(11) != "X";
+///
+/// The observation sub-ID in OBX-4 tells apart the OBX segments under one OBR (or SPM) that share
+/// the same observation identifier in OBX-3, so it is required when another OBX of the group has
+/// it.
+///
+pub const CONDITION_OBX_SUB_ID: V2ComponentConditionFn = |c: &V2ConditionContext| {
+    let identifier = c.segment_field_value(3);
+    !identifier.is_empty()
+        && c.group_segments(&["OBR", "ORC", "SPM"])
+            .iter()
+            .filter(|segment| segment.name() == "OBX")
+            .filter(|segment| field_value(segment, 3) == identifier)
+            .count()
+            > 1
+};
+///
+/// The observation value in OBX-5 may only be left empty when OBX-11 (observation result status)
+/// is X (results cannot be obtained), N (not asked), or D (deletes the OBX record).
+///
+pub const CONDITION_OBX_VALUE: V2ComponentConditionFn = |c: &V2ConditionContext| {
+    c.segment.is_some() && !matches!(c.segment_field_value(11), "X" | "N" | "D")
+};
+///
+/// The override reason in ORC-26 is required when ORC-20 (advanced beneficiary notice code) is 4,
+/// meaning the patient did not sign the notice.
+///
+pub const CONDITION_ORC_ABN_OVERRIDE_REASON: V2ComponentConditionFn =
+    |c: &V2ConditionContext| c.segment_field_value(20) == "4";
+///
+/// The grouped specimen count in SPM-13 is required when SPM-11 (specimen role) is G, meaning the
+/// SPM describes a group of specimens.
+///
+pub const CONDITION_SPM_GROUPED_COUNT: V2ComponentConditionFn =
+    |c: &V2ConditionContext| c.segment_field_value(11) == "G";
+///
+/// The administered units in RXA-7 are required when the administered amount in RXA-6 is valued and
+/// is not 999 (amount unknown).
+///
+pub const CONDITION_RXA_ADMINISTERED_UNITS: V2ComponentConditionFn =
+    |c: &V2ConditionContext| c.is_segment_field_valued(6) && c.segment_field_value(6) != "999";
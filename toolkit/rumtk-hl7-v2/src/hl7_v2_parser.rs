@@ -33,37 +33,280 @@
 ///
 
 pub mod v2_parser {
+    use crate::hl7_v2_base_types::v2_primitives::V2ComponentList;
     pub use crate::hl7_v2_base_types::v2_primitives::{
-        V2DateTime, V2ParserCharacters, V2PrimitiveCasting, V2Result, V2SearchIndex, V2String,
+        find_segment_id, is_custom_segment_name, is_header_segment_name, V2DateTime, V2Diagnostic,
+        V2DiagnosticSeverity, V2Diagnostics, V2ParseMode, V2ParserCharacters, V2PrimitiveCasting,
+        V2Quantity, V2Result, V2SearchIndex, V2SegmentIds, V2String, V2StructuredNumeric,
     };
+    use crate::hl7_v2_complex_types::hl7_v2_complex_types::{
+        cast_component, V2FieldToString, V2Type,
+    };
+    use crate::hl7_v2_constants::V2_SEARCH_EXPR_TYPE;
     pub use crate::hl7_v2_constants::{
-        V2_DELETE_FIELD, V2_EMPTY_STRING, V2_MSHEADER_PATTERN, V2_SEGMENT_DESC, V2_SEGMENT_IDS,
-        V2_SEGMENT_TERMINATOR,
+        V2_CUSTOM_SEGMENT_PATTERN, V2_DELETE_FIELD, V2_EMPTY_STRING, V2_MSHEADER_PATTERN,
+        V2_SEGMENT_DESC, V2_SEGMENT_IDS, V2_SEGMENT_TERMINATOR,
     };
+    use crate::hl7_v2_field_descriptors::v2_field_descriptor::V2ComponentTypeDescriptor;
+    use crate::hl7_v2_search::compile_search_pattern;
     pub use rumtk_core::cache::{get_or_set_from_cache, new_cache, AHashMap, LazyRUMCache};
     use rumtk_core::core::clamp_index;
     use rumtk_core::json::serialization::{Deserialize, Serialize};
-    use rumtk_core::rumtk_cache_fetch;
     use rumtk_core::strings::CompactStringExt;
     pub use rumtk_core::strings::{
         format_compact, try_decode_with, unescape_string, AsStr, RUMString, RUMStringConversions,
     };
+    use std::borrow::Cow;
     use std::ops::{Index, IndexMut};
-    /**************************** Globals ***************************************/
+    use std::sync::OnceLock;
+    /**************************** Helpers ***************************************/
 
-    static mut search_cache: LazyRUMCache<RUMString, V2SearchIndex> = new_cache();
+    fn default_subcomponent_separator() -> V2String {
+        V2ParserCharacters::new().subcomponent_separator
+    }
 
-    /**************************** Helpers ***************************************/
-    fn compile_search_index(search_pattern: &RUMString) -> V2SearchIndex {
-        V2SearchIndex::from(search_pattern)
+    ///
+    /// Characters that have no business inside a message. We usually see these when MLLP framing
+    /// (<VT> and <FS>) or a byte order mark leaks into the payload.
+    ///
+    fn is_stray_character(c: char) -> bool {
+        (c.is_control() && c != '\t') || c == '\u{feff}'
+    }
+
+    ///
+    /// Returns the segment without stray characters along with the byte position of each
+    /// character we removed.
+    ///
+    fn strip_stray_characters(raw_segment: &str) -> (Cow<'_, str>, Vec<usize>) {
+        if !raw_segment.contains(is_stray_character) {
+            return (Cow::Borrowed(raw_segment), Vec::new());
+        }
+        let mut clean_segment = String::with_capacity(raw_segment.len());
+        let mut positions = Vec::new();
+        for (position, c) in raw_segment.char_indices() {
+            if is_stray_character(c) {
+                positions.push(position);
+            } else {
+                clean_segment.push(c);
+            }
+        }
+        (Cow::Owned(clean_segment.trim().to_string()), positions)
+    }
+
+    ///
+    /// Figures out which field and component a byte position of a raw segment falls in. The
+    /// encoding characters field of header segments has no components.
+    ///
+    fn locate_position(
+        raw_segment: &str,
+        position: usize,
+        parser_chars: &V2ParserCharacters,
+    ) -> (isize, Option<isize>) {
+        let prefix = &raw_segment[..position];
+        let field = prefix
+            .matches(parser_chars.field_separator.as_str())
+            .count() as isize;
+        let segment_name = raw_segment.get(0..3).unwrap_or_default();
+        if field == 0 || (field == 1 && is_header_segment_name(segment_name)) {
+            return (field, None);
+        }
+        let field_prefix = prefix
+            .rsplit(parser_chars.field_separator.as_str())
+            .next()
+            .unwrap_or_default();
+        let repetition_prefix = field_prefix
+            .rsplit(parser_chars.repetition_separator.as_str())
+            .next()
+            .unwrap_or_default();
+        let component = repetition_prefix
+            .matches(parser_chars.component_separator.as_str())
+            .count() as isize;
+        (field, Some(component + 1))
+    }
+
+    ///
+    /// Rebuilds an MSH segment whose encoding characters are missing or malformed. We keep
+    /// whatever usable encoding characters the sender declared and fill in the rest with the
+    /// defaults. If the field holds data instead, we assume the sender left the encoding
+    /// characters out and insert the defaults ahead of it.
+    ///
+    fn repair_msh(msh_segment: &str) -> V2Result<(V2ParserCharacters, RUMString)> {
+        let defaults = V2ParserCharacters::new();
+        let default_encoding = format_compact!(
+            "{}{}{}{}",
+            &defaults.component_separator,
+            &defaults.repetition_separator,
+            &defaults.escape_character,
+            &defaults.subcomponent_separator
+        );
+        let header = &msh_segment[V2_MSHEADER_PATTERN.len()..];
+        let (field_separator, fields) = match header.chars().next() {
+            Some(c) if c.is_ascii_punctuation() => (c, &header[1..]),
+            _ => (defaults.field_separator.chars().next().unwrap(), header),
+        };
+        let (declared, remaining_fields) = match fields.find(field_separator) {
+            Some(indx) => fields.split_at(indx),
+            None => (fields, ""),
+        };
+
+        let mut encoding = String::with_capacity(default_encoding.len() + 1);
+        let remaining_fields = if declared.chars().all(|c| c.is_ascii_punctuation()) {
+            for c in declared.chars() {
+                if !encoding.contains(c) && encoding.len() < default_encoding.len() + 1 {
+                    encoding.push(c);
+                }
+            }
+            remaining_fields.to_rumstring()
+        } else {
+            format_compact!("{}{}", field_separator, fields)
+        };
+        for c in default_encoding.chars() {
+            if encoding.len() >= default_encoding.len() {
+                break;
+            }
+            if c != field_separator && !encoding.contains(c) {
+                encoding.push(c);
+            }
+        }
+
+        let repaired_msh = format_compact!(
+            "{}{}{}{}",
+            V2_MSHEADER_PATTERN,
+            field_separator,
+            &encoding,
+            &remaining_fields
+        );
+        let parser_chars = V2ParserCharacters::from_msh(&repaired_msh)?;
+        Ok((parser_chars, repaired_msh))
     }
 
     /**************************** Types *****************************************/
     ///
-    /// V2Component.
-    /// All V2Components contain the field's component data as a UTF-8 string.
+    /// V2SubComponent.
+    /// This is the leaf of the message tree. All V2SubComponents contain the component's
+    /// subcomponent data as a UTF-8 string.
     /// You can request a conversion to an atomic type via the as_* family of methods.
     ///
+    /// ## Per Section 2.5.3.3
+    ///
+    /// ```text
+    /// A component MAY itself be a composite data type. In that case, its parts are called
+    /// subcomponents and are separated by the subcomponent separator (&). Subcomponents SHALL
+    /// only contain primitive data types.
+    /// ```
+    ///
+    /// ## Example
+    ///
+    /// PID-3.4 in `PATID14567^^^NIST MPI&2.16.840.1.113883.3.72.5.30.2&ISO^MR` is an HD composite
+    /// made of the subcomponents `NIST MPI`, `2.16.840.1.113883.3.72.5.30.2` and `ISO`.
+    ///
+    #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+    pub struct V2SubComponent {
+        subcomponent: V2String,
+    }
+
+    impl V2SubComponent {
+        fn new() -> V2SubComponent {
+            V2SubComponent {
+                subcomponent: V2String::from(""),
+            }
+        }
+
+        #[allow(clippy::should_implement_trait)]
+        pub fn from_str(item: &str) -> V2SubComponent {
+            V2SubComponent {
+                subcomponent: V2String::from(item),
+            }
+        }
+
+        pub fn to_string(&self) -> V2String {
+            self.subcomponent.clone()
+        }
+
+        ///
+        /// Returns the subcomponent with its escape sequences interpreted. See
+        /// [V2ParserCharacters::unescape].
+        ///
+        pub fn decoded(&self, parser_chars: &V2ParserCharacters) -> V2Result<V2String> {
+            parser_chars.unescape(&self.subcomponent)
+        }
+
+        ///
+        /// Replaces the value of this subcomponent. The value is stored as given, so any escape
+        /// sequences in it are kept as is. Delimiter characters are escaped when the message is
+        /// generated.
+        ///
+        pub fn set(&mut self, value: &str) {
+            self.subcomponent = V2String::from(value);
+        }
+
+        ///
+        /// Replaces the value of this subcomponent with plain text. Every character with special
+        /// meaning, including the escape character itself, is escaped before storing the value.
+        ///
+        pub fn set_decoded(&mut self, value: &str, parser_chars: &V2ParserCharacters) {
+            self.subcomponent = parser_chars.escape(value);
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.subcomponent.is_empty()
+        }
+
+        pub fn is_delete(&self) -> bool {
+            self.subcomponent == V2_DELETE_FIELD
+        }
+
+        #[deprecated(
+            since = "0.7.4",
+            note = "use V2PrimitiveCasting::to_v2datetime instead"
+        )]
+        pub fn as_datetime(&self) -> V2DateTime {
+            V2DateTime::from_str(&self.subcomponent)
+        }
+
+        ///
+        /// Panics if the value is not a Yes/No indicator.
+        ///
+        #[deprecated(since = "0.7.4", note = "use V2PrimitiveCasting::to_v2boolean instead")]
+        pub fn as_bool(&self) -> bool {
+            self.to_v2boolean().unwrap()
+        }
+
+        ///
+        /// Panics if the value is not a whole number.
+        ///
+        #[deprecated(since = "0.7.4", note = "use V2PrimitiveCasting::to_v2integer instead")]
+        pub fn as_integer(&self) -> i64 {
+            self.to_v2integer().unwrap()
+        }
+
+        ///
+        /// Panics if the value is not a number.
+        ///
+        #[deprecated(since = "0.7.4", note = "use V2PrimitiveCasting::to_v2decimal instead")]
+        pub fn as_float(&self) -> f64 {
+            self.to_v2decimal().unwrap()
+        }
+    }
+
+    impl AsStr for V2SubComponent {
+        fn as_str(&self) -> &str {
+            self.subcomponent.as_str()
+        }
+    }
+
+    impl V2PrimitiveCasting for V2SubComponent {}
+
+    pub type SubComponentList = Vec<V2SubComponent>;
+
+    ///
+    /// V2Component.
+    /// All V2Components contain the field's component data as a list of subcomponents. Most
+    /// components hold a single subcomponent.
+    /// [V2Component::as_str] and [V2Component::to_string] yield the whole component in wire
+    /// format, subcomponent separators included. Use [V2Component::get] to reach an individual
+    /// subcomponent.
+    ///
     /// ## Per Section 2.5.3.1
     ///
     /// ```text
@@ -88,18 +331,16 @@ pub mod v2_parser {
     /// a field for other purposes is prohibited.
     /// ```
     ///
-    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    #[derive(Debug, Clone, Serialize, Deserialize)]
     pub struct V2Component {
-        component: V2String,
+        subcomponents: SubComponentList,
+        #[serde(skip, default = "default_subcomponent_separator")]
+        separator: V2String,
+        #[serde(skip)]
+        value: OnceLock<V2String>,
     }
 
     impl V2Component {
-        fn new() -> V2Component {
-            V2Component {
-                component: V2String::from(""),
-            }
-        }
-
         ///
         /// Constructs HL7 V2 Component.
         /// ### Per Section 2.7
@@ -144,49 +385,227 @@ pub mod v2_parser {
         ///
         /// Will not support 2.7.8 Local encodings (\Zxxyy) until needed in the wild.
         ///
-        pub fn from_str(item: &str) -> V2Component {
+        /// The component is split into its subcomponents using the subcomponent separator.
+        ///
+        pub fn from_str(item: &str, parser_chars: &V2ParserCharacters) -> V2Component {
+            let subcomponent_vec: Vec<&str> = item
+                .split(parser_chars.subcomponent_separator.as_str())
+                .collect();
+            let mut subcomponent_list = SubComponentList::with_capacity(subcomponent_vec.len());
+            for s in subcomponent_vec {
+                subcomponent_list.push(V2SubComponent::from_str(s));
+            }
+            V2Component::from_subcomponents(subcomponent_list, &parser_chars.subcomponent_separator)
+        }
+
+        fn from_subcomponents(subcomponents: SubComponentList, separator: &str) -> V2Component {
             V2Component {
-                component: V2String::from(item),
+                subcomponents,
+                separator: V2String::from(separator),
+                value: OnceLock::new(),
             }
         }
 
+        ///
+        /// Constructs a component with a single subcomponent holding the input verbatim. Useful
+        /// for values like MSH-2 which contain the separator characters themselves.
+        ///
+        pub fn with_raw_str(item: &str) -> V2Component {
+            V2Component::from_subcomponents(
+                vec![V2SubComponent::from_str(item)],
+                &default_subcomponent_separator(),
+            )
+        }
+
+        ///
+        /// Returns the whole component in wire format, e.g. `NIST MPI&2.16.840.1.113883.3.72.5.30.2&ISO`
+        /// for PID-3.4. Same as [V2Component::as_str].
+        ///
         pub fn to_string(&self) -> V2String {
-            self.component.clone()
+            self.as_str().to_rumstring()
+        }
+
+        ///
+        /// Sets the separator used to join the subcomponents in [V2Component::as_str]. Components
+        /// added while growing a message start with the default (`&`).
+        ///
+        pub fn set_subcomponent_separator(&mut self, separator: &str) {
+            if self.separator != separator {
+                self.separator = V2String::from(separator);
+                self.value = OnceLock::new();
+            }
+        }
+
+        ///
+        /// Generates the component with the `target` delimiters. The values are stored in the wire
+        /// format of `source`. See [V2ParserCharacters::redelimit].
+        ///
+        pub fn to_string_with(
+            &self,
+            source: &V2ParserCharacters,
+            target: &V2ParserCharacters,
+        ) -> V2String {
+            let mut subcomponents: Vec<V2String> = Vec::with_capacity(self.subcomponents.len());
+            for subcomponent in self.subcomponents.iter() {
+                subcomponents.push(source.redelimit(subcomponent.as_str(), target))
+            }
+            subcomponents.join_compact(target.subcomponent_separator.as_str())
+        }
+
+        ///
+        /// Returns the whole component with its escape sequences interpreted. Useful for display
+        /// purposes. Use [V2SubComponent::decoded] if you need the individual parts of a composite.
+        ///
+        pub fn decoded(&self, parser_chars: &V2ParserCharacters) -> V2Result<V2String> {
+            parser_chars.unescape(&self.to_string_with(parser_chars, parser_chars))
+        }
+
+        pub fn len(&self) -> usize {
+            self.subcomponents.len()
         }
 
         pub fn is_empty(&self) -> bool {
-            self.component == ""
+            self.subcomponents.iter().all(|s| s.is_empty())
         }
 
         pub fn is_delete(&self) -> bool {
-            self.component == V2_DELETE_FIELD
+            self.subcomponents.len() == 1 && self.subcomponents[0].is_delete()
         }
 
+        pub fn get(&self, indx: isize) -> V2Result<&V2SubComponent> {
+            let subcomponent_indx = clamp_index(&indx, &(self.subcomponents.len() as isize))? - 1;
+            match self.subcomponents.get(subcomponent_indx) {
+                Some(subcomponent) => Ok(subcomponent),
+                None => Err(format_compact!("Subcomponent at index {} not found!", indx)),
+            }
+        }
+
+        pub fn get_mut(&mut self, indx: isize) -> V2Result<&mut V2SubComponent> {
+            let subcomponent_indx = clamp_index(&indx, &(self.subcomponents.len() as isize))? - 1;
+            self.value = OnceLock::new();
+            match self.subcomponents.get_mut(subcomponent_indx) {
+                Some(subcomponent) => Ok(subcomponent),
+                None => Err(format_compact!("Subcomponent at index {} not found!", indx)),
+            }
+        }
+
+        ///
+        /// Same as [V2Component::get_mut] but pads the component with empty subcomponents when
+        /// the index is past the end. Indices are 1-indexed.
+        ///
+        pub fn get_or_insert_mut(&mut self, indx: usize) -> V2Result<&mut V2SubComponent> {
+            if indx == 0 {
+                return Err("Subcomponent indices are 1-indexed! Got 0!".to_rumstring());
+            }
+            self.value = OnceLock::new();
+            while self.subcomponents.len() < indx {
+                self.subcomponents.push(V2SubComponent::new());
+            }
+            Ok(&mut self.subcomponents[indx - 1])
+        }
+
+        fn subcomponent_list(&self) -> Vec<&str> {
+            self.subcomponents
+                .iter()
+                .map(|subcomponent| subcomponent.as_str())
+                .collect()
+        }
+
+        ///
+        /// Casts the subcomponents to SN, e.g. when SN is a component of a complex type.
+        ///
+        pub fn to_structured_numeric(&self) -> V2Result<V2StructuredNumeric> {
+            V2StructuredNumeric::from_parts(&self.subcomponent_list())
+        }
+
+        ///
+        /// Casts the subcomponents to CQ, e.g. the quantity of a TQ.
+        ///
+        pub fn to_quantity(&self) -> V2Result<V2Quantity> {
+            V2Quantity::from_parts(&self.subcomponent_list())
+        }
+
+        ///
+        /// Casts the component following its descriptor, so the data type, optionality, and
+        /// nested components come from the data type tables. See [cast_component].
+        ///
+        pub fn to_typed(
+            &self,
+            descriptor: &V2ComponentTypeDescriptor,
+            characters: &V2ParserCharacters,
+        ) -> V2Type {
+            cast_component(self.subcomponent_list(), descriptor, characters)
+        }
+
+        #[deprecated(
+            since = "0.7.4",
+            note = "use V2PrimitiveCasting::to_v2datetime instead"
+        )]
         pub fn as_datetime(&self) -> V2DateTime {
-            V2DateTime::from_str(&self.component)
+            V2DateTime::from_str(self.as_str())
         }
 
+        ///
+        /// Panics if the value is not a Yes/No indicator.
+        ///
+        #[deprecated(since = "0.7.4", note = "use V2PrimitiveCasting::to_v2boolean instead")]
         pub fn as_bool(&self) -> bool {
-            self.component.parse::<bool>().unwrap()
+            self.to_v2boolean().unwrap()
         }
 
+        ///
+        /// Panics if the value is not a whole number.
+        ///
+        #[deprecated(since = "0.7.4", note = "use V2PrimitiveCasting::to_v2integer instead")]
         pub fn as_integer(&self) -> i64 {
-            self.component.parse::<i64>().unwrap()
+            self.to_v2integer().unwrap()
         }
 
+        ///
+        /// Panics if the value is not a number.
+        ///
+        #[deprecated(since = "0.7.4", note = "use V2PrimitiveCasting::to_v2decimal instead")]
         pub fn as_float(&self) -> f64 {
-            self.component.parse::<f64>().unwrap()
+            self.to_v2decimal().unwrap()
         }
     }
 
     impl AsStr for V2Component {
         fn as_str(&self) -> &str {
-            self.component.as_str()
+            match self.subcomponents.as_slice() {
+                [subcomponent] => subcomponent.as_str(),
+                subcomponents => self.value.get_or_init(|| {
+                    subcomponents
+                        .iter()
+                        .map(|subcomponent| subcomponent.as_str())
+                        .collect::<Vec<&str>>()
+                        .join_compact(self.separator.as_str())
+                }),
+            }
+        }
+    }
+
+    impl PartialEq for V2Component {
+        fn eq(&self, other: &Self) -> bool {
+            self.subcomponents == other.subcomponents
         }
     }
 
     impl V2PrimitiveCasting for V2Component {}
 
+    impl Index<isize> for V2Component {
+        type Output = V2SubComponent;
+        fn index(&self, indx: isize) -> &V2SubComponent {
+            self.get(indx).unwrap()
+        }
+    }
+
+    impl IndexMut<isize> for V2Component {
+        fn index_mut(&mut self, indx: isize) -> &mut V2SubComponent {
+            self.get_mut(indx).unwrap()
+        }
+    }
+
     pub type ComponentList = Vec<V2Component>;
 
     ///
@@ -205,7 +624,7 @@ pub mod v2_parser {
     /// comprehensive data dictionary of all HL7 fields is provided in Appendix A.
     ///```
     ///
-    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
     pub struct V2Field {
         components: ComponentList,
     }
@@ -222,7 +641,7 @@ pub mod v2_parser {
                 .collect();
             let mut component_list: ComponentList = ComponentList::new();
             for c in comp_vec {
-                component_list.push(V2Component::from_str(c));
+                component_list.push(V2Component::from_str(c, parser_chars));
             }
             V2Field {
                 components: component_list,
@@ -230,15 +649,30 @@ pub mod v2_parser {
         }
 
         pub fn to_string(&self, parser_chars: &V2ParserCharacters) -> V2String {
-            let mut components: Vec<&str> = Vec::with_capacity(self.components.len());
+            self.to_string_with(parser_chars, parser_chars)
+        }
+
+        ///
+        /// Generates the field with the `target` delimiters. See [V2Component::to_string_with].
+        ///
+        pub fn to_string_with(
+            &self,
+            source: &V2ParserCharacters,
+            target: &V2ParserCharacters,
+        ) -> V2String {
+            let mut components: Vec<V2String> = Vec::with_capacity(self.components.len());
             for component in self.components.iter() {
-                components.push(component.as_str())
+                components.push(component.to_string_with(source, target))
             }
-            components.join_compact(parser_chars.component_separator.as_str())
+            components.join_compact(target.component_separator.as_str())
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.components.iter().all(|c| c.is_empty())
         }
 
         pub fn with_raw_str(val: &str) -> V2Field {
-            let mut component_list: ComponentList = vec![V2Component::from_str(val)];
+            let component_list: ComponentList = vec![V2Component::with_raw_str(val)];
             V2Field {
                 components: component_list,
             }
@@ -263,6 +697,57 @@ pub mod v2_parser {
                 None => Err(format_compact!("Component at index {} not found!", indx)),
             }
         }
+
+        ///
+        /// Same as [V2Field::get_mut] but pads the field with empty components when the index is
+        /// past the end. Indices are 1-indexed.
+        ///
+        pub fn get_or_insert_mut(&mut self, indx: usize) -> V2Result<&mut V2Component> {
+            if indx == 0 {
+                return Err("Component indices are 1-indexed! Got 0!".to_rumstring());
+            }
+            while self.components.len() < indx {
+                self.components
+                    .push(V2Component::with_raw_str(V2_EMPTY_STRING));
+            }
+            Ok(&mut self.components[indx - 1])
+        }
+
+        ///
+        /// Casts the components to SN, e.g. OBX-5 when OBX-2 is `SN`.
+        ///
+        pub fn to_structured_numeric(&self) -> V2Result<V2StructuredNumeric> {
+            V2StructuredNumeric::from_parts(&self.first_subcomponents())
+        }
+
+        ///
+        /// Casts the components to CQ. The units are the identifier of the CWE.
+        ///
+        pub fn to_quantity(&self) -> V2Result<V2Quantity> {
+            V2Quantity::from_parts(&self.first_subcomponents())
+        }
+
+        fn first_subcomponents(&self) -> Vec<&str> {
+            self.components
+                .iter()
+                .map(|component| component.subcomponents[0].as_str())
+                .collect()
+        }
+    }
+
+    impl V2FieldToString for V2Field {
+        fn to_component_list(&self) -> V2ComponentList<'_> {
+            self.components
+                .iter()
+                .map(|component| {
+                    component
+                        .subcomponents
+                        .iter()
+                        .map(|subcomponent| subcomponent.as_str())
+                        .collect()
+                })
+                .collect()
+        }
     }
 
     impl Index<isize> for V2Field {
@@ -297,7 +782,7 @@ pub mod v2_parser {
     /// Event Type (EVN), Patient ID (PID), and Patient Visit (PV1).
     /// ```
     ///
-    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
     pub struct V2Segment {
         name: RUMString,
         description: RUMString,
@@ -323,7 +808,7 @@ pub mod v2_parser {
             let field_name = raw_fields[0].to_rumstring().to_uppercase();
 
             if raw_field_count > 1 {
-                if field_name == "MSH" {
+                if is_header_segment_name(&field_name) {
                     field_list.push(vec![V2Field::with_raw_str(raw_fields[1])]);
                     for i in 2..raw_field_count {
                         let raw_field = raw_fields[i];
@@ -339,6 +824,9 @@ pub mod v2_parser {
 
             let field_description = RUMString::from(match V2_SEGMENT_DESC.get(&field_name) {
                 Some(description) => description,
+                None if is_custom_segment_name(&field_name) => {
+                    V2_SEGMENT_DESC[V2_CUSTOM_SEGMENT_PATTERN]
+                }
                 None => V2_EMPTY_STRING,
             });
 
@@ -350,19 +838,39 @@ pub mod v2_parser {
         }
 
         pub fn to_string(&self, parser_chars: &V2ParserCharacters) -> V2String {
+            self.to_string_with(parser_chars, parser_chars)
+        }
+
+        ///
+        /// Generates the segment with the `target` delimiters. The encoding characters of header
+        /// segments are rewritten to match. See [V2Component::to_string_with].
+        ///
+        pub fn to_string_with(
+            &self,
+            source: &V2ParserCharacters,
+            target: &V2ParserCharacters,
+        ) -> V2String {
             let mut segment: Vec<V2String> = Vec::with_capacity(self.fields.len());
-            for field_group in self.fields.iter() {
+            for (i, field_group) in self.fields.iter().enumerate() {
+                if i == 0 && is_header_segment_name(&self.name) {
+                    // MSH-2 (and FHS-2/BHS-2) holds the encoding characters. Never escape them.
+                    segment.push(match source == target {
+                        true => field_group[0].components[0].as_str().to_rumstring(),
+                        false => target.encoding_characters(),
+                    });
+                    continue;
+                }
                 let mut fields: Vec<V2String> = Vec::with_capacity(field_group.len());
                 for field in field_group {
-                    fields.push(field.to_string(parser_chars));
+                    fields.push(field.to_string_with(source, target));
                 }
-                segment.push(fields.join_compact(parser_chars.repetition_separator.as_str()));
+                segment.push(fields.join_compact(target.repetition_separator.as_str()));
             }
             format_compact!(
                 "{}{}{}",
                 self.name,
-                parser_chars.field_separator.as_str(),
-                segment.join_compact(parser_chars.field_separator.as_str())
+                target.field_separator.as_str(),
+                segment.join_compact(target.field_separator.as_str())
             )
         }
 
@@ -382,10 +890,122 @@ pub mod v2_parser {
             }
         }
 
+        ///
+        /// Same as [V2Segment::get_mut] but pads the segment with empty fields when the index is
+        /// past the end. Indices are 1-indexed.
+        ///
+        pub fn get_or_insert_mut(&mut self, indx: usize) -> V2Result<&mut V2FieldGroup> {
+            if indx == 0 {
+                return Err("Field indices are 1-indexed! Got 0!".to_rumstring());
+            }
+            while self.fields.len() < indx {
+                self.fields
+                    .push(vec![V2Field::with_raw_str(V2_EMPTY_STRING)]);
+            }
+            Ok(&mut self.fields[indx - 1])
+        }
+
         pub fn len(&self) -> usize {
             self.fields.len()
         }
 
+        pub fn is_empty(&self) -> bool {
+            self.fields.is_empty()
+        }
+
+        pub fn name(&self) -> &str {
+            self.name.as_str()
+        }
+
+        pub fn description(&self) -> &str {
+            self.description.as_str()
+        }
+
+        ///
+        /// Resolves the field, repetition, and component of a search index within this segment.
+        /// The segment and subcomponent portions of the index are ignored.
+        ///
+        pub fn find_component(&self, index: &V2SearchIndex) -> V2Result<&V2Component> {
+            let field = match self.get(index.field as isize)?.get((index.field_group as usize).wrapping_sub(1)) {
+                Some(field) => field,
+                None => return Err(format_compact!("Subfield provided is not 1 indexed or out of bounds. Did you give us a 0 when you meant 1? Got {}!", index.field_group))
+            };
+            field.get(index.component as isize)
+        }
+
+        ///
+        /// Same as [V2Segment::find_component] but goes down to the subcomponent of the index.
+        ///
+        pub fn find_subcomponent(&self, index: &V2SearchIndex) -> V2Result<&V2SubComponent> {
+            self.find_component(index)?.get(index.subcomponent as isize)
+        }
+
+        ///
+        /// Same as [V2Segment::find_subcomponent] but grows the segment as needed so the position
+        /// exists. Missing fields, repetitions, components, and subcomponents are added empty.
+        /// Only positive indices can be used and the encoding characters of header segments
+        /// cannot be modified.
+        ///
+        pub fn find_subcomponent_or_insert_mut(
+            &mut self,
+            index: &V2SearchIndex,
+            parser_chars: &V2ParserCharacters,
+        ) -> V2Result<&mut V2SubComponent> {
+            if index.component < 1 || index.subcomponent < 1 {
+                return Err(format_compact!(
+                    "Cannot grow segment {} at negative or 0 positions! Got component {} and subcomponent {}!",
+                    &self.name,
+                    index.component,
+                    index.subcomponent
+                ));
+            }
+            let field = self
+                .get_or_insert_repetition_mut(index.field as isize, index.field_group as usize)?;
+            let component = field.get_or_insert_mut(index.component as usize)?;
+            component.set_subcomponent_separator(&parser_chars.subcomponent_separator);
+            component.get_or_insert_mut(index.subcomponent as usize)
+        }
+
+        ///
+        /// Returns the nth repetition (1-indexed) of a field, adding empty fields and repetitions
+        /// as needed.
+        ///
+        pub fn get_or_insert_repetition_mut(
+            &mut self,
+            field: isize,
+            repetition: usize,
+        ) -> V2Result<&mut V2Field> {
+            if repetition == 0 {
+                return Err("Repetition indices are 1-indexed! Got 0!".to_rumstring());
+            }
+            let field_group = self.get_writable_field_mut(field)?;
+            while field_group.len() < repetition {
+                field_group.push(V2Field::with_raw_str(V2_EMPTY_STRING));
+            }
+            Ok(&mut field_group[repetition - 1])
+        }
+
+        ///
+        /// Same as [V2Segment::get_or_insert_mut] but refuses to hand out the encoding characters
+        /// field of header segments (MSH-2, FHS-2, BHS-2). Those come from the message separators.
+        ///
+        pub fn get_writable_field_mut(&mut self, field: isize) -> V2Result<&mut V2FieldGroup> {
+            if field < 1 {
+                return Err(format_compact!(
+                    "Cannot grow segment {} at negative or 0 positions! Got field {}!",
+                    &self.name,
+                    field
+                ));
+            }
+            if field == 1 && is_header_segment_name(&self.name) {
+                return Err(format_compact!(
+                    "{}-1 holds the encoding characters and cannot be modified!",
+                    &self.name
+                ));
+            }
+            self.get_or_insert_mut(field as usize)
+        }
+
         fn generate_subfields(field: &str, parser_chars: &V2ParserCharacters) -> Vec<V2Field> {
             let repetition_char = parser_chars.repetition_separator.as_str();
             let subfields: Vec<&str> = field.split(&repetition_char).collect();
@@ -428,10 +1048,181 @@ pub mod v2_parser {
     ///
     pub type SegmentMap = AHashMap<u8, V2SegmentGroup>;
 
+    ///
+    /// Location of a segment inside the [SegmentMap]. The first item is the segment id and the
+    /// second item is the 0-based position of the segment inside its group.
+    ///
+    pub type V2SegmentPosition = (u8, usize);
+
+    ///
+    /// Segments in the order they appeared in the original message. We keep this list next to the
+    /// [SegmentMap] so lookups by segment id stay cheap while generation reproduces the original
+    /// interleaving of segments (e.g. ORC/OBR/OBX/ORC/OBR/OBX).
+    ///
+    pub type SegmentOrder = Vec<V2SegmentPosition>;
+
     #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    #[serde(try_from = "V2MessageRepr")]
     pub struct V2Message {
         separators: V2ParserCharacters,
         segment_groups: SegmentMap,
+        segment_order: SegmentOrder,
+        segment_ids: V2SegmentIds,
+    }
+
+    ///
+    /// Serialized shapes we accept for a [V2Message]. Messages serialized before the subcomponent
+    /// level existed stored each component as a single `component` string in wire format. We
+    /// split those using the message's own separators so they load exactly as if we had parsed
+    /// the original message.
+    ///
+    /// Older messages also lack the `segment_order` list. For those, we cannot know the original
+    /// order, so we place MSH first followed by the remaining segment groups sorted by segment id.
+    ///
+    /// Custom segment ids come from the message's own `segment_ids` table. Messages serialized
+    /// before the table existed get their custom ids assigned from the segment names.
+    ///
+    #[derive(Deserialize)]
+    struct V2MessageRepr {
+        separators: V2ParserCharacters,
+        segment_groups: AHashMap<u8, Vec<V2SegmentRepr>>,
+        #[serde(default)]
+        segment_order: SegmentOrder,
+        #[serde(default)]
+        segment_ids: V2SegmentIds,
+    }
+
+    ///
+    /// Raw segment along with where it starts in the original message. In lenient mode, `text`
+    /// has the stray characters stripped and `stray` lists where they were in `raw`.
+    ///
+    struct V2LocatedSegment<'a> {
+        offset: usize,
+        raw: &'a str,
+        text: Cow<'a, str>,
+        stray: Vec<usize>,
+    }
+
+    #[derive(Deserialize)]
+    struct V2SegmentRepr {
+        name: RUMString,
+        description: RUMString,
+        fields: Vec<Vec<V2FieldRepr>>,
+    }
+
+    #[derive(Deserialize)]
+    struct V2FieldRepr {
+        components: Vec<V2ComponentRepr>,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum V2ComponentRepr {
+        Current { subcomponents: SubComponentList },
+        Legacy { component: V2String },
+    }
+
+    impl V2ComponentRepr {
+        fn into_component(self, parser_chars: &V2ParserCharacters, raw: bool) -> V2Component {
+            match self {
+                V2ComponentRepr::Current { subcomponents } => V2Component::from_subcomponents(
+                    subcomponents,
+                    &parser_chars.subcomponent_separator,
+                ),
+                V2ComponentRepr::Legacy { component } => match raw {
+                    true => V2Component::with_raw_str(&component),
+                    false => V2Component::from_str(&component, parser_chars),
+                },
+            }
+        }
+    }
+
+    impl V2SegmentRepr {
+        fn into_segment(self, parser_chars: &V2ParserCharacters) -> V2Segment {
+            let is_header = is_header_segment_name(&self.name);
+            let mut fields = V2FieldList::with_capacity(self.fields.len());
+            for (i, field_group) in self.fields.into_iter().enumerate() {
+                let mut group = V2FieldGroup::with_capacity(field_group.len());
+                for field in field_group {
+                    let mut components = ComponentList::with_capacity(field.components.len());
+                    for component in field.components {
+                        components
+                            .push(component.into_component(parser_chars, is_header && i == 0));
+                    }
+                    group.push(V2Field { components });
+                }
+                fields.push(group);
+            }
+            V2Segment {
+                name: self.name,
+                description: self.description,
+                fields,
+            }
+        }
+    }
+
+    impl TryFrom<V2MessageRepr> for V2Message {
+        type Error = V2String;
+        fn try_from(repr: V2MessageRepr) -> V2Result<Self> {
+            // Rebuild the id table from the segment names so the serialized ids cannot disagree
+            // with the segments they point at. Names listed in the serialized table keep their
+            // ids. Anything else is remapped.
+            let mut segment_ids = V2SegmentIds::new();
+            for name in repr.segment_ids.custom_names() {
+                segment_ids.get_or_assign(name, V2ParseMode::Lenient)?;
+            }
+            let mut repr_groups: Vec<(u8, Vec<V2SegmentRepr>)> =
+                repr.segment_groups.into_iter().collect();
+            repr_groups.sort_by_key(|(key, _)| *key);
+            let mut segment_groups = SegmentMap::with_capacity(repr_groups.len());
+            let mut key_map: AHashMap<u8, u8> = AHashMap::with_capacity(repr_groups.len());
+            for (key, group) in repr_groups {
+                let new_key = match group.first() {
+                    Some(segment) => {
+                        segment_ids.get_or_assign(&segment.name, V2ParseMode::Lenient)?
+                    }
+                    None => continue,
+                };
+                let mut segments = V2SegmentGroup::with_capacity(group.len());
+                for segment in group {
+                    segments.push(segment.into_segment(&repr.separators));
+                }
+                key_map.insert(key, new_key);
+                segment_groups.insert(new_key, segments);
+            }
+
+            let segment_order = match repr.segment_order.is_empty() {
+                true => V2Message::default_segment_order(&segment_groups),
+                false => repr
+                    .segment_order
+                    .iter()
+                    .map(|(key, indx)| match key_map.get(key) {
+                        Some(new_key) => (*new_key, *indx),
+                        None => (*key, *indx),
+                    })
+                    .collect(),
+            };
+            V2Message::validate_segment_order(&segment_groups, &segment_order)?;
+
+            // Messages serialized before the truncation_declared flag existed only tell us
+            // through MSH-2.
+            let mut separators = repr.separators;
+            let declared = segment_groups
+                .get(&V2_SEGMENT_IDS[V2_MSHEADER_PATTERN])
+                .and_then(|group| group.first())
+                .and_then(|msh| msh.get(1).ok())
+                .and_then(|field_group| field_group.first())
+                .and_then(|field| field.get(1).ok())
+                .is_some_and(|encoding_chars| encoding_chars.as_str().chars().count() > 4);
+            separators.truncation_declared |= declared;
+
+            Ok(V2Message {
+                separators,
+                segment_groups,
+                segment_order,
+                segment_ids,
+            })
+        }
     }
 
     impl V2Message {
@@ -439,27 +1230,328 @@ pub mod v2_parser {
             Self::try_from_str(raw_msg).expect("If calls to from_str are failing for V2Message, consider using try_from_str or the TryFrom trait! You should not see this message.")
         }
         pub fn try_from_str(raw_msg: &str) -> V2Result<Self> {
-            let clean_msg = V2Message::sanitize(raw_msg);
-            let segment_tokens = V2Message::tokenize_segments(clean_msg.as_str());
-            let msh_segment = V2Message::find_msh(&segment_tokens)?;
-            let parse_characters = V2ParserCharacters::from_msh(msh_segment)?;
-            let segments = V2Message::extract_segments(&segment_tokens, &parse_characters)?;
+            Self::try_from_str_with_mode(raw_msg, V2ParseMode::Strict)
+        }
 
-            Ok(V2Message {
-                separators: parse_characters,
-                segment_groups: segments,
-            })
+        ///
+        /// Parses a message. Use [V2ParseMode::Lenient] to accept segments that are not part of
+        /// the standard and to recover from common defects. See
+        /// [V2Message::try_from_str_with_diagnostics] if you need to know what was repaired.
+        ///
+        pub fn try_from_str_with_mode(raw_msg: &str, mode: V2ParseMode) -> V2Result<Self> {
+            match V2Message::try_from_str_with_diagnostics(raw_msg, mode) {
+                Ok((message, _)) => Ok(message),
+                Err(diagnostic) => Err(format_compact!("{}", diagnostic)),
+            }
         }
 
+        ///
+        /// Parses a message and reports each defect found along the way with its location in the
+        /// raw input.
+        ///
+        /// In [V2ParseMode::Strict], the first defect we cannot live with aborts parsing and is
+        /// returned as the error. In [V2ParseMode::Lenient], the parser recovers from the common
+        /// defects we see in production feeds and returns the message alongside the diagnostics.
+        ///
+        /// - Missing or malformed encoding characters in MSH are filled in with the defaults.
+        /// - Stray control characters (MLLP framing, NUL, byte order mark) are removed.
+        /// - Unknown segments are kept. Segments with a malformed id are dropped.
+        ///
+        /// Segments terminated by <LF> or <CR><LF> are accepted in both modes but reported.
+        ///
+        /// ## Per Figure 2-1
+        ///
+        /// ```text
+        /// Segment Terminator | <cr> | hex 0D | Terminates a segment record. This value cannot be
+        /// changed by implementers.
+        /// ```
+        ///
+        pub fn try_from_str_with_diagnostics(
+            raw_msg: &str,
+            mode: V2ParseMode,
+        ) -> Result<(Self, V2Diagnostics), V2Diagnostic> {
+            let lenient = mode == V2ParseMode::Lenient;
+            let mut diagnostics = V2Diagnostics::new();
+            if let Some(offset) = raw_msg.find('\n') {
+                diagnostics.push(V2Diagnostic::new(
+                    V2DiagnosticSeverity::Warning,
+                    offset,
+                    "Segments should be terminated by <CR>! Found <LF> instead.",
+                ));
+            }
+
+            let mut tokens: Vec<V2LocatedSegment> = V2Message::tokenize_located_segments(raw_msg)
+                .into_iter()
+                .map(|(offset, raw)| {
+                    let (text, stray) = match lenient {
+                        true => strip_stray_characters(raw),
+                        false => (Cow::Borrowed(raw), Vec::new()),
+                    };
+                    V2LocatedSegment {
+                        offset,
+                        raw,
+                        text,
+                        stray,
+                    }
+                })
+                .collect();
+
+            let msh_indx = match tokens
+                .iter()
+                .position(|token| token.text.starts_with(V2_MSHEADER_PATTERN))
+            {
+                Some(indx) => indx,
+                None => {
+                    return Err(V2Diagnostic::new(
+                        V2DiagnosticSeverity::Error,
+                        0,
+                        "No MSH segment found! The message is malformed or incomplete!",
+                    ))
+                }
+            };
+            let msh_token = &tokens[msh_indx];
+            let header_result = match msh_token.text.len() > V2_MSHEADER_PATTERN.len() {
+                true => V2ParserCharacters::from_msh(&msh_token.text),
+                false => Err("MSH segment is missing its encoding characters!".to_rumstring()),
+            };
+            let parse_characters = match header_result {
+                Ok(parse_characters) => parse_characters,
+                Err(e) => {
+                    let header_start = msh_token.raw.find(V2_MSHEADER_PATTERN).unwrap_or(0);
+                    let encoding_offset =
+                        (msh_token.offset + header_start + V2_MSHEADER_PATTERN.len() + 1)
+                            .min(raw_msg.len());
+                    let diagnostic =
+                        V2Diagnostic::new(V2DiagnosticSeverity::Error, encoding_offset, &e)
+                            .at_segment(msh_indx + 1, V2_MSHEADER_PATTERN)
+                            .at_field(1, None);
+                    if !lenient {
+                        return Err(diagnostic);
+                    }
+                    let (parse_characters, repaired_msh) = match repair_msh(&msh_token.text) {
+                        Ok(repaired) => repaired,
+                        Err(_) => return Err(diagnostic),
+                    };
+                    diagnostics.push(V2Diagnostic {
+                        severity: V2DiagnosticSeverity::Warning,
+                        message: format_compact!(
+                            "{} Using encoding characters {}{}{}{} instead.",
+                            &e,
+                            &parse_characters.component_separator,
+                            &parse_characters.repetition_separator,
+                            &parse_characters.escape_character,
+                            &parse_characters.subcomponent_separator
+                        ),
+                        ..diagnostic
+                    });
+                    tokens[msh_indx].text = Cow::Owned(repaired_msh.to_string());
+                    parse_characters
+                }
+            };
+
+            let mut segments = SegmentMap::new();
+            let mut segment_order = SegmentOrder::with_capacity(tokens.len());
+            let mut segment_ids = V2SegmentIds::new();
+            for (indx, token) in tokens.iter().enumerate() {
+                let position = indx + 1;
+                let segment_name = token.text.get(0..3).unwrap_or(&token.text);
+                for stray in &token.stray {
+                    let (field, component) = locate_position(token.raw, *stray, &parse_characters);
+                    let stray_char = token.raw[*stray..].chars().next().unwrap_or_default();
+                    diagnostics.push(
+                        V2Diagnostic::new(
+                            V2DiagnosticSeverity::Warning,
+                            token.offset + stray,
+                            &format_compact!("Removed stray character {:?}!", stray_char),
+                        )
+                        .at_segment(position, segment_name)
+                        .at_field(field, component),
+                    );
+                }
+                if token.text.is_empty() {
+                    continue;
+                }
+
+                let segment = match V2Segment::from_str(&token.text, &parse_characters) {
+                    Ok(segment) => segment,
+                    Err(e) => {
+                        return Err(V2Diagnostic::new(
+                            V2DiagnosticSeverity::Error,
+                            token.offset,
+                            &e,
+                        )
+                        .at_segment(position, segment_name))
+                    }
+                };
+                match segment_ids.get_or_assign(&segment.name, mode) {
+                    Ok(key) => {
+                        if lenient
+                            && !V2_SEGMENT_IDS.contains_key(segment.name.as_str())
+                            && !is_custom_segment_name(&segment.name)
+                        {
+                            diagnostics.push(
+                                V2Diagnostic::new(
+                                    V2DiagnosticSeverity::Warning,
+                                    token.offset,
+                                    &format_compact!(
+                                        "Unknown segment {} kept as is!",
+                                        &segment.name
+                                    ),
+                                )
+                                .at_segment(position, &segment.name)
+                                .at_field(0, None),
+                            );
+                        }
+                        let segment_group = segments.entry(key).or_default();
+                        segment_order.push((key, segment_group.len()));
+                        segment_group.push(segment);
+                    }
+                    Err(e) => {
+                        let diagnostic =
+                            V2Diagnostic::new(V2DiagnosticSeverity::Error, token.offset, &e)
+                                .at_segment(position, &segment.name)
+                                .at_field(0, None);
+                        if !lenient {
+                            return Err(diagnostic);
+                        }
+                        diagnostics.push(V2Diagnostic {
+                            message: format_compact!("{} Segment dropped.", &e),
+                            ..diagnostic
+                        });
+                    }
+                }
+            }
+
+            Ok((
+                V2Message::from_parts(parse_characters, segments, segment_order, segment_ids),
+                diagnostics,
+            ))
+        }
+
+        pub(crate) fn from_parts(
+            separators: V2ParserCharacters,
+            segment_groups: SegmentMap,
+            segment_order: SegmentOrder,
+            segment_ids: V2SegmentIds,
+        ) -> Self {
+            V2Message {
+                separators,
+                segment_groups,
+                segment_order,
+                segment_ids,
+            }
+        }
+
+        ///
+        /// Generates the message string. Segments are emitted in the same order they had in the
+        /// original message.
+        ///
         pub fn to_string(&self) -> V2String {
-            let mut msg: Vec<V2String> = Vec::with_capacity(self.segment_groups.len());
-            for segment_key in self.segment_groups.keys() {
-                let segment_group = &self.segment_groups[segment_key];
-                for segment in segment_group {
-                    msg.push(segment.to_string(&self.separators));
+            self.generate(&self.separators)
+        }
+
+        ///
+        /// Generates the message with a different set of encoding characters and segment
+        /// terminator, e.g. the classic `|^~\&` for receivers that cannot handle anything else.
+        /// MSH-1 and MSH-2 are rewritten, and data colliding with the new delimiters is escaped.
+        ///
+        pub fn to_string_with(&self, separators: &V2ParserCharacters) -> V2Result<V2String> {
+            separators.validate()?;
+            Ok(self.generate(separators))
+        }
+
+        fn generate(&self, target: &V2ParserCharacters) -> V2String {
+            let mut msg: Vec<V2String> = Vec::with_capacity(self.segment_order.len());
+            for segment in self.segments() {
+                msg.push(segment.to_string_with(&self.separators, target));
+            }
+            msg.join_compact(target.segment_terminator.as_str())
+        }
+
+        ///
+        /// Switches the message to a different set of encoding characters. Every stored value
+        /// is rewritten as in [V2Message::to_string_with], so later generation uses the new set.
+        ///
+        pub fn set_separators(&mut self, separators: V2ParserCharacters) -> V2Result<()> {
+            separators.validate()?;
+            for segment in self.segment_groups.values_mut().flatten() {
+                let is_header = is_header_segment_name(&segment.name);
+                for (i, field_group) in segment.fields.iter_mut().enumerate() {
+                    if i == 0 && is_header {
+                        field_group[0].components[0] =
+                            V2Component::with_raw_str(&separators.encoding_characters());
+                        continue;
+                    }
+                    for field in field_group.iter_mut() {
+                        for component in field.components.iter_mut() {
+                            for subcomponent in component.subcomponents.iter_mut() {
+                                let raw = self
+                                    .separators
+                                    .redelimit(subcomponent.as_str(), &separators);
+                                subcomponent.set(&raw);
+                            }
+                        }
+                    }
                 }
             }
-            msg.join_compact(self.separators.segment_terminator.as_str())
+            self.separators = separators;
+            Ok(())
+        }
+
+        ///
+        /// Iterates through every segment in the message in their original order.
+        ///
+        pub fn segments(&self) -> impl Iterator<Item = &V2Segment> {
+            self.segment_order
+                .iter()
+                .map(|(key, indx)| &self.segment_groups[key][*indx])
+        }
+
+        ///
+        /// Returns the list of segment positions in their original order. Each entry can be
+        /// resolved with [V2Message::get] using the segment id and the position + 1.
+        ///
+        pub fn segment_order(&self) -> &SegmentOrder {
+            &self.segment_order
+        }
+
+        pub fn separators(&self) -> &V2ParserCharacters {
+            &self.separators
+        }
+
+        ///
+        /// Ids of the segments in this message. Custom segments only have an id in the messages
+        /// that contain them.
+        ///
+        pub fn segment_ids(&self) -> &V2SegmentIds {
+            &self.segment_ids
+        }
+
+        ///
+        /// Looks up the id of a segment in this message. Never assigns a new id.
+        ///
+        pub fn segment_id(&self, name: &str) -> Option<u8> {
+            self.segment_ids.find(name)
+        }
+
+        ///
+        /// Resolves the segment id of a search index against this message. See
+        /// [V2SegmentIds::find_indexed].
+        ///
+        pub fn index_segment_id(&self, index: &V2SearchIndex) -> u8 {
+            self.segment_ids.find_indexed(index)
+        }
+
+        ///
+        /// Returns the segment addressed by a search index, e.g. the one compiled from `ZPI(2)`.
+        ///
+        pub fn get_indexed(&self, index: &V2SearchIndex) -> V2Result<&V2Segment> {
+            self.get(&self.index_segment_id(index), index.segment_group as usize)
+        }
+
+        pub fn get_indexed_mut(&mut self, index: &V2SearchIndex) -> V2Result<&mut V2Segment> {
+            let segment_id = self.index_segment_id(index);
+            self.get_mut(&segment_id, index.segment_group as usize)
         }
 
         pub fn len(&self) -> usize {
@@ -520,14 +1612,225 @@ pub mod v2_parser {
             }
         }
 
+        ///
+        /// Resolves a search pattern to the component it addresses. Omitted indices default
+        /// to 1. A subcomponent in the pattern is ignored, so `PID3.4` and `PID3.4.2` both yield
+        /// the whole assigning authority in PID-3. See [V2Message::find_subcomponent].
+        ///
+        /// Query expressions (see [V2Query](crate::hl7_v2_query::v2_query::V2Query)) are accepted
+        /// too. The first match is returned.
+        ///
         pub fn find_component(&self, search_pattern: &RUMString) -> V2Result<&V2Component> {
-            let index = rumtk_cache_fetch!(&mut search_cache, search_pattern, compile_search_index);
-            let segment = self.get(&index.segment, index.segment_group as usize)?;
-            let field = match segment.get((index.field) as isize)?.get((index.field_group - 1) as usize) {
-                Some(field) => field,
-                None => return Err(format_compact!("Subfield provided is not 1 indexed or out of bounds. Did you give us a 0 when you meant 1? Got {}!", index.field_group))
-            };
-            field.get(index.component as isize)
+            if let V2_SEARCH_EXPR_TYPE::V2_QUERY = V2SearchIndex::expr_type(search_pattern) {
+                return match self.query(search_pattern)?.next() {
+                    Some(found) => Ok(found.component),
+                    None => Err(format_compact!(
+                        "No component matches query {}!",
+                        search_pattern
+                    )),
+                };
+            }
+            let index = compile_search_pattern(search_pattern);
+            let segment = self.get_indexed(index)?;
+            segment.find_component(index)
+        }
+
+        ///
+        /// Resolves a search pattern down to the subcomponent it addresses. Omitted indices
+        /// default to 1, so `PID5.4` yields the first subcomponent of PID-5.4 while `PID3.4.2`
+        /// yields the universal ID of the assigning authority in PID-3.
+        ///
+        /// Query expressions are accepted too. The first match is returned.
+        ///
+        pub fn find_subcomponent(&self, search_pattern: &RUMString) -> V2Result<&V2SubComponent> {
+            if let V2_SEARCH_EXPR_TYPE::V2_QUERY = V2SearchIndex::expr_type(search_pattern) {
+                return match self.query(search_pattern)?.next() {
+                    Some(found) => Ok(found.value),
+                    None => Err(format_compact!(
+                        "No subcomponent matches query {}!",
+                        search_pattern
+                    )),
+                };
+            }
+            let index = compile_search_pattern(search_pattern);
+            let segment = self.get_indexed(index)?;
+            segment.find_subcomponent(index)
+        }
+
+        ///
+        /// Age of the patient in whole years at `reference`, computed from the date of birth in
+        /// PID-7. See [V2DateTime::age_at].
+        ///
+        pub fn patient_age(&self, reference: &V2DateTime) -> V2Result<u32> {
+            self.find_component(&RUMString::from("PID7"))?
+                .to_v2datetime()?
+                .age_at(reference)
+        }
+
+        ///
+        /// Sets the subcomponent addressed by a search pattern, e.g. `PID5[1].2`. The value is
+        /// plain text and is escaped per Section 2.7.1. Fields, repetitions, components, and
+        /// subcomponents are added as needed. The segment itself must exist.
+        ///
+        /// Setting a value of `""` ([V2_DELETE_FIELD]) sends the delete indicator at that
+        /// position. See [V2Message::delete_field] to flag a whole field.
+        ///
+        pub fn set(&mut self, search_pattern: &str, value: &str) -> V2Result<()> {
+            let index = compile_search_pattern(search_pattern);
+            let separators = self.separators.clone();
+            self.get_indexed_mut(index)?
+                .find_subcomponent_or_insert_mut(index, &separators)?
+                .set_decoded(value, &separators);
+            Ok(())
+        }
+
+        ///
+        /// Appends a repetition to the field addressed by a search pattern (e.g. `PID13`). The
+        /// repetition part of the pattern is ignored. Returns the position (1-indexed) of the new
+        /// repetition so it can be addressed as `PID13[n]`.
+        ///
+        pub fn add_repetition(&mut self, search_pattern: &str, value: &str) -> V2Result<usize> {
+            let index = compile_search_pattern(search_pattern);
+            let separators = self.separators.clone();
+            let segment = self.get_indexed_mut(index)?;
+            let field_group = segment.get_writable_field_mut(index.field as isize)?;
+            let only_empty = field_group.len() == 1 && field_group[0].is_empty();
+            if !only_empty {
+                field_group.push(V2Field::with_raw_str(V2_EMPTY_STRING));
+            }
+            let repetition = field_group.len();
+            field_group[repetition - 1]
+                .get_or_insert_mut(1)?
+                .get_or_insert_mut(1)?
+                .set_decoded(value, &separators);
+            Ok(repetition)
+        }
+
+        ///
+        /// Empties the field addressed by a search pattern, dropping all of its repetitions. The
+        /// field will not be populated when the message is generated.
+        ///
+        pub fn clear_field(&mut self, search_pattern: &str) -> V2Result<()> {
+            self.replace_field(search_pattern, V2_EMPTY_STRING)
+        }
+
+        ///
+        /// Replaces the field addressed by a search pattern with the delete indicator.
+        ///
+        /// ## Per Section 2.5.3.1
+        ///
+        /// ```text
+        /// Populated with Delete Indicator: Any existing value for the corresponding data base
+        /// element in the receiving application SHOULD be deleted. This is symbolically
+        /// communicated as two double-quotes between the delimiters (i.e., |""|).
+        /// ```
+        ///
+        pub fn delete_field(&mut self, search_pattern: &str) -> V2Result<()> {
+            self.replace_field(search_pattern, V2_DELETE_FIELD)
+        }
+
+        ///
+        /// Parses a raw segment using this message's separators and inserts it right after the
+        /// segment addressed by a search pattern (e.g. `OBX(2)`). Only the segment portion of the
+        /// pattern is used. Like [V2Message::try_from_str], only standard segments and Z-segments
+        /// are accepted. See [V2Message::insert_segment_after_with_mode].
+        ///
+        pub fn insert_segment_after(
+            &mut self,
+            search_pattern: &str,
+            raw_segment: &str,
+        ) -> V2Result<()> {
+            self.insert_segment_after_with_mode(search_pattern, raw_segment, V2ParseMode::Strict)
+        }
+
+        ///
+        /// Same as [V2Message::insert_segment_after] but accepts the segments `mode` accepts when
+        /// parsing, so [V2ParseMode::Lenient] can insert segments that are not part of the
+        /// standard into a message parsed with it.
+        ///
+        pub fn insert_segment_after_with_mode(
+            &mut self,
+            search_pattern: &str,
+            raw_segment: &str,
+            mode: V2ParseMode,
+        ) -> V2Result<()> {
+            let index = compile_search_pattern(search_pattern);
+            let anchor =
+                self.order_position(&self.index_segment_id(index), index.segment_group as usize)?;
+            let segment = V2Segment::from_str(raw_segment, &self.separators)?;
+            if segment.name == V2_MSHEADER_PATTERN {
+                return Err("A message can only have one MSH segment!".to_rumstring());
+            }
+            let key = self.segment_ids.get_or_assign(&segment.name, mode)?;
+
+            // Keep each segment group in message order so SEG(n) still means the nth one.
+            let group_indx = self.segment_order[..=anchor]
+                .iter()
+                .filter(|(k, _)| *k == key)
+                .count();
+            for (k, indx) in self.segment_order.iter_mut() {
+                if *k == key && *indx >= group_indx {
+                    *indx += 1;
+                }
+            }
+            self.segment_groups
+                .entry(key)
+                .or_default()
+                .insert(group_indx, segment);
+            self.segment_order.insert(anchor + 1, (key, group_indx));
+            Ok(())
+        }
+
+        ///
+        /// Removes the segment addressed by a search pattern (e.g. `NTE(2)`) and returns it. The
+        /// MSH segment cannot be removed.
+        ///
+        pub fn remove_segment(&mut self, search_pattern: &str) -> V2Result<V2Segment> {
+            let index = compile_search_pattern(search_pattern);
+            let key = self.index_segment_id(index);
+            if key == V2_SEGMENT_IDS[V2_MSHEADER_PATTERN] {
+                return Err("The MSH segment cannot be removed from a message!".to_rumstring());
+            }
+            let position = self.order_position(&key, index.segment_group as usize)?;
+            let (_, group_indx) = self.segment_order.remove(position);
+            for (k, indx) in self.segment_order.iter_mut() {
+                if *k == key && *indx > group_indx {
+                    *indx -= 1;
+                }
+            }
+            let segment_group = self.get_mut_group(&key)?;
+            let segment = segment_group.remove(group_indx);
+            if segment_group.is_empty() {
+                self.segment_groups.remove(&key);
+            }
+            Ok(segment)
+        }
+
+        fn replace_field(&mut self, search_pattern: &str, value: &str) -> V2Result<()> {
+            let index = compile_search_pattern(search_pattern);
+            let segment = self.get_indexed_mut(index)?;
+            let field_group = segment.get_writable_field_mut(index.field as isize)?;
+            *field_group = vec![V2Field::with_raw_str(value)];
+            Ok(())
+        }
+
+        ///
+        /// Position in [V2Message::segment_order] of the nth (1-indexed) segment with this id.
+        ///
+        fn order_position(&self, segment_index: &u8, sub_segment: usize) -> V2Result<usize> {
+            let group_indx = sub_segment.wrapping_sub(1);
+            match self
+                .segment_order
+                .iter()
+                .position(|position| *position == (*segment_index, group_indx))
+            {
+                Some(position) => Ok(position),
+                None => Err(format_compact!(
+                    "Subsegment {} was not found in segment group {}!",
+                    sub_segment,
+                    segment_index
+                )),
+            }
         }
 
         pub fn is_repeat_segment(&self, segment_index: &u8) -> bool {
@@ -569,11 +1872,44 @@ pub mod v2_parser {
             trimmed_tokens
         }
 
+        ///
+        /// Same as [V2Message::tokenize_segments] but works on the unsanitized message and pairs
+        /// each segment with its byte offset in the message. Empty lines are skipped.
+        ///
+        pub fn tokenize_located_segments(raw_message: &str) -> Vec<(usize, &str)> {
+            let message_start = raw_message.as_ptr() as usize;
+            raw_message
+                .split(['\r', '\n'])
+                .map(|token| token.trim())
+                .filter(|token| !token.is_empty())
+                .map(|token| (token.as_ptr() as usize - message_start, token))
+                .collect()
+        }
+
         pub fn extract_segments(
             raw_segments: &Vec<&str>,
             parser_chars: &V2ParserCharacters,
         ) -> V2Result<SegmentMap> {
+            let (segments, _, _) = V2Message::extract_ordered_segments(
+                raw_segments,
+                parser_chars,
+                V2ParseMode::Strict,
+            )?;
+            Ok(segments)
+        }
+
+        ///
+        /// Same as [V2Message::extract_segments] but also returns the order in which each segment
+        /// was found and the ids assigned to custom segments.
+        ///
+        pub fn extract_ordered_segments(
+            raw_segments: &Vec<&str>,
+            parser_chars: &V2ParserCharacters,
+            mode: V2ParseMode,
+        ) -> V2Result<(SegmentMap, SegmentOrder, V2SegmentIds)> {
             let mut segments: SegmentMap = SegmentMap::new();
+            let mut segment_order = SegmentOrder::with_capacity(raw_segments.len());
+            let mut segment_ids = V2SegmentIds::new();
 
             for segment_str in raw_segments {
                 if segment_str.is_empty() {
@@ -582,17 +1918,59 @@ pub mod v2_parser {
 
                 let segment: V2Segment = V2Segment::from_str(segment_str, parser_chars)?;
 
-                let key = match V2_SEGMENT_IDS.get(&segment.name) {
-                    Some(k) => k,
-                    None => return Err(format_compact!("Segment name is not a valid segment!")),
-                };
-                if !segments.contains_key(key) {
-                    segments.insert(*key, V2SegmentGroup::new());
+                let key = segment_ids.get_or_assign(&segment.name, mode)?;
+                if !segments.contains_key(&key) {
+                    segments.insert(key, V2SegmentGroup::new());
                 }
-                segments.get_mut(key).unwrap().push(segment);
+                let segment_group = segments.get_mut(&key).unwrap();
+                segment_order.push((key, segment_group.len()));
+                segment_group.push(segment);
             }
 
-            Ok(segments)
+            Ok((segments, segment_order, segment_ids))
+        }
+
+        fn default_segment_order(segment_groups: &SegmentMap) -> SegmentOrder {
+            let msh_key = V2_SEGMENT_IDS[V2_MSHEADER_PATTERN];
+            let mut keys: Vec<&u8> = segment_groups.keys().collect();
+            keys.sort_by_key(|key| (**key != msh_key, **key));
+            let mut segment_order = SegmentOrder::new();
+            for key in keys {
+                for indx in 0..segment_groups[key].len() {
+                    segment_order.push((*key, indx));
+                }
+            }
+            segment_order
+        }
+
+        fn validate_segment_order(
+            segment_groups: &SegmentMap,
+            segment_order: &SegmentOrder,
+        ) -> V2Result<()> {
+            let segment_count: usize = segment_groups.values().map(|group| group.len()).sum();
+            let mut seen: AHashMap<V2SegmentPosition, bool> =
+                AHashMap::with_capacity(segment_order.len());
+            for position in segment_order {
+                let (key, indx) = position;
+                let exists = match segment_groups.get(key) {
+                    Some(group) => *indx < group.len(),
+                    None => false,
+                };
+                if !exists || seen.insert(*position, true).is_some() {
+                    return Err(format_compact!(
+                        "Segment order entry {:?} does not match the segments in message!",
+                        position
+                    ));
+                }
+            }
+            if seen.len() != segment_count {
+                return Err(format_compact!(
+                    "Segment order lists {} segments but the message has {} segments!",
+                    seen.len(),
+                    segment_count
+                ));
+            }
+            Ok(())
         }
     }
 
@@ -640,7 +2018,7 @@ pub mod v2_parser {
     impl TryFrom<&[u8]> for V2Message {
         type Error = V2String;
         fn try_from(input: &[u8]) -> V2Result<Self> {
-            V2Message::try_from_str(try_decode_with(input, "ascii").as_str())
+            V2Message::from_bytes(input)
         }
     }
 }
@@ -674,15 +2052,16 @@ pub mod v2_parser_interface {
     /// Simple interface for searching for a component inside a V2Message.
     /// This macro takes a borrow of a V2Message instance and a string search pattern.
     /// The only search pattern supported at the moment takes the form
-    /// **<3-letter segment>(optional, segment_group)<field>\[optional, field_group\].<component>**.
-    /// For example, you can search with **PID5.1** or **PID(1)5.1** or **PID(1)5\[1\].1**.
+    /// **<3-letter segment>(optional, segment_group)<field>\[optional, field_group\].<component>.<optional, subcomponent>**.
+    /// For example, you can search with **PID5.1** or **PID(1)5.1** or **PID(1)5\[1\].1** or
+    /// **PID3.4.2**.
     ///
     /// The optional portions are for when you need to select a specific repeated segment or field.
     ///
     /// All of these indices must be 1-indexed.
     ///
     /// For the main indices, you can use negative values. For example, a -1 means you want to select
-    /// the last item. This is applicable for the field and component indices.
+    /// the last item. This is applicable for the field, component, and subcomponent indices.
     ///
     /// ## Example
     ///
@@ -703,6 +2082,28 @@ pub mod v2_parser_interface {
         }};
     }
 
+    ///
+    /// Same as [rumtk_v2_find_component] but yields the subcomponent addressed by the pattern,
+    /// e.g. **PID3.4.2**.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    ///     use rumtk_hl7_v2::{rumtk_v2_parse_message, rumtk_v2_find_subcomponent};
+    ///     let hl7_v2_message = "MSH|^~\\&|NISTEHRAPP|NISTEHRFAC|NISTIISAPP|NISTIISFAC|20150625072816.601-0500||VXU^V04^VXU_V04|NIST-IZ-AD-10.1_Send_V04_Z22|P|2.5.1|||ER|AL|||||Z22^CDCPHINVS|NISTEHRFAC|NISTIISFAC\rPID|1||PATID14567^^^NIST MPI&2.16.840.1.113883.3.72.5.30.2&ISO^MR\n";
+    ///     let message = rumtk_v2_parse_message!(&hl7_v2_message).unwrap();
+    ///     let subcomponent = rumtk_v2_find_subcomponent!(message, "PID3.4.2").unwrap();
+    ///     assert_eq!(subcomponent.to_string(), "2.16.840.1.113883.3.72.5.30.2");
+    /// ```
+    ///
+    #[macro_export]
+    macro_rules! rumtk_v2_find_subcomponent {
+        ( $v2_msg:expr, $v2_search_pattern:expr ) => {{
+            use rumtk_core::strings::RUMString;
+            $v2_msg.find_subcomponent(&RUMString::from($v2_search_pattern))
+        }};
+    }
+
     ///
     /// Macro for generating V2 string message out of an instance of [hl7_v2_parser::v2_parser::V2Message].
     /// Basically, this is the opposite operation to [crate::rumtk_v2_parse_message].
@@ -0,0 +1,1077 @@
+/*
+ * rumtk attempts to implement HL7 and medical protocols for interoperability in medicine.
+ * This toolkit aims to be reliable, simple, performant, and standards compliant.
+ * Copyright (C) 2024  Luis M. Santos, M.D.
+ *
+ * This library is free software; you can redistribute it and/or
+ * modify it under the terms of the GNU Lesser General Public
+ * License as published by the Free Software Foundation; either
+ * version 2.1 of the License, or (at your option) any later version.
+ *
+ * This library is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+ * Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public
+ * License along with this library; if not, write to the Free Software
+ * Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301  USA
+ */
+
+///
+/// Conformance profiles and the validator that checks messages against them.
+///
+/// ## Per Section 2.12.6
+/// ```text
+/// An HL7 message profile is an unambiguous specification of one or more standard HL7 messages
+/// that have been analyzed for a particular use case. It prescribes a set of precise constraints
+/// upon one or more standard HL7 messages.
+/// ```
+///
+/// Profiles are loaded from the HL7 message profile XML (`HL7v2xConformanceProfile`) or from the
+/// equivalent JSON form of [V2Profile]. Fields, components, and subcomponents are listed in order,
+/// so their position in the list is their sequence.
+///
+pub mod v2_profiles {
+    use crate::hl7_v2_base_types::v2_base_types::{
+        is_header_segment_name, V2DiagnosticSeverity, V2Result,
+    };
+    use crate::hl7_v2_base_types::v2_primitives::V2ComponentList;
+    use crate::hl7_v2_complex_types::hl7_v2_complex_types::{
+        cast_field, complex_type_descriptor, V2FieldToString,
+    };
+    use crate::hl7_v2_constants::V2_MSH_MESSAGE_TYPE;
+    use crate::hl7_v2_field_descriptors::v2_field_descriptor::{
+        data_type_from_str, V2ComponentType,
+    };
+    use crate::hl7_v2_optionality_rules::{Optionality, V2ConditionContext};
+    use crate::hl7_v2_parser::v2_parser::{V2Message, V2Segment};
+    use crate::hl7_v2_segment_descriptors::v2_segment_descriptor::seq_to_position;
+    use crate::hl7_v2_tables::v2_tables::{bound_code, is_coded, V2TableRegistry};
+    use crate::hl7_v2_versions::v2_versions::V2Version;
+    use rumtk_core::json::serialization::{Deserialize, Serialize};
+    use rumtk_core::rumtk_deserialize;
+    use rumtk_core::strings::{format_compact, RUMString, RUMStringConversions};
+    use std::fmt::Display;
+
+    /**************************** Types *****************************************/
+
+    ///
+    /// A message profile. The message type, trigger event, and structure are checked against
+    /// MSH-9 when given.
+    ///
+    #[derive(Debug, Default, Clone, Serialize, Deserialize)]
+    pub struct V2Profile {
+        #[serde(default)]
+        pub name: RUMString,
+        #[serde(default)]
+        pub hl7_version: RUMString,
+        #[serde(default)]
+        pub message_type: RUMString,
+        #[serde(default)]
+        pub event: RUMString,
+        #[serde(default)]
+        pub structure: RUMString,
+        #[serde(default)]
+        pub segments: Vec<V2ProfileSegment>,
+    }
+
+    ///
+    /// A segment or, if it lists `segments`, a segment group of a profile. `max` is `"*"` in JSON
+    /// for unbounded repetitions.
+    ///
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct V2ProfileSegment {
+        pub name: RUMString,
+        #[serde(with = "usage")]
+        pub usage: Optionality,
+        #[serde(default)]
+        pub min: u32,
+        #[serde(default = "single", with = "cardinality")]
+        pub max: Option<u32>,
+        #[serde(default)]
+        pub fields: Vec<V2ProfileField>,
+        #[serde(default)]
+        pub segments: Vec<V2ProfileSegment>,
+    }
+
+    ///
+    /// A field of a profile segment. Components and subcomponents use the same definition, where
+    /// `min` and `max` do not apply.
+    ///
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct V2ProfileField {
+        #[serde(default)]
+        pub name: RUMString,
+        #[serde(with = "usage")]
+        pub usage: Optionality,
+        #[serde(default)]
+        pub min: u32,
+        #[serde(default = "single", with = "cardinality")]
+        pub max: Option<u32>,
+        #[serde(default)]
+        pub data_type: Option<RUMString>,
+        #[serde(default)]
+        pub length: Option<u32>,
+        #[serde(default)]
+        pub table: Option<u16>,
+        #[serde(default)]
+        pub components: Vec<V2ProfileField>,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+    pub enum V2ConformanceIssueKind {
+        ///
+        /// Wrong message type or segments the profile does not expect where they were found.
+        ///
+        Structure,
+        Usage,
+        Cardinality,
+        Length,
+        DataType,
+        ValueSet,
+    }
+
+    ///
+    /// A profile violation.
+    ///
+    /// - **segment** => 1-indexed position of the segment in the message. Missing segments and
+    ///   groups have none.
+    /// - **location** => Search pattern of the offending value (e.g. `PID(1)5[2].7`) or, for
+    ///   segments and groups, their path in the profile (e.g. `PATIENT/PV1`).
+    ///
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct V2ConformanceIssue {
+        pub severity: V2DiagnosticSeverity,
+        pub kind: V2ConformanceIssueKind,
+        pub segment: Option<usize>,
+        pub location: RUMString,
+        pub message: RUMString,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct V2ConformanceReport {
+        pub profile: RUMString,
+        pub issues: Vec<V2ConformanceIssue>,
+    }
+
+    ///
+    /// Walks the segments of a message through the segment tree of a profile. Matching is greedy,
+    /// like [V2MessageTree](crate::hl7_v2_message_structures::v2_message_structures::V2MessageTree)
+    /// does for the standard structures.
+    ///
+    struct V2ProfileValidator<'a> {
+        registry: &'a V2TableRegistry,
+        message: &'a V2Message,
+        version: V2Version,
+        segments: Vec<&'a V2Segment>,
+        cursor: usize,
+        issues: Vec<V2ConformanceIssue>,
+    }
+
+    enum V2ProfileNode {
+        Segment(V2ProfileSegment),
+        Field(V2ProfileField),
+    }
+
+    struct V2XmlTag {
+        name: RUMString,
+        attributes: Vec<(RUMString, RUMString)>,
+        closing: bool,
+        self_closing: bool,
+    }
+
+    /**************************** Helpers ***************************************/
+
+    fn single() -> Option<u32> {
+        Some(1)
+    }
+
+    mod usage {
+        use crate::hl7_v2_optionality_rules::Optionality;
+        use rumtk_core::json::serialization::{Deserialize, Deserializer, Serializer};
+        use rumtk_core::strings::RUMString;
+        use serde::de::Error;
+
+        pub fn serialize<S: Serializer>(usage: &Optionality, s: S) -> Result<S::Ok, S::Error> {
+            s.serialize_str(usage.as_str())
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Optionality, D::Error> {
+            let usage = RUMString::deserialize(d)?;
+            Optionality::from_str(&usage).map_err(D::Error::custom)
+        }
+    }
+
+    mod cardinality {
+        use rumtk_core::json::serialization::{Deserialize, Deserializer, Serializer};
+        use serde::de::Error;
+
+        pub fn serialize<S: Serializer>(max: &Option<u32>, s: S) -> Result<S::Ok, S::Error> {
+            match max {
+                Some(max) => s.serialize_u32(*max),
+                None => s.serialize_str("*"),
+            }
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Option<u32>, D::Error> {
+            match serde_json::Value::deserialize(d)? {
+                serde_json::Value::Number(max) => match max.as_u64() {
+                    Some(max) => Ok(Some(max as u32)),
+                    None => Err(D::Error::custom("Cardinality must be a positive number!")),
+                },
+                serde_json::Value::String(max) => super::parse_max(&max).map_err(D::Error::custom),
+                other => Err(D::Error::custom(format!(
+                    "Expected a number or * for cardinality! Got {}",
+                    other
+                ))),
+            }
+        }
+    }
+
+    fn parse_max(max: &str) -> V2Result<Option<u32>> {
+        match max.trim() {
+            "*" => Ok(None),
+            max => match max.parse::<u32>() {
+                Ok(max) => Ok(Some(max)),
+                Err(_) => Err(format_compact!("Invalid cardinality {}!", max)),
+            },
+        }
+    }
+
+    fn parse_number(value: Option<&str>) -> Option<u32> {
+        value.and_then(|value| value.trim().parse::<u32>().ok())
+    }
+
+    ///
+    /// Table number of a `Table` attribute. Accepts `0001` and `HL70001`. Value sets that are not
+    /// numbered tables are not checked.
+    ///
+    fn parse_table(value: Option<&str>) -> Option<u16> {
+        let value = value?.trim();
+        value
+            .strip_prefix("HL7")
+            .unwrap_or(value)
+            .parse::<u16>()
+            .ok()
+    }
+
+    fn is_valued(value: &V2ComponentList) -> bool {
+        value.iter().flatten().any(|piece| !piece.is_empty())
+    }
+
+    ///
+    /// Length of a value as sent, counting one character per separator between the pieces that
+    /// are valued.
+    ///
+    fn value_length(value: &V2ComponentList) -> usize {
+        let component_length = |component: &Vec<&str>| -> usize {
+            let valued = component
+                .iter()
+                .rposition(|piece| !piece.is_empty())
+                .map_or(0, |last| last + 1);
+            component[..valued]
+                .iter()
+                .map(|piece| piece.chars().count())
+                .sum::<usize>()
+                + valued.saturating_sub(1)
+        };
+        let valued = value
+            .iter()
+            .rposition(|component| component_length(component) > 0)
+            .map_or(0, |last| last + 1);
+        value[..valued].iter().map(component_length).sum::<usize>() + valued.saturating_sub(1)
+    }
+
+    fn profile_data_type(data_type: &Option<RUMString>) -> Option<V2ComponentType> {
+        data_type
+            .as_ref()
+            .and_then(|data_type| data_type_from_str(data_type))
+    }
+
+    ///
+    /// Profiles state their conditions in prose, so a conditional element is evaluated with the
+    /// predicate of its standard definition when that one is conditional too. Other elements keep
+    /// the usage of the profile, which leaves conditional ones undeclared.
+    ///
+    fn conditional_usage(
+        usage: &Optionality,
+        standard: Option<Optionality>,
+        context: &V2ConditionContext,
+    ) -> Optionality {
+        match (usage, standard) {
+            (Optionality::C(_), Some(standard @ Optionality::C(_))) => standard.evaluate(context),
+            _ => *usage,
+        }
+    }
+
+    fn unescape_xml(value: &str) -> RUMString {
+        value
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&apos;", "'")
+            .replace("&amp;", "&")
+            .to_rumstring()
+    }
+
+    ///
+    /// Splits an XML document into its element tags. Text, comments, processing instructions,
+    /// and declarations are skipped since profiles keep everything we need in attributes.
+    ///
+    fn parse_xml_tags(xml: &str) -> V2Result<Vec<V2XmlTag>> {
+        let mut tags = Vec::new();
+        let mut rest = xml;
+        while let Some(start) = rest.find('<') {
+            rest = &rest[start..];
+            let skipped_until = [
+                ("<!--", "-->"),
+                ("<![CDATA[", "]]>"),
+                ("<?", "?>"),
+                ("<!", ">"),
+            ]
+            .iter()
+            .find(|(open, _)| rest.starts_with(open))
+            .map(|(_, close)| *close);
+            if let Some(close) = skipped_until {
+                rest = match rest.find(close) {
+                    Some(end) => &rest[end + close.len()..],
+                    None => return Err(format_compact!("Unterminated XML markup {}!", close)),
+                };
+                continue;
+            }
+
+            let mut quote = None;
+            let mut end = None;
+            for (indx, c) in rest.char_indices().skip(1) {
+                match (quote, c) {
+                    (Some(q), c) if c == q => quote = None,
+                    (Some(_), _) => {}
+                    (None, '"' | '\'') => quote = Some(c),
+                    (None, '>') => {
+                        end = Some(indx);
+                        break;
+                    }
+                    _ => {}
+                }
+            }
+            let end = match end {
+                Some(end) => end,
+                None => return Err(format_compact!("Unterminated XML tag!")),
+            };
+            let mut body = rest[1..end].trim();
+            rest = &rest[end + 1..];
+
+            let closing = body.starts_with('/');
+            if closing {
+                body = &body[1..];
+            }
+            let self_closing = body.ends_with('/');
+            if self_closing {
+                body = &body[..body.len() - 1];
+            }
+            let name_end = body.find(char::is_whitespace).unwrap_or(body.len());
+            let mut attributes = Vec::new();
+            let mut attribute_list = body[name_end..].trim_start();
+            while !attribute_list.is_empty() {
+                let equals = match attribute_list.find('=') {
+                    Some(equals) => equals,
+                    None => {
+                        return Err(format_compact!(
+                            "Malformed attributes in XML tag {}!",
+                            &body[..name_end]
+                        ))
+                    }
+                };
+                let key = attribute_list[..equals].trim();
+                let value = attribute_list[equals + 1..].trim_start();
+                let quote = match value.chars().next() {
+                    Some(quote) if quote == '"' || quote == '\'' => quote,
+                    _ => {
+                        return Err(format_compact!(
+                            "Attribute {} of XML tag {} is not quoted!",
+                            key,
+                            &body[..name_end]
+                        ))
+                    }
+                };
+                let close = match value[1..].find(quote) {
+                    Some(close) => close + 1,
+                    None => return Err(format_compact!("Unterminated attribute {}!", key)),
+                };
+                attributes.push((key.to_rumstring(), unescape_xml(&value[1..close])));
+                attribute_list = value[close + 1..].trim_start();
+            }
+            tags.push(V2XmlTag {
+                name: body[..name_end].to_rumstring(),
+                attributes,
+                closing,
+                self_closing,
+            });
+        }
+        Ok(tags)
+    }
+
+    impl V2XmlTag {
+        fn attribute(&self, key: &str) -> Option<&str> {
+            self.attributes
+                .iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, value)| value.as_str())
+        }
+
+        fn usage(&self) -> V2Result<Optionality> {
+            Optionality::from_str(self.attribute("Usage").unwrap_or("O"))
+        }
+
+        fn max(&self) -> V2Result<Option<u32>> {
+            match self.attribute("Max") {
+                Some(max) => parse_max(max),
+                None => Ok(Some(1)),
+            }
+        }
+    }
+
+    fn close_profile_node(
+        stack: &mut Vec<V2ProfileNode>,
+        profile: &mut V2Profile,
+        tag: &str,
+    ) -> V2Result<()> {
+        let node = match stack.pop() {
+            Some(node) => node,
+            None => return Err(format_compact!("Unbalanced closing tag {}!", tag)),
+        };
+        match (stack.last_mut(), node) {
+            (None, V2ProfileNode::Segment(segment)) => profile.segments.push(segment),
+            (Some(V2ProfileNode::Segment(parent)), V2ProfileNode::Segment(segment)) => {
+                parent.segments.push(segment)
+            }
+            (Some(V2ProfileNode::Segment(parent)), V2ProfileNode::Field(field)) => {
+                parent.fields.push(field)
+            }
+            (Some(V2ProfileNode::Field(parent)), V2ProfileNode::Field(component)) => {
+                parent.components.push(component)
+            }
+            _ => return Err(format_compact!("Misplaced XML element {}!", tag)),
+        }
+        Ok(())
+    }
+
+    impl<'a> V2ProfileValidator<'a> {
+        fn push(
+            &mut self,
+            severity: V2DiagnosticSeverity,
+            kind: V2ConformanceIssueKind,
+            segment: Option<usize>,
+            location: &str,
+            message: RUMString,
+        ) {
+            self.issues.push(V2ConformanceIssue {
+                severity,
+                kind,
+                segment,
+                location: location.to_rumstring(),
+                message,
+            });
+        }
+
+        fn error(
+            &mut self,
+            kind: V2ConformanceIssueKind,
+            segment: Option<usize>,
+            location: &str,
+            message: RUMString,
+        ) {
+            self.push(
+                V2DiagnosticSeverity::Error,
+                kind,
+                segment,
+                location,
+                message,
+            )
+        }
+
+        ///
+        /// Flags required items that are missing and unsupported items that are valued. Returns
+        /// false for the latter since nothing else about them is worth reporting. Conditional
+        /// items whose predicate is not declared get a warning saying they were not evaluated.
+        ///
+        fn check_usage(
+            &mut self,
+            usage: &Optionality,
+            valued: bool,
+            what: &str,
+            location: &str,
+            segment: Option<usize>,
+        ) -> bool {
+            match (usage, valued) {
+                (Optionality::R, false) => self.error(
+                    V2ConformanceIssueKind::Usage,
+                    segment,
+                    location,
+                    format_compact!("Required {} {} is missing!", what, location),
+                ),
+                (Optionality::X, true) => {
+                    self.error(
+                        V2ConformanceIssueKind::Usage,
+                        segment,
+                        location,
+                        format_compact!("{} {} is not supported by the profile!", what, location),
+                    );
+                    return false;
+                }
+                (Optionality::C(None), _) => self.push(
+                    V2DiagnosticSeverity::Warning,
+                    V2ConformanceIssueKind::Usage,
+                    segment,
+                    location,
+                    format_compact!(
+                        "Condition of {} {} is not declared, so it was not evaluated!",
+                        what,
+                        location
+                    ),
+                ),
+                _ => {}
+            }
+            true
+        }
+
+        fn check_cardinality(
+            &mut self,
+            min: u32,
+            max: Option<u32>,
+            count: usize,
+            what: &str,
+            location: &str,
+            segment: Option<usize>,
+        ) {
+            if count > 0 && count < min as usize {
+                self.error(
+                    V2ConformanceIssueKind::Cardinality,
+                    segment,
+                    location,
+                    format_compact!(
+                        "{} {} appears {} times but the profile expects at least {}!",
+                        what,
+                        location,
+                        count,
+                        min
+                    ),
+                );
+            }
+            if let Some(max) = max.filter(|max| count > *max as usize) {
+                self.error(
+                    V2ConformanceIssueKind::Cardinality,
+                    segment,
+                    location,
+                    format_compact!(
+                        "{} {} appears {} times but the profile allows at most {}!",
+                        what,
+                        location,
+                        count,
+                        max
+                    ),
+                );
+            }
+        }
+
+        fn contains_segment(elements: &[V2ProfileSegment], name: &str) -> bool {
+            elements.iter().any(|element| {
+                element.name == name || Self::contains_segment(&element.segments, name)
+            })
+        }
+
+        fn is_required(element: &V2ProfileSegment) -> bool {
+            element.usage.is_required() || element.min > 0
+        }
+
+        ///
+        /// Same rule as the standard structures. Groups can start with any of their leading
+        /// elements up to and including the first required one.
+        ///
+        fn can_start_with(element: &V2ProfileSegment, name: &str) -> bool {
+            if element.segments.is_empty() {
+                return element.name == name;
+            }
+            for child in element.segments.iter() {
+                if Self::can_start_with(child, name) {
+                    return true;
+                }
+                if Self::is_required(child) {
+                    break;
+                }
+            }
+            false
+        }
+
+        fn skip_foreign_segments(&mut self, profile: &V2Profile) {
+            while let Some(segment) = self.segments.get(self.cursor) {
+                if Self::contains_segment(&profile.segments, segment.name()) {
+                    break;
+                }
+                self.push(
+                    V2DiagnosticSeverity::Warning,
+                    V2ConformanceIssueKind::Structure,
+                    Some(self.cursor + 1),
+                    segment.name(),
+                    format_compact!("Segment {} is not part of the profile!", segment.name()),
+                );
+                self.cursor += 1;
+            }
+        }
+
+        fn match_elements(
+            &mut self,
+            profile: &V2Profile,
+            elements: &[V2ProfileSegment],
+            path: &str,
+        ) {
+            for element in elements.iter() {
+                let element_path = match path.is_empty() {
+                    true => element.name.clone(),
+                    false => format_compact!("{}/{}", path, element.name),
+                };
+                let mut count = 0;
+                loop {
+                    self.skip_foreign_segments(profile);
+                    let segment = match self.segments.get(self.cursor) {
+                        Some(segment) => *segment,
+                        None => break,
+                    };
+                    // Elements that cannot repeat end after the first match so the next occurrence
+                    // can start a new repetition of an enclosing group.
+                    if !Self::can_start_with(element, segment.name())
+                        || (count > 0 && element.max == Some(1))
+                    {
+                        break;
+                    }
+                    match element.segments.is_empty() {
+                        true => {
+                            if !matches!(element.usage, Optionality::X) {
+                                self.validate_segment(element, segment);
+                            }
+                            self.cursor += 1;
+                        }
+                        false => self.match_elements(profile, &element.segments, &element_path),
+                    }
+                    count += 1;
+                }
+
+                let what = match element.segments.is_empty() {
+                    true => "segment",
+                    false => "group",
+                };
+                if self.check_usage(&element.usage, count > 0, what, &element_path, None) {
+                    self.check_cardinality(
+                        element.min,
+                        element.max,
+                        count,
+                        what,
+                        &element_path,
+                        None,
+                    );
+                }
+            }
+        }
+
+        fn validate_segment(&mut self, element: &V2ProfileSegment, segment: &V2Segment) {
+            let order = self.cursor + 1;
+            let repetition = self.segments[..self.cursor]
+                .iter()
+                .filter(|s| s.name() == segment.name())
+                .count()
+                + 1;
+            let segment_location = format_compact!("{}({})", segment.name(), repetition);
+            for (indx, field) in element.fields.iter().enumerate() {
+                let seq = indx + 1;
+                // MSH-1 and MSH-2 are the delimiters, which the parser already validated.
+                if is_header_segment_name(segment.name()) && seq <= 2 {
+                    continue;
+                }
+                let position = seq_to_position(segment.name(), seq as isize);
+                self.validate_field(segment, field, position, &segment_location, order);
+            }
+        }
+
+        fn validate_field(
+            &mut self,
+            segment: &V2Segment,
+            field: &V2ProfileField,
+            position: isize,
+            segment_location: &str,
+            order: usize,
+        ) {
+            let location = format_compact!("{}{}", segment_location, position);
+            let repetitions: Vec<V2ComponentList> = match position <= segment.len() as isize {
+                true => match segment.get(position) {
+                    Ok(group) => group.iter().map(|f| f.to_component_list()).collect(),
+                    Err(_) => Vec::new(),
+                },
+                false => Vec::new(),
+            };
+            let valued: Vec<(usize, &V2ComponentList)> = repetitions
+                .iter()
+                .enumerate()
+                .filter(|(_, repetition)| is_valued(repetition))
+                .collect();
+            let first = repetitions.first().cloned().unwrap_or_default();
+            let context = V2ConditionContext::new(&first)
+                .with_segment(segment)
+                .with_message(self.message)
+                .with_version(self.version);
+            let standard = segment
+                .field_definition_for(position, self.version)
+                .ok()
+                .map(|descriptor| descriptor.optionality);
+            let usage = conditional_usage(&field.usage, standard, &context);
+            if !self.check_usage(&usage, !valued.is_empty(), "field", &location, Some(order)) {
+                return;
+            }
+            self.check_cardinality(
+                field.min,
+                field.max,
+                valued.len(),
+                "field",
+                &location,
+                Some(order),
+            );
+
+            let data_type = profile_data_type(&field.data_type)
+                .or_else(|| segment.field_data_type_for(position, self.version).ok());
+            for (indx, repetition) in valued {
+                let repetition_location = format_compact!("{}[{}]", location, indx + 1);
+                self.check_value(field, repetition, data_type, &repetition_location, order);
+            }
+        }
+
+        ///
+        /// Checks length, value set, and data type of a valued field repetition, component, or
+        /// subcomponent, then walks into the components the profile lists. Components are given
+        /// as lists of subcomponents, and subcomponents as single piece components, so every
+        /// level has the same shape.
+        ///
+        fn check_value(
+            &mut self,
+            node: &V2ProfileField,
+            value: &V2ComponentList,
+            data_type: Option<V2ComponentType>,
+            location: &str,
+            order: usize,
+        ) {
+            if let Some(length) = node.length {
+                let value_length = value_length(value);
+                if value_length > length as usize {
+                    self.error(
+                        V2ConformanceIssueKind::Length,
+                        Some(order),
+                        location,
+                        format_compact!(
+                            "{} is {} characters long but the profile allows {}!",
+                            location,
+                            value_length,
+                            length
+                        ),
+                    );
+                }
+            }
+
+            if let Some(table) = node.table {
+                let pieces: Vec<&str> = value
+                    .iter()
+                    .map(|component| component.first().copied().unwrap_or_default())
+                    .collect();
+                let coded = match data_type {
+                    Some(V2ComponentType::Complex(complex)) => is_coded(&complex),
+                    _ => false,
+                };
+                if let Some(message) = bound_code(&pieces, coded, table)
+                    .and_then(|code| self.registry.check(table, code))
+                {
+                    self.error(
+                        V2ConformanceIssueKind::ValueSet,
+                        Some(order),
+                        location,
+                        format_compact!("{}: {}", location, message),
+                    );
+                }
+            }
+
+            if node.components.is_empty() {
+                if let Some(data_type) = data_type.filter(|t| *t != V2ComponentType::Varies) {
+                    let typed =
+                        cast_field(value, &data_type, self.message.separators(), self.version);
+                    for error in typed.errors() {
+                        self.error(
+                            V2ConformanceIssueKind::DataType,
+                            Some(order),
+                            location,
+                            format_compact!("{}: {}", location, error),
+                        );
+                    }
+                }
+                return;
+            }
+
+            for (indx, component) in node.components.iter().enumerate() {
+                let child: V2ComponentList = value
+                    .get(indx)
+                    .map(|pieces| pieces.iter().map(|piece| vec![*piece]).collect())
+                    .unwrap_or_default();
+                let child_location = format_compact!("{}.{}", location, indx + 1);
+                let valued = is_valued(&child);
+                let standard = match data_type {
+                    Some(V2ComponentType::Complex(complex)) => {
+                        complex_type_descriptor(&complex, indx + 1, self.version)
+                            .ok()
+                            .map(|descriptor| descriptor.optionality)
+                    }
+                    _ => None,
+                };
+                let context = V2ConditionContext::new(value)
+                    .with_message(self.message)
+                    .with_version(self.version);
+                let usage = conditional_usage(&component.usage, standard, &context);
+                let supported =
+                    self.check_usage(&usage, valued, "component", &child_location, Some(order));
+                if supported && valued {
+                    let child_type = profile_data_type(&component.data_type);
+                    self.check_value(component, &child, child_type, &child_location, order);
+                }
+            }
+        }
+    }
+
+    /**************************** API *******************************************/
+
+    impl V2Profile {
+        pub fn from_json(json: &str) -> V2Result<V2Profile> {
+            match rumtk_deserialize!(json) {
+                Ok(profile) => Ok(profile),
+                Err(e) => Err(format_compact!("Failed to load profile from JSON! {}", e)),
+            }
+        }
+
+        ///
+        /// Loads an HL7 message profile (`HL7v2xConformanceProfile`). The static definition gives
+        /// the segments, groups, fields, components, and subcomponents along with their `Usage`,
+        /// `Min`, `Max`, `Datatype`, `Length`, and `Table` attributes. Predicates, notes, and
+        /// other elements are ignored.
+        ///
+        pub fn from_xml(xml: &str) -> V2Result<V2Profile> {
+            let mut profile = V2Profile::default();
+            let mut stack: Vec<V2ProfileNode> = Vec::new();
+            for tag in parse_xml_tags(xml)? {
+                let name = tag.name.as_str();
+                let is_node = matches!(
+                    name,
+                    "Segment" | "SegGroup" | "Field" | "Component" | "SubComponent"
+                );
+                if tag.closing {
+                    if is_node {
+                        close_profile_node(&mut stack, &mut profile, name)?;
+                    }
+                    continue;
+                }
+                let attribute = |key: &str| tag.attribute(key).unwrap_or_default().to_rumstring();
+                match name {
+                    "HL7v2xConformanceProfile" => profile.hl7_version = attribute("HL7Version"),
+                    "MetaData" => profile.name = attribute("Name"),
+                    "HL7v2xStaticDef" => {
+                        profile.message_type = attribute("MsgType");
+                        profile.event = attribute("EventType");
+                        profile.structure = attribute("MsgStructID");
+                    }
+                    "Segment" | "SegGroup" => {
+                        stack.push(V2ProfileNode::Segment(V2ProfileSegment {
+                            name: attribute("Name"),
+                            usage: tag.usage()?,
+                            min: parse_number(tag.attribute("Min")).unwrap_or(0),
+                            max: tag.max()?,
+                            fields: Vec::new(),
+                            segments: Vec::new(),
+                        }))
+                    }
+                    "Field" | "Component" | "SubComponent" => {
+                        stack.push(V2ProfileNode::Field(V2ProfileField {
+                            name: attribute("Name"),
+                            usage: tag.usage()?,
+                            min: parse_number(tag.attribute("Min")).unwrap_or(0),
+                            max: tag.max()?,
+                            data_type: tag.attribute("Datatype").map(|t| t.to_rumstring()),
+                            length: parse_number(tag.attribute("Length")),
+                            table: parse_table(tag.attribute("Table")),
+                            components: Vec::new(),
+                        }))
+                    }
+                    _ => {}
+                }
+                if tag.self_closing && is_node {
+                    close_profile_node(&mut stack, &mut profile, name)?;
+                }
+            }
+            match stack.is_empty() {
+                true => Ok(profile),
+                false => Err(format_compact!("Profile XML ended with unclosed elements!")),
+            }
+        }
+
+        ///
+        /// Loads a profile from a `.json` or `.xml` file.
+        ///
+        pub fn load_file(path: &str) -> V2Result<V2Profile> {
+            let contents = match std::fs::read_to_string(path) {
+                Ok(contents) => contents,
+                Err(e) => {
+                    return Err(format_compact!(
+                        "Failed to read profile file {}! {}",
+                        path,
+                        e
+                    ))
+                }
+            };
+            let extension = path.rsplit('.').next().unwrap_or_default();
+            match extension.to_ascii_lowercase().as_str() {
+                "json" => V2Profile::from_json(&contents),
+                "xml" => V2Profile::from_xml(&contents),
+                _ => Err(format_compact!(
+                    "Unsupported profile file {}! Expected a .json or .xml file.",
+                    path
+                )),
+            }
+        }
+    }
+
+    impl V2ConformanceReport {
+        ///
+        /// True when no errors were found. Warnings do not break conformance.
+        ///
+        pub fn is_conformant(&self) -> bool {
+            !self
+                .issues
+                .iter()
+                .any(|issue| issue.severity == V2DiagnosticSeverity::Error)
+        }
+
+        pub fn errors(&self) -> impl Iterator<Item = &V2ConformanceIssue> {
+            self.issues
+                .iter()
+                .filter(|issue| issue.severity == V2DiagnosticSeverity::Error)
+        }
+
+        pub fn warnings(&self) -> impl Iterator<Item = &V2ConformanceIssue> {
+            self.issues
+                .iter()
+                .filter(|issue| issue.severity == V2DiagnosticSeverity::Warning)
+        }
+    }
+
+    impl Display for V2ConformanceIssue {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "[{:?}] {:?}", self.severity, self.kind)?;
+            if let Some(segment) = self.segment {
+                write!(f, ", segment {}", segment)?;
+            }
+            write!(f, ", {}: {}", self.location, self.message)
+        }
+    }
+
+    impl V2Message {
+        ///
+        /// Validates this message against a conformance profile: message type, usage and
+        /// cardinality of segments, groups, and fields, usage of components, lengths, data types,
+        /// and value sets. Values are checked against the tables in `registry`. Tables it does not
+        /// have are skipped. Conditional usages are evaluated with the predicates of the standard
+        /// definitions. Those without one are reported as warnings since they were not evaluated.
+        ///
+        /// ## Example
+        /// ```
+        /// use rumtk_hl7_v2::hl7_v2_parser::v2_parser::V2Message;
+        /// use rumtk_hl7_v2::hl7_v2_profiles::v2_profiles::V2Profile;
+        /// use rumtk_hl7_v2::hl7_v2_tables::v2_tables::V2TableRegistry;
+        ///
+        /// let profile = V2Profile::from_json(r#"{"name": "ADT", "message_type": "ADT", "segments": [
+        ///     {"name": "MSH", "usage": "R", "min": 1},
+        ///     {"name": "PID", "usage": "R", "min": 1, "fields": [
+        ///         {"usage": "O"}, {"usage": "X"}, {"usage": "R", "max": "*"}
+        ///     ]},
+        ///     {"name": "PV1", "usage": "R", "min": 1}
+        /// ]}"#).unwrap();
+        /// let message = V2Message::try_from_str("MSH|^~\\&|||||||ADT^A01|1|P|2.5.1\rPID|1|99|123^^^HOSP^MR").unwrap();
+        /// let report = message.validate_profile(&profile, &V2TableRegistry::new());
+        ///
+        /// assert!(!report.is_conformant(), "PID-2 is valued and PV1 is missing!");
+        /// assert_eq!(report.issues.len(), 2, "Expected PID-2 and PV1 to be flagged!");
+        /// ```
+        ///
+        pub fn validate_profile(
+            &self,
+            profile: &V2Profile,
+            registry: &V2TableRegistry,
+        ) -> V2ConformanceReport {
+            let mut validator = V2ProfileValidator {
+                registry,
+                message: self,
+                version: self.version(),
+                segments: self.segments().collect(),
+                cursor: 0,
+                issues: Vec::new(),
+            };
+            validator.check_message_type(profile);
+            validator.match_elements(profile, &profile.segments, "");
+            while let Some(segment) = validator.segments.get(validator.cursor) {
+                let name = segment.name().to_rumstring();
+                validator.error(
+                    V2ConformanceIssueKind::Structure,
+                    Some(validator.cursor + 1),
+                    &name,
+                    format_compact!("Segment {} is not expected here by the profile!", name),
+                );
+                validator.cursor += 1;
+            }
+            V2ConformanceReport {
+                profile: profile.name.clone(),
+                issues: validator.issues,
+            }
+        }
+    }
+
+    impl<'a> V2ProfileValidator<'a> {
+        fn check_message_type(&mut self, profile: &V2Profile) {
+            let msh = match self.segments.first() {
+                Some(msh) => *msh,
+                None => return,
+            };
+            let message_type = match msh.get(V2_MSH_MESSAGE_TYPE) {
+                Ok(group) => group
+                    .first()
+                    .map(|field| field.to_component_list())
+                    .unwrap_or_default(),
+                Err(_) => V2ComponentList::new(),
+            };
+            let location = format_compact!("MSH(1){}", V2_MSH_MESSAGE_TYPE);
+            let expected = [&profile.message_type, &profile.event, &profile.structure];
+            for (indx, expected) in expected.into_iter().enumerate() {
+                let found = message_type
+                    .get(indx)
+                    .and_then(|component| component.first())
+                    .copied()
+                    .unwrap_or_default();
+                // A missing MSH-9.3 is derived from the event, so only a different one is wrong.
+                if expected.is_empty() || (indx == 2 && found.is_empty()) || expected == found {
+                    continue;
+                }
+                self.error(
+                    V2ConformanceIssueKind::Structure,
+                    Some(1),
+                    &format_compact!("{}.{}", location, indx + 1),
+                    format_compact!(
+                        "Message type component {} is {} but the profile is for {}!",
+                        indx + 1,
+                        found,
+                        expected
+                    ),
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,568 @@
+/*
+ * rumtk attempts to implement HL7 and medical protocols for interoperability in medicine.
+ * This toolkit aims to be reliable, simple, performant, and standards compliant.
+ * Copyright (C) 2024  Luis M. Santos, M.D.
+ *
+ * This library is free software; you can redistribute it and/or
+ * modify it under the terms of the GNU Lesser General Public
+ * License as published by the Free Software Foundation; either
+ * version 2.1 of the License, or (at your option) any later version.
+ *
+ * This library is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+ * Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public
+ * License along with this library; if not, write to the Free Software
+ * Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301  USA
+ */
+
+///
+/// Query expressions that can match many positions in a message. They extend the search syntax
+/// used by [V2Message::find_component] (`SEG(group)field[repetition].component.subcomponent`)
+/// with:
+///
+/// - **Wildcards** => `*` selects every segment, repetition, field, component, or subcomponent.
+///   E.g. `OBX(*)5` or `PID3[*]`.
+/// - **Ranges** => `first..last` (inclusive) selects a range of segments, repetitions, fields,
+///   components, or subcomponents. E.g. `OBX(2..4)5`, `PID3[1..2]`, or `OBX3.1..2`. Negative
+///   indices count from the end, e.g. `PID-1` or `OBX3.-2..-1`.
+/// - **Predicates** => `WHERE <operand> <operator> <value>`, joined with `AND`. Operands are
+///   either an address within the same segment (`OBX3.1`) or a component of the matched
+///   repetition (`.5` or `.4.2`). Operators are `=`, `!=`, and `~` (regex). Values may be quoted.
+///
+/// Omitted indices default to 1, same as [V2Message::find_component]. Values are compared in
+/// their wire format.
+///
+/// ## Example
+///
+/// ```text
+/// OBX(*)5 WHERE OBX3.1 = 2345-7
+/// PID3[*] WHERE .5 = MR
+/// NTE(*)3 WHERE NTE3 ~ "(?i)hemolyzed"
+/// ```
+///
+pub mod v2_query {
+    use crate::hl7_v2_base_types::v2_base_types::V2Result;
+    use crate::hl7_v2_parser::v2_parser::{
+        V2Component, V2Field, V2Message, V2Segment, V2SubComponent,
+    };
+    use regex::Regex;
+    use rumtk_core::strings::{format_compact, AsStr, RUMString, RUMStringConversions};
+    use std::fmt::Display;
+
+    /**************************** Constants**************************************/
+
+    const V2_QUERY_WHERE: &str = "WHERE";
+    const V2_QUERY_AND: &str = "AND";
+    const V2_QUERY_RANGE: &str = "..";
+
+    /**************************** Types *****************************************/
+
+    ///
+    /// Selects one or more positions of a list. All indices are 1-indexed.
+    ///
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum V2QuerySelector {
+        All,
+        Index(isize),
+        Range(isize, isize),
+    }
+
+    impl V2QuerySelector {
+        ///
+        /// Resolves the selector against a list of `len` items. Returns the 1-indexed positions
+        /// that exist. Negative indices count from the end so -1 is the last item.
+        ///
+        pub fn positions(&self, len: usize) -> impl Iterator<Item = usize> {
+            let len = len as isize;
+            let resolve = |indx: isize| if indx < 0 { len + indx + 1 } else { indx };
+            let (first, last) = match *self {
+                V2QuerySelector::All => (1, len),
+                V2QuerySelector::Index(indx) => (resolve(indx), resolve(indx)),
+                V2QuerySelector::Range(first, last) => (resolve(first), resolve(last)),
+            };
+            (first.max(1)..=last.min(len)).map(|indx| indx as usize)
+        }
+    }
+
+    ///
+    /// What a predicate looks at.
+    ///
+    /// - **Segment** => An address within the segment being matched, e.g. `OBX3.1`.
+    /// - **Repetition** => A component of the repetition being matched, e.g. `.5` in
+    ///   `PID3[*] WHERE .5 = MR`.
+    ///
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum V2QueryOperand {
+        Segment {
+            field: isize,
+            repetition: usize,
+            component: isize,
+            subcomponent: isize,
+        },
+        Repetition {
+            component: isize,
+            subcomponent: isize,
+        },
+    }
+
+    #[derive(Debug, Clone)]
+    pub enum V2QueryOperator {
+        Equals(RUMString),
+        NotEquals(RUMString),
+        Matches(Regex),
+    }
+
+    impl V2QueryOperator {
+        pub fn test(&self, value: &str) -> bool {
+            match self {
+                V2QueryOperator::Equals(expected) => value == expected.as_str(),
+                V2QueryOperator::NotEquals(expected) => value != expected.as_str(),
+                V2QueryOperator::Matches(regex) => regex.is_match(value),
+            }
+        }
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct V2QueryPredicate {
+        pub operand: V2QueryOperand,
+        pub operator: V2QueryOperator,
+    }
+
+    ///
+    /// Compiled query. Compile once with [V2Query::from_str] and run it against as many
+    /// messages as needed with [V2Query::run].
+    ///
+    #[derive(Debug, Clone)]
+    pub struct V2Query {
+        segment: RUMString,
+        segment_group: V2QuerySelector,
+        field: V2QuerySelector,
+        repetition: V2QuerySelector,
+        component: V2QuerySelector,
+        subcomponent: V2QuerySelector,
+        predicates: Vec<V2QueryPredicate>,
+    }
+
+    ///
+    /// Exact location of a match. Its string form is a plain search pattern that can be handed
+    /// back to [V2Message::find_component] or [V2Message::set].
+    ///
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct V2QueryPosition {
+        pub segment: RUMString,
+        pub segment_group: usize,
+        pub field: usize,
+        pub repetition: usize,
+        pub component: usize,
+        pub subcomponent: usize,
+    }
+
+    impl Display for V2QueryPosition {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(
+                f,
+                "{}({}){}[{}].{}.{}",
+                &self.segment,
+                self.segment_group,
+                self.field,
+                self.repetition,
+                self.component,
+                self.subcomponent
+            )
+        }
+    }
+
+    ///
+    /// A single match. `value` is the subcomponent at `position` and `component` is the
+    /// component holding it.
+    ///
+    #[derive(Debug)]
+    pub struct V2QueryMatch<'a> {
+        pub position: V2QueryPosition,
+        pub component: &'a V2Component,
+        pub value: &'a V2SubComponent,
+    }
+
+    impl V2Query {
+        #[allow(clippy::should_implement_trait)]
+        pub fn from_str(expr: &str) -> V2Result<Self> {
+            V2QueryParser::new(expr).query()
+        }
+
+        pub fn segment(&self) -> &str {
+            self.segment.as_str()
+        }
+
+        pub fn predicates(&self) -> &Vec<V2QueryPredicate> {
+            &self.predicates
+        }
+
+        ///
+        /// Runs the query against a message. Matches are returned in message order.
+        ///
+        pub fn run<'a>(&self, message: &'a V2Message) -> impl Iterator<Item = V2QueryMatch<'a>> {
+            let segments = match message.segment_id(&self.segment) {
+                Some(id) => match message.get_group(&id) {
+                    Ok(segments) => segments.as_slice(),
+                    Err(_) => &[],
+                },
+                None => &[],
+            };
+
+            let mut matches = Vec::new();
+            for segment_group in self.segment_group.positions(segments.len()) {
+                let segment = &segments[segment_group - 1];
+                if !self.segment_matches(segment) {
+                    continue;
+                }
+                for field in self.field.positions(segment.len()) {
+                    let field_group = &segment[field as isize];
+                    for repetition in self.repetition.positions(field_group.len()) {
+                        let repetition_field = &field_group[repetition - 1];
+                        if !self.repetition_matches(repetition_field) {
+                            continue;
+                        }
+                        for component in self.component.positions(repetition_field.len()) {
+                            let component_item = &repetition_field[component as isize];
+                            for subcomponent in self.subcomponent.positions(component_item.len()) {
+                                matches.push(V2QueryMatch {
+                                    position: V2QueryPosition {
+                                        segment: self.segment.clone(),
+                                        segment_group,
+                                        field,
+                                        repetition,
+                                        component,
+                                        subcomponent,
+                                    },
+                                    component: component_item,
+                                    value: &component_item[subcomponent as isize],
+                                });
+                            }
+                        }
+                    }
+                }
+            }
+            matches.into_iter()
+        }
+
+        fn segment_matches(&self, segment: &V2Segment) -> bool {
+            self.predicates
+                .iter()
+                .all(|predicate| match predicate.operand {
+                    V2QueryOperand::Segment {
+                        field,
+                        repetition,
+                        component,
+                        subcomponent,
+                    } => {
+                        let value = segment
+                            .get(field)
+                            .ok()
+                            .and_then(|field_group| field_group.get(repetition.wrapping_sub(1)))
+                            .map(|field| field_value(field, component, subcomponent))
+                            .unwrap_or_default();
+                        predicate.operator.test(value)
+                    }
+                    V2QueryOperand::Repetition { .. } => true,
+                })
+        }
+
+        fn repetition_matches(&self, field: &V2Field) -> bool {
+            self.predicates
+                .iter()
+                .all(|predicate| match predicate.operand {
+                    V2QueryOperand::Repetition {
+                        component,
+                        subcomponent,
+                    } => predicate
+                        .operator
+                        .test(field_value(field, component, subcomponent)),
+                    V2QueryOperand::Segment { .. } => true,
+                })
+        }
+    }
+
+    ///
+    /// Hand written recursive descent parser for query expressions. See the module
+    /// documentation for the syntax.
+    ///
+    struct V2QueryParser<'a> {
+        expr: &'a str,
+        pos: usize,
+    }
+
+    impl<'a> V2QueryParser<'a> {
+        fn new(expr: &'a str) -> Self {
+            V2QueryParser { expr, pos: 0 }
+        }
+
+        fn query(mut self) -> V2Result<V2Query> {
+            self.skip_whitespace();
+            let segment = self.segment_name()?;
+            let mut query = V2Query {
+                segment,
+                segment_group: V2QuerySelector::Index(1),
+                field: V2QuerySelector::Index(1),
+                repetition: V2QuerySelector::Index(1),
+                component: V2QuerySelector::Index(1),
+                subcomponent: V2QuerySelector::Index(1),
+                predicates: Vec::new(),
+            };
+            if self.eat('(') {
+                query.segment_group = self.selector()?;
+                self.expect(')')?;
+            }
+            match self.peek() {
+                Some(c) if c == '-' || c == '*' || c.is_ascii_digit() => {
+                    query.field = self.selector()?;
+                    if self.eat('[') {
+                        query.repetition = self.selector()?;
+                        self.expect(']')?;
+                    }
+                    if self.eat('.') {
+                        query.component = self.selector()?;
+                        if self.eat('.') {
+                            query.subcomponent = self.selector()?;
+                        }
+                    }
+                }
+                _ => (),
+            }
+
+            self.skip_whitespace();
+            if self.at_end() {
+                return Ok(query);
+            }
+            if !self.keyword(V2_QUERY_WHERE) {
+                return Err(self.error("Expected WHERE!"));
+            }
+            loop {
+                let predicate = self.predicate(&query.segment)?;
+                query.predicates.push(predicate);
+                self.skip_whitespace();
+                if self.at_end() {
+                    return Ok(query);
+                }
+                if !self.keyword(V2_QUERY_AND) {
+                    return Err(self.error("Expected AND!"));
+                }
+            }
+        }
+
+        fn predicate(&mut self, segment: &str) -> V2Result<V2QueryPredicate> {
+            self.skip_whitespace();
+            let operand = if self.eat('.') {
+                let component = self.integer()?;
+                let subcomponent = match self.eat('.') {
+                    true => self.integer()?,
+                    false => 1,
+                };
+                V2QueryOperand::Repetition {
+                    component,
+                    subcomponent,
+                }
+            } else {
+                let name = self.segment_name()?;
+                if name != segment {
+                    return Err(self.error(&format_compact!(
+                        "Predicates can only look at the segment being matched ({})!",
+                        segment
+                    )));
+                }
+                let field = self.integer()?;
+                let mut repetition = 1;
+                if self.eat('[') {
+                    repetition = self.integer()?.max(0) as usize;
+                    self.expect(']')?;
+                }
+                let mut component = 1;
+                let mut subcomponent = 1;
+                if self.eat('.') {
+                    component = self.integer()?;
+                    if self.eat('.') {
+                        subcomponent = self.integer()?;
+                    }
+                }
+                V2QueryOperand::Segment {
+                    field,
+                    repetition,
+                    component,
+                    subcomponent,
+                }
+            };
+
+            self.skip_whitespace();
+            let operator_kind = if self.rest().starts_with("!=") {
+                self.pos += 2;
+                "!="
+            } else if self.eat('=') {
+                "="
+            } else if self.eat('~') {
+                "~"
+            } else {
+                return Err(self.error("Expected one of =, !=, or ~!"));
+            };
+            self.skip_whitespace();
+            let value = self.literal()?;
+            let operator = match operator_kind {
+                "=" => V2QueryOperator::Equals(value),
+                "!=" => V2QueryOperator::NotEquals(value),
+                _ => match Regex::new(&value) {
+                    Ok(regex) => V2QueryOperator::Matches(regex),
+                    Err(e) => {
+                        return Err(self.error(&format_compact!("Invalid regex! {}", e)));
+                    }
+                },
+            };
+            Ok(V2QueryPredicate { operand, operator })
+        }
+
+        fn segment_name(&mut self) -> V2Result<RUMString> {
+            match self.rest().get(0..3) {
+                Some(name) if name.chars().all(|c| c.is_ascii_alphanumeric()) => {
+                    self.pos += 3;
+                    Ok(name.to_uppercase().to_rumstring())
+                }
+                _ => Err(self.error("Expected a segment name!")),
+            }
+        }
+
+        fn selector(&mut self) -> V2Result<V2QuerySelector> {
+            if self.eat('*') {
+                return Ok(V2QuerySelector::All);
+            }
+            let first = self.integer()?;
+            if self.rest().starts_with(V2_QUERY_RANGE) {
+                self.pos += V2_QUERY_RANGE.len();
+                let last = self.integer()?;
+                return Ok(V2QuerySelector::Range(first, last));
+            }
+            Ok(V2QuerySelector::Index(first))
+        }
+
+        fn integer(&mut self) -> V2Result<isize> {
+            let rest = self.rest();
+            let sign_len = match rest.starts_with('-') {
+                true => 1,
+                false => 0,
+            };
+            let digit_len = rest[sign_len..]
+                .chars()
+                .take_while(|c| c.is_ascii_digit())
+                .count();
+            match rest[..sign_len + digit_len].parse::<isize>() {
+                Ok(number) => {
+                    self.pos += sign_len + digit_len;
+                    Ok(number)
+                }
+                Err(_) => Err(self.error("Expected a number!")),
+            }
+        }
+
+        fn literal(&mut self) -> V2Result<RUMString> {
+            if self.eat('"') {
+                return match self.rest().find('"') {
+                    Some(end) => {
+                        let value = self.rest()[..end].to_rumstring();
+                        self.pos += end + 1;
+                        Ok(value)
+                    }
+                    None => Err(self.error("Unterminated quoted value!")),
+                };
+            }
+            let end = self
+                .rest()
+                .find(char::is_whitespace)
+                .unwrap_or(self.rest().len());
+            if end == 0 {
+                return Err(self.error("Expected a value!"));
+            }
+            let value = self.rest()[..end].to_rumstring();
+            self.pos += end;
+            Ok(value)
+        }
+
+        fn keyword(&mut self, word: &str) -> bool {
+            let rest = self.rest();
+            let matches = match rest.get(0..word.len()) {
+                Some(candidate) => candidate.eq_ignore_ascii_case(word),
+                None => false,
+            };
+            let separated = rest[word.len().min(rest.len())..]
+                .chars()
+                .next()
+                .map_or(true, char::is_whitespace);
+            if matches && separated {
+                self.pos += word.len();
+                return true;
+            }
+            false
+        }
+
+        fn rest(&self) -> &'a str {
+            &self.expr[self.pos..]
+        }
+
+        fn peek(&self) -> Option<char> {
+            self.rest().chars().next()
+        }
+
+        fn at_end(&self) -> bool {
+            self.pos >= self.expr.len()
+        }
+
+        fn eat(&mut self, c: char) -> bool {
+            if self.peek() == Some(c) {
+                self.pos += c.len_utf8();
+                return true;
+            }
+            false
+        }
+
+        fn expect(&mut self, c: char) -> V2Result<()> {
+            match self.eat(c) {
+                true => Ok(()),
+                false => Err(self.error(&format_compact!("Expected '{}'!", c))),
+            }
+        }
+
+        fn skip_whitespace(&mut self) {
+            let trimmed = self.rest().trim_start();
+            self.pos = self.expr.len() - trimmed.len();
+        }
+
+        fn error(&self, message: &str) -> RUMString {
+            format_compact!(
+                "Invalid query [{}] at position {}! {}",
+                self.expr,
+                self.pos,
+                message
+            )
+        }
+    }
+
+    /**************************** Helpers ***************************************/
+
+    fn field_value(field: &V2Field, component: isize, subcomponent: isize) -> &str {
+        field
+            .get(component)
+            .ok()
+            .and_then(|component| component.get(subcomponent).ok())
+            .map(|subcomponent| subcomponent.as_str())
+            .unwrap_or_default()
+    }
+
+    /**************************** API *******************************************/
+
+    impl V2Message {
+        ///
+        /// Compiles and runs a query against this message. See [V2Query] for the syntax.
+        ///
+        pub fn query(&self, expr: &str) -> V2Result<impl Iterator<Item = V2QueryMatch<'_>>> {
+            Ok(V2Query::from_str(expr)?.run(self))
+        }
+    }
+}
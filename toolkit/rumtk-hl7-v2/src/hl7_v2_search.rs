@@ -18,18 +18,43 @@
  * Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301  USA
  */
 
-
+use crate::hl7_v2_base_types::v2_base_types::V2SearchIndex;
+use rumtk_core::cache::{new_cache, LazyRUMCache};
+use rumtk_core::rumtk_cache_fetch;
 pub use rumtk_core::search::rumtk_search::*;
+use rumtk_core::strings::{RUMString, RUMStringConversions};
 
 /**************************** Globals **************************************/
 
+static mut search_cache: LazyRUMCache<RUMString, V2SearchIndex> = new_cache();
+
 /**************************** Constants**************************************/
 
 //pub const REGEX_V2_SEARCH_DEFAULT: &str = r"(?<segment>\w{3}).*(?<field>-?\d+).*.(?<component>-?\d+)|\w{3}.*\((?<segment_group>\d+)\).*|.*\d+\((?<sub_field>\d+)\)";
 pub const REGEX_V2_SEARCH_DEFAULT: &str = r"(?<segment>\w{3})|(\((?<segment_group>\d+)\))|(?<field>-?\d+)|(\[(?<sub_field>\d+)\])|(.(?<component>-?\d+))";
+///
+/// Optional subcomponent suffix, e.g. the trailing `.2` in `PID3.4.2`. It is split off before the
+/// rest of the expression goes through [REGEX_V2_SEARCH_DEFAULT].
+///
+pub const REGEX_V2_SEARCH_SUBCOMPONENT: &str =
+    r"^(?<components>.+\.-?\d+)\.(?<subcomponent>-?\d+)$";
 
 /**************************** Types *****************************************/
 
 /**************************** Traits ****************************************/
 
 /**************************** Helpers ***************************************/
+
+fn compile_search_index(search_pattern: &RUMString) -> V2SearchIndex {
+    V2SearchIndex::from(search_pattern)
+}
+
+///
+/// Compiles a plain search pattern into a [V2SearchIndex]. Compiled patterns are cached and the
+/// cache is shared by the owned and borrowed message types, so a path resolves the same way in
+/// both.
+///
+pub(crate) fn compile_search_pattern(search_pattern: &str) -> &'static V2SearchIndex {
+    let search_pattern = search_pattern.to_rumstring();
+    rumtk_cache_fetch!(&mut search_cache, &search_pattern, compile_search_index)
+}
@@ -0,0 +1,1010 @@
+/*
+ * rumtk attempts to implement HL7 and medical protocols for interoperability in medicine.
+ * This toolkit aims to be reliable, simple, performant, and standards compliant.
+ * Copyright (C) 2024  Luis M. Santos, M.D.
+ *
+ * This library is free software; you can redistribute it and/or
+ * modify it under the terms of the GNU Lesser General Public
+ * License as published by the Free Software Foundation; either
+ * version 2.1 of the License, or (at your option) any later version.
+ *
+ * This library is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+ * Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public
+ * License along with this library; if not, write to the Free Software
+ * Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301  USA
+ */
+
+pub mod v2_segment_descriptor {
+    use crate::hl7_v2_base_types::v2_base_types::{
+        is_header_segment_name, V2ComponentList, V2Result,
+    };
+    pub use crate::hl7_v2_base_types::v2_primitives::V2PrimitiveType;
+    use crate::hl7_v2_complex_types::hl7_v2_complex_types::{
+        cast_component_as, cast_field, complex_type_descriptor, component_data_type,
+        V2FieldToString, V2Type,
+    };
+    use crate::hl7_v2_constants::V2_SEARCH_EXPR_TYPE;
+    pub use crate::hl7_v2_field_descriptors::v2_field_descriptor::{
+        data_type_from_str, V2ComplexType, V2ComponentType,
+    };
+    pub use crate::hl7_v2_message_structures::v2_message_structures::{V2_REPEATING, V2_SINGLE};
+    pub use crate::hl7_v2_optionality_rules::*;
+    use crate::hl7_v2_parser::v2_parser::{
+        V2FieldGroup, V2Message, V2ParserCharacters, V2SearchIndex, V2Segment,
+    };
+    use crate::hl7_v2_search::REGEX_V2_SEARCH_DEFAULT;
+    use crate::hl7_v2_versions::v2_versions::{
+        version_data_type, version_field_descriptor, version_segment_descriptor, V2Version,
+        V2_BASELINE_VERSION,
+    };
+    use ::phf::Map;
+    use ::phf_macros::phf_map;
+    use rumtk_core::search::rumtk_search::string_search_named_captures;
+    use rumtk_core::strings::{format_compact, AsStr, RUMStringConversions};
+
+    /**************************** Types *****************************************/
+
+    ///
+    /// Definition of a field (sequence) of a segment as given in the segment attribute tables.
+    ///
+    /// ## Per Section 2.5.3
+    /// ```text
+    /// The segment attribute tables list the fields of each segment. For each field, the table gives
+    /// the sequence within the segment (SEQ), the maximum length (LEN), the data type (DT), the
+    /// optionality (OPT), the repetition (RP/#), the table number (TBL#), the item number (ITEM #),
+    /// and the element name.
+    /// ```
+    ///
+    #[derive(Debug)]
+    pub struct V2FieldTypeDescriptor {
+        pub name: &'static str,
+        pub description: &'static str,
+        pub data_type: V2ComponentType,
+        pub max_input_len: u32,
+        pub seq: u16,
+        pub valid_table: u16,
+        pub optionality: Optionality,
+        pub repeating: bool,
+    }
+
+    impl V2FieldTypeDescriptor {
+        #[allow(clippy::too_many_arguments)]
+        pub const fn new(
+            name: &'static str,
+            description: &'static str,
+            data_type: V2ComponentType,
+            max_input_len: u32,
+            seq: u16,
+            valid_table: u16,
+            optionality: Optionality,
+            repeating: bool,
+        ) -> V2FieldTypeDescriptor {
+            V2FieldTypeDescriptor {
+                name,
+                description,
+                data_type,
+                max_input_len,
+                seq,
+                valid_table,
+                optionality,
+                repeating,
+            }
+        }
+    }
+
+    pub type V2SegmentDescriptor = [&'static V2FieldTypeDescriptor];
+    pub type V2SegmentFieldDescriptors = Map<&'static str, &'static V2SegmentDescriptor>;
+    ///
+    /// Pairs of (field with varying type, field holding the HL7 data type code) per segment.
+    ///
+    pub type V2VariesFields = Map<&'static str, &'static [(u16, u16)]>;
+
+    /**************************** Constants *************************************/
+
+    ///
+    /// Generates instance of V2FieldTypeDescriptor which defines a field of a segment.
+    ///
+    /// ## Arguments
+    /// * `name` - String representing the field name.
+    /// * `description` - Element name as given in the segment attribute table.
+    /// * `data_type` - Appropriate [`V2ComponentType`] enumerator item describing the type of the
+    ///   field. [`V2ComponentType::Varies`] if another field of the segment carries the type.
+    /// * `max_input_len` - Maximum length of the field.
+    /// * `seq` - Sequence number of the field in the segment (MSH-1 is the field separator).
+    /// * `valid_table` - Validation table used for additional validation of input.
+    /// * `optionality` - Optionality of the field.
+    /// * `repeating` - [V2_REPEATING] or [V2_SINGLE].
+    ///
+    #[macro_export]
+    macro_rules! v2_field_descriptor {
+        (
+            $name:expr,
+            $description:expr,
+            $data_type:expr,
+            $max_input_len:expr,
+            $seq:expr,
+            $valid_table:expr,
+            $optionality:expr,
+            $repeating:expr ) => {{
+            &V2FieldTypeDescriptor::new(
+                $name,
+                $description,
+                $data_type,
+                $max_input_len,
+                $seq,
+                $valid_table,
+                $optionality,
+                $repeating,
+            )
+        }};
+    }
+
+    pub static V2_SEGMENT_FIELD_DESCRIPTORS: V2SegmentFieldDescriptors = phf_map! {
+        "AL1" => &[
+            v2_field_descriptor!("set_id", "Set ID - AL1", V2ComponentType::Primitive(V2PrimitiveType::SI), 4, 1, 0, Optionality::R, V2_SINGLE),
+            v2_field_descriptor!("allergen_type_code", "Allergen Type Code", V2ComponentType::Complex(V2ComplexType::CE), 250, 2, 127, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("allergen_code_mnemonic_description", "Allergen Code/Mnemonic/Description", V2ComponentType::Complex(V2ComplexType::CE), 250, 3, 0, Optionality::R, V2_SINGLE),
+            v2_field_descriptor!("allergy_severity_code", "Allergy Severity Code", V2ComponentType::Complex(V2ComplexType::CE), 250, 4, 128, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("allergy_reaction_code", "Allergy Reaction Code", V2ComponentType::Primitive(V2PrimitiveType::ST), 15, 5, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("identification_date", "Identification Date", V2ComponentType::Primitive(V2PrimitiveType::Date), 8, 6, 0, Optionality::B, V2_SINGLE)
+        ],
+        "BHS" => &[
+            v2_field_descriptor!("batch_field_separator", "Batch Field Separator", V2ComponentType::Primitive(V2PrimitiveType::ST), 1, 1, 0, Optionality::R, V2_SINGLE),
+            v2_field_descriptor!("batch_encoding_characters", "Batch Encoding Characters", V2ComponentType::Primitive(V2PrimitiveType::ST), 4, 2, 0, Optionality::R, V2_SINGLE),
+            v2_field_descriptor!("batch_sending_application", "Batch Sending Application", V2ComponentType::Complex(V2ComplexType::HD), 227, 3, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("batch_sending_facility", "Batch Sending Facility", V2ComponentType::Complex(V2ComplexType::HD), 227, 4, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("batch_receiving_application", "Batch Receiving Application", V2ComponentType::Complex(V2ComplexType::HD), 227, 5, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("batch_receiving_facility", "Batch Receiving Facility", V2ComponentType::Complex(V2ComplexType::HD), 227, 6, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("batch_creation_date_time", "Batch Creation Date/Time", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 26, 7, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("batch_security", "Batch Security", V2ComponentType::Primitive(V2PrimitiveType::ST), 40, 8, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("batch_name_id_type", "Batch Name/ID/Type", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 9, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("batch_comment", "Batch Comment", V2ComponentType::Primitive(V2PrimitiveType::ST), 80, 10, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("batch_control_id", "Batch Control ID", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 11, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("reference_batch_control_id", "Reference Batch Control ID", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 12, 0, Optionality::O, V2_SINGLE)
+        ],
+        "BTS" => &[
+            v2_field_descriptor!("batch_message_count", "Batch Message Count", V2ComponentType::Primitive(V2PrimitiveType::ST), 10, 1, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("batch_comment", "Batch Comment", V2ComponentType::Primitive(V2PrimitiveType::ST), 80, 2, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("batch_totals", "Batch Totals", V2ComponentType::Primitive(V2PrimitiveType::NM), 100, 3, 0, Optionality::O, V2_REPEATING)
+        ],
+        "DG1" => &[
+            v2_field_descriptor!("set_id", "Set ID - DG1", V2ComponentType::Primitive(V2PrimitiveType::SI), 4, 1, 0, Optionality::R, V2_SINGLE),
+            v2_field_descriptor!("diagnosis_coding_method", "Diagnosis Coding Method", V2ComponentType::Primitive(V2PrimitiveType::ID), 2, 2, 53, Optionality::B, V2_SINGLE),
+            v2_field_descriptor!("diagnosis_code", "Diagnosis Code - DG1", V2ComponentType::Complex(V2ComplexType::CE), 250, 3, 51, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("diagnosis_description", "Diagnosis Description", V2ComponentType::Primitive(V2PrimitiveType::ST), 40, 4, 0, Optionality::B, V2_SINGLE),
+            v2_field_descriptor!("diagnosis_date_time", "Diagnosis Date/Time", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 26, 5, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("diagnosis_type", "Diagnosis Type", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 6, 52, Optionality::R, V2_SINGLE),
+            v2_field_descriptor!("major_diagnostic_category", "Major Diagnostic Category", V2ComponentType::Complex(V2ComplexType::CE), 250, 7, 118, Optionality::B, V2_SINGLE),
+            v2_field_descriptor!("diagnostic_related_group", "Diagnostic Related Group", V2ComponentType::Complex(V2ComplexType::CE), 250, 8, 55, Optionality::B, V2_SINGLE),
+            v2_field_descriptor!("drg_approval_indicator", "DRG Approval Indicator", V2ComponentType::Primitive(V2PrimitiveType::ID), 1, 9, 136, Optionality::B, V2_SINGLE),
+            v2_field_descriptor!("drg_grouper_review_code", "DRG Grouper Review Code", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 10, 56, Optionality::B, V2_SINGLE),
+            v2_field_descriptor!("outlier_type", "Outlier Type", V2ComponentType::Complex(V2ComplexType::CE), 250, 11, 83, Optionality::B, V2_SINGLE),
+            v2_field_descriptor!("outlier_days", "Outlier Days", V2ComponentType::Primitive(V2PrimitiveType::NM), 3, 12, 0, Optionality::B, V2_SINGLE),
+            v2_field_descriptor!("outlier_cost", "Outlier Cost", V2ComponentType::Complex(V2ComplexType::CP), 12, 13, 0, Optionality::B, V2_SINGLE),
+            v2_field_descriptor!("grouper_version_and_type", "Grouper Version And Type", V2ComponentType::Primitive(V2PrimitiveType::ST), 4, 14, 0, Optionality::B, V2_SINGLE),
+            v2_field_descriptor!("diagnosis_priority", "Diagnosis Priority", V2ComponentType::Primitive(V2PrimitiveType::ID), 2, 15, 359, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("diagnosing_clinician", "Diagnosing Clinician", V2ComponentType::Complex(V2ComplexType::XCN), 250, 16, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("diagnosis_classification", "Diagnosis Classification", V2ComponentType::Primitive(V2PrimitiveType::IS), 3, 17, 228, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("confidential_indicator", "Confidential Indicator", V2ComponentType::Primitive(V2PrimitiveType::ID), 1, 18, 136, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("attestation_date_time", "Attestation Date/Time", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 26, 19, 0, Optionality::O, V2_SINGLE)
+        ],
+        "ERR" => &[
+            v2_field_descriptor!("error_code_and_location", "Error Code and Location", V2ComponentType::Complex(V2ComplexType::ELD), 493, 1, 0, Optionality::B, V2_REPEATING),
+            v2_field_descriptor!("error_location", "Error Location", V2ComponentType::Complex(V2ComplexType::ERL), 18, 2, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("hl7_error_code", "HL7 Error Code", V2ComponentType::Complex(V2ComplexType::CWE), 705, 3, 357, Optionality::R, V2_SINGLE),
+            v2_field_descriptor!("severity", "Severity", V2ComponentType::Primitive(V2PrimitiveType::ID), 2, 4, 516, Optionality::R, V2_SINGLE),
+            v2_field_descriptor!("application_error_code", "Application Error Code", V2ComponentType::Complex(V2ComplexType::CWE), 705, 5, 533, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("application_error_parameter", "Application Error Parameter", V2ComponentType::Primitive(V2PrimitiveType::ST), 80, 6, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("diagnostic_information", "Diagnostic Information", V2ComponentType::Primitive(V2PrimitiveType::Text), 2048, 7, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("user_message", "User Message", V2ComponentType::Primitive(V2PrimitiveType::Text), 250, 8, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("inform_person_indicator", "Inform Person Indicator", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 9, 517, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("override_type", "Override Type", V2ComponentType::Complex(V2ComplexType::CWE), 705, 10, 518, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("override_reason_code", "Override Reason Code", V2ComponentType::Complex(V2ComplexType::CWE), 705, 11, 519, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("help_desk_contact_point", "Help Desk Contact Point", V2ComponentType::Complex(V2ComplexType::XTN), 652, 12, 0, Optionality::O, V2_REPEATING)
+        ],
+        "EVN" => &[
+            v2_field_descriptor!("event_type_code", "Event Type Code", V2ComponentType::Primitive(V2PrimitiveType::ID), 3, 1, 3, Optionality::B, V2_SINGLE),
+            v2_field_descriptor!("recorded_date_time", "Recorded Date/Time", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 26, 2, 0, Optionality::R, V2_SINGLE),
+            v2_field_descriptor!("date_time_planned_event", "Date/Time Planned Event", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 26, 3, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("event_reason_code", "Event Reason Code", V2ComponentType::Primitive(V2PrimitiveType::IS), 3, 4, 62, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("operator_id", "Operator ID", V2ComponentType::Complex(V2ComplexType::XCN), 250, 5, 188, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("event_occurred", "Event Occurred", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 26, 6, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("event_facility", "Event Facility", V2ComponentType::Complex(V2ComplexType::HD), 241, 7, 0, Optionality::O, V2_SINGLE)
+        ],
+        "FHS" => &[
+            v2_field_descriptor!("file_field_separator", "File Field Separator", V2ComponentType::Primitive(V2PrimitiveType::ST), 1, 1, 0, Optionality::R, V2_SINGLE),
+            v2_field_descriptor!("file_encoding_characters", "File Encoding Characters", V2ComponentType::Primitive(V2PrimitiveType::ST), 4, 2, 0, Optionality::R, V2_SINGLE),
+            v2_field_descriptor!("file_sending_application", "File Sending Application", V2ComponentType::Complex(V2ComplexType::HD), 227, 3, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("file_sending_facility", "File Sending Facility", V2ComponentType::Complex(V2ComplexType::HD), 227, 4, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("file_receiving_application", "File Receiving Application", V2ComponentType::Complex(V2ComplexType::HD), 227, 5, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("file_receiving_facility", "File Receiving Facility", V2ComponentType::Complex(V2ComplexType::HD), 227, 6, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("file_creation_date_time", "File Creation Date/Time", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 26, 7, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("file_security", "File Security", V2ComponentType::Primitive(V2PrimitiveType::ST), 40, 8, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("file_name_id", "File Name/ID", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 9, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("file_header_comment", "File Header Comment", V2ComponentType::Primitive(V2PrimitiveType::ST), 80, 10, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("file_control_id", "File Control ID", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 11, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("reference_file_control_id", "Reference File Control ID", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 12, 0, Optionality::O, V2_SINGLE)
+        ],
+        "FTS" => &[
+            v2_field_descriptor!("file_batch_count", "File Batch Count", V2ComponentType::Primitive(V2PrimitiveType::NM), 10, 1, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("file_trailer_comment", "File Trailer Comment", V2ComponentType::Primitive(V2PrimitiveType::ST), 80, 2, 0, Optionality::O, V2_SINGLE)
+        ],
+        "MRG" => &[
+            v2_field_descriptor!("prior_patient_identifier_list", "Prior Patient Identifier List", V2ComponentType::Complex(V2ComplexType::CX), 250, 1, 0, Optionality::R, V2_REPEATING),
+            v2_field_descriptor!("prior_alternate_patient_id", "Prior Alternate Patient ID", V2ComponentType::Complex(V2ComplexType::CX), 250, 2, 0, Optionality::B, V2_REPEATING),
+            v2_field_descriptor!("prior_patient_account_number", "Prior Patient Account Number", V2ComponentType::Complex(V2ComplexType::CX), 250, 3, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("prior_patient_id", "Prior Patient ID", V2ComponentType::Complex(V2ComplexType::CX), 250, 4, 0, Optionality::B, V2_SINGLE),
+            v2_field_descriptor!("prior_visit_number", "Prior Visit Number", V2ComponentType::Complex(V2ComplexType::CX), 250, 5, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("prior_alternate_visit_id", "Prior Alternate Visit ID", V2ComponentType::Complex(V2ComplexType::CX), 250, 6, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("prior_patient_name", "Prior Patient Name", V2ComponentType::Complex(V2ComplexType::XPN), 250, 7, 0, Optionality::O, V2_REPEATING)
+        ],
+        "MSA" => &[
+            v2_field_descriptor!("acknowledgment_code", "Acknowledgment Code", V2ComponentType::Primitive(V2PrimitiveType::ID), 2, 1, 8, Optionality::R, V2_SINGLE),
+            v2_field_descriptor!("message_control_id", "Message Control ID", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 2, 0, Optionality::R, V2_SINGLE),
+            v2_field_descriptor!("text_message", "Text Message", V2ComponentType::Primitive(V2PrimitiveType::ST), 80, 3, 0, Optionality::B, V2_SINGLE),
+            v2_field_descriptor!("expected_sequence_number", "Expected Sequence Number", V2ComponentType::Primitive(V2PrimitiveType::NM), 15, 4, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("delayed_acknowledgment_type", "Delayed Acknowledgment Type", V2ComponentType::Primitive(V2PrimitiveType::ID), 1, 5, 0, Optionality::X, V2_SINGLE),
+            v2_field_descriptor!("error_condition", "Error Condition", V2ComponentType::Complex(V2ComplexType::CE), 250, 6, 357, Optionality::B, V2_SINGLE)
+        ],
+        "MSH" => &[
+            v2_field_descriptor!("field_separator", "Field Separator", V2ComponentType::Primitive(V2PrimitiveType::ST), 1, 1, 0, Optionality::R, V2_SINGLE),
+            v2_field_descriptor!("encoding_characters", "Encoding Characters", V2ComponentType::Primitive(V2PrimitiveType::ST), 4, 2, 0, Optionality::R, V2_SINGLE),
+            v2_field_descriptor!("sending_application", "Sending Application", V2ComponentType::Complex(V2ComplexType::HD), 227, 3, 361, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("sending_facility", "Sending Facility", V2ComponentType::Complex(V2ComplexType::HD), 227, 4, 362, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("receiving_application", "Receiving Application", V2ComponentType::Complex(V2ComplexType::HD), 227, 5, 361, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("receiving_facility", "Receiving Facility", V2ComponentType::Complex(V2ComplexType::HD), 227, 6, 362, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("date_time_of_message", "Date/Time of Message", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 26, 7, 0, Optionality::R, V2_SINGLE),
+            v2_field_descriptor!("security", "Security", V2ComponentType::Primitive(V2PrimitiveType::ST), 40, 8, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("message_type", "Message Type", V2ComponentType::Complex(V2ComplexType::MSG), 15, 9, 0, Optionality::R, V2_SINGLE),
+            v2_field_descriptor!("message_control_id", "Message Control ID", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 10, 0, Optionality::R, V2_SINGLE),
+            v2_field_descriptor!("processing_id", "Processing ID", V2ComponentType::Complex(V2ComplexType::PT), 3, 11, 0, Optionality::R, V2_SINGLE),
+            v2_field_descriptor!("version_id", "Version ID", V2ComponentType::Complex(V2ComplexType::VID), 60, 12, 104, Optionality::R, V2_SINGLE),
+            v2_field_descriptor!("sequence_number", "Sequence Number", V2ComponentType::Primitive(V2PrimitiveType::NM), 15, 13, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("continuation_pointer", "Continuation Pointer", V2ComponentType::Primitive(V2PrimitiveType::ST), 180, 14, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("accept_acknowledgment_type", "Accept Acknowledgment Type", V2ComponentType::Primitive(V2PrimitiveType::ID), 2, 15, 155, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("application_acknowledgment_type", "Application Acknowledgment Type", V2ComponentType::Primitive(V2PrimitiveType::ID), 2, 16, 155, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("country_code", "Country Code", V2ComponentType::Primitive(V2PrimitiveType::ID), 3, 17, 399, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("character_set", "Character Set", V2ComponentType::Primitive(V2PrimitiveType::ID), 16, 18, 211, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("principal_language_of_message", "Principal Language of Message", V2ComponentType::Complex(V2ComplexType::CE), 250, 19, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("alternate_character_set_handling_scheme", "Alternate Character Set Handling Scheme", V2ComponentType::Primitive(V2PrimitiveType::ID), 20, 20, 356, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("message_profile_identifier", "Message Profile Identifier", V2ComponentType::Complex(V2ComplexType::EI), 427, 21, 0, Optionality::O, V2_REPEATING)
+        ],
+        "NK1" => &[
+            v2_field_descriptor!("set_id", "Set ID - NK1", V2ComponentType::Primitive(V2PrimitiveType::SI), 4, 1, 0, Optionality::R, V2_SINGLE),
+            v2_field_descriptor!("name", "Name", V2ComponentType::Complex(V2ComplexType::XPN), 250, 2, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("relationship", "Relationship", V2ComponentType::Complex(V2ComplexType::CE), 250, 3, 63, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("address", "Address", V2ComponentType::Complex(V2ComplexType::XAD), 250, 4, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("phone_number", "Phone Number", V2ComponentType::Complex(V2ComplexType::XTN), 250, 5, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("business_phone_number", "Business Phone Number", V2ComponentType::Complex(V2ComplexType::XTN), 250, 6, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("contact_role", "Contact Role", V2ComponentType::Complex(V2ComplexType::CE), 250, 7, 131, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("start_date", "Start Date", V2ComponentType::Primitive(V2PrimitiveType::Date), 8, 8, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("end_date", "End Date", V2ComponentType::Primitive(V2PrimitiveType::Date), 8, 9, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("next_of_kin_associated_parties_job_title", "Next of Kin / Associated Parties Job Title", V2ComponentType::Primitive(V2PrimitiveType::ST), 60, 10, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("next_of_kin_associated_parties_job_code_class", "Next of Kin / Associated Parties Job Code/Class", V2ComponentType::Complex(V2ComplexType::JCC), 20, 11, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("next_of_kin_associated_parties_employee_number", "Next of Kin / Associated Parties Employee Number", V2ComponentType::Complex(V2ComplexType::CX), 250, 12, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("organization_name", "Organization Name - NK1", V2ComponentType::Complex(V2ComplexType::XON), 250, 13, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("marital_status", "Marital Status", V2ComponentType::Complex(V2ComplexType::CE), 250, 14, 2, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("administrative_sex", "Administrative Sex", V2ComponentType::Primitive(V2PrimitiveType::IS), 1, 15, 1, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("date_time_of_birth", "Date/Time of Birth", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 26, 16, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("living_dependency", "Living Dependency", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 17, 223, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("ambulatory_status", "Ambulatory Status", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 18, 9, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("citizenship", "Citizenship", V2ComponentType::Complex(V2ComplexType::CE), 250, 19, 171, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("primary_language", "Primary Language", V2ComponentType::Complex(V2ComplexType::CE), 250, 20, 296, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("living_arrangement", "Living Arrangement", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 21, 220, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("publicity_code", "Publicity Code", V2ComponentType::Complex(V2ComplexType::CE), 250, 22, 215, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("protection_indicator", "Protection Indicator", V2ComponentType::Primitive(V2PrimitiveType::ID), 1, 23, 136, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("student_indicator", "Student Indicator", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 24, 231, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("religion", "Religion", V2ComponentType::Complex(V2ComplexType::CE), 250, 25, 6, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("mothers_maiden_name", "Mother's Maiden Name", V2ComponentType::Complex(V2ComplexType::XPN), 250, 26, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("nationality", "Nationality", V2ComponentType::Complex(V2ComplexType::CE), 250, 27, 212, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("ethnic_group", "Ethnic Group", V2ComponentType::Complex(V2ComplexType::CE), 250, 28, 189, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("contact_reason", "Contact Reason", V2ComponentType::Complex(V2ComplexType::CE), 250, 29, 222, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("contact_persons_name", "Contact Person's Name", V2ComponentType::Complex(V2ComplexType::XPN), 250, 30, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("contact_persons_telephone_number", "Contact Person's Telephone Number", V2ComponentType::Complex(V2ComplexType::XTN), 250, 31, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("contact_persons_address", "Contact Person's Address", V2ComponentType::Complex(V2ComplexType::XAD), 250, 32, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("next_of_kin_associated_partys_identifiers", "Next of Kin/Associated Party's Identifiers", V2ComponentType::Complex(V2ComplexType::CX), 250, 33, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("job_status", "Job Status", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 34, 311, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("race", "Race", V2ComponentType::Complex(V2ComplexType::CE), 250, 35, 5, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("handicap", "Handicap", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 36, 295, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("contact_person_social_security_number", "Contact Person Social Security Number", V2ComponentType::Primitive(V2PrimitiveType::ST), 16, 37, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("next_of_kin_birth_place", "Next of Kin Birth Place", V2ComponentType::Primitive(V2PrimitiveType::ST), 250, 38, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("vip_indicator", "VIP Indicator", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 39, 99, Optionality::O, V2_SINGLE)
+        ],
+        "NTE" => &[
+            v2_field_descriptor!("set_id", "Set ID - NTE", V2ComponentType::Primitive(V2PrimitiveType::SI), 4, 1, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("source_of_comment", "Source of Comment", V2ComponentType::Primitive(V2PrimitiveType::ID), 8, 2, 105, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("comment", "Comment", V2ComponentType::Primitive(V2PrimitiveType::FT), 65536, 3, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("comment_type", "Comment Type", V2ComponentType::Complex(V2ComplexType::CE), 250, 4, 364, Optionality::O, V2_SINGLE)
+        ],
+        "OBR" => &[
+            v2_field_descriptor!("set_id", "Set ID - OBR", V2ComponentType::Primitive(V2PrimitiveType::SI), 4, 1, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("placer_order_number", "Placer Order Number", V2ComponentType::Complex(V2ComplexType::EI), 22, 2, 0, Optionality::C(Some(CONDITION_PLACER_ORDER_NUMBER)), V2_SINGLE),
+            v2_field_descriptor!("filler_order_number", "Filler Order Number", V2ComponentType::Complex(V2ComplexType::EI), 22, 3, 0, Optionality::C(Some(CONDITION_FILLER_ORDER_NUMBER)), V2_SINGLE),
+            v2_field_descriptor!("universal_service_identifier", "Universal Service Identifier", V2ComponentType::Complex(V2ComplexType::CE), 250, 4, 0, Optionality::R, V2_SINGLE),
+            v2_field_descriptor!("priority", "Priority - OBR", V2ComponentType::Primitive(V2PrimitiveType::ID), 2, 5, 0, Optionality::B, V2_SINGLE),
+            v2_field_descriptor!("requested_date_time", "Requested Date/Time", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 26, 6, 0, Optionality::B, V2_SINGLE),
+            v2_field_descriptor!("observation_date_time", "Observation Date/Time", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 26, 7, 0, Optionality::C(Some(CONDITION_OBR_RESULT)), V2_SINGLE),
+            v2_field_descriptor!("observation_end_date_time", "Observation End Date/Time", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 26, 8, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("collection_volume", "Collection Volume", V2ComponentType::Complex(V2ComplexType::CQ), 20, 9, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("collector_identifier", "Collector Identifier", V2ComponentType::Complex(V2ComplexType::XCN), 250, 10, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("specimen_action_code", "Specimen Action Code", V2ComponentType::Primitive(V2PrimitiveType::ID), 1, 11, 65, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("danger_code", "Danger Code", V2ComponentType::Complex(V2ComplexType::CE), 250, 12, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("relevant_clinical_information", "Relevant Clinical Information", V2ComponentType::Primitive(V2PrimitiveType::ST), 300, 13, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("specimen_received_date_time", "Specimen Received Date/Time", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 26, 14, 0, Optionality::B, V2_SINGLE),
+            v2_field_descriptor!("specimen_source", "Specimen Source", V2ComponentType::Complex(V2ComplexType::SPS), 300, 15, 0, Optionality::B, V2_SINGLE),
+            v2_field_descriptor!("ordering_provider", "Ordering Provider", V2ComponentType::Complex(V2ComplexType::XCN), 250, 16, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("order_callback_phone_number", "Order Callback Phone Number", V2ComponentType::Complex(V2ComplexType::XTN), 250, 17, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("placer_field_1", "Placer Field 1", V2ComponentType::Primitive(V2PrimitiveType::ST), 60, 18, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("placer_field_2", "Placer Field 2", V2ComponentType::Primitive(V2PrimitiveType::ST), 60, 19, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("filler_field_1", "Filler Field 1", V2ComponentType::Primitive(V2PrimitiveType::ST), 60, 20, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("filler_field_2", "Filler Field 2", V2ComponentType::Primitive(V2PrimitiveType::ST), 60, 21, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("results_rpt_status_chng_date_time", "Results Rpt/Status Chng - Date/Time", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 26, 22, 0, Optionality::C(Some(CONDITION_OBR_RESULT)), V2_SINGLE),
+            v2_field_descriptor!("charge_to_practice", "Charge to Practice", V2ComponentType::Complex(V2ComplexType::MOC), 40, 23, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("diagnostic_serv_sect_id", "Diagnostic Serv Sect ID", V2ComponentType::Primitive(V2PrimitiveType::ID), 10, 24, 74, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("result_status", "Result Status", V2ComponentType::Primitive(V2PrimitiveType::ID), 1, 25, 123, Optionality::C(Some(CONDITION_OBR_RESULT)), V2_SINGLE),
+            v2_field_descriptor!("parent_result", "Parent Result", V2ComponentType::Complex(V2ComplexType::PRL), 400, 26, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("quantity_timing", "Quantity/Timing", V2ComponentType::Complex(V2ComplexType::TQ), 200, 27, 0, Optionality::B, V2_REPEATING),
+            v2_field_descriptor!("result_copies_to", "Result Copies To", V2ComponentType::Complex(V2ComplexType::XCN), 250, 28, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("parent", "Parent", V2ComponentType::Complex(V2ComplexType::EIP), 200, 29, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("transportation_mode", "Transportation Mode", V2ComponentType::Primitive(V2PrimitiveType::ID), 20, 30, 124, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("reason_for_study", "Reason for Study", V2ComponentType::Complex(V2ComplexType::CE), 250, 31, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("principal_result_interpreter", "Principal Result Interpreter", V2ComponentType::Complex(V2ComplexType::NDL), 200, 32, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("assistant_result_interpreter", "Assistant Result Interpreter", V2ComponentType::Complex(V2ComplexType::NDL), 200, 33, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("technician", "Technician", V2ComponentType::Complex(V2ComplexType::NDL), 200, 34, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("transcriptionist", "Transcriptionist", V2ComponentType::Complex(V2ComplexType::NDL), 200, 35, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("scheduled_date_time", "Scheduled Date/Time", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 26, 36, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("number_of_sample_containers", "Number of Sample Containers", V2ComponentType::Primitive(V2PrimitiveType::NM), 4, 37, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("transport_logistics_of_collected_sample", "Transport Logistics of Collected Sample", V2ComponentType::Complex(V2ComplexType::CE), 250, 38, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("collectors_comment", "Collector's Comment", V2ComponentType::Complex(V2ComplexType::CE), 250, 39, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("transport_arrangement_responsibility", "Transport Arrangement Responsibility", V2ComponentType::Complex(V2ComplexType::CE), 250, 40, 336, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("transport_arranged", "Transport Arranged", V2ComponentType::Primitive(V2PrimitiveType::ID), 30, 41, 224, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("escort_required", "Escort Required", V2ComponentType::Primitive(V2PrimitiveType::ID), 1, 42, 225, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("planned_patient_transport_comment", "Planned Patient Transport Comment", V2ComponentType::Complex(V2ComplexType::CE), 250, 43, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("procedure_code", "Procedure Code", V2ComponentType::Complex(V2ComplexType::CE), 250, 44, 88, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("procedure_code_modifier", "Procedure Code Modifier", V2ComponentType::Complex(V2ComplexType::CE), 250, 45, 340, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("placer_supplemental_service_information", "Placer Supplemental Service Information", V2ComponentType::Complex(V2ComplexType::CE), 250, 46, 411, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("filler_supplemental_service_information", "Filler Supplemental Service Information", V2ComponentType::Complex(V2ComplexType::CE), 250, 47, 411, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("medically_necessary_duplicate_procedure_reason", "Medically Necessary Duplicate Procedure Reason", V2ComponentType::Complex(V2ComplexType::CWE), 250, 48, 476, Optionality::C(None), V2_SINGLE),
+            v2_field_descriptor!("result_handling", "Result Handling", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 49, 507, Optionality::O, V2_SINGLE)
+        ],
+        "OBX" => &[
+            v2_field_descriptor!("set_id", "Set ID - OBX", V2ComponentType::Primitive(V2PrimitiveType::SI), 4, 1, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("value_type", "Value Type", V2ComponentType::Primitive(V2PrimitiveType::ID), 2, 2, 125, Optionality::C(Some(CONDITION_OBX_VALUE_TYPE)), V2_SINGLE),
+            v2_field_descriptor!("observation_identifier", "Observation Identifier", V2ComponentType::Complex(V2ComplexType::CE), 250, 3, 0, Optionality::R, V2_SINGLE),
+            v2_field_descriptor!("observation_sub_id", "Observation Sub-ID", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 4, 0, Optionality::C(Some(CONDITION_OBX_SUB_ID)), V2_SINGLE),
+            v2_field_descriptor!("observation_value", "Observation Value", V2ComponentType::Varies, 99999, 5, 0, Optionality::C(Some(CONDITION_OBX_VALUE)), V2_REPEATING),
+            v2_field_descriptor!("units", "Units", V2ComponentType::Complex(V2ComplexType::CE), 250, 6, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("references_range", "References Range", V2ComponentType::Primitive(V2PrimitiveType::ST), 60, 7, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("abnormal_flags", "Abnormal Flags", V2ComponentType::Primitive(V2PrimitiveType::IS), 5, 8, 78, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("probability", "Probability", V2ComponentType::Primitive(V2PrimitiveType::NM), 5, 9, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("nature_of_abnormal_test", "Nature of Abnormal Test", V2ComponentType::Primitive(V2PrimitiveType::ID), 2, 10, 80, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("observation_result_status", "Observation Result Status", V2ComponentType::Primitive(V2PrimitiveType::ID), 1, 11, 85, Optionality::R, V2_SINGLE),
+            v2_field_descriptor!("effective_date_of_reference_range", "Effective Date of Reference Range", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 26, 12, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("user_defined_access_checks", "User Defined Access Checks", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 13, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("date_time_of_the_observation", "Date/Time of the Observation", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 26, 14, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("producers_id", "Producer's ID", V2ComponentType::Complex(V2ComplexType::CE), 250, 15, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("responsible_observer", "Responsible Observer", V2ComponentType::Complex(V2ComplexType::XCN), 250, 16, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("observation_method", "Observation Method", V2ComponentType::Complex(V2ComplexType::CE), 250, 17, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("equipment_instance_identifier", "Equipment Instance Identifier", V2ComponentType::Complex(V2ComplexType::EI), 22, 18, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("date_time_of_the_analysis", "Date/Time of the Analysis", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 26, 19, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("observation_site", "Observation Site", V2ComponentType::Complex(V2ComplexType::CWE), 250, 20, 163, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("observation_instance_identifier", "Observation Instance Identifier", V2ComponentType::Complex(V2ComplexType::EI), 427, 21, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("mood_code", "Mood Code", V2ComponentType::Complex(V2ComplexType::CNE), 705, 22, 725, Optionality::C(None), V2_SINGLE),
+            v2_field_descriptor!("performing_organization_name", "Performing Organization Name", V2ComponentType::Complex(V2ComplexType::XON), 570, 23, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("performing_organization_address", "Performing Organization Address", V2ComponentType::Complex(V2ComplexType::XAD), 2915, 24, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("performing_organization_medical_director", "Performing Organization Medical Director", V2ComponentType::Complex(V2ComplexType::XCN), 3220, 25, 0, Optionality::O, V2_SINGLE)
+        ],
+        "ORC" => &[
+            v2_field_descriptor!("order_control", "Order Control", V2ComponentType::Primitive(V2PrimitiveType::ID), 2, 1, 119, Optionality::R, V2_SINGLE),
+            v2_field_descriptor!("placer_order_number", "Placer Order Number", V2ComponentType::Complex(V2ComplexType::EI), 22, 2, 0, Optionality::C(Some(CONDITION_PLACER_ORDER_NUMBER)), V2_SINGLE),
+            v2_field_descriptor!("filler_order_number", "Filler Order Number", V2ComponentType::Complex(V2ComplexType::EI), 22, 3, 0, Optionality::C(Some(CONDITION_FILLER_ORDER_NUMBER)), V2_SINGLE),
+            v2_field_descriptor!("placer_group_number", "Placer Group Number", V2ComponentType::Complex(V2ComplexType::EI), 22, 4, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("order_status", "Order Status", V2ComponentType::Primitive(V2PrimitiveType::ID), 2, 5, 38, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("response_flag", "Response Flag", V2ComponentType::Primitive(V2PrimitiveType::ID), 1, 6, 121, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("quantity_timing", "Quantity/Timing", V2ComponentType::Complex(V2ComplexType::TQ), 200, 7, 0, Optionality::B, V2_REPEATING),
+            v2_field_descriptor!("parent", "Parent", V2ComponentType::Complex(V2ComplexType::EIP), 200, 8, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("date_time_of_transaction", "Date/Time of Transaction", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 26, 9, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("entered_by", "Entered By", V2ComponentType::Complex(V2ComplexType::XCN), 250, 10, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("verified_by", "Verified By", V2ComponentType::Complex(V2ComplexType::XCN), 250, 11, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("ordering_provider", "Ordering Provider", V2ComponentType::Complex(V2ComplexType::XCN), 250, 12, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("enterers_location", "Enterer's Location", V2ComponentType::Complex(V2ComplexType::PL), 80, 13, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("call_back_phone_number", "Call Back Phone Number", V2ComponentType::Complex(V2ComplexType::XTN), 250, 14, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("order_effective_date_time", "Order Effective Date/Time", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 26, 15, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("order_control_code_reason", "Order Control Code Reason", V2ComponentType::Complex(V2ComplexType::CE), 250, 16, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("entering_organization", "Entering Organization", V2ComponentType::Complex(V2ComplexType::CE), 250, 17, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("entering_device", "Entering Device", V2ComponentType::Complex(V2ComplexType::CE), 250, 18, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("action_by", "Action By", V2ComponentType::Complex(V2ComplexType::XCN), 250, 19, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("advanced_beneficiary_notice_code", "Advanced Beneficiary Notice Code", V2ComponentType::Complex(V2ComplexType::CE), 250, 20, 339, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("ordering_facility_name", "Ordering Facility Name", V2ComponentType::Complex(V2ComplexType::XON), 250, 21, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("ordering_facility_address", "Ordering Facility Address", V2ComponentType::Complex(V2ComplexType::XAD), 250, 22, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("ordering_facility_phone_number", "Ordering Facility Phone Number", V2ComponentType::Complex(V2ComplexType::XTN), 250, 23, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("ordering_provider_address", "Ordering Provider Address", V2ComponentType::Complex(V2ComplexType::XAD), 250, 24, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("order_status_modifier", "Order Status Modifier", V2ComponentType::Complex(V2ComplexType::CWE), 250, 25, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("advanced_beneficiary_notice_override_reason", "Advanced Beneficiary Notice Override Reason", V2ComponentType::Complex(V2ComplexType::CWE), 60, 26, 552, Optionality::C(Some(CONDITION_ORC_ABN_OVERRIDE_REASON)), V2_SINGLE),
+            v2_field_descriptor!("fillers_expected_availability_date_time", "Filler's Expected Availability Date/Time", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 26, 27, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("confidentiality_code", "Confidentiality Code", V2ComponentType::Complex(V2ComplexType::CWE), 250, 28, 177, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("order_type", "Order Type", V2ComponentType::Complex(V2ComplexType::CWE), 250, 29, 482, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("enterer_authorization_mode", "Enterer Authorization Mode", V2ComponentType::Complex(V2ComplexType::CNE), 250, 30, 483, Optionality::O, V2_SINGLE)
+        ],
+        "PD1" => &[
+            v2_field_descriptor!("living_dependency", "Living Dependency", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 1, 223, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("living_arrangement", "Living Arrangement", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 2, 220, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("patient_primary_facility", "Patient Primary Facility", V2ComponentType::Complex(V2ComplexType::XON), 250, 3, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("patient_primary_care_provider_name_and_id_no", "Patient Primary Care Provider Name & ID No.", V2ComponentType::Complex(V2ComplexType::XCN), 250, 4, 0, Optionality::B, V2_REPEATING),
+            v2_field_descriptor!("student_indicator", "Student Indicator", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 5, 231, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("handicap", "Handicap", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 6, 295, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("living_will_code", "Living Will Code", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 7, 315, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("organ_donor_code", "Organ Donor Code", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 8, 316, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("separate_bill", "Separate Bill", V2ComponentType::Primitive(V2PrimitiveType::ID), 1, 9, 136, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("duplicate_patient", "Duplicate Patient", V2ComponentType::Complex(V2ComplexType::CX), 250, 10, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("publicity_code", "Publicity Code", V2ComponentType::Complex(V2ComplexType::CE), 250, 11, 215, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("protection_indicator", "Protection Indicator", V2ComponentType::Primitive(V2PrimitiveType::ID), 1, 12, 136, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("protection_indicator_effective_date", "Protection Indicator Effective Date", V2ComponentType::Primitive(V2PrimitiveType::Date), 8, 13, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("place_of_worship", "Place of Worship", V2ComponentType::Complex(V2ComplexType::XON), 250, 14, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("advance_directive_code", "Advance Directive Code", V2ComponentType::Complex(V2ComplexType::CE), 250, 15, 435, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("immunization_registry_status", "Immunization Registry Status", V2ComponentType::Primitive(V2PrimitiveType::IS), 1, 16, 441, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("immunization_registry_status_effective_date", "Immunization Registry Status Effective Date", V2ComponentType::Primitive(V2PrimitiveType::Date), 8, 17, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("publicity_code_effective_date", "Publicity Code Effective Date", V2ComponentType::Primitive(V2PrimitiveType::Date), 8, 18, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("military_branch", "Military Branch", V2ComponentType::Primitive(V2PrimitiveType::IS), 5, 19, 140, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("military_rank_grade", "Military Rank/Grade", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 20, 141, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("military_status", "Military Status", V2ComponentType::Primitive(V2PrimitiveType::IS), 3, 21, 142, Optionality::O, V2_SINGLE)
+        ],
+        "PID" => &[
+            v2_field_descriptor!("set_id", "Set ID - PID", V2ComponentType::Primitive(V2PrimitiveType::SI), 4, 1, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("patient_id", "Patient ID", V2ComponentType::Complex(V2ComplexType::CX), 20, 2, 0, Optionality::B, V2_SINGLE),
+            v2_field_descriptor!("patient_identifier_list", "Patient Identifier List", V2ComponentType::Complex(V2ComplexType::CX), 250, 3, 0, Optionality::R, V2_REPEATING),
+            v2_field_descriptor!("alternate_patient_id", "Alternate Patient ID - PID", V2ComponentType::Complex(V2ComplexType::CX), 20, 4, 0, Optionality::B, V2_REPEATING),
+            v2_field_descriptor!("patient_name", "Patient Name", V2ComponentType::Complex(V2ComplexType::XPN), 250, 5, 0, Optionality::R, V2_REPEATING),
+            v2_field_descriptor!("mothers_maiden_name", "Mother's Maiden Name", V2ComponentType::Complex(V2ComplexType::XPN), 250, 6, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("date_time_of_birth", "Date/Time of Birth", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 26, 7, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("administrative_sex", "Administrative Sex", V2ComponentType::Primitive(V2PrimitiveType::IS), 1, 8, 1, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("patient_alias", "Patient Alias", V2ComponentType::Complex(V2ComplexType::XPN), 250, 9, 0, Optionality::B, V2_REPEATING),
+            v2_field_descriptor!("race", "Race", V2ComponentType::Complex(V2ComplexType::CE), 250, 10, 5, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("patient_address", "Patient Address", V2ComponentType::Complex(V2ComplexType::XAD), 250, 11, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("county_code", "County Code", V2ComponentType::Primitive(V2PrimitiveType::IS), 4, 12, 289, Optionality::B, V2_SINGLE),
+            v2_field_descriptor!("phone_number_home", "Phone Number - Home", V2ComponentType::Complex(V2ComplexType::XTN), 250, 13, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("phone_number_business", "Phone Number - Business", V2ComponentType::Complex(V2ComplexType::XTN), 250, 14, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("primary_language", "Primary Language", V2ComponentType::Complex(V2ComplexType::CE), 250, 15, 296, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("marital_status", "Marital Status", V2ComponentType::Complex(V2ComplexType::CE), 250, 16, 2, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("religion", "Religion", V2ComponentType::Complex(V2ComplexType::CE), 250, 17, 6, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("patient_account_number", "Patient Account Number", V2ComponentType::Complex(V2ComplexType::CX), 250, 18, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("ssn_number_patient", "SSN Number - Patient", V2ComponentType::Primitive(V2PrimitiveType::ST), 16, 19, 0, Optionality::B, V2_SINGLE),
+            v2_field_descriptor!("drivers_license_number_patient", "Driver's License Number - Patient", V2ComponentType::Complex(V2ComplexType::DLN), 25, 20, 0, Optionality::B, V2_SINGLE),
+            v2_field_descriptor!("mothers_identifier", "Mother's Identifier", V2ComponentType::Complex(V2ComplexType::CX), 250, 21, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("ethnic_group", "Ethnic Group", V2ComponentType::Complex(V2ComplexType::CE), 250, 22, 189, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("birth_place", "Birth Place", V2ComponentType::Primitive(V2PrimitiveType::ST), 250, 23, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("multiple_birth_indicator", "Multiple Birth Indicator", V2ComponentType::Primitive(V2PrimitiveType::ID), 1, 24, 136, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("birth_order", "Birth Order", V2ComponentType::Primitive(V2PrimitiveType::NM), 2, 25, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("citizenship", "Citizenship", V2ComponentType::Complex(V2ComplexType::CE), 250, 26, 171, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("veterans_military_status", "Veterans Military Status", V2ComponentType::Complex(V2ComplexType::CE), 250, 27, 172, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("nationality", "Nationality", V2ComponentType::Complex(V2ComplexType::CE), 250, 28, 212, Optionality::B, V2_SINGLE),
+            v2_field_descriptor!("patient_death_date_and_time", "Patient Death Date and Time", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 26, 29, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("patient_death_indicator", "Patient Death Indicator", V2ComponentType::Primitive(V2PrimitiveType::ID), 1, 30, 136, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("identity_unknown_indicator", "Identity Unknown Indicator", V2ComponentType::Primitive(V2PrimitiveType::ID), 1, 31, 136, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("identity_reliability_code", "Identity Reliability Code", V2ComponentType::Primitive(V2PrimitiveType::IS), 20, 32, 445, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("last_update_date_time", "Last Update Date/Time", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 26, 33, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("last_update_facility", "Last Update Facility", V2ComponentType::Complex(V2ComplexType::HD), 241, 34, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("species_code", "Species Code", V2ComponentType::Complex(V2ComplexType::CE), 250, 35, 446, Optionality::C(None), V2_SINGLE),
+            v2_field_descriptor!("breed_code", "Breed Code", V2ComponentType::Complex(V2ComplexType::CE), 250, 36, 447, Optionality::C(None), V2_SINGLE),
+            v2_field_descriptor!("strain", "Strain", V2ComponentType::Primitive(V2PrimitiveType::ST), 80, 37, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("production_class_code", "Production Class Code", V2ComponentType::Complex(V2ComplexType::CE), 250, 38, 429, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("tribal_citizenship", "Tribal Citizenship", V2ComponentType::Complex(V2ComplexType::CWE), 250, 39, 171, Optionality::O, V2_REPEATING)
+        ],
+        "PV1" => &[
+            v2_field_descriptor!("set_id", "Set ID - PV1", V2ComponentType::Primitive(V2PrimitiveType::SI), 4, 1, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("patient_class", "Patient Class", V2ComponentType::Primitive(V2PrimitiveType::IS), 1, 2, 4, Optionality::R, V2_SINGLE),
+            v2_field_descriptor!("assigned_patient_location", "Assigned Patient Location", V2ComponentType::Complex(V2ComplexType::PL), 80, 3, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("admission_type", "Admission Type", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 4, 7, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("preadmit_number", "Preadmit Number", V2ComponentType::Complex(V2ComplexType::CX), 250, 5, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("prior_patient_location", "Prior Patient Location", V2ComponentType::Complex(V2ComplexType::PL), 80, 6, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("attending_doctor", "Attending Doctor", V2ComponentType::Complex(V2ComplexType::XCN), 250, 7, 10, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("referring_doctor", "Referring Doctor", V2ComponentType::Complex(V2ComplexType::XCN), 250, 8, 10, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("consulting_doctor", "Consulting Doctor", V2ComponentType::Complex(V2ComplexType::XCN), 250, 9, 10, Optionality::B, V2_REPEATING),
+            v2_field_descriptor!("hospital_service", "Hospital Service", V2ComponentType::Primitive(V2PrimitiveType::IS), 3, 10, 69, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("temporary_location", "Temporary Location", V2ComponentType::Complex(V2ComplexType::PL), 80, 11, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("preadmit_test_indicator", "Preadmit Test Indicator", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 12, 87, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("re_admission_indicator", "Re-admission Indicator", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 13, 92, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("admit_source", "Admit Source", V2ComponentType::Primitive(V2PrimitiveType::IS), 6, 14, 23, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("ambulatory_status", "Ambulatory Status", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 15, 9, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("vip_indicator", "VIP Indicator", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 16, 99, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("admitting_doctor", "Admitting Doctor", V2ComponentType::Complex(V2ComplexType::XCN), 250, 17, 10, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("patient_type", "Patient Type", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 18, 18, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("visit_number", "Visit Number", V2ComponentType::Complex(V2ComplexType::CX), 250, 19, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("financial_class", "Financial Class", V2ComponentType::Complex(V2ComplexType::FC), 50, 20, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("charge_price_indicator", "Charge Price Indicator", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 21, 32, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("courtesy_code", "Courtesy Code", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 22, 45, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("credit_rating", "Credit Rating", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 23, 46, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("contract_code", "Contract Code", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 24, 44, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("contract_effective_date", "Contract Effective Date", V2ComponentType::Primitive(V2PrimitiveType::Date), 8, 25, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("contract_amount", "Contract Amount", V2ComponentType::Primitive(V2PrimitiveType::NM), 12, 26, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("contract_period", "Contract Period", V2ComponentType::Primitive(V2PrimitiveType::NM), 3, 27, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("interest_code", "Interest Code", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 28, 73, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("transfer_to_bad_debt_code", "Transfer to Bad Debt Code", V2ComponentType::Primitive(V2PrimitiveType::IS), 4, 29, 110, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("transfer_to_bad_debt_date", "Transfer to Bad Debt Date", V2ComponentType::Primitive(V2PrimitiveType::Date), 8, 30, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("bad_debt_agency_code", "Bad Debt Agency Code", V2ComponentType::Primitive(V2PrimitiveType::IS), 10, 31, 21, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("bad_debt_transfer_amount", "Bad Debt Transfer Amount", V2ComponentType::Primitive(V2PrimitiveType::NM), 12, 32, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("bad_debt_recovery_amount", "Bad Debt Recovery Amount", V2ComponentType::Primitive(V2PrimitiveType::NM), 12, 33, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("delete_account_indicator", "Delete Account Indicator", V2ComponentType::Primitive(V2PrimitiveType::IS), 1, 34, 111, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("delete_account_date", "Delete Account Date", V2ComponentType::Primitive(V2PrimitiveType::Date), 8, 35, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("discharge_disposition", "Discharge Disposition", V2ComponentType::Primitive(V2PrimitiveType::IS), 3, 36, 112, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("discharged_to_location", "Discharged to Location", V2ComponentType::Complex(V2ComplexType::DLD), 47, 37, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("diet_type", "Diet Type", V2ComponentType::Complex(V2ComplexType::CE), 250, 38, 114, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("servicing_facility", "Servicing Facility", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 39, 115, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("bed_status", "Bed Status", V2ComponentType::Primitive(V2PrimitiveType::IS), 1, 40, 116, Optionality::B, V2_SINGLE),
+            v2_field_descriptor!("account_status", "Account Status", V2ComponentType::Primitive(V2PrimitiveType::IS), 2, 41, 117, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("pending_location", "Pending Location", V2ComponentType::Complex(V2ComplexType::PL), 80, 42, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("prior_temporary_location", "Prior Temporary Location", V2ComponentType::Complex(V2ComplexType::PL), 80, 43, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("admit_date_time", "Admit Date/Time", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 26, 44, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("discharge_date_time", "Discharge Date/Time", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 26, 45, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("current_patient_balance", "Current Patient Balance", V2ComponentType::Primitive(V2PrimitiveType::NM), 12, 46, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("total_charges", "Total Charges", V2ComponentType::Primitive(V2PrimitiveType::NM), 12, 47, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("total_adjustments", "Total Adjustments", V2ComponentType::Primitive(V2PrimitiveType::NM), 12, 48, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("total_payments", "Total Payments", V2ComponentType::Primitive(V2PrimitiveType::NM), 12, 49, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("alternate_visit_id", "Alternate Visit ID", V2ComponentType::Complex(V2ComplexType::CX), 250, 50, 203, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("visit_indicator", "Visit Indicator", V2ComponentType::Primitive(V2PrimitiveType::IS), 1, 51, 326, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("other_healthcare_provider", "Other Healthcare Provider", V2ComponentType::Complex(V2ComplexType::XCN), 250, 52, 10, Optionality::B, V2_REPEATING)
+        ],
+        "RXA" => &[
+            v2_field_descriptor!("give_sub_id_counter", "Give Sub-ID Counter", V2ComponentType::Primitive(V2PrimitiveType::NM), 4, 1, 0, Optionality::R, V2_SINGLE),
+            v2_field_descriptor!("administration_sub_id_counter", "Administration Sub-ID Counter", V2ComponentType::Primitive(V2PrimitiveType::NM), 4, 2, 0, Optionality::R, V2_SINGLE),
+            v2_field_descriptor!("date_time_start_of_administration", "Date/Time Start of Administration", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 26, 3, 0, Optionality::R, V2_SINGLE),
+            v2_field_descriptor!("date_time_end_of_administration", "Date/Time End of Administration", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 26, 4, 0, Optionality::R, V2_SINGLE),
+            v2_field_descriptor!("administered_code", "Administered Code", V2ComponentType::Complex(V2ComplexType::CE), 250, 5, 292, Optionality::R, V2_SINGLE),
+            v2_field_descriptor!("administered_amount", "Administered Amount", V2ComponentType::Primitive(V2PrimitiveType::NM), 20, 6, 0, Optionality::R, V2_SINGLE),
+            v2_field_descriptor!("administered_units", "Administered Units", V2ComponentType::Complex(V2ComplexType::CE), 250, 7, 0, Optionality::C(Some(CONDITION_RXA_ADMINISTERED_UNITS)), V2_SINGLE),
+            v2_field_descriptor!("administered_dosage_form", "Administered Dosage Form", V2ComponentType::Complex(V2ComplexType::CE), 250, 8, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("administration_notes", "Administration Notes", V2ComponentType::Complex(V2ComplexType::CE), 250, 9, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("administering_provider", "Administering Provider", V2ComponentType::Complex(V2ComplexType::XCN), 250, 10, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("administered_at_location", "Administered-at Location", V2ComponentType::Complex(V2ComplexType::LA2), 200, 11, 0, Optionality::C(None), V2_SINGLE),
+            v2_field_descriptor!("administered_per_time_unit", "Administered Per (Time Unit)", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 12, 0, Optionality::C(None), V2_SINGLE),
+            v2_field_descriptor!("administered_strength", "Administered Strength", V2ComponentType::Primitive(V2PrimitiveType::NM), 20, 13, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("administered_strength_units", "Administered Strength Units", V2ComponentType::Complex(V2ComplexType::CE), 250, 14, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("substance_lot_number", "Substance Lot Number", V2ComponentType::Primitive(V2PrimitiveType::ST), 20, 15, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("substance_expiration_date", "Substance Expiration Date", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 26, 16, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("substance_manufacturer_name", "Substance Manufacturer Name", V2ComponentType::Complex(V2ComplexType::CE), 250, 17, 227, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("substance_treatment_refusal_reason", "Substance/Treatment Refusal Reason", V2ComponentType::Complex(V2ComplexType::CE), 250, 18, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("indication", "Indication", V2ComponentType::Complex(V2ComplexType::CE), 250, 19, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("completion_status", "Completion Status", V2ComponentType::Primitive(V2PrimitiveType::ID), 2, 20, 322, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("action_code", "Action Code - RXA", V2ComponentType::Primitive(V2PrimitiveType::ID), 2, 21, 323, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("system_entry_date_time", "System Entry Date/Time", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 26, 22, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("administered_drug_strength_volume", "Administered Drug Strength Volume", V2ComponentType::Primitive(V2PrimitiveType::NM), 5, 23, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("administered_drug_strength_volume_units", "Administered Drug Strength Volume Units", V2ComponentType::Complex(V2ComplexType::CWE), 250, 24, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("administered_barcode_identifier", "Administered Barcode Identifier", V2ComponentType::Complex(V2ComplexType::CWE), 60, 25, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("pharmacy_order_type", "Pharmacy Order Type", V2ComponentType::Primitive(V2PrimitiveType::ID), 1, 26, 480, Optionality::O, V2_SINGLE)
+        ],
+        "RXR" => &[
+            v2_field_descriptor!("route", "Route", V2ComponentType::Complex(V2ComplexType::CE), 250, 1, 162, Optionality::R, V2_SINGLE),
+            v2_field_descriptor!("administration_site", "Administration Site", V2ComponentType::Complex(V2ComplexType::CWE), 250, 2, 550, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("administration_device", "Administration Device", V2ComponentType::Complex(V2ComplexType::CE), 250, 3, 164, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("administration_method", "Administration Method", V2ComponentType::Complex(V2ComplexType::CWE), 250, 4, 165, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("routing_instruction", "Routing Instruction", V2ComponentType::Complex(V2ComplexType::CE), 250, 5, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("administration_site_modifier", "Administration Site Modifier", V2ComponentType::Complex(V2ComplexType::CWE), 250, 6, 495, Optionality::O, V2_SINGLE)
+        ],
+        "SPM" => &[
+            v2_field_descriptor!("set_id", "Set ID - SPM", V2ComponentType::Primitive(V2PrimitiveType::SI), 4, 1, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("specimen_id", "Specimen ID", V2ComponentType::Complex(V2ComplexType::EIP), 80, 2, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("specimen_parent_ids", "Specimen Parent IDs", V2ComponentType::Complex(V2ComplexType::EIP), 80, 3, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("specimen_type", "Specimen Type", V2ComponentType::Complex(V2ComplexType::CWE), 250, 4, 487, Optionality::R, V2_SINGLE),
+            v2_field_descriptor!("specimen_type_modifier", "Specimen Type Modifier", V2ComponentType::Complex(V2ComplexType::CWE), 250, 5, 541, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("specimen_additives", "Specimen Additives", V2ComponentType::Complex(V2ComplexType::CWE), 250, 6, 371, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("specimen_collection_method", "Specimen Collection Method", V2ComponentType::Complex(V2ComplexType::CWE), 250, 7, 488, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("specimen_source_site", "Specimen Source Site", V2ComponentType::Complex(V2ComplexType::CWE), 250, 8, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("specimen_source_site_modifier", "Specimen Source Site Modifier", V2ComponentType::Complex(V2ComplexType::CWE), 250, 9, 542, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("specimen_collection_site", "Specimen Collection Site", V2ComponentType::Complex(V2ComplexType::CWE), 250, 10, 543, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("specimen_role", "Specimen Role", V2ComponentType::Complex(V2ComplexType::CWE), 250, 11, 369, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("specimen_collection_amount", "Specimen Collection Amount", V2ComponentType::Complex(V2ComplexType::CQ), 20, 12, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("grouped_specimen_count", "Grouped Specimen Count", V2ComponentType::Primitive(V2PrimitiveType::NM), 6, 13, 0, Optionality::C(Some(CONDITION_SPM_GROUPED_COUNT)), V2_SINGLE),
+            v2_field_descriptor!("specimen_description", "Specimen Description", V2ComponentType::Primitive(V2PrimitiveType::ST), 250, 14, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("specimen_handling_code", "Specimen Handling Code", V2ComponentType::Complex(V2ComplexType::CWE), 250, 15, 376, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("specimen_risk_code", "Specimen Risk Code", V2ComponentType::Complex(V2ComplexType::CWE), 250, 16, 489, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("specimen_collection_date_time", "Specimen Collection Date/Time", V2ComponentType::Complex(V2ComplexType::DR), 26, 17, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("specimen_received_date_time", "Specimen Received Date/Time", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 26, 18, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("specimen_expiration_date_time", "Specimen Expiration Date/Time", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 26, 19, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("specimen_availability", "Specimen Availability", V2ComponentType::Primitive(V2PrimitiveType::ID), 1, 20, 136, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("specimen_reject_reason", "Specimen Reject Reason", V2ComponentType::Complex(V2ComplexType::CWE), 250, 21, 490, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("specimen_quality", "Specimen Quality", V2ComponentType::Complex(V2ComplexType::CWE), 250, 22, 491, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("specimen_appropriateness", "Specimen Appropriateness", V2ComponentType::Complex(V2ComplexType::CWE), 250, 23, 492, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("specimen_condition", "Specimen Condition", V2ComponentType::Complex(V2ComplexType::CWE), 250, 24, 493, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("specimen_current_quantity", "Specimen Current Quantity", V2ComponentType::Complex(V2ComplexType::CQ), 20, 25, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("number_of_specimen_containers", "Number of Specimen Containers", V2ComponentType::Primitive(V2PrimitiveType::NM), 4, 26, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("container_type", "Container Type", V2ComponentType::Complex(V2ComplexType::CWE), 250, 27, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("container_condition", "Container Condition", V2ComponentType::Complex(V2ComplexType::CWE), 250, 28, 544, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("specimen_child_role", "Specimen Child Role", V2ComponentType::Complex(V2ComplexType::CWE), 250, 29, 494, Optionality::O, V2_SINGLE)
+        ],
+        "TQ1" => &[
+            v2_field_descriptor!("set_id", "Set ID - TQ1", V2ComponentType::Primitive(V2PrimitiveType::SI), 4, 1, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("quantity", "Quantity", V2ComponentType::Complex(V2ComplexType::CQ), 20, 2, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("repeat_pattern", "Repeat Pattern", V2ComponentType::Complex(V2ComplexType::RPT), 540, 3, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("explicit_time", "Explicit Time", V2ComponentType::Primitive(V2PrimitiveType::Time), 20, 4, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("relative_time_and_units", "Relative Time and Units", V2ComponentType::Complex(V2ComplexType::CQ), 20, 5, 0, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("service_duration", "Service Duration", V2ComponentType::Complex(V2ComplexType::CQ), 20, 6, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("start_date_time", "Start Date/Time", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 26, 7, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("end_date_time", "End Date/Time", V2ComponentType::Primitive(V2PrimitiveType::DateTime), 26, 8, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("priority", "Priority", V2ComponentType::Complex(V2ComplexType::CWE), 250, 9, 485, Optionality::O, V2_REPEATING),
+            v2_field_descriptor!("condition_text", "Condition Text", V2ComponentType::Primitive(V2PrimitiveType::Text), 250, 10, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("text_instruction", "Text Instruction", V2ComponentType::Primitive(V2PrimitiveType::Text), 250, 11, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("conjunction", "Conjunction", V2ComponentType::Primitive(V2PrimitiveType::ID), 10, 12, 427, Optionality::C(None), V2_SINGLE),
+            v2_field_descriptor!("occurrence_duration", "Occurrence Duration", V2ComponentType::Complex(V2ComplexType::CQ), 20, 13, 0, Optionality::O, V2_SINGLE),
+            v2_field_descriptor!("total_occurrences", "Total Occurrences", V2ComponentType::Primitive(V2PrimitiveType::NM), 10, 14, 0, Optionality::O, V2_SINGLE)
+        ]
+    };
+
+    pub static V2_VARIES_FIELDS: V2VariesFields = phf_map! {
+        "OBX" => &[(5, 2)],
+    };
+
+    /**************************** Helpers ***************************************/
+
+    fn segment_descriptor(segment_name: &str) -> V2Result<&'static V2SegmentDescriptor> {
+        match V2_SEGMENT_FIELD_DESCRIPTORS.get(segment_name) {
+            Some(descriptor) => Ok(*descriptor),
+            None => Err(format_compact!(
+                "No field definitions available for segment {}!",
+                segment_name
+            )),
+        }
+    }
+
+    ///
+    /// Header segments (MSH, FHS, BHS) do not store the field separator, so position 1 of the
+    /// segment is sequence 2.
+    ///
+    fn position_to_seq(segment_name: &str, position: isize) -> isize {
+        match is_header_segment_name(segment_name) {
+            true => position + 1,
+            false => position,
+        }
+    }
+
+    pub(crate) fn seq_to_position(segment_name: &str, seq: isize) -> isize {
+        match is_header_segment_name(segment_name) {
+            true => seq - 1,
+            false => seq,
+        }
+    }
+
+    /**************************** API *******************************************/
+
+    impl V2Segment {
+        ///
+        /// Returns the definition of the field at `position`, using the same 1-indexed positions
+        /// as [V2Segment::get].
+        ///
+        pub fn field_definition(
+            &self,
+            position: isize,
+        ) -> V2Result<&'static V2FieldTypeDescriptor> {
+            self.field_definition_for(position, V2_BASELINE_VERSION)
+        }
+
+        ///
+        /// Same as [V2Segment::field_definition] but for the definitions of `version`. The data
+        /// type is the one listed in the tables. See [V2Segment::field_data_type_for] for the
+        /// type the field has in `version`.
+        ///
+        pub fn field_definition_for(
+            &self,
+            position: isize,
+            version: V2Version,
+        ) -> V2Result<&'static V2FieldTypeDescriptor> {
+            segment_descriptor(self.name())?;
+            let seq = position_to_seq(self.name(), position);
+            match u16::try_from(seq)
+                .ok()
+                .and_then(|seq| version_field_descriptor(self.name(), seq, version))
+            {
+                Some(descriptor) => Ok(descriptor),
+                None => Err(format_compact!(
+                    "Field {} is not defined for segment {}!",
+                    position,
+                    self.name()
+                )),
+            }
+        }
+
+        ///
+        /// Returns the definition of the field named `name` (e.g. `patient_name`) along with its
+        /// position in the segment.
+        ///
+        pub fn field_definition_by_name(
+            &self,
+            name: &str,
+        ) -> V2Result<(isize, &'static V2FieldTypeDescriptor)> {
+            self.field_definition_by_name_for(name, V2_BASELINE_VERSION)
+        }
+
+        ///
+        /// Same as [V2Segment::field_definition_by_name] but for the definitions of `version`.
+        /// Fields renamed between versions are only found under the name they have in `version`.
+        ///
+        pub fn field_definition_by_name_for(
+            &self,
+            name: &str,
+            version: V2Version,
+        ) -> V2Result<(isize, &'static V2FieldTypeDescriptor)> {
+            segment_descriptor(self.name())?;
+            match version_segment_descriptor(self.name(), version)
+                .into_iter()
+                .find(|descriptor| descriptor.name == name)
+            {
+                Some(descriptor) => Ok((
+                    seq_to_position(self.name(), descriptor.seq as isize),
+                    descriptor,
+                )),
+                None => Err(format_compact!(
+                    "Field {} is not defined for segment {}!",
+                    name,
+                    self.name()
+                )),
+            }
+        }
+
+        ///
+        /// Same as [V2Segment::get] but looks the field up by name.
+        ///
+        /// ## Example
+        /// ```
+        /// use rumtk_hl7_v2::hl7_v2_base_types::v2_base_types::find_segment_id;
+        /// use rumtk_hl7_v2::hl7_v2_parser::v2_parser::V2Message;
+        /// use rumtk_core::strings::AsStr;
+        ///
+        /// let message = V2Message::try_from_str("MSH|^~\\&|||||||ADT^A01|1|P|2.5.1\rPID|1||123||DOE^JOHN").unwrap();
+        /// let pid = message.get(&find_segment_id("PID").unwrap(), 1).unwrap();
+        /// let name = pid.field_by_name("patient_name").unwrap();
+        ///
+        /// assert_eq!(name[0].get(1).unwrap().get(1).unwrap().as_str(), "DOE", "Wrong patient name!");
+        /// ```
+        ///
+        pub fn field_by_name(&self, name: &str) -> V2Result<&V2FieldGroup> {
+            let (position, _) = self.field_definition_by_name(name)?;
+            self.get(position)
+        }
+
+        ///
+        /// Returns the data type of the field at `position` as listed in the tables. Fields whose
+        /// type varies, like OBX-5, are resolved from the field carrying the data type code, like
+        /// OBX-2.
+        ///
+        pub fn field_data_type(&self, position: isize) -> V2Result<V2ComponentType> {
+            let descriptor = self.field_definition(position)?;
+            match descriptor.data_type {
+                V2ComponentType::Varies => self.resolve_varies(descriptor),
+                data_type => Ok(data_type),
+            }
+        }
+
+        ///
+        /// Returns the data type of the field at `position` in `version`. See
+        /// [version_data_type] for how types change between versions. Types resolved from a data
+        /// type code (OBX-5) are taken as sent.
+        ///
+        pub fn field_data_type_for(
+            &self,
+            position: isize,
+            version: V2Version,
+        ) -> V2Result<V2ComponentType> {
+            let descriptor = self.field_definition_for(position, version)?;
+            match descriptor.data_type {
+                V2ComponentType::Varies => self.resolve_varies(descriptor),
+                data_type => Ok(version_data_type(&data_type, version)),
+            }
+        }
+
+        fn resolve_varies(&self, descriptor: &V2FieldTypeDescriptor) -> V2Result<V2ComponentType> {
+            let type_seq = match V2_VARIES_FIELDS.get(self.name()).and_then(|fields| {
+                fields
+                    .iter()
+                    .find(|(seq, _)| *seq == descriptor.seq)
+                    .map(|(_, type_seq)| *type_seq)
+            }) {
+                Some(type_seq) => type_seq,
+                None => {
+                    return Err(format_compact!(
+                        "No field declares the data type of {}-{}!",
+                        self.name(),
+                        descriptor.seq
+                    ))
+                }
+            };
+            let type_position = seq_to_position(self.name(), type_seq as isize);
+            let type_code = self.get(type_position)?[0].get(1)?.get(1)?.as_str();
+            match data_type_from_str(type_code) {
+                Some(data_type) => Ok(data_type),
+                None => Err(format_compact!(
+                    "Unknown data type {} in {}-{}!",
+                    type_code,
+                    self.name(),
+                    type_seq
+                )),
+            }
+        }
+
+        ///
+        /// Casts repetition `repetition` (1-indexed) of the field at `position` to the data type
+        /// given by its definition in `version`. Complex types are casted component by component,
+        /// see [V2Type::errors] for collecting what failed.
+        ///
+        pub fn get_typed(
+            &self,
+            position: isize,
+            repetition: usize,
+            characters: &V2ParserCharacters,
+            version: V2Version,
+        ) -> V2Result<V2Type> {
+            self.get_typed_in(position, repetition, characters, version, None)
+        }
+
+        ///
+        /// Same as [V2Segment::get_typed] but evaluates conditional fields with the message the
+        /// segment belongs to, if any.
+        ///
+        fn get_typed_in(
+            &self,
+            position: isize,
+            repetition: usize,
+            characters: &V2ParserCharacters,
+            version: V2Version,
+            message: Option<&V2Message>,
+        ) -> V2Result<V2Type> {
+            let descriptor = self.field_definition_for(position, version)?;
+            let data_type = self.field_data_type_for(position, version)?;
+            let components = self.field_components(position, repetition)?;
+            if components.iter().flatten().all(|c| c.is_empty()) {
+                if descriptor.optionality.is_required() {
+                    return Ok(V2Type::Err(format_compact!(
+                        "Required field {}-{} is missing!",
+                        self.name(),
+                        descriptor.seq
+                    )));
+                }
+                let mut context = V2ConditionContext::new(&components)
+                    .with_segment(self)
+                    .with_version(version);
+                context.message = message;
+                if descriptor.optionality.is_conditionally_required(&context) {
+                    return Ok(V2Type::Err(format_compact!(
+                        "Conditionally required field {}-{} is missing!",
+                        self.name(),
+                        descriptor.seq
+                    )));
+                }
+            }
+            Ok(cast_field(&components, &data_type, characters, version))
+        }
+
+        ///
+        /// Component list of a repetition of a field. Fields and repetitions that were not sent
+        /// yield an empty list.
+        ///
+        fn field_components(
+            &self,
+            position: isize,
+            repetition: usize,
+        ) -> V2Result<V2ComponentList> {
+            if repetition == 0 {
+                return Err("Repetitions are 1-indexed! Got 0!".to_rumstring());
+            }
+            if position > self.len() as isize {
+                return Ok(V2ComponentList::new());
+            }
+            match self.get(position)?.get(repetition - 1) {
+                Some(field) => Ok(field.to_component_list()),
+                None => Ok(V2ComponentList::new()),
+            }
+        }
+    }
+
+    impl V2Message {
+        ///
+        /// Casts the field, component, or subcomponent addressed by a search pattern (e.g.
+        /// `PID7`, `PID5.1`, or `PID3.4.2`) using the segment field definitions and the data
+        /// type descriptors of the message version (see [V2Message::version]). Fields of complex
+        /// type yield [V2Type::V2Complex] values with every component casted.
+        ///
+        /// ## Example
+        /// ```
+        /// use rumtk_hl7_v2::hl7_v2_complex_types::hl7_v2_complex_types::V2Type;
+        /// use rumtk_hl7_v2::hl7_v2_parser::v2_parser::V2Message;
+        ///
+        /// let message = V2Message::try_from_str("MSH|^~\\&|||||||ADT^A01|1|P|2.6\rPID|1||123||DOE^JOHN||19800101").unwrap();
+        /// match message.get_typed("PID7").unwrap() {
+        ///     V2Type::V2DateTime(birth_date) => assert_eq!(birth_date.unwrap().as_utc_string(), "1980-01-01T00:00:00.0000"),
+        ///     other => panic!("Wrong type {:?}!", other),
+        /// }
+        ///
+        /// // Before v2.6, PID-7 is a TS and the date is its first component.
+        /// let message = V2Message::try_from_str("MSH|^~\\&|||||||ADT^A01|1|P|2.5.1\rPID|1||123||DOE^JOHN||19800101").unwrap();
+        /// match message.get_typed("PID7.1").unwrap() {
+        ///     V2Type::V2DateTime(birth_date) => assert_eq!(birth_date.unwrap().as_utc_string(), "1980-01-01T00:00:00.0000"),
+        ///     other => panic!("Wrong type {:?}!", other),
+        /// }
+        /// ```
+        ///
+        pub fn get_typed(&self, search_pattern: &str) -> V2Result<V2Type> {
+            if let V2_SEARCH_EXPR_TYPE::V2_QUERY = V2SearchIndex::expr_type(search_pattern) {
+                return Err(format_compact!(
+                    "Typed access needs a single position. Got query {}!",
+                    search_pattern
+                ));
+            }
+            let index = V2SearchIndex::from(search_pattern);
+            let groups = string_search_named_captures(search_pattern, REGEX_V2_SEARCH_DEFAULT, "");
+            let has_group = |name: &str| groups.get(name).is_some_and(|g| !g.is_empty());
+            let version = self.version();
+            let separators = self.separators();
+
+            let segment = self.get_indexed(&index)?;
+            let position = index.field as isize;
+            if !has_group("component") {
+                return segment.get_typed_in(
+                    position,
+                    index.field_group as usize,
+                    separators,
+                    version,
+                    Some(self),
+                );
+            }
+
+            let complex = match segment.field_data_type_for(position, version)? {
+                V2ComponentType::Complex(complex) => complex,
+                data_type => {
+                    return Err(format_compact!(
+                        "{}-{} is of type {:?} and has no components!",
+                        segment.name(),
+                        position,
+                        data_type
+                    ))
+                }
+            };
+            let components = segment.field_components(position, index.field_group as usize)?;
+            let component_seq = usize::try_from(index.component).unwrap_or(0);
+            let component_type = complex_type_descriptor(&complex, component_seq, version)?;
+            let data_type = component_data_type(&complex, component_type, version);
+            let component = components
+                .get(component_seq - 1)
+                .cloned()
+                .unwrap_or_default();
+            if !has_group("subcomponent") {
+                return Ok(cast_component_as(
+                    component,
+                    component_type,
+                    &data_type,
+                    separators,
+                    version,
+                ));
+            }
+
+            let subcomplex = match data_type {
+                V2ComponentType::Complex(subcomplex) => subcomplex,
+                data_type => {
+                    return Err(format_compact!(
+                        "{}-{}.{} is of type {:?} and has no subcomponents!",
+                        segment.name(),
+                        position,
+                        component_seq,
+                        data_type
+                    ))
+                }
+            };
+            let subcomponent_seq = usize::try_from(index.subcomponent).unwrap_or(0);
+            let subcomponent_type =
+                complex_type_descriptor(&subcomplex, subcomponent_seq, version)?;
+            let subcomponent_data_type =
+                component_data_type(&subcomplex, subcomponent_type, version);
+            let subcomponent = component
+                .get(subcomponent_seq - 1)
+                .map_or(vec![], |subcomponent| vec![*subcomponent]);
+            Ok(cast_component_as(
+                subcomponent,
+                subcomponent_type,
+                &subcomponent_data_type,
+                separators,
+                version,
+            ))
+        }
+    }
+}
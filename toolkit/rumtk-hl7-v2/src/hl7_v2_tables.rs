@@ -0,0 +1,966 @@
+/*
+ * rumtk attempts to implement HL7 and medical protocols for interoperability in medicine.
+ * This toolkit aims to be reliable, simple, performant, and standards compliant.
+ * Copyright (C) 2024  Luis M. Santos, M.D.
+ *
+ * This library is free software; you can redistribute it and/or
+ * modify it under the terms of the GNU Lesser General Public
+ * License as published by the Free Software Foundation; either
+ * version 2.1 of the License, or (at your option) any later version.
+ *
+ * This library is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+ * Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public
+ * License along with this library; if not, write to the Free Software
+ * Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301  USA
+ */
+
+pub mod v2_tables {
+    use crate::hl7_v2_base_types::v2_base_types::{
+        V2Diagnostic, V2DiagnosticSeverity, V2Diagnostics, V2Result, V2SearchIndex,
+    };
+    use crate::hl7_v2_complex_types::hl7_v2_complex_types::{
+        complex_type_descriptor, component_data_type, V2FieldToString,
+    };
+    use crate::hl7_v2_constants::{V2_DELETE_FIELD, V2_SEARCH_EXPR_TYPE};
+    use crate::hl7_v2_field_descriptors::v2_field_descriptor::{V2ComplexType, V2ComponentType};
+    use crate::hl7_v2_parser::v2_parser::{V2Message, V2Segment};
+    use crate::hl7_v2_segment_descriptors::v2_segment_descriptor::seq_to_position;
+    use crate::hl7_v2_versions::v2_versions::{
+        version_segment_descriptor, version_type_descriptor, V2Version,
+    };
+    use rumtk_core::cache::AHashMap;
+    use rumtk_core::json::serialization::{Deserialize, Serialize};
+    use rumtk_core::rumtk_deserialize;
+    use rumtk_core::strings::{format_compact, AsStr, RUMString, RUMStringConversions};
+
+    /**************************** Types *****************************************/
+
+    ///
+    /// Who maintains the values of a table.
+    ///
+    /// ## Per Section 2.5.3.6
+    /// ```text
+    /// HL7 defines table values in 3 ways: HL7 tables, User-defined tables, and External tables.
+    /// HL7 tables are those tables for which HL7 specifies the values. User-defined tables are
+    /// those tables for which the values are supplied by the site, although HL7 may suggest some
+    /// values.
+    /// ```
+    ///
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+    pub enum V2TableType {
+        HL7,
+        #[default]
+        User,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct V2TableValue {
+        pub code: RUMString,
+        #[serde(default)]
+        pub description: RUMString,
+    }
+
+    ///
+    /// Values of a table. Tables loaded from JSON default to user-defined tables.
+    ///
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct V2Table {
+        pub id: u16,
+        #[serde(default)]
+        pub name: RUMString,
+        #[serde(default)]
+        pub table_type: V2TableType,
+        #[serde(default)]
+        pub values: Vec<V2TableValue>,
+    }
+
+    ///
+    /// Compile time definition of an HL7 table. See [V2_HL7_TABLES].
+    ///
+    #[derive(Debug)]
+    pub struct V2TableDefinition {
+        pub id: u16,
+        pub name: &'static str,
+        pub values: &'static [(&'static str, &'static str)],
+    }
+
+    ///
+    /// Tables available for validating coded values, keyed by table number. Fields bound to a
+    /// table that is not in the registry are not validated.
+    ///
+    #[derive(Debug, Default, Clone)]
+    pub struct V2TableRegistry {
+        tables: AHashMap<u16, V2Table>,
+    }
+
+    /**************************** Constants**************************************/
+
+    ///
+    /// HL7 tables bundled with the toolkit. Externally maintained tables, like 0396 (coding
+    /// systems) and 0399 (ISO 3166 country codes), and user-defined tables have to be loaded into
+    /// a [V2TableRegistry].
+    ///
+    pub static V2_HL7_TABLES: &[V2TableDefinition] = &[
+        V2TableDefinition {
+            id: 8,
+            name: "Acknowledgment code",
+            values: &[
+                ("AA", "Original mode: Application Accept - Enhanced mode: Application acknowledgment: Accept"),
+                ("AE", "Original mode: Application Error - Enhanced mode: Application acknowledgment: Error"),
+                ("AR", "Original mode: Application Reject - Enhanced mode: Application acknowledgment: Reject"),
+                ("CA", "Enhanced mode: Accept acknowledgment: Commit Accept"),
+                ("CE", "Enhanced mode: Accept acknowledgment: Commit Error"),
+                ("CR", "Enhanced mode: Accept acknowledgment: Commit Reject"),
+            ],
+        },
+        V2TableDefinition {
+            id: 38,
+            name: "Order status",
+            values: &[
+                ("A", "Some, but not all, results available"),
+                ("CA", "Order was canceled"),
+                ("CM", "Order is completed"),
+                ("DC", "Order was discontinued"),
+                ("ER", "Error, order not found"),
+                ("HD", "Order is on hold"),
+                ("IP", "In process, unspecified"),
+                ("RP", "Order has been replaced"),
+                ("SC", "In process, scheduled"),
+            ],
+        },
+        V2TableDefinition {
+            id: 65,
+            name: "Specimen action code",
+            values: &[
+                ("A", "Add ordered tests to the existing specimen"),
+                ("G", "Generated order; reflex order"),
+                ("L", "Lab to obtain specimen from patient"),
+                ("O", "Specimen obtained by service other than Lab"),
+                ("P", "Pending specimen; Order sent prior to delivery"),
+                ("R", "Revised order"),
+                ("S", "Schedule the tests specified below"),
+            ],
+        },
+        V2TableDefinition {
+            id: 78,
+            name: "Abnormal flags",
+            values: &[
+                ("L", "Below low normal"),
+                ("H", "Above high normal"),
+                ("LL", "Below lower panic limits"),
+                ("HH", "Above upper panic limits"),
+                ("<", "Below absolute low-off instrument scale"),
+                (">", "Above absolute high-off instrument scale"),
+                ("N", "Normal (applies to non-numeric results)"),
+                ("A", "Abnormal (applies to non-numeric results)"),
+                ("AA", "Very abnormal (applies to non-numeric units, analogous to panic limits for numeric units)"),
+                ("null", "No range defined, or normal ranges don't apply"),
+                ("U", "Significant change up"),
+                ("D", "Significant change down"),
+                ("B", "Better--use when direction not relevant"),
+                ("W", "Worse--use when direction not relevant"),
+                ("S", "Susceptible"),
+                ("R", "Resistant"),
+                ("I", "Intermediate"),
+                ("MS", "Moderately susceptible"),
+                ("VS", "Very susceptible"),
+            ],
+        },
+        V2TableDefinition {
+            id: 85,
+            name: "Observation result status codes interpretation",
+            values: &[
+                ("C", "Record coming over is a correction and thus replaces a final result"),
+                ("D", "Deletes the OBX record"),
+                ("F", "Final results; Can only be changed with a corrected result"),
+                ("I", "Specimen in lab; results pending"),
+                ("N", "Not asked; used to affirmatively document that the observation identified in the OBX was not sought when the universal service ID in OBR-4 implies that it would be sought"),
+                ("O", "Order detail description only (no result)"),
+                ("P", "Preliminary results"),
+                ("R", "Results entered -- not verified"),
+                ("S", "Partial results"),
+                ("U", "Results status change to final without retransmitting results already sent as 'preliminary'"),
+                ("W", "Post original as wrong, e.g., transmitted for wrong patient"),
+                ("X", "Results cannot be obtained for this observation"),
+            ],
+        },
+        V2TableDefinition {
+            id: 100,
+            name: "Invocation event",
+            values: &[
+                ("D", "On discharge"),
+                ("O", "On receipt of order"),
+                ("R", "At time service is completed"),
+                ("S", "At time service is started"),
+                ("T", "At a designated date/time"),
+            ],
+        },
+        V2TableDefinition {
+            id: 103,
+            name: "Processing ID",
+            values: &[("D", "Debugging"), ("P", "Production"), ("T", "Training")],
+        },
+        V2TableDefinition {
+            id: 104,
+            name: "Version ID",
+            values: &[
+                ("2.0", "Release 2.0"),
+                ("2.0D", "Demo 2.0"),
+                ("2.1", "Release 2.1"),
+                ("2.2", "Release 2.2"),
+                ("2.3", "Release 2.3"),
+                ("2.3.1", "Release 2.3.1"),
+                ("2.4", "Release 2.4"),
+                ("2.5", "Release 2.5"),
+                ("2.5.1", "Release 2.5.1"),
+                ("2.6", "Release 2.6"),
+                ("2.7", "Release 2.7"),
+                ("2.7.1", "Release 2.7.1"),
+                ("2.8", "Release 2.8"),
+                ("2.8.1", "Release 2.8.1"),
+                ("2.8.2", "Release 2.8.2"),
+                ("2.9", "Release 2.9"),
+            ],
+        },
+        V2TableDefinition {
+            id: 105,
+            name: "Source of comment",
+            values: &[
+                ("L", "Ancillary (filler) department is source of comment"),
+                ("O", "Other system is source of comment"),
+                ("P", "Orderer (placer) is source of comment"),
+            ],
+        },
+        V2TableDefinition {
+            id: 123,
+            name: "Result status",
+            values: &[
+                ("O", "Order received; specimen not yet received"),
+                ("I", "No results available; specimen received, procedure incomplete"),
+                ("S", "No results available; procedure scheduled, but not done"),
+                ("A", "Some, but not all, results available"),
+                ("P", "Preliminary: A verified early result is available, final results not yet obtained"),
+                ("C", "Correction to results"),
+                ("R", "Results stored; not yet verified"),
+                ("F", "Final results; results stored and verified. Can only be changed with a corrected result."),
+                ("X", "No results available; Order canceled."),
+                ("Y", "No order on record for this test. (Used only on queries)"),
+                ("Z", "No record of this patient. (Used only on queries)"),
+            ],
+        },
+        V2TableDefinition {
+            id: 125,
+            name: "Value type",
+            values: &[
+                ("AD", "Address"),
+                ("CE", "Coded Entry"),
+                ("CF", "Coded Element With Formatted Values"),
+                ("CK", "Composite ID With Check Digit"),
+                ("CN", "Composite ID And Name"),
+                ("CNE", "Coded with No Exceptions"),
+                ("CP", "Composite Price"),
+                ("CWE", "Coded Entry"),
+                ("CX", "Extended Composite ID With Check Digit"),
+                ("DR", "Date/Time Range"),
+                ("DT", "Date"),
+                ("DTM", "Time Stamp (Date & Time)"),
+                ("ED", "Encapsulated Data"),
+                ("FT", "Formatted Text (Display)"),
+                ("MO", "Money"),
+                ("NM", "Numeric"),
+                ("PN", "Person Name"),
+                ("RP", "Reference Pointer"),
+                ("SN", "Structured Numeric"),
+                ("ST", "String Data."),
+                ("TM", "Time"),
+                ("TN", "Telephone Number"),
+                ("TS", "Time Stamp (Date & Time)"),
+                ("TX", "Text Data (Display)"),
+                ("XAD", "Extended Address"),
+                ("XCN", "Extended Composite Name And Number For Persons"),
+                ("XON", "Extended Composite Name And Number For Organizations"),
+                ("XPN", "Extended Person Name"),
+                ("XTN", "Extended Telecommunications Number"),
+            ],
+        },
+        V2TableDefinition {
+            id: 136,
+            name: "Yes/no indicator",
+            values: &[("Y", "Yes"), ("N", "No")],
+        },
+        V2TableDefinition {
+            id: 155,
+            name: "Accept/application acknowledgment conditions",
+            values: &[
+                ("AL", "Always"),
+                ("NE", "Never"),
+                ("ER", "Error/reject conditions only"),
+                ("SU", "Successful completion only"),
+            ],
+        },
+        V2TableDefinition {
+            id: 190,
+            name: "Address type",
+            values: &[
+                ("BA", "Bad address"),
+                ("BI", "Billing Address"),
+                ("N", "Birth (nee) (birth address, not otherwise specified)"),
+                ("BDL", "Birth delivery location (address where birth occurred)"),
+                ("F", "Country Of Origin"),
+                ("C", "Current Or Temporary"),
+                ("B", "Firm/Business"),
+                ("H", "Home"),
+                ("L", "Legal Address"),
+                ("M", "Mailing"),
+                ("O", "Office"),
+                ("P", "Permanent"),
+                ("RH", "Registry home"),
+                ("BR", "Residence at birth (home address at time of birth)"),
+            ],
+        },
+        V2TableDefinition {
+            id: 200,
+            name: "Name type",
+            values: &[
+                ("A", "Alias Name"),
+                ("B", "Name at Birth"),
+                ("BAD", "Bad Name"),
+                ("C", "Adopted Name"),
+                ("D", "Display Name"),
+                ("I", "Licensing Name"),
+                ("L", "Legal Name"),
+                ("M", "Maiden Name"),
+                ("N", "Nickname /\"Call me\" Name/Street Name"),
+                ("P", "Name of Partner/Spouse - obsolete"),
+                ("R", "Registered Name (animals only)"),
+                ("S", "Coded Pseudo-Name to ensure anonymity"),
+                ("T", "Indigenous/Tribal/Community Name"),
+                ("U", "Unspecified"),
+            ],
+        },
+        V2TableDefinition {
+            id: 201,
+            name: "Telecommunication use code",
+            values: &[
+                ("ASN", "Answering Service Number"),
+                ("BPN", "Beeper Number"),
+                ("EMR", "Emergency Number"),
+                ("NET", "Network (email) Address"),
+                ("ORN", "Other Residence Number"),
+                ("PRN", "Primary Residence Number"),
+                ("PRS", "Personal"),
+                ("VHN", "Vacation Home Number"),
+                ("WPN", "Work Number"),
+            ],
+        },
+        V2TableDefinition {
+            id: 202,
+            name: "Telecommunication equipment type",
+            values: &[
+                ("BP", "Beeper"),
+                ("CP", "Cellular Phone"),
+                ("FX", "Fax"),
+                ("Internet", "Internet Address: Use Only If Telecommunication Use Code Is NET"),
+                ("MD", "Modem"),
+                ("PH", "Telephone"),
+                ("SAT", "Satellite Phone"),
+                ("TDD", "Telecommunications Device for the Deaf"),
+                ("TTY", "Teletypewriter"),
+                ("X.400", "X.400 email address: Use Only If Telecommunication Use Code Is NET"),
+            ],
+        },
+        V2TableDefinition {
+            id: 207,
+            name: "Processing mode",
+            values: &[
+                ("A", "Archive"),
+                ("R", "Restore from archive"),
+                ("I", "Initial load"),
+                ("T", "Current processing, transmitted at intervals (scheduled or on demand)"),
+            ],
+        },
+        V2TableDefinition {
+            id: 211,
+            name: "Alternate character sets",
+            values: &[
+                ("ASCII", "The printable 7-bit ASCII character set."),
+                ("8859/1", "The printable characters from the ISO 8859/1 Character set"),
+                ("8859/2", "The printable characters from the ISO 8859/2 Character set"),
+                ("8859/3", "The printable characters from the ISO 8859/3 Character set"),
+                ("8859/4", "The printable characters from the ISO 8859/4 Character set"),
+                ("8859/5", "The printable characters from the ISO 8859/5 Character set"),
+                ("8859/6", "The printable characters from the ISO 8859/6 Character set"),
+                ("8859/7", "The printable characters from the ISO 8859/7 Character set"),
+                ("8859/8", "The printable characters from the ISO 8859/8 Character set"),
+                ("8859/9", "The printable characters from the ISO 8859/9 Character set"),
+                ("8859/15", "The printable characters from the ISO 8859/15 (Latin-15)"),
+                ("ISO IR6", "ASCII graphic character set consisting of 94 characters."),
+                ("ISO IR14", "Code for Information Exchange (one byte)(JIS X 0201-1976)."),
+                ("ISO IR87", "Code for the Japanese Graphic Character set for information interchange (JIS X 0208-1990)"),
+                ("ISO IR159", "Code of the supplementary Japanese Graphic Character set for information interchange (JIS X 0212-1990)."),
+                ("GB 18030-2000", "Code for Chinese Character Set (GB 18030-2000)"),
+                ("KS X 1001", "Code for Korean Character Set (KS X 1001)"),
+                ("CNS 11643-1992", "Code for Taiwanese Character Set (CNS 11643-1992)"),
+                ("BIG-5", "Code for Taiwanese Character Set (BIG-5)"),
+                ("UNICODE", "The world wide character standard from ISO/IEC 10646-1-1993"),
+                ("UNICODE UTF-8", "UCS Transformation Format, 8-bit form"),
+                ("UNICODE UTF-16", "UCS Transformation Format, 16-bit form"),
+                ("UNICODE UTF-32", "UCS Transformation Format, 32-bit form"),
+            ],
+        },
+        V2TableDefinition {
+            id: 301,
+            name: "Universal ID type",
+            values: &[
+                ("DNS", "An Internet dotted name. Either in ASCII or as integers"),
+                ("GUID", "Same as UUID."),
+                ("HCD", "The CEN Healthcare Coding Scheme Designator."),
+                ("HL7", "Reserved for future HL7 registration schemes"),
+                ("ISO", "An International Standards Organization Object Identifier"),
+                ("L", "Reserved for locally defined coding schemes."),
+                ("M", "Reserved for locally defined coding schemes."),
+                ("N", "Reserved for locally defined coding schemes."),
+                ("Random", "Usually a base64 encoded string of random bits."),
+                ("URI", "Uniform Resource Identifier"),
+                ("UUID", "The DCE Universal Unique Identifier"),
+                ("x400", "An X.400 MHS format identifier"),
+                ("x500", "An X.500 directory name"),
+            ],
+        },
+        V2TableDefinition {
+            id: 322,
+            name: "Completion status",
+            values: &[
+                ("CP", "Complete"),
+                ("RE", "Refused"),
+                ("NA", "Not Administered"),
+                ("PA", "Partially Administered"),
+            ],
+        },
+        V2TableDefinition {
+            id: 323,
+            name: "Action code",
+            values: &[
+                ("A", "Add/Insert"),
+                ("D", "Delete"),
+                ("U", "Update"),
+                ("X", "No Change"),
+            ],
+        },
+        V2TableDefinition {
+            id: 357,
+            name: "Message error condition codes",
+            values: &[
+                ("0", "Message accepted"),
+                ("100", "Segment sequence error"),
+                ("101", "Required field missing"),
+                ("102", "Data type error"),
+                ("103", "Table value not found"),
+                ("200", "Unsupported message type"),
+                ("201", "Unsupported event code"),
+                ("202", "Unsupported processing id"),
+                ("203", "Unsupported version id"),
+                ("204", "Unknown key identifier"),
+                ("205", "Duplicate key identifier"),
+                ("206", "Application record locked"),
+                ("207", "Application internal error"),
+            ],
+        },
+        V2TableDefinition {
+            id: 516,
+            name: "Error severity",
+            values: &[("W", "Warning"), ("I", "Information"), ("E", "Error")],
+        },
+    ];
+
+    /**************************** Helpers ***************************************/
+
+    ///
+    /// Code bound to a table out of the pieces of a component (or field). The code is the first
+    /// piece. For coded types (CE, CWE, CNE) the third piece names the coding system, and codes
+    /// from a coding system other than the bound table are not ours to check.
+    ///
+    pub(crate) fn bound_code<'a>(pieces: &[&'a str], coded: bool, table: u16) -> Option<&'a str> {
+        let code = *pieces.first()?;
+        if code.is_empty() || code == V2_DELETE_FIELD {
+            return None;
+        }
+        if coded {
+            let coding_system = pieces.get(2).copied().unwrap_or_default();
+            if !coding_system.is_empty() && coding_system != format_compact!("HL7{:04}", table) {
+                return None;
+            }
+        }
+        Some(code)
+    }
+
+    pub(crate) fn is_coded(complex: &V2ComplexType) -> bool {
+        matches!(
+            complex,
+            V2ComplexType::CE | V2ComplexType::CWE | V2ComplexType::CNE
+        )
+    }
+
+    ///
+    /// Finds every value of one field repetition that is bound to a table. Yields the component
+    /// (if the table is bound to a component), the code, and the table.
+    ///
+    fn bound_codes<'a>(
+        components: &[Vec<&'a str>],
+        data_type: &V2ComponentType,
+        table: u16,
+        version: V2Version,
+    ) -> Vec<(Option<isize>, &'a str, u16)> {
+        let mut codes = Vec::new();
+        let complex = match data_type {
+            V2ComponentType::Primitive(_) => {
+                let pieces = components.first().map_or(&[][..], |c| &c[..]);
+                if let Some(code) = bound_code(pieces, false, table).filter(|_| table != 0) {
+                    codes.push((None, code, table));
+                }
+                return codes;
+            }
+            V2ComponentType::Complex(complex) => complex,
+            V2ComponentType::Varies => return codes,
+        };
+
+        for component_type in version_type_descriptor(complex, version).unwrap_or_default() {
+            let seq = component_type.seq as usize;
+            let (component_table, pieces, coded) = match (component_type.valid_table, seq) {
+                // A table bound to the field applies to its first component. For coded types,
+                // the coding system is in the third component.
+                (0, 1) if table != 0 => (
+                    table,
+                    components
+                        .iter()
+                        .map(|component| component.first().copied().unwrap_or_default())
+                        .collect(),
+                    is_coded(complex),
+                ),
+                (0, _) => continue,
+                (component_table, _) => {
+                    let coded = match component_data_type(complex, component_type, version) {
+                        V2ComponentType::Complex(subcomplex) => is_coded(&subcomplex),
+                        _ => false,
+                    };
+                    let pieces = components.get(seq - 1).cloned().unwrap_or_default();
+                    (component_table, pieces, coded)
+                }
+            };
+            if let Some(code) = bound_code(&pieces, coded, component_table) {
+                codes.push((Some(seq as isize), code, component_table));
+            }
+        }
+        codes
+    }
+
+    /**************************** API *******************************************/
+
+    impl V2Table {
+        pub fn new(id: u16, name: &str, table_type: V2TableType) -> V2Table {
+            V2Table {
+                id,
+                name: name.to_rumstring(),
+                table_type,
+                values: Vec::new(),
+            }
+        }
+
+        ///
+        /// Adds a value to the table. Adding a code that is already in the table replaces its
+        /// description.
+        ///
+        pub fn insert(&mut self, code: &str, description: &str) {
+            match self.values.iter_mut().find(|value| value.code == code) {
+                Some(value) => value.description = description.to_rumstring(),
+                None => self.values.push(V2TableValue {
+                    code: code.to_rumstring(),
+                    description: description.to_rumstring(),
+                }),
+            }
+        }
+
+        pub fn get(&self, code: &str) -> Option<&V2TableValue> {
+            self.values.iter().find(|value| value.code == code)
+        }
+
+        pub fn contains(&self, code: &str) -> bool {
+            self.get(code).is_some()
+        }
+
+        pub fn description(&self, code: &str) -> Option<&str> {
+            self.get(code).map(|value| value.description.as_str())
+        }
+
+        pub fn len(&self) -> usize {
+            self.values.len()
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.values.is_empty()
+        }
+    }
+
+    impl From<&V2TableDefinition> for V2Table {
+        fn from(definition: &V2TableDefinition) -> Self {
+            let mut table = V2Table::new(definition.id, definition.name, V2TableType::HL7);
+            for (code, description) in definition.values {
+                table.insert(code, description);
+            }
+            table
+        }
+    }
+
+    impl V2TableRegistry {
+        ///
+        /// Registry with the HL7 tables in [V2_HL7_TABLES]. Load user-defined tables with
+        /// [V2TableRegistry::load_json], [V2TableRegistry::load_csv], or
+        /// [V2TableRegistry::load_file].
+        ///
+        pub fn new() -> V2TableRegistry {
+            let mut registry = V2TableRegistry::empty();
+            for definition in V2_HL7_TABLES {
+                registry.insert(V2Table::from(definition));
+            }
+            registry
+        }
+
+        pub fn empty() -> V2TableRegistry {
+            V2TableRegistry::default()
+        }
+
+        ///
+        /// Adds a table to the registry, replacing and returning the table with the same number.
+        ///
+        pub fn insert(&mut self, table: V2Table) -> Option<V2Table> {
+            self.tables.insert(table.id, table)
+        }
+
+        pub fn get(&self, table: u16) -> Option<&V2Table> {
+            self.tables.get(&table)
+        }
+
+        pub fn len(&self) -> usize {
+            self.tables.len()
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.tables.is_empty()
+        }
+
+        ///
+        /// Whether `code` is a value of `table`. `None` if the table is not in the registry.
+        ///
+        pub fn contains(&self, table: u16, code: &str) -> Option<bool> {
+            self.get(table).map(|t| t.contains(code))
+        }
+
+        ///
+        /// Display text of `code` in `table`.
+        ///
+        pub fn description(&self, table: u16, code: &str) -> Option<&str> {
+            self.get(table)?.description(code)
+        }
+
+        ///
+        /// Loads tables from a JSON list of tables. Each table has an `id`, and optionally a `name`,
+        /// a `table_type` (`HL7` or `User`, the default), and `values` with a `code` and a
+        /// `description` each. A single table object is accepted too. Tables replace the tables
+        /// with the same number.
+        ///
+        /// Returns the number of tables loaded.
+        ///
+        /// ## Example
+        /// ```
+        /// use rumtk_hl7_v2::hl7_v2_tables::v2_tables::V2TableRegistry;
+        ///
+        /// let mut registry = V2TableRegistry::new();
+        /// registry.load_json(r#"[{"id": 1, "name": "Administrative Sex", "values": [
+        ///     {"code": "F", "description": "Female"}, {"code": "M", "description": "Male"}
+        /// ]}]"#).unwrap();
+        ///
+        /// assert_eq!(registry.description(1, "F"), Some("Female"), "Wrong display text!");
+        /// ```
+        ///
+        pub fn load_json(&mut self, json: &str) -> V2Result<usize> {
+            let tables: Vec<V2Table> = match rumtk_deserialize!(json) {
+                Ok(tables) => tables,
+                Err(_) => match rumtk_deserialize!(json) {
+                    Ok(table) => vec![table],
+                    Err(e) => {
+                        return Err(format_compact!("Failed to load tables from JSON! {}", e))
+                    }
+                },
+            };
+            let count = tables.len();
+            for table in tables {
+                self.insert(table);
+            }
+            Ok(count)
+        }
+
+        ///
+        /// Loads user-defined tables from CSV rows of `table,code,description`. A header row is
+        /// skipped. Values may be quoted to include commas. Every table found in the input
+        /// replaces the table with the same number.
+        ///
+        /// Returns the number of tables loaded.
+        ///
+        pub fn load_csv(&mut self, csv: &str) -> V2Result<usize> {
+            let mut tables: Vec<V2Table> = Vec::new();
+            for (line_number, line) in csv.lines().enumerate() {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let columns = split_csv_line(line)?;
+                let id = match columns[0].trim().parse::<u16>() {
+                    Ok(id) => id,
+                    Err(_) if line_number == 0 => continue,
+                    Err(_) => {
+                        return Err(format_compact!(
+                            "Invalid table number in CSV line {}! Got [{}]",
+                            line_number + 1,
+                            columns[0]
+                        ))
+                    }
+                };
+                let code = match columns.get(1) {
+                    Some(code) if !code.trim().is_empty() => code.trim(),
+                    _ => {
+                        return Err(format_compact!(
+                            "Missing code in CSV line {}!",
+                            line_number + 1
+                        ))
+                    }
+                };
+                let description = columns.get(2).map_or("", |d| d.trim());
+                match tables.iter_mut().find(|table| table.id == id) {
+                    Some(table) => table.insert(code, description),
+                    None => {
+                        let mut table = V2Table::new(id, "", V2TableType::User);
+                        table.insert(code, description);
+                        tables.push(table);
+                    }
+                }
+            }
+            let count = tables.len();
+            for table in tables {
+                self.insert(table);
+            }
+            Ok(count)
+        }
+
+        ///
+        /// Loads tables from a `.json` or `.csv` file. See [V2TableRegistry::load_json] and
+        /// [V2TableRegistry::load_csv].
+        ///
+        pub fn load_file(&mut self, path: &str) -> V2Result<usize> {
+            let contents = match std::fs::read_to_string(path) {
+                Ok(contents) => contents,
+                Err(e) => return Err(format_compact!("Failed to read table file {}! {}", path, e)),
+            };
+            let extension = path.rsplit('.').next().unwrap_or_default();
+            match extension.to_ascii_lowercase().as_str() {
+                "json" => self.load_json(&contents),
+                "csv" => self.load_csv(&contents),
+                _ => Err(format_compact!(
+                    "Unsupported table file {}! Expected a .json or .csv file.",
+                    path
+                )),
+            }
+        }
+
+        ///
+        /// Returns an error message if `code` is not a value of `table`. Codes of tables that are
+        /// not in the registry are not checked.
+        ///
+        pub fn check(&self, table: u16, code: &str) -> Option<RUMString> {
+            let t = self.get(table)?;
+            match t.contains(code) {
+                true => None,
+                false => Some(format_compact!(
+                    "Value {} is not in {} table {:04} ({})!",
+                    code,
+                    match t.table_type {
+                        V2TableType::HL7 => "HL7",
+                        V2TableType::User => "user-defined",
+                    },
+                    table,
+                    t.name
+                )),
+            }
+        }
+    }
+
+    fn split_csv_line(line: &str) -> V2Result<Vec<RUMString>> {
+        let mut columns = vec![RUMString::default()];
+        let mut quoted = false;
+        let mut chars = line.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' if quoted && chars.peek() == Some(&'"') => {
+                    chars.next();
+                    columns.last_mut().unwrap().push('"');
+                }
+                '"' => quoted = !quoted,
+                ',' if !quoted => columns.push(RUMString::default()),
+                _ => columns.last_mut().unwrap().push(c),
+            }
+        }
+        match quoted {
+            true => Err(format_compact!(
+                "Unterminated quote in CSV line [{}]!",
+                line
+            )),
+            false => Ok(columns),
+        }
+    }
+
+    impl V2Segment {
+        ///
+        /// Checks every value bound to a table, in fields and in their components, against the
+        /// tables in `registry`. Fields are looked up per `version`. Only the field and component
+        /// of each diagnostic are set.
+        ///
+        pub fn validate_tables(
+            &self,
+            registry: &V2TableRegistry,
+            version: V2Version,
+        ) -> V2Diagnostics {
+            let mut diagnostics = V2Diagnostics::new();
+            for descriptor in version_segment_descriptor(self.name(), version) {
+                let position = seq_to_position(self.name(), descriptor.seq as isize);
+                if position < 1 || position > self.len() as isize {
+                    continue;
+                }
+                let (data_type, field) = match (
+                    self.field_data_type_for(position, version),
+                    self.get(position),
+                ) {
+                    (Ok(data_type), Ok(field)) => (data_type, field),
+                    _ => continue,
+                };
+                for repetition in field {
+                    let components = repetition.to_component_list();
+                    for (component, code, table) in
+                        bound_codes(&components, &data_type, descriptor.valid_table, version)
+                    {
+                        if let Some(message) = registry.check(table, code) {
+                            diagnostics.push(
+                                V2Diagnostic::new(V2DiagnosticSeverity::Error, 0, &message)
+                                    .at_field(position, component),
+                            );
+                        }
+                    }
+                }
+            }
+            diagnostics
+        }
+    }
+
+    impl V2Message {
+        ///
+        /// Flags coded values (ID, IS, CWE, ...) that are not in the table they are bound to.
+        /// Tables missing from `registry` are skipped, so user-defined tables are only checked
+        /// once loaded. Diagnostics point at the segment, field, and component, but not at a byte
+        /// offset since a parsed message does not keep one.
+        ///
+        /// ## Example
+        /// ```
+        /// use rumtk_hl7_v2::hl7_v2_parser::v2_parser::V2Message;
+        /// use rumtk_hl7_v2::hl7_v2_tables::v2_tables::V2TableRegistry;
+        ///
+        /// let message = V2Message::try_from_str("MSH|^~\\&|||||||ADT^A01|1|Q|2.5.1|||XX\rPID|1||123||DOE^JOHN").unwrap();
+        /// let diagnostics = message.validate_tables(&V2TableRegistry::new());
+        ///
+        /// assert_eq!(diagnostics.len(), 2, "Expected MSH-11 and MSH-15 to be flagged!");
+        /// ```
+        ///
+        pub fn validate_tables(&self, registry: &V2TableRegistry) -> V2Diagnostics {
+            let version = self.version();
+            let mut diagnostics = V2Diagnostics::new();
+            for (indx, segment) in self.segments().enumerate() {
+                for diagnostic in segment.validate_tables(registry, version) {
+                    diagnostics.push(diagnostic.at_segment(indx + 1, segment.name()));
+                }
+            }
+            diagnostics
+        }
+
+        ///
+        /// Returns the number of the table bound to the field, component, or subcomponent
+        /// addressed by a search pattern. A table bound to a field of complex type applies to its
+        /// first component.
+        ///
+        pub fn bound_table(&self, search_pattern: &str) -> V2Result<u16> {
+            if let V2_SEARCH_EXPR_TYPE::V2_QUERY = V2SearchIndex::expr_type(search_pattern) {
+                return Err(format_compact!(
+                    "A table lookup needs a single position. Got query {}!",
+                    search_pattern
+                ));
+            }
+            let index = V2SearchIndex::from(search_pattern);
+            let version = self.version();
+
+            let segment = self.get_indexed(&index)?;
+            let position = index.field as isize;
+            let mut table = segment.field_definition_for(position, version)?.valid_table;
+            // Omitted components and subcomponents default to the first one, which is also
+            // where a table bound to the enclosing type applies.
+            if let V2ComponentType::Complex(complex) =
+                segment.field_data_type_for(position, version)?
+            {
+                let seq = usize::try_from(index.component).unwrap_or(0);
+                let component_type = complex_type_descriptor(&complex, seq, version)?;
+                table = match (component_type.valid_table, seq) {
+                    (0, 1) => table,
+                    (component_table, _) => component_table,
+                };
+                if let V2ComponentType::Complex(subcomplex) =
+                    component_data_type(&complex, component_type, version)
+                {
+                    let seq = usize::try_from(index.subcomponent).unwrap_or(0);
+                    let subcomponent_type = complex_type_descriptor(&subcomplex, seq, version)?;
+                    table = match (subcomponent_type.valid_table, seq) {
+                        (0, 1) => table,
+                        (subcomponent_table, _) => subcomponent_table,
+                    };
+                }
+            }
+            match table {
+                0 => Err(format_compact!("No table is bound to {}!", search_pattern)),
+                table => Ok(table),
+            }
+        }
+
+        ///
+        /// Display text of the code addressed by a search pattern, looked up in the table bound to
+        /// that position. `None` if the table is not in `registry` or does not list the code.
+        ///
+        /// ## Example
+        /// ```
+        /// use rumtk_hl7_v2::hl7_v2_parser::v2_parser::V2Message;
+        /// use rumtk_hl7_v2::hl7_v2_tables::v2_tables::V2TableRegistry;
+        ///
+        /// let message = V2Message::try_from_str("MSH|^~\\&|||||||ADT^A01|1|P|2.5.1\rPID|1||123||DOE^JOHN^^^^^L").unwrap();
+        /// let registry = V2TableRegistry::new();
+        ///
+        /// assert_eq!(message.display_text("MSH10", &registry).unwrap(), Some("Production"), "Wrong processing ID!");
+        /// assert_eq!(message.display_text("PID5.7", &registry).unwrap(), Some("Legal Name"), "Wrong name type!");
+        /// ```
+        ///
+        pub fn display_text<'a>(
+            &self,
+            search_pattern: &str,
+            registry: &'a V2TableRegistry,
+        ) -> V2Result<Option<&'a str>> {
+            let table = self.bound_table(search_pattern)?;
+            let code = self.find_subcomponent(&search_pattern.to_rumstring())?;
+            Ok(registry.description(table, code.as_str()))
+        }
+    }
+}
@@ -18,3 +18,1339 @@
  * Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301  USA
  */
 
+///
+/// Strongly typed views of the segments we have field definitions for. Each segment struct names
+/// its fields after the segment field definitions in
+/// [V2_SEGMENT_FIELD_DESCRIPTORS](crate::hl7_v2_segment_descriptors::v2_segment_descriptor::V2_SEGMENT_FIELD_DESCRIPTORS)
+/// and each complex type struct names its components after the descriptors in
+/// [V2_FIELD_DESCRIPTORS](crate::hl7_v2_field_descriptors::v2_field_descriptor::V2_FIELD_DESCRIPTORS).
+/// Values are kept as they were sent (escape sequences intact) so a segment converts back to the
+/// same text. Use [V2Message::get_typed] or the [V2PrimitiveCasting] methods to cast values.
+///
+/// ## Example
+/// ```
+/// use rumtk_hl7_v2::hl7_v2_parser::v2_parser::V2Message;
+/// use rumtk_hl7_v2::hl7_v2_types::v2_types::Pid;
+///
+/// let message = V2Message::try_from_str("MSH|^~\\&|||||||ADT^A01|1|P|2.5.1\rPID|1||123^^^HOSP^MR||DOE^JOHN").unwrap();
+/// let pid: Pid = message.segments_as().unwrap().remove(0);
+///
+/// assert_eq!(pid.patient_name[0].family_name.surname, "DOE", "Wrong patient name!");
+/// assert_eq!(pid.patient_identifier_list[0].assigning_authority.namespace_id, "HOSP", "Wrong authority!");
+/// ```
+///
+/// [V2Message::get_typed]: crate::hl7_v2_parser::v2_parser::V2Message::get_typed
+/// [V2PrimitiveCasting]: crate::hl7_v2_base_types::v2_primitives::V2PrimitiveCasting
+///
+pub mod v2_types {
+    use crate::hl7_v2_base_types::v2_base_types::{
+        find_segment_id, is_header_segment_name, V2ComponentList, V2ParserCharacters, V2Result,
+    };
+    use crate::hl7_v2_complex_types::hl7_v2_complex_types::V2FieldToString;
+    use crate::hl7_v2_field_descriptors::v2_field_descriptor::{
+        V2ComponentDescriptor, V2_FIELD_DESCRIPTORS,
+    };
+    use crate::hl7_v2_parser::v2_parser::{V2Message, V2Segment};
+    use crate::hl7_v2_segment_descriptors::v2_segment_descriptor::{
+        V2SegmentDescriptor, V2_SEGMENT_FIELD_DESCRIPTORS,
+    };
+    pub use rumtk_core::json::serialization::{Deserialize, Serialize};
+    pub use rumtk_core::strings::{format_compact, AsStr, RUMString, RUMStringConversions};
+
+    /**************************** Types *****************************************/
+
+    ///
+    /// Components (outer) and subcomponents (inner) of a value as owned strings.
+    ///
+    pub type V2OwnedComponentList = Vec<Vec<RUMString>>;
+
+    ///
+    /// Conversion between the strings of the parser and a typed value. A value is either a whole
+    /// field (its components) or a component (its subcomponents). Complex types nested in a
+    /// component take one subcomponent per component.
+    ///
+    pub trait V2TypedValue: Default {
+        fn from_components(components: &[Vec<&str>]) -> Self;
+        fn from_subcomponents(subcomponents: &[&str]) -> Self;
+        fn to_components(&self) -> V2OwnedComponentList;
+        fn to_subcomponents(&self) -> Vec<RUMString>;
+    }
+
+    ///
+    /// Conversion between the repetitions of a field and a typed value. Repeating fields map to
+    /// a [Vec] of values. Non repeating fields only look at the first repetition.
+    ///
+    pub trait V2FieldValue: Default {
+        fn from_repetitions(repetitions: &[V2ComponentList]) -> Self;
+        fn to_repetitions(&self) -> Vec<V2OwnedComponentList>;
+    }
+
+    ///
+    /// Struct generated from the descriptor of a complex type.
+    ///
+    pub trait V2CompositeType: V2TypedValue {
+        const V2_TYPE: &'static str;
+        const COMPONENTS: &'static [&'static str];
+
+        fn descriptor() -> &'static V2ComponentDescriptor {
+            V2_FIELD_DESCRIPTORS[Self::V2_TYPE]
+        }
+    }
+
+    ///
+    /// Struct generated from the field definitions of a segment.
+    ///
+    pub trait V2SegmentType: Default + for<'a> TryFrom<&'a V2Segment, Error = RUMString> {
+        const SEGMENT_NAME: &'static str;
+        /// Field names and their sequence in the segment.
+        const FIELDS: &'static [(&'static str, u16)];
+
+        fn descriptor() -> &'static V2SegmentDescriptor {
+            V2_SEGMENT_FIELD_DESCRIPTORS[Self::SEGMENT_NAME]
+        }
+
+        fn to_segment(&self, parser_chars: &V2ParserCharacters) -> V2Result<V2Segment>;
+    }
+
+    ///
+    /// Value of a field whose data type is given by another field, like OBX-5. The components are
+    /// kept as is. Use [V2Segment::field_data_type] to find out what they hold.
+    ///
+    #[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct V2Varies {
+        pub components: V2OwnedComponentList,
+    }
+
+    impl V2TypedValue for RUMString {
+        fn from_components(components: &[Vec<&str>]) -> Self {
+            match components.first() {
+                Some(component) => Self::from_subcomponents(component),
+                None => RUMString::default(),
+            }
+        }
+
+        fn from_subcomponents(subcomponents: &[&str]) -> Self {
+            subcomponents
+                .first()
+                .copied()
+                .unwrap_or_default()
+                .to_rumstring()
+        }
+
+        fn to_components(&self) -> V2OwnedComponentList {
+            vec![self.to_subcomponents()]
+        }
+
+        fn to_subcomponents(&self) -> Vec<RUMString> {
+            vec![self.clone()]
+        }
+    }
+
+    impl V2TypedValue for V2Varies {
+        fn from_components(components: &[Vec<&str>]) -> Self {
+            V2Varies {
+                components: components
+                    .iter()
+                    .map(|component| component.iter().map(|s| s.to_rumstring()).collect())
+                    .collect(),
+            }
+        }
+
+        fn from_subcomponents(subcomponents: &[&str]) -> Self {
+            V2Varies {
+                components: subcomponents
+                    .iter()
+                    .map(|s| vec![s.to_rumstring()])
+                    .collect(),
+            }
+        }
+
+        fn to_components(&self) -> V2OwnedComponentList {
+            self.components.clone()
+        }
+
+        fn to_subcomponents(&self) -> Vec<RUMString> {
+            self.components
+                .iter()
+                .map(|component| component.first().cloned().unwrap_or_default())
+                .collect()
+        }
+    }
+
+    impl<T: V2TypedValue> V2FieldValue for T {
+        fn from_repetitions(repetitions: &[V2ComponentList]) -> Self {
+            match repetitions.first() {
+                Some(repetition) => T::from_components(repetition),
+                None => T::default(),
+            }
+        }
+
+        fn to_repetitions(&self) -> Vec<V2OwnedComponentList> {
+            vec![self.to_components()]
+        }
+    }
+
+    impl<T: V2TypedValue> V2FieldValue for Vec<T> {
+        fn from_repetitions(repetitions: &[V2ComponentList]) -> Self {
+            repetitions
+                .iter()
+                .filter(|repetition| !repetition.iter().flatten().all(|s| s.is_empty()))
+                .map(|repetition| T::from_components(repetition))
+                .collect()
+        }
+
+        fn to_repetitions(&self) -> Vec<V2OwnedComponentList> {
+            self.iter().map(|value| value.to_components()).collect()
+        }
+    }
+
+    /**************************** Helpers ***************************************/
+
+    ///
+    /// Returns the repetitions of the field with sequence `seq`. Fields that were not sent yield
+    /// no repetitions.
+    ///
+    pub fn segment_repetitions(segment: &V2Segment, seq: u16) -> Vec<V2ComponentList> {
+        let position = match is_header_segment_name(segment.name()) {
+            true => seq as isize - 1,
+            false => seq as isize,
+        };
+        if position < 1 || position > segment.len() as isize {
+            return vec![];
+        }
+        match segment.get(position) {
+            Ok(field_group) => field_group
+                .iter()
+                .map(|field| field.to_component_list())
+                .collect(),
+            Err(_) => vec![],
+        }
+    }
+
+    fn join_trimmed(parts: Vec<RUMString>, separator: &str) -> RUMString {
+        let end = parts
+            .iter()
+            .rposition(|part| !part.is_empty())
+            .map_or(0, |i| i + 1);
+        parts[..end].join(separator).to_rumstring()
+    }
+
+    fn field_to_string(
+        repetitions: Vec<V2OwnedComponentList>,
+        parser_chars: &V2ParserCharacters,
+    ) -> RUMString {
+        let repetitions: Vec<RUMString> = repetitions
+            .into_iter()
+            .map(|components| {
+                let components: Vec<RUMString> = components
+                    .into_iter()
+                    .map(|subcomponents| {
+                        join_trimmed(subcomponents, parser_chars.subcomponent_separator.as_str())
+                    })
+                    .collect();
+                join_trimmed(components, parser_chars.component_separator.as_str())
+            })
+            .collect();
+        match repetitions.iter().all(|repetition| repetition.is_empty()) {
+            true => RUMString::default(),
+            false => repetitions
+                .join(parser_chars.repetition_separator.as_str())
+                .to_rumstring(),
+        }
+    }
+
+    ///
+    /// Builds a segment out of the repetitions of each field, keyed by sequence.
+    ///
+    pub fn build_segment(
+        segment_name: &str,
+        fields: Vec<(u16, Vec<V2OwnedComponentList>)>,
+        parser_chars: &V2ParserCharacters,
+    ) -> V2Result<V2Segment> {
+        let offset = match is_header_segment_name(segment_name) {
+            true => 2,
+            false => 1,
+        };
+        let field_count = fields
+            .iter()
+            .map(|(seq, _)| *seq as usize)
+            .max()
+            .unwrap_or(0);
+        let mut raw_fields = vec![RUMString::default(); field_count.saturating_sub(offset - 1)];
+        for (seq, repetitions) in fields {
+            if (seq as usize) < offset {
+                continue;
+            }
+            raw_fields[seq as usize - offset] = field_to_string(repetitions, parser_chars);
+        }
+        let mut raw_segment = vec![segment_name.to_rumstring()];
+        raw_segment.extend(raw_fields);
+        V2Segment::from_str(
+            &join_trimmed(raw_segment, parser_chars.field_separator.as_str()),
+            parser_chars,
+        )
+    }
+
+    /**************************** Macros ****************************************/
+
+    ///
+    /// Generates a struct for a complex type. List the components in the same order as the
+    /// descriptor of the type in
+    /// [V2_FIELD_DESCRIPTORS](crate::hl7_v2_field_descriptors::v2_field_descriptor::V2_FIELD_DESCRIPTORS)
+    /// and use the descriptor names.
+    ///
+    /// ## Arguments
+    /// * `name` - Name of the struct.
+    /// * `type_name` - Name of the complex type, e.g. `"XPN"`.
+    /// * `component: type` - List of components. Primitive components are [RUMString]s.
+    ///
+    #[macro_export]
+    macro_rules! v2_composite_type {
+        ( $name:ident, $type_name:expr, { $($component:ident: $component_type:ty),+ $(,)? } ) => {
+            #[doc = concat!("Generated from the descriptor of complex type ", $type_name, ".")]
+            #[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+            pub struct $name {
+                $(pub $component: $component_type,)+
+            }
+
+            impl V2TypedValue for $name {
+                fn from_components(components: &[Vec<&str>]) -> Self {
+                    let mut components = components.iter();
+                    $name {
+                        $($component: match components.next() {
+                            Some(component) => <$component_type>::from_subcomponents(component),
+                            None => Default::default(),
+                        },)+
+                    }
+                }
+
+                fn from_subcomponents(subcomponents: &[&str]) -> Self {
+                    let mut subcomponents = subcomponents.iter();
+                    $name {
+                        $($component: match subcomponents.next() {
+                            Some(subcomponent) => <$component_type>::from_subcomponents(&[*subcomponent]),
+                            None => Default::default(),
+                        },)+
+                    }
+                }
+
+                fn to_components(&self) -> V2OwnedComponentList {
+                    vec![$(self.$component.to_subcomponents(),)+]
+                }
+
+                fn to_subcomponents(&self) -> Vec<RUMString> {
+                    vec![$(self.$component.to_subcomponents().into_iter().next().unwrap_or_default(),)+]
+                }
+            }
+
+            impl V2CompositeType for $name {
+                const V2_TYPE: &'static str = $type_name;
+                const COMPONENTS: &'static [&'static str] = &[$(stringify!($component),)+];
+            }
+        };
+    }
+
+    ///
+    /// Generates a struct for a segment. Field names must match the segment field definitions in
+    /// [V2_SEGMENT_FIELD_DESCRIPTORS](crate::hl7_v2_segment_descriptors::v2_segment_descriptor::V2_SEGMENT_FIELD_DESCRIPTORS).
+    ///
+    /// ## Arguments
+    /// * `name` - Name of the struct.
+    /// * `segment_name` - Name of the segment, e.g. `"PID"`.
+    /// * `field: seq => type` - List of fields. Repeating fields are [Vec]s.
+    ///
+    #[macro_export]
+    macro_rules! v2_segment_type {
+        ( $name:ident, $segment_name:expr, { $($field:ident: $seq:expr => $field_type:ty),+ $(,)? } ) => {
+            #[doc = concat!("Generated from the field definitions of segment ", $segment_name, ".")]
+            #[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+            pub struct $name {
+                $(pub $field: $field_type,)+
+            }
+
+            impl TryFrom<&V2Segment> for $name {
+                type Error = RUMString;
+
+                fn try_from(segment: &V2Segment) -> V2Result<Self> {
+                    if segment.name() != $segment_name {
+                        return Err(format_compact!(
+                            "Expected a {} segment! Got {}!",
+                            $segment_name,
+                            segment.name()
+                        ));
+                    }
+                    Ok($name {
+                        $($field: <$field_type>::from_repetitions(&segment_repetitions(segment, $seq)),)+
+                    })
+                }
+            }
+
+            impl V2SegmentType for $name {
+                const SEGMENT_NAME: &'static str = $segment_name;
+                const FIELDS: &'static [(&'static str, u16)] = &[$((stringify!($field), $seq),)+];
+
+                fn to_segment(&self, parser_chars: &V2ParserCharacters) -> V2Result<V2Segment> {
+                    build_segment(
+                        $segment_name,
+                        vec![$(($seq, self.$field.to_repetitions()),)+],
+                        parser_chars,
+                    )
+                }
+            }
+        };
+    }
+
+    /**************************** Complex Types *********************************/
+
+    v2_composite_type!(Ce, "CE", {
+        id: RUMString,
+        text: RUMString,
+        coding_system: RUMString,
+        alt_id: RUMString,
+        alt_text: RUMString,
+        alt_coding_system: RUMString,
+    });
+
+    v2_composite_type!(Cne, "CNE", {
+        id: RUMString,
+        text: RUMString,
+        coding_system: RUMString,
+        alt_id: RUMString,
+        alt_text: RUMString,
+        alt_coding_system: RUMString,
+        version_id: RUMString,
+        alt_version_id: RUMString,
+        original_text: RUMString,
+        second_alt_id: RUMString,
+        second_alt_text: RUMString,
+        second_alt_coding_system: RUMString,
+        second_alt_version_id: RUMString,
+        coding_system_oid: RUMString,
+        valueset_oid: RUMString,
+        valueset_version_id: RUMString,
+        alt_coding_system_oid: RUMString,
+        alt_valueset_oid: RUMString,
+        alt_valueset_version_id: RUMString,
+        second_alt_coding_system_oid: RUMString,
+        second_alt_valueset_oid: RUMString,
+        second_alt_valueset_version_id: RUMString,
+    });
+
+    v2_composite_type!(Cnn, "CNN", {
+        id: RUMString,
+        family_name: RUMString,
+        given_name: RUMString,
+        second_given_name: RUMString,
+        suffix: RUMString,
+        prefix: RUMString,
+        degree: RUMString,
+        source_table: RUMString,
+        aa_namespace_id: RUMString,
+        aa_universal_id: RUMString,
+        aa_universal_id_type: RUMString,
+    });
+
+    v2_composite_type!(Cp, "CP", {
+        price: Mo,
+        price_type: RUMString,
+        from_value: RUMString,
+        to_value: RUMString,
+        range_units: Cwe,
+        range_type: RUMString,
+    });
+
+    v2_composite_type!(Cq, "CQ", {
+        quantity: RUMString,
+        units: Cwe,
+    });
+
+    v2_composite_type!(Cwe, "CWE", {
+        id: RUMString,
+        text: RUMString,
+        coding_system: RUMString,
+        alt_id: RUMString,
+        alt_text: RUMString,
+        alt_coding_system: RUMString,
+        version_id: RUMString,
+        alt_version_id: RUMString,
+        original_text: RUMString,
+        second_alt_id: RUMString,
+        second_alt_text: RUMString,
+        second_alt_coding_system: RUMString,
+        second_alt_version_id: RUMString,
+        coding_system_oid: RUMString,
+        valueset_oid: RUMString,
+        valueset_version_id: RUMString,
+        alt_coding_system_oid: RUMString,
+        alt_valueset_oid: RUMString,
+        alt_valueset_version_id: RUMString,
+        second_alt_coding_system_oid: RUMString,
+        second_alt_valueset_oid: RUMString,
+        second_alt_valueset_version_id: RUMString,
+    });
+
+    v2_composite_type!(Cx, "CX", {
+        id: RUMString,
+        check_digit: RUMString,
+        check_digit_scheme: RUMString,
+        assigning_authority: Hd,
+        id_type: RUMString,
+        assigning_facility: Hd,
+        effective_date: RUMString,
+        expiration_date: RUMString,
+        assigning_jurisdiction: Cwe,
+        assigning_agency: Cwe,
+        security_check: RUMString,
+        security_check_scheme: RUMString,
+    });
+
+    v2_composite_type!(Dld, "DLD", {
+        discharge_location: Cwe,
+        effective_date: RUMString,
+    });
+
+    v2_composite_type!(Dln, "DLN", {
+        license_number: RUMString,
+        issuing_state: Cwe,
+        expiration_date: RUMString,
+    });
+
+    v2_composite_type!(Dr, "DR", {
+        range_start: RUMString,
+        range_end: RUMString,
+    });
+
+    v2_composite_type!(Ei, "EI", {
+        entity_id: RUMString,
+        namespace_id: RUMString,
+        universal_id: RUMString,
+        universal_id_type: RUMString,
+    });
+
+    v2_composite_type!(Eip, "EIP", {
+        placer_id: Ei,
+        filler_id: Ei,
+    });
+
+    v2_composite_type!(Eld, "ELD", {
+        segment_id: RUMString,
+        segment_sequence: RUMString,
+        field_position: RUMString,
+        error_code: Ce,
+    });
+
+    v2_composite_type!(Erl, "ERL", {
+        segment_id: RUMString,
+        segment_sequence: RUMString,
+        field_position: RUMString,
+        field_repetition: RUMString,
+        component_number: RUMString,
+        subcomponent_number: RUMString,
+    });
+
+    v2_composite_type!(Fc, "FC", {
+        financial_class: Cwe,
+        effective_date: RUMString,
+    });
+
+    v2_composite_type!(Fn, "FN", {
+        surname: RUMString,
+        own_surname_prefix: RUMString,
+        own_surname: RUMString,
+        partner_surname_prefix: RUMString,
+        partner_surname: RUMString,
+    });
+
+    v2_composite_type!(Hd, "HD", {
+        namespace_id: RUMString,
+        universal_id: RUMString,
+        universal_id_type: RUMString,
+    });
+
+    v2_composite_type!(Jcc, "JCC", {
+        job_code: RUMString,
+        job_class: RUMString,
+        job_description: RUMString,
+    });
+
+    v2_composite_type!(La2, "LA2", {
+        point_of_care: RUMString,
+        room: RUMString,
+        bed: RUMString,
+        facility: Hd,
+        location_status: RUMString,
+        patient_location_type: RUMString,
+        building: RUMString,
+        floor: RUMString,
+        street_address: RUMString,
+        second_address: RUMString,
+        city: RUMString,
+        state: RUMString,
+        zip: RUMString,
+        country: RUMString,
+        address_type: RUMString,
+        county: RUMString,
+    });
+
+    v2_composite_type!(Mo, "MO", {
+        quantity: RUMString,
+        denomination: RUMString,
+    });
+
+    v2_composite_type!(Moc, "MOC", {
+        monetary_amount: Mo,
+        charge_code: Cwe,
+    });
+
+    v2_composite_type!(Msg, "MSG", {
+        message_code: RUMString,
+        trigger_event: RUMString,
+        message_structure: RUMString,
+    });
+
+    v2_composite_type!(Ndl, "NDL", {
+        name: Cnn,
+        start_date: RUMString,
+        end_date: RUMString,
+        point_of_care: RUMString,
+        room: RUMString,
+        bed: RUMString,
+        facility: Hd,
+        location_status: RUMString,
+        patient_location_type: RUMString,
+        building: RUMString,
+        floor: RUMString,
+    });
+
+    v2_composite_type!(Osd, "OSD", {
+        sequence_results_flag: RUMString,
+        placer_entity_id: RUMString,
+        placer_namespace_id: RUMString,
+        filler_entity_id: RUMString,
+        filler_namespace_id: RUMString,
+        sequence_condition_value: RUMString,
+        maximum_repeats: RUMString,
+        placer_universal_id: RUMString,
+        placer_universal_id_type: RUMString,
+        filler_universal_id: RUMString,
+        filler_universal_id_type: RUMString,
+    });
+
+    v2_composite_type!(Pl, "PL", {
+        point_of_care: Hd,
+        room: Hd,
+        bed: Hd,
+        facility: Hd,
+        location_status: RUMString,
+        person_location_type: RUMString,
+        building: Hd,
+        floor: Hd,
+        location_description: RUMString,
+        location_id: Ei,
+        location_assigning_authority: Hd,
+    });
+
+    v2_composite_type!(Prl, "PRL", {
+        parent_observation_id: Cwe,
+        parent_observation_sub_id: RUMString,
+        parent_observation_value: RUMString,
+    });
+
+    v2_composite_type!(Pt, "PT", {
+        processing_id: RUMString,
+        processing_mode: RUMString,
+    });
+
+    v2_composite_type!(Ri, "RI", {
+        repeat_pattern: Cwe,
+        explicit_time_interval: RUMString,
+    });
+
+    v2_composite_type!(Rpt, "RPT", {
+        repeat_pattern_code: Cwe,
+        calendar_alignment: RUMString,
+        phase_range_begin: RUMString,
+        phase_range_end: RUMString,
+        period_quantity: RUMString,
+        period_units: Cwe,
+        institution_specified_time: RUMString,
+        event: RUMString,
+        event_offset_quantity: RUMString,
+        event_offset_units: Cwe,
+        general_timing_specification: RUMString,
+    });
+
+    v2_composite_type!(Sad, "SAD", {
+        street_address: RUMString,
+        street_name: RUMString,
+        dwelling_number: RUMString,
+    });
+
+    v2_composite_type!(Sps, "SPS", {
+        specimen_source: Cwe,
+        additives: Cwe,
+        collection_method: RUMString,
+        body_site: Cwe,
+        site_modifier: Cwe,
+        collection_method_modifier: Cwe,
+        specimen_role: Cwe,
+    });
+
+    v2_composite_type!(Tq, "TQ", {
+        quantity: Cq,
+        interval: Ri,
+        duration: RUMString,
+        start_date: RUMString,
+        end_date: RUMString,
+        priority: RUMString,
+        condition: RUMString,
+        text: RUMString,
+        conjunction: RUMString,
+        order_sequencing: Osd,
+        occurrence_duration: Cwe,
+        total_occurrences: RUMString,
+    });
+
+    v2_composite_type!(Vid, "VID", {
+        version_id: RUMString,
+        internationalization_code: Cwe,
+        international_version_id: Cwe,
+    });
+
+    v2_composite_type!(Xad, "XAD", {
+        street_address: Sad,
+        second_address: RUMString,
+        city: RUMString,
+        state: RUMString,
+        zip: RUMString,
+        country: RUMString,
+        address_type: RUMString,
+        county: RUMString,
+        county_code: Cwe,
+        census_tract: Cwe,
+        address_representation: RUMString,
+        address_validity_range: Dr,
+        effective_date: RUMString,
+        expiration_date: RUMString,
+        expiration_reason: Cwe,
+        temporary_indicator: RUMString,
+        bad_address_indicator: RUMString,
+        address_usage: RUMString,
+        addressee: RUMString,
+        comment: RUMString,
+        preference_order: RUMString,
+        protection_code: Cwe,
+        address_id: Ei,
+    });
+
+    v2_composite_type!(Xcn, "XCN", {
+        person_id: RUMString,
+        family_name: Fn,
+        given_name: RUMString,
+        second_given_name: RUMString,
+        suffix: RUMString,
+        prefix: RUMString,
+        degree: RUMString,
+        source_table: Cwe,
+        assigning_authority: Hd,
+        name_type: RUMString,
+        check_digit: RUMString,
+        check_digit_scheme: RUMString,
+        id_type: RUMString,
+        assigning_facility: Hd,
+        name_representation: RUMString,
+        name_context: Cwe,
+        name_validity_range: Dr,
+        name_assembly_order: RUMString,
+        effective_date: RUMString,
+        expiration_date: RUMString,
+        professional_suffix: RUMString,
+        assigning_jurisdiction: Cwe,
+        assigning_agency: Cwe,
+        security_check: RUMString,
+        security_check_scheme: RUMString,
+    });
+
+    v2_composite_type!(Xon, "XON", {
+        organization_name: RUMString,
+        organization_name_type: Cwe,
+        id: RUMString,
+        check_digit: RUMString,
+        check_digit_scheme: RUMString,
+        assigning_authority: Hd,
+        id_type: RUMString,
+        assigning_facility: Hd,
+        name_representation: RUMString,
+        organization_id: RUMString,
+    });
+
+    v2_composite_type!(Xpn, "XPN", {
+        family_name: Fn,
+        given_name: RUMString,
+        second_given_name: RUMString,
+        suffix: RUMString,
+        prefix: RUMString,
+        degree: RUMString,
+        name_type: RUMString,
+        name_representation: RUMString,
+        name_context: Cwe,
+        name_validity_range: Dr,
+        name_assembly_order: RUMString,
+        effective_date: RUMString,
+        expiration_date: RUMString,
+        professional_suffix: RUMString,
+        called_by: RUMString,
+    });
+
+    v2_composite_type!(Xtn, "XTN", {
+        telephone_number: RUMString,
+        use_code: RUMString,
+        equipment_type: RUMString,
+        communication_address: RUMString,
+        country_code: RUMString,
+        area_code: RUMString,
+        local_number: RUMString,
+        extension: RUMString,
+        any_text: RUMString,
+        extension_prefix: RUMString,
+        speed_dial_code: RUMString,
+        unformatted_number: RUMString,
+        effective_start_date: RUMString,
+        expiration_date: RUMString,
+        expiration_reason: Cwe,
+        protection_code: Cwe,
+        shared_telecommunication_id: Ei,
+        preference_order: RUMString,
+    });
+
+    /**************************** Segments **************************************/
+
+    v2_segment_type!(Al1, "AL1", {
+        set_id: 1 => RUMString,
+        allergen_type_code: 2 => Ce,
+        allergen_code_mnemonic_description: 3 => Ce,
+        allergy_severity_code: 4 => Ce,
+        allergy_reaction_code: 5 => Vec<RUMString>,
+        identification_date: 6 => RUMString,
+    });
+
+    v2_segment_type!(Bhs, "BHS", {
+        batch_encoding_characters: 2 => RUMString,
+        batch_sending_application: 3 => Hd,
+        batch_sending_facility: 4 => Hd,
+        batch_receiving_application: 5 => Hd,
+        batch_receiving_facility: 6 => Hd,
+        batch_creation_date_time: 7 => RUMString,
+        batch_security: 8 => RUMString,
+        batch_name_id_type: 9 => RUMString,
+        batch_comment: 10 => RUMString,
+        batch_control_id: 11 => RUMString,
+        reference_batch_control_id: 12 => RUMString,
+    });
+
+    v2_segment_type!(Bts, "BTS", {
+        batch_message_count: 1 => RUMString,
+        batch_comment: 2 => RUMString,
+        batch_totals: 3 => Vec<RUMString>,
+    });
+
+    v2_segment_type!(Dg1, "DG1", {
+        set_id: 1 => RUMString,
+        diagnosis_coding_method: 2 => RUMString,
+        diagnosis_code: 3 => Ce,
+        diagnosis_description: 4 => RUMString,
+        diagnosis_date_time: 5 => RUMString,
+        diagnosis_type: 6 => RUMString,
+        major_diagnostic_category: 7 => Ce,
+        diagnostic_related_group: 8 => Ce,
+        drg_approval_indicator: 9 => RUMString,
+        drg_grouper_review_code: 10 => RUMString,
+        outlier_type: 11 => Ce,
+        outlier_days: 12 => RUMString,
+        outlier_cost: 13 => Cp,
+        grouper_version_and_type: 14 => RUMString,
+        diagnosis_priority: 15 => RUMString,
+        diagnosing_clinician: 16 => Vec<Xcn>,
+        diagnosis_classification: 17 => RUMString,
+        confidential_indicator: 18 => RUMString,
+        attestation_date_time: 19 => RUMString,
+    });
+
+    v2_segment_type!(Err, "ERR", {
+        error_code_and_location: 1 => Vec<Eld>,
+        error_location: 2 => Vec<Erl>,
+        hl7_error_code: 3 => Cwe,
+        severity: 4 => RUMString,
+        application_error_code: 5 => Cwe,
+        application_error_parameter: 6 => Vec<RUMString>,
+        diagnostic_information: 7 => RUMString,
+        user_message: 8 => RUMString,
+        inform_person_indicator: 9 => Vec<RUMString>,
+        override_type: 10 => Cwe,
+        override_reason_code: 11 => Vec<Cwe>,
+        help_desk_contact_point: 12 => Vec<Xtn>,
+    });
+
+    v2_segment_type!(Evn, "EVN", {
+        event_type_code: 1 => RUMString,
+        recorded_date_time: 2 => RUMString,
+        date_time_planned_event: 3 => RUMString,
+        event_reason_code: 4 => RUMString,
+        operator_id: 5 => Vec<Xcn>,
+        event_occurred: 6 => RUMString,
+        event_facility: 7 => Hd,
+    });
+
+    v2_segment_type!(Fhs, "FHS", {
+        file_encoding_characters: 2 => RUMString,
+        file_sending_application: 3 => Hd,
+        file_sending_facility: 4 => Hd,
+        file_receiving_application: 5 => Hd,
+        file_receiving_facility: 6 => Hd,
+        file_creation_date_time: 7 => RUMString,
+        file_security: 8 => RUMString,
+        file_name_id: 9 => RUMString,
+        file_header_comment: 10 => RUMString,
+        file_control_id: 11 => RUMString,
+        reference_file_control_id: 12 => RUMString,
+    });
+
+    v2_segment_type!(Fts, "FTS", {
+        file_batch_count: 1 => RUMString,
+        file_trailer_comment: 2 => RUMString,
+    });
+
+    v2_segment_type!(Mrg, "MRG", {
+        prior_patient_identifier_list: 1 => Vec<Cx>,
+        prior_alternate_patient_id: 2 => Vec<Cx>,
+        prior_patient_account_number: 3 => Cx,
+        prior_patient_id: 4 => Cx,
+        prior_visit_number: 5 => Cx,
+        prior_alternate_visit_id: 6 => Cx,
+        prior_patient_name: 7 => Vec<Xpn>,
+    });
+
+    v2_segment_type!(Msa, "MSA", {
+        acknowledgment_code: 1 => RUMString,
+        message_control_id: 2 => RUMString,
+        text_message: 3 => RUMString,
+        expected_sequence_number: 4 => RUMString,
+        delayed_acknowledgment_type: 5 => RUMString,
+        error_condition: 6 => Ce,
+    });
+
+    v2_segment_type!(Msh, "MSH", {
+        encoding_characters: 2 => RUMString,
+        sending_application: 3 => Hd,
+        sending_facility: 4 => Hd,
+        receiving_application: 5 => Hd,
+        receiving_facility: 6 => Hd,
+        date_time_of_message: 7 => RUMString,
+        security: 8 => RUMString,
+        message_type: 9 => Msg,
+        message_control_id: 10 => RUMString,
+        processing_id: 11 => Pt,
+        version_id: 12 => Vid,
+        sequence_number: 13 => RUMString,
+        continuation_pointer: 14 => RUMString,
+        accept_acknowledgment_type: 15 => RUMString,
+        application_acknowledgment_type: 16 => RUMString,
+        country_code: 17 => RUMString,
+        character_set: 18 => Vec<RUMString>,
+        principal_language_of_message: 19 => Ce,
+        alternate_character_set_handling_scheme: 20 => RUMString,
+        message_profile_identifier: 21 => Vec<Ei>,
+    });
+
+    v2_segment_type!(Nk1, "NK1", {
+        set_id: 1 => RUMString,
+        name: 2 => Vec<Xpn>,
+        relationship: 3 => Ce,
+        address: 4 => Vec<Xad>,
+        phone_number: 5 => Vec<Xtn>,
+        business_phone_number: 6 => Vec<Xtn>,
+        contact_role: 7 => Ce,
+        start_date: 8 => RUMString,
+        end_date: 9 => RUMString,
+        next_of_kin_associated_parties_job_title: 10 => RUMString,
+        next_of_kin_associated_parties_job_code_class: 11 => Jcc,
+        next_of_kin_associated_parties_employee_number: 12 => Cx,
+        organization_name: 13 => Vec<Xon>,
+        marital_status: 14 => Ce,
+        administrative_sex: 15 => RUMString,
+        date_time_of_birth: 16 => RUMString,
+        living_dependency: 17 => Vec<RUMString>,
+        ambulatory_status: 18 => Vec<RUMString>,
+        citizenship: 19 => Vec<Ce>,
+        primary_language: 20 => Ce,
+        living_arrangement: 21 => RUMString,
+        publicity_code: 22 => Ce,
+        protection_indicator: 23 => RUMString,
+        student_indicator: 24 => RUMString,
+        religion: 25 => Ce,
+        mothers_maiden_name: 26 => Vec<Xpn>,
+        nationality: 27 => Ce,
+        ethnic_group: 28 => Vec<Ce>,
+        contact_reason: 29 => Vec<Ce>,
+        contact_persons_name: 30 => Vec<Xpn>,
+        contact_persons_telephone_number: 31 => Vec<Xtn>,
+        contact_persons_address: 32 => Vec<Xad>,
+        next_of_kin_associated_partys_identifiers: 33 => Vec<Cx>,
+        job_status: 34 => RUMString,
+        race: 35 => Vec<Ce>,
+        handicap: 36 => RUMString,
+        contact_person_social_security_number: 37 => RUMString,
+        next_of_kin_birth_place: 38 => RUMString,
+        vip_indicator: 39 => RUMString,
+    });
+
+    v2_segment_type!(Nte, "NTE", {
+        set_id: 1 => RUMString,
+        source_of_comment: 2 => RUMString,
+        comment: 3 => Vec<RUMString>,
+        comment_type: 4 => Ce,
+    });
+
+    v2_segment_type!(Obr, "OBR", {
+        set_id: 1 => RUMString,
+        placer_order_number: 2 => Ei,
+        filler_order_number: 3 => Ei,
+        universal_service_identifier: 4 => Ce,
+        priority: 5 => RUMString,
+        requested_date_time: 6 => RUMString,
+        observation_date_time: 7 => RUMString,
+        observation_end_date_time: 8 => RUMString,
+        collection_volume: 9 => Cq,
+        collector_identifier: 10 => Vec<Xcn>,
+        specimen_action_code: 11 => RUMString,
+        danger_code: 12 => Ce,
+        relevant_clinical_information: 13 => RUMString,
+        specimen_received_date_time: 14 => RUMString,
+        specimen_source: 15 => Sps,
+        ordering_provider: 16 => Vec<Xcn>,
+        order_callback_phone_number: 17 => Vec<Xtn>,
+        placer_field_1: 18 => RUMString,
+        placer_field_2: 19 => RUMString,
+        filler_field_1: 20 => RUMString,
+        filler_field_2: 21 => RUMString,
+        results_rpt_status_chng_date_time: 22 => RUMString,
+        charge_to_practice: 23 => Moc,
+        diagnostic_serv_sect_id: 24 => RUMString,
+        result_status: 25 => RUMString,
+        parent_result: 26 => Prl,
+        quantity_timing: 27 => Vec<Tq>,
+        result_copies_to: 28 => Vec<Xcn>,
+        parent: 29 => Eip,
+        transportation_mode: 30 => RUMString,
+        reason_for_study: 31 => Vec<Ce>,
+        principal_result_interpreter: 32 => Ndl,
+        assistant_result_interpreter: 33 => Vec<Ndl>,
+        technician: 34 => Vec<Ndl>,
+        transcriptionist: 35 => Vec<Ndl>,
+        scheduled_date_time: 36 => RUMString,
+        number_of_sample_containers: 37 => RUMString,
+        transport_logistics_of_collected_sample: 38 => Vec<Ce>,
+        collectors_comment: 39 => Vec<Ce>,
+        transport_arrangement_responsibility: 40 => Ce,
+        transport_arranged: 41 => RUMString,
+        escort_required: 42 => RUMString,
+        planned_patient_transport_comment: 43 => Vec<Ce>,
+        procedure_code: 44 => Ce,
+        procedure_code_modifier: 45 => Vec<Ce>,
+        placer_supplemental_service_information: 46 => Vec<Ce>,
+        filler_supplemental_service_information: 47 => Vec<Ce>,
+        medically_necessary_duplicate_procedure_reason: 48 => Cwe,
+        result_handling: 49 => RUMString,
+    });
+
+    v2_segment_type!(Obx, "OBX", {
+        set_id: 1 => RUMString,
+        value_type: 2 => RUMString,
+        observation_identifier: 3 => Ce,
+        observation_sub_id: 4 => RUMString,
+        observation_value: 5 => Vec<V2Varies>,
+        units: 6 => Ce,
+        references_range: 7 => RUMString,
+        abnormal_flags: 8 => Vec<RUMString>,
+        probability: 9 => RUMString,
+        nature_of_abnormal_test: 10 => Vec<RUMString>,
+        observation_result_status: 11 => RUMString,
+        effective_date_of_reference_range: 12 => RUMString,
+        user_defined_access_checks: 13 => RUMString,
+        date_time_of_the_observation: 14 => RUMString,
+        producers_id: 15 => Ce,
+        responsible_observer: 16 => Vec<Xcn>,
+        observation_method: 17 => Vec<Ce>,
+        equipment_instance_identifier: 18 => Vec<Ei>,
+        date_time_of_the_analysis: 19 => RUMString,
+        observation_site: 20 => Vec<Cwe>,
+        observation_instance_identifier: 21 => Ei,
+        mood_code: 22 => Cne,
+        performing_organization_name: 23 => Xon,
+        performing_organization_address: 24 => Xad,
+        performing_organization_medical_director: 25 => Xcn,
+    });
+
+    v2_segment_type!(Orc, "ORC", {
+        order_control: 1 => RUMString,
+        placer_order_number: 2 => Ei,
+        filler_order_number: 3 => Ei,
+        placer_group_number: 4 => Ei,
+        order_status: 5 => RUMString,
+        response_flag: 6 => RUMString,
+        quantity_timing: 7 => Vec<Tq>,
+        parent: 8 => Eip,
+        date_time_of_transaction: 9 => RUMString,
+        entered_by: 10 => Vec<Xcn>,
+        verified_by: 11 => Vec<Xcn>,
+        ordering_provider: 12 => Vec<Xcn>,
+        enterers_location: 13 => Pl,
+        call_back_phone_number: 14 => Vec<Xtn>,
+        order_effective_date_time: 15 => RUMString,
+        order_control_code_reason: 16 => Ce,
+        entering_organization: 17 => Ce,
+        entering_device: 18 => Ce,
+        action_by: 19 => Vec<Xcn>,
+        advanced_beneficiary_notice_code: 20 => Ce,
+        ordering_facility_name: 21 => Vec<Xon>,
+        ordering_facility_address: 22 => Vec<Xad>,
+        ordering_facility_phone_number: 23 => Vec<Xtn>,
+        ordering_provider_address: 24 => Vec<Xad>,
+        order_status_modifier: 25 => Cwe,
+        advanced_beneficiary_notice_override_reason: 26 => Cwe,
+        fillers_expected_availability_date_time: 27 => RUMString,
+        confidentiality_code: 28 => Cwe,
+        order_type: 29 => Cwe,
+        enterer_authorization_mode: 30 => Cne,
+    });
+
+    v2_segment_type!(Pd1, "PD1", {
+        living_dependency: 1 => Vec<RUMString>,
+        living_arrangement: 2 => RUMString,
+        patient_primary_facility: 3 => Vec<Xon>,
+        patient_primary_care_provider_name_and_id_no: 4 => Vec<Xcn>,
+        student_indicator: 5 => RUMString,
+        handicap: 6 => RUMString,
+        living_will_code: 7 => RUMString,
+        organ_donor_code: 8 => RUMString,
+        separate_bill: 9 => RUMString,
+        duplicate_patient: 10 => Vec<Cx>,
+        publicity_code: 11 => Ce,
+        protection_indicator: 12 => RUMString,
+        protection_indicator_effective_date: 13 => RUMString,
+        place_of_worship: 14 => Vec<Xon>,
+        advance_directive_code: 15 => Vec<Ce>,
+        immunization_registry_status: 16 => RUMString,
+        immunization_registry_status_effective_date: 17 => RUMString,
+        publicity_code_effective_date: 18 => RUMString,
+        military_branch: 19 => RUMString,
+        military_rank_grade: 20 => RUMString,
+        military_status: 21 => RUMString,
+    });
+
+    v2_segment_type!(Pid, "PID", {
+        set_id: 1 => RUMString,
+        patient_id: 2 => Cx,
+        patient_identifier_list: 3 => Vec<Cx>,
+        alternate_patient_id: 4 => Vec<Cx>,
+        patient_name: 5 => Vec<Xpn>,
+        mothers_maiden_name: 6 => Vec<Xpn>,
+        date_time_of_birth: 7 => RUMString,
+        administrative_sex: 8 => RUMString,
+        patient_alias: 9 => Vec<Xpn>,
+        race: 10 => Vec<Ce>,
+        patient_address: 11 => Vec<Xad>,
+        county_code: 12 => RUMString,
+        phone_number_home: 13 => Vec<Xtn>,
+        phone_number_business: 14 => Vec<Xtn>,
+        primary_language: 15 => Ce,
+        marital_status: 16 => Ce,
+        religion: 17 => Ce,
+        patient_account_number: 18 => Cx,
+        ssn_number_patient: 19 => RUMString,
+        drivers_license_number_patient: 20 => Dln,
+        mothers_identifier: 21 => Vec<Cx>,
+        ethnic_group: 22 => Vec<Ce>,
+        birth_place: 23 => RUMString,
+        multiple_birth_indicator: 24 => RUMString,
+        birth_order: 25 => RUMString,
+        citizenship: 26 => Vec<Ce>,
+        veterans_military_status: 27 => Ce,
+        nationality: 28 => Ce,
+        patient_death_date_and_time: 29 => RUMString,
+        patient_death_indicator: 30 => RUMString,
+        identity_unknown_indicator: 31 => RUMString,
+        identity_reliability_code: 32 => Vec<RUMString>,
+        last_update_date_time: 33 => RUMString,
+        last_update_facility: 34 => Hd,
+        species_code: 35 => Ce,
+        breed_code: 36 => Ce,
+        strain: 37 => RUMString,
+        production_class_code: 38 => Vec<Ce>,
+        tribal_citizenship: 39 => Vec<Cwe>,
+    });
+
+    v2_segment_type!(Pv1, "PV1", {
+        set_id: 1 => RUMString,
+        patient_class: 2 => RUMString,
+        assigned_patient_location: 3 => Pl,
+        admission_type: 4 => RUMString,
+        preadmit_number: 5 => Cx,
+        prior_patient_location: 6 => Pl,
+        attending_doctor: 7 => Vec<Xcn>,
+        referring_doctor: 8 => Vec<Xcn>,
+        consulting_doctor: 9 => Vec<Xcn>,
+        hospital_service: 10 => RUMString,
+        temporary_location: 11 => Pl,
+        preadmit_test_indicator: 12 => RUMString,
+        re_admission_indicator: 13 => RUMString,
+        admit_source: 14 => RUMString,
+        ambulatory_status: 15 => Vec<RUMString>,
+        vip_indicator: 16 => RUMString,
+        admitting_doctor: 17 => Vec<Xcn>,
+        patient_type: 18 => RUMString,
+        visit_number: 19 => Cx,
+        financial_class: 20 => Vec<Fc>,
+        charge_price_indicator: 21 => RUMString,
+        courtesy_code: 22 => RUMString,
+        credit_rating: 23 => RUMString,
+        contract_code: 24 => Vec<RUMString>,
+        contract_effective_date: 25 => Vec<RUMString>,
+        contract_amount: 26 => Vec<RUMString>,
+        contract_period: 27 => Vec<RUMString>,
+        interest_code: 28 => RUMString,
+        transfer_to_bad_debt_code: 29 => RUMString,
+        transfer_to_bad_debt_date: 30 => RUMString,
+        bad_debt_agency_code: 31 => RUMString,
+        bad_debt_transfer_amount: 32 => RUMString,
+        bad_debt_recovery_amount: 33 => RUMString,
+        delete_account_indicator: 34 => RUMString,
+        delete_account_date: 35 => RUMString,
+        discharge_disposition: 36 => RUMString,
+        discharged_to_location: 37 => Dld,
+        diet_type: 38 => Ce,
+        servicing_facility: 39 => RUMString,
+        bed_status: 40 => RUMString,
+        account_status: 41 => RUMString,
+        pending_location: 42 => Pl,
+        prior_temporary_location: 43 => Pl,
+        admit_date_time: 44 => RUMString,
+        discharge_date_time: 45 => Vec<RUMString>,
+        current_patient_balance: 46 => RUMString,
+        total_charges: 47 => RUMString,
+        total_adjustments: 48 => RUMString,
+        total_payments: 49 => RUMString,
+        alternate_visit_id: 50 => Cx,
+        visit_indicator: 51 => RUMString,
+        other_healthcare_provider: 52 => Vec<Xcn>,
+    });
+
+    v2_segment_type!(Rxa, "RXA", {
+        give_sub_id_counter: 1 => RUMString,
+        administration_sub_id_counter: 2 => RUMString,
+        date_time_start_of_administration: 3 => RUMString,
+        date_time_end_of_administration: 4 => RUMString,
+        administered_code: 5 => Ce,
+        administered_amount: 6 => RUMString,
+        administered_units: 7 => Ce,
+        administered_dosage_form: 8 => Ce,
+        administration_notes: 9 => Vec<Ce>,
+        administering_provider: 10 => Vec<Xcn>,
+        administered_at_location: 11 => La2,
+        administered_per_time_unit: 12 => RUMString,
+        administered_strength: 13 => RUMString,
+        administered_strength_units: 14 => Ce,
+        substance_lot_number: 15 => Vec<RUMString>,
+        substance_expiration_date: 16 => Vec<RUMString>,
+        substance_manufacturer_name: 17 => Vec<Ce>,
+        substance_treatment_refusal_reason: 18 => Vec<Ce>,
+        indication: 19 => Vec<Ce>,
+        completion_status: 20 => RUMString,
+        action_code: 21 => RUMString,
+        system_entry_date_time: 22 => RUMString,
+        administered_drug_strength_volume: 23 => RUMString,
+        administered_drug_strength_volume_units: 24 => Cwe,
+        administered_barcode_identifier: 25 => Cwe,
+        pharmacy_order_type: 26 => RUMString,
+    });
+
+    v2_segment_type!(Rxr, "RXR", {
+        route: 1 => Ce,
+        administration_site: 2 => Cwe,
+        administration_device: 3 => Ce,
+        administration_method: 4 => Cwe,
+        routing_instruction: 5 => Ce,
+        administration_site_modifier: 6 => Cwe,
+    });
+
+    v2_segment_type!(Spm, "SPM", {
+        set_id: 1 => RUMString,
+        specimen_id: 2 => Eip,
+        specimen_parent_ids: 3 => Vec<Eip>,
+        specimen_type: 4 => Cwe,
+        specimen_type_modifier: 5 => Vec<Cwe>,
+        specimen_additives: 6 => Vec<Cwe>,
+        specimen_collection_method: 7 => Cwe,
+        specimen_source_site: 8 => Cwe,
+        specimen_source_site_modifier: 9 => Vec<Cwe>,
+        specimen_collection_site: 10 => Cwe,
+        specimen_role: 11 => Vec<Cwe>,
+        specimen_collection_amount: 12 => Cq,
+        grouped_specimen_count: 13 => RUMString,
+        specimen_description: 14 => Vec<RUMString>,
+        specimen_handling_code: 15 => Vec<Cwe>,
+        specimen_risk_code: 16 => Vec<Cwe>,
+        specimen_collection_date_time: 17 => Dr,
+        specimen_received_date_time: 18 => RUMString,
+        specimen_expiration_date_time: 19 => RUMString,
+        specimen_availability: 20 => RUMString,
+        specimen_reject_reason: 21 => Vec<Cwe>,
+        specimen_quality: 22 => Cwe,
+        specimen_appropriateness: 23 => Cwe,
+        specimen_condition: 24 => Vec<Cwe>,
+        specimen_current_quantity: 25 => Cq,
+        number_of_specimen_containers: 26 => RUMString,
+        container_type: 27 => Cwe,
+        container_condition: 28 => Cwe,
+        specimen_child_role: 29 => Cwe,
+    });
+
+    v2_segment_type!(Tq1, "TQ1", {
+        set_id: 1 => RUMString,
+        quantity: 2 => Cq,
+        repeat_pattern: 3 => Vec<Rpt>,
+        explicit_time: 4 => Vec<RUMString>,
+        relative_time_and_units: 5 => Vec<Cq>,
+        service_duration: 6 => Cq,
+        start_date_time: 7 => RUMString,
+        end_date_time: 8 => RUMString,
+        priority: 9 => Vec<Cwe>,
+        condition_text: 10 => RUMString,
+        text_instruction: 11 => RUMString,
+        conjunction: 12 => RUMString,
+        occurrence_duration: 13 => Cq,
+        total_occurrences: 14 => RUMString,
+    });
+
+    /**************************** API *******************************************/
+
+    impl V2Message {
+        ///
+        /// Returns every segment of type `T` in the message as the typed struct, e.g.
+        /// `message.segments_as::<Obx>()`.
+        ///
+        pub fn segments_as<T: V2SegmentType>(&self) -> V2Result<Vec<T>> {
+            let segment_id = match find_segment_id(T::SEGMENT_NAME) {
+                Some(segment_id) => segment_id,
+                None => return Err(format_compact!("Unknown segment {}!", T::SEGMENT_NAME)),
+            };
+            if !self.segment_exists(&segment_id) {
+                return Ok(vec![]);
+            }
+            self.get_group(&segment_id)?
+                .iter()
+                .map(T::try_from)
+                .collect()
+        }
+    }
+}
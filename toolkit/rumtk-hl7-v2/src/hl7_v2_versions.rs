@@ -0,0 +1,359 @@
+/*
+ * rumtk attempts to implement HL7 and medical protocols for interoperability in medicine.
+ * This toolkit aims to be reliable, simple, performant, and standards compliant.
+ * Copyright (C) 2024  Luis M. Santos, M.D.
+ *
+ * This library is free software; you can redistribute it and/or
+ * modify it under the terms of the GNU Lesser General Public
+ * License as published by the Free Software Foundation; either
+ * version 2.1 of the License, or (at your option) any later version.
+ *
+ * This library is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+ * Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public
+ * License along with this library; if not, write to the Free Software
+ * Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301  USA
+ */
+
+pub mod v2_versions {
+    use crate::hl7_v2_base_types::v2_base_types::find_segment_id;
+    pub use crate::hl7_v2_base_types::v2_primitives::V2PrimitiveType;
+    use crate::hl7_v2_constants::V2_MSH_VERSION;
+    pub use crate::hl7_v2_field_descriptors::v2_field_descriptor::{
+        complex_type_to_str, V2ComplexType, V2ComponentDescriptor, V2ComponentType,
+        V2ComponentTypeDescriptor, V2_FIELD_DESCRIPTORS,
+    };
+    pub use crate::hl7_v2_message_structures::v2_message_structures::{V2_REPEATING, V2_SINGLE};
+    pub use crate::hl7_v2_optionality_rules::*;
+    use crate::hl7_v2_parser::v2_parser::V2Message;
+    pub use crate::hl7_v2_segment_descriptors::v2_segment_descriptor::{
+        V2FieldTypeDescriptor, V2_SEGMENT_FIELD_DESCRIPTORS,
+    };
+    use crate::{v2_component_descriptor, v2_field_descriptor};
+    use ::phf::Map;
+    use ::phf_macros::phf_map;
+    use rumtk_core::strings::AsStr;
+
+    /**************************** Types *****************************************/
+
+    ///
+    /// HL7 v2 versions we keep definitions for. Versions we do not list are mapped to the
+    /// closest one by [V2Version::from_str].
+    ///
+    #[allow(non_camel_case_types)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub enum V2Version {
+        V2_3,
+        V2_3_1,
+        V2_4,
+        V2_5,
+        V2_5_1,
+        V2_6,
+        V2_7,
+        V2_8,
+        V2_9,
+    }
+
+    ///
+    /// Overrides of segment field definitions keyed by version. Each entry pairs the segment name
+    /// with the definition replacing the one with the same sequence.
+    ///
+    pub type V2SegmentFieldOverrides =
+        Map<&'static str, &'static [(&'static str, &'static V2FieldTypeDescriptor)]>;
+    ///
+    /// Overrides of data type descriptors keyed by version. Each entry pairs the data type name
+    /// with the descriptor replacing it.
+    ///
+    pub type V2DataTypeOverrides =
+        Map<&'static str, &'static [(&'static str, &'static V2ComponentDescriptor)]>;
+
+    /**************************** Constants *************************************/
+
+    pub const V2_VERSIONS: [V2Version; 9] = [
+        V2Version::V2_3,
+        V2Version::V2_3_1,
+        V2Version::V2_4,
+        V2Version::V2_5,
+        V2Version::V2_5_1,
+        V2Version::V2_6,
+        V2Version::V2_7,
+        V2Version::V2_8,
+        V2Version::V2_9,
+    ];
+
+    ///
+    /// Version the segment field definitions in
+    /// [V2_SEGMENT_FIELD_DESCRIPTORS] describe. Their field layouts follow v2.5.1 but they list
+    /// TS fields as DTM, the type they carry from v2.6 on. The data type descriptors in
+    /// [V2_FIELD_DESCRIPTORS] follow the latest version. Every other version is described by
+    /// overrides on top of them plus the rules in [version_data_type].
+    ///
+    pub const V2_BASELINE_VERSION: V2Version = V2Version::V2_5_1;
+
+    ///
+    /// Version assumed when MSH-12 is missing or we cannot make sense of it.
+    ///
+    pub const V2_DEFAULT_VERSION: V2Version = V2_BASELINE_VERSION;
+
+    ///
+    /// Field definitions that differ from [V2_SEGMENT_FIELD_DESCRIPTORS]. A version inherits the
+    /// overrides of the versions between it and [V2_BASELINE_VERSION], so changes made in v2.7
+    /// also apply to v2.8 and v2.9, and changes undone in v2.4 also apply to v2.3.1 and v2.3.
+    ///
+    /// ## Per Section 2.A
+    /// ```text
+    /// Backward compatible (B) elements are retained for backward compatibility as of a version.
+    /// Withdrawn (W) elements are no longer part of the standard and shall not be sent.
+    /// ```
+    ///
+    pub static V2_SEGMENT_FIELD_OVERRIDES: V2SegmentFieldOverrides = phf_map! {
+        "2.3" => &[
+            ("MSH", v2_field_descriptor!("version_id", "Version ID", V2ComponentType::Primitive(V2PrimitiveType::ID), 8, 12, 104, Optionality::R, V2_SINGLE)),
+            ("EVN", v2_field_descriptor!("event_type_code", "Event Type Code", V2ComponentType::Primitive(V2PrimitiveType::ID), 3, 1, 3, Optionality::R, V2_SINGLE)),
+            ("PID", v2_field_descriptor!("patient_id", "Patient ID (External ID)", V2ComponentType::Complex(V2ComplexType::CX), 20, 2, 0, Optionality::O, V2_SINGLE)),
+            ("PID", v2_field_descriptor!("alternate_patient_id", "Alternate Patient ID - PID", V2ComponentType::Complex(V2ComplexType::CX), 20, 4, 0, Optionality::O, V2_REPEATING)),
+            ("PID", v2_field_descriptor!("ssn_number_patient", "SSN Number - Patient", V2ComponentType::Primitive(V2PrimitiveType::ST), 16, 19, 0, Optionality::O, V2_SINGLE)),
+            ("PID", v2_field_descriptor!("drivers_license_number_patient", "Driver's License Number - Patient", V2ComponentType::Complex(V2ComplexType::DLN), 25, 20, 0, Optionality::O, V2_SINGLE))
+        ],
+        "2.3.1" => &[
+            ("MSH", v2_field_descriptor!("message_profile_identifier", "Message Profile Identifier", V2ComponentType::Complex(V2ComplexType::EI), 427, 21, 0, Optionality::X, V2_REPEATING))
+        ],
+        "2.4" => &[
+            ("MSH", v2_field_descriptor!("conformance_statement_id", "Conformance Statement ID", V2ComponentType::Primitive(V2PrimitiveType::ID), 10, 21, 449, Optionality::O, V2_REPEATING))
+        ],
+        "2.7" => &[
+            ("EVN", v2_field_descriptor!("event_type_code", "Event Type Code", V2ComponentType::Primitive(V2PrimitiveType::ID), 3, 1, 3, Optionality::X, V2_SINGLE)),
+            ("PID", v2_field_descriptor!("patient_id", "Patient ID", V2ComponentType::Complex(V2ComplexType::CX), 20, 2, 0, Optionality::X, V2_SINGLE)),
+            ("PID", v2_field_descriptor!("alternate_patient_id", "Alternate Patient ID - PID", V2ComponentType::Complex(V2ComplexType::CX), 20, 4, 0, Optionality::X, V2_REPEATING)),
+            ("PID", v2_field_descriptor!("county_code", "County Code", V2ComponentType::Primitive(V2PrimitiveType::IS), 4, 12, 289, Optionality::X, V2_SINGLE)),
+            ("PID", v2_field_descriptor!("ssn_number_patient", "SSN Number - Patient", V2ComponentType::Primitive(V2PrimitiveType::ST), 16, 19, 0, Optionality::X, V2_SINGLE)),
+            ("PID", v2_field_descriptor!("drivers_license_number_patient", "Driver's License Number - Patient", V2ComponentType::Complex(V2ComplexType::DLN), 25, 20, 0, Optionality::X, V2_SINGLE))
+        ],
+    };
+
+    ///
+    /// Data type descriptors that differ from [V2_FIELD_DESCRIPTORS]. Inherited the same way as
+    /// [V2_SEGMENT_FIELD_OVERRIDES], except the baseline of the data type tables is the latest
+    /// version, so older versions inherit the overrides of every version newer than them.
+    ///
+    pub static V2_DATA_TYPE_OVERRIDES: V2DataTypeOverrides = phf_map! {
+        "2.3.1" => &[
+            ("CX", &[
+                v2_component_descriptor!("id", "ID", V2ComponentType::Primitive(V2PrimitiveType::ST), 15, 1, 0, Optionality::R, false),
+                v2_component_descriptor!("check_digit", "Check Digit", V2ComponentType::Primitive(V2PrimitiveType::ST), 4, 2, 0, Optionality::O, false),
+                v2_component_descriptor!("check_digit_scheme", "Code Identifying the Check Digit Scheme Employed", V2ComponentType::Primitive(V2PrimitiveType::ID), 3, 3, 61, Optionality::O, false),
+                v2_component_descriptor!("assigning_authority", "Assigning Authority", V2ComponentType::Complex(V2ComplexType::HD), 0, 4, 363, Optionality::O, false),
+                v2_component_descriptor!("id_type", "Identifier Type Code", V2ComponentType::Primitive(V2PrimitiveType::IS), 5, 5, 203, Optionality::O, false),
+                v2_component_descriptor!("assigning_facility", "Assigning Facility", V2ComponentType::Complex(V2ComplexType::HD), 0, 6, 0, Optionality::O, false)
+            ]),
+            ("XPN", &[
+                v2_component_descriptor!("family_name", "Family Name", V2ComponentType::Primitive(V2PrimitiveType::ST), 0, 1, 0, Optionality::O, false),
+                v2_component_descriptor!("given_name", "Given Name", V2ComponentType::Primitive(V2PrimitiveType::ST), 0, 2, 0, Optionality::O, false),
+                v2_component_descriptor!("second_given_name", "Middle Initial or Name", V2ComponentType::Primitive(V2PrimitiveType::ST), 0, 3, 0, Optionality::O, false),
+                v2_component_descriptor!("suffix", "Suffix (e.g. JR or III)", V2ComponentType::Primitive(V2PrimitiveType::ST), 0, 4, 0, Optionality::O, false),
+                v2_component_descriptor!("prefix", "Prefix (e.g. DR)", V2ComponentType::Primitive(V2PrimitiveType::ST), 0, 5, 0, Optionality::O, false),
+                v2_component_descriptor!("degree", "Degree (e.g. MD)", V2ComponentType::Primitive(V2PrimitiveType::IS), 0, 6, 360, Optionality::O, false),
+                v2_component_descriptor!("name_type", "Name Type Code", V2ComponentType::Primitive(V2PrimitiveType::ID), 1, 7, 200, Optionality::O, false),
+                v2_component_descriptor!("name_representation", "Name Representation Code", V2ComponentType::Primitive(V2PrimitiveType::ID), 1, 8, 4465, Optionality::O, false)
+            ])
+        ],
+    };
+
+    /**************************** Helpers ***************************************/
+
+    fn chain_toward(version: V2Version, baseline: V2Version) -> Vec<V2Version> {
+        let position = |v: V2Version| V2_VERSIONS.iter().position(|x| *x == v).unwrap();
+        let (from, to) = (position(version), position(baseline));
+        match from <= to {
+            true => V2_VERSIONS[from..=to].to_vec(),
+            false => V2_VERSIONS[to..=from].iter().rev().copied().collect(),
+        }
+    }
+
+    /**************************** API *******************************************/
+
+    impl V2Version {
+        ///
+        /// Maps a version string (MSH-12.1) to the closest version we keep definitions for.
+        /// Patch releases fall back to their minor version (e.g. `2.7.1` to v2.7 and `2.5.2` to
+        /// v2.5.1), versions older than v2.3 to v2.3, and versions newer than v2.9 to v2.9.
+        /// Strings that are not a v2 version yield [V2_DEFAULT_VERSION].
+        ///
+        #[allow(clippy::should_implement_trait)]
+        pub fn from_str(version: &str) -> V2Version {
+            let numbers: Vec<Option<u16>> = version
+                .trim()
+                .trim_start_matches(['v', 'V'])
+                .split('.')
+                .map(|n| n.parse::<u16>().ok())
+                .collect();
+            let (major, minor, patch) = match numbers.as_slice() {
+                [Some(major)] => (*major, 0, 0),
+                [Some(major), Some(minor)] => (*major, *minor, 0),
+                [Some(major), Some(minor), Some(patch), ..] => (*major, *minor, *patch),
+                _ => return V2_DEFAULT_VERSION,
+            };
+            if major != 2 {
+                return V2_DEFAULT_VERSION;
+            }
+            match (minor, patch) {
+                (0..=2, _) | (3, 0) => V2Version::V2_3,
+                (3, _) => V2Version::V2_3_1,
+                (4, _) => V2Version::V2_4,
+                (5, 0) => V2Version::V2_5,
+                (5, _) => V2Version::V2_5_1,
+                (6, _) => V2Version::V2_6,
+                (7, _) => V2Version::V2_7,
+                (8, _) => V2Version::V2_8,
+                _ => V2Version::V2_9,
+            }
+        }
+
+        pub fn as_str(&self) -> &'static str {
+            match self {
+                V2Version::V2_3 => "2.3",
+                V2Version::V2_3_1 => "2.3.1",
+                V2Version::V2_4 => "2.4",
+                V2Version::V2_5 => "2.5",
+                V2Version::V2_5_1 => "2.5.1",
+                V2Version::V2_6 => "2.6",
+                V2Version::V2_7 => "2.7",
+                V2Version::V2_8 => "2.8",
+                V2Version::V2_9 => "2.9",
+            }
+        }
+
+        ///
+        /// Whether `+0000` and `-0000` mean different things in a DTM offset.
+        ///
+        /// ## Per Section 2A.3.22
+        /// ```text
+        /// For implementations prior to V2.9 +0000 or -0000 both represent UTC (without offset).
+        /// For implementations starting with V2.9 use of the plus sign (+0000) represents the civil
+        /// time zone offset is known to be zero, use of the minus sign (-0000) represents UTC
+        /// (without offset).
+        /// ```
+        ///
+        pub fn distinguishes_zero_offset(&self) -> bool {
+            *self >= V2Version::V2_9
+        }
+    }
+
+    ///
+    /// Applies the data type changes between versions that are not spelled out in the
+    /// overrides. CE was replaced by CWE (or CNE) as of v2.6. TS was replaced by DTM as of v2.6,
+    /// so DTM fields are TS fields before then.
+    ///
+    pub fn version_data_type(data_type: &V2ComponentType, version: V2Version) -> V2ComponentType {
+        match data_type {
+            V2ComponentType::Complex(V2ComplexType::CE) if version >= V2Version::V2_6 => {
+                V2ComponentType::Complex(V2ComplexType::CWE)
+            }
+            V2ComponentType::Primitive(V2PrimitiveType::DateTime) if version < V2Version::V2_6 => {
+                V2ComponentType::Complex(V2ComplexType::TS)
+            }
+            _ => *data_type,
+        }
+    }
+
+    ///
+    /// Returns the definition of field `seq` of a segment for a version, before
+    /// [version_data_type] is applied.
+    ///
+    pub fn version_field_descriptor(
+        segment_name: &str,
+        seq: u16,
+        version: V2Version,
+    ) -> Option<&'static V2FieldTypeDescriptor> {
+        for v in chain_toward(version, V2_BASELINE_VERSION) {
+            let overrides = V2_SEGMENT_FIELD_OVERRIDES
+                .get(v.as_str())
+                .copied()
+                .unwrap_or_default();
+            if let Some((_, descriptor)) = overrides
+                .iter()
+                .find(|(segment, descriptor)| *segment == segment_name && descriptor.seq == seq)
+            {
+                return Some(*descriptor);
+            }
+        }
+        V2_SEGMENT_FIELD_DESCRIPTORS
+            .get(segment_name)?
+            .iter()
+            .find(|descriptor| descriptor.seq == seq)
+            .copied()
+    }
+
+    ///
+    /// Returns every field definition of a segment for a version, ordered by sequence.
+    ///
+    pub fn version_segment_descriptor(
+        segment_name: &str,
+        version: V2Version,
+    ) -> Vec<&'static V2FieldTypeDescriptor> {
+        let mut sequences: Vec<u16> = V2_SEGMENT_FIELD_DESCRIPTORS
+            .get(segment_name)
+            .map(|fields| fields.iter().map(|field| field.seq).collect())
+            .unwrap_or_default();
+        for v in chain_toward(version, V2_BASELINE_VERSION) {
+            let overrides = V2_SEGMENT_FIELD_OVERRIDES
+                .get(v.as_str())
+                .copied()
+                .unwrap_or_default();
+            for (segment, descriptor) in overrides.iter() {
+                if *segment == segment_name && !sequences.contains(&descriptor.seq) {
+                    sequences.push(descriptor.seq);
+                }
+            }
+        }
+        sequences.sort();
+        sequences
+            .into_iter()
+            .filter_map(|seq| version_field_descriptor(segment_name, seq, version))
+            .collect()
+    }
+
+    ///
+    /// Returns the descriptor of a data type for a version.
+    ///
+    pub fn version_type_descriptor(
+        complex: &V2ComplexType,
+        version: V2Version,
+    ) -> Option<&'static V2ComponentDescriptor> {
+        let type_name = complex_type_to_str(complex);
+        for v in chain_toward(version, V2Version::V2_9) {
+            let overrides = V2_DATA_TYPE_OVERRIDES
+                .get(v.as_str())
+                .copied()
+                .unwrap_or_default();
+            if let Some((_, descriptor)) = overrides.iter().find(|(name, _)| *name == type_name) {
+                return Some(*descriptor);
+            }
+        }
+        V2_FIELD_DESCRIPTORS.get(type_name).copied()
+    }
+
+    impl V2Message {
+        ///
+        /// Version of the definitions this message is interpreted against, from MSH-12. See
+        /// [V2Version::from_str] for how versions we do not list are mapped.
+        ///
+        pub fn version(&self) -> V2Version {
+            let version_id = find_segment_id("MSH")
+                .ok_or_else(|| "No MSH segment id!".into())
+                .and_then(|msh_id| self.get(&msh_id, 1))
+                .and_then(|msh| msh.get(V2_MSH_VERSION))
+                .and_then(|field| match field.first() {
+                    Some(field) => Ok(field.get(1)?.get(1)?.as_str()),
+                    None => Err("MSH-12 is empty!".into()),
+                });
+            match version_id {
+                Ok(version_id) => V2Version::from_str(version_id),
+                Err(_) => V2_DEFAULT_VERSION,
+            }
+        }
+    }
+}
@@ -22,38 +22,75 @@
 
 extern crate rumtk_core;
 pub mod hl7_v2_base_types;
+pub mod hl7_v2_batch;
+pub mod hl7_v2_builder;
+pub mod hl7_v2_charsets;
 pub mod hl7_v2_complex_types;
 pub mod hl7_v2_constants;
+pub mod hl7_v2_diff;
 pub mod hl7_v2_field_descriptors;
 pub mod hl7_v2_interpreter;
+pub mod hl7_v2_lengths;
+pub mod hl7_v2_message_ref;
+pub mod hl7_v2_message_structures;
 pub mod hl7_v2_mllp;
 mod hl7_v2_optionality_rules;
 pub mod hl7_v2_parser;
+pub mod hl7_v2_profiles;
+pub mod hl7_v2_query;
 mod hl7_v2_scripting;
 pub mod hl7_v2_search;
+pub mod hl7_v2_segment_descriptors;
+pub mod hl7_v2_tables;
 pub mod hl7_v2_types;
+pub mod hl7_v2_versions;
 /*****************************************Tests****************************************/
 #[cfg(test)]
 mod tests {
     use crate::hl7_v2_base_types::v2_base_types::{
-        V2DateTime, V2ParserCharacters, V2SearchIndex, V2String,
+        find_segment_id, V2DateTime, V2DateTimePrecision, V2DiagnosticSeverity, V2ParseMode,
+        V2ParserCharacters, V2SearchIndex, V2String, V2TimeZone,
     };
     use crate::hl7_v2_base_types::v2_primitives::{
-        V2PrimitiveCasting, V2PrimitiveType, TRUNCATE_FT,
+        V2PrimitiveCasting, V2PrimitiveType, V2StructuredNumeric, TRUNCATE_FT,
     };
+    use crate::hl7_v2_batch::v2_batch::{V2Batch, V2BatchError, V2File};
+    use crate::hl7_v2_builder::v2_builder::V2MessageBuilder;
     use crate::hl7_v2_complex_types::hl7_v2_complex_types::{cast_component, V2Type};
-    use crate::hl7_v2_constants::{V2_SEGMENT_IDS, V2_SEGMENT_NAMES};
+    use crate::hl7_v2_constants::{
+        V2_CUSTOM_SEGMENT_ID_START, V2_MSH_CONTROL_ID, V2_MSH_MESSAGE_TYPE, V2_MSH_VERSION,
+        V2_SEGMENT_IDS, V2_SEGMENT_NAMES,
+    };
+    use crate::hl7_v2_diff::v2_diff::{V2Change, V2DiffPosition, V2DiffRules};
     use crate::hl7_v2_field_descriptors::v2_field_descriptor::{
-        V2ComponentType, V2ComponentTypeDescriptor,
+        complex_type_to_str, V2ComplexType, V2ComponentType, V2ComponentTypeDescriptor,
+        V2_FIELD_DESCRIPTORS,
+    };
+    use crate::hl7_v2_lengths::v2_lengths::V2LengthPolicy;
+    use crate::hl7_v2_message_ref::v2_message_ref::V2MessageRef;
+    use crate::hl7_v2_message_structures::v2_message_structures::V2StructureIssue;
+    use crate::hl7_v2_mllp::mllp_v2::{
+        mllp_decode, mllp_encode, mllp_encode_bytes, CR, EB, MLLP_FILTER_POLICY, SB,
+    };
+    use crate::hl7_v2_optionality_rules::{
+        Optionality, V2ConditionContext, CONDITION_CF1, CONDITION_CNN1, CONDITION_CWE3,
+        CONDITION_XCN1, CONDITION_XCN4, CONDITION_XTN1, CONDITION_XTN2,
     };
-    use crate::hl7_v2_mllp::mllp_v2::{mllp_decode, mllp_encode, CR, EB, MLLP_FILTER_POLICY, SB};
-    use crate::hl7_v2_optionality_rules::Optionality;
     use crate::hl7_v2_parser::v2_parser::{V2Field, V2Message};
+    use crate::hl7_v2_profiles::v2_profiles::{V2ConformanceIssueKind, V2Profile};
+    use crate::hl7_v2_query::v2_query::V2Query;
     use crate::hl7_v2_search::REGEX_V2_SEARCH_DEFAULT;
+    use crate::hl7_v2_segment_descriptors::v2_segment_descriptor::V2_SEGMENT_FIELD_DESCRIPTORS;
+    use crate::hl7_v2_tables::v2_tables::{V2TableRegistry, V2TableType};
+    use crate::hl7_v2_types::v2_types::{
+        Cx, Msh, Obx, Pid, V2CompositeType, V2SegmentType, Xcn, Xpn,
+    };
+    use crate::hl7_v2_versions::v2_versions::V2Version;
     use crate::{
-        rumtk_v2_find_component, rumtk_v2_generate_message, rumtk_v2_mllp_connect,
-        rumtk_v2_mllp_get_client_ids, rumtk_v2_mllp_get_ip_port, rumtk_v2_mllp_iter_channels,
-        rumtk_v2_mllp_listen, rumtk_v2_mllp_send, rumtk_v2_parse_message, tests,
+        rumtk_v2_find_component, rumtk_v2_find_subcomponent, rumtk_v2_generate_message,
+        rumtk_v2_mllp_connect, rumtk_v2_mllp_get_client_ids, rumtk_v2_mllp_get_ip_port,
+        rumtk_v2_mllp_iter_channels, rumtk_v2_mllp_listen, rumtk_v2_mllp_send,
+        rumtk_v2_parse_message, tests,
     };
     use rumtk_core::core::RUMResult;
     use rumtk_core::search::rumtk_search::{string_search_named_captures, SearchGroups};
@@ -123,6 +160,26 @@ mod tests {
     const V2_JSON_MESSAGE: &str = "MSH|^~\\&#|NIST EHR^2.16.840.1.113883.3.72.5.22^ISO|NIST EHR Facility^2.16.840.1.113883.3.72.5.23^ISO|NIST Test Lab APP^2.16.840.1.113883.3.72.5.20^ISO|NIST Lab Facility^2.16.840.1.113883.3.72.5.21^ISO|20130211184101-0500||OML^O21^OML_O21|NIST-LOI_9.0_1.1-GU_PRU|T|2.5.1|||AL|AL|||||LOI_Common_Component^LOI BaseProfile^2.16.840.1.113883.9.66^ISO~LOI_GU_Component^LOI GU Profile^2.16.840.1.113883.9.78^ISO~LAB_PRU_Component^LOI PRU Profile^2.16.840.1.113883.9.82^ISO\nPID|1||PATID14567^^^NIST MPI&2.16.840.1.113883.3.72.5.30.2&ISO^MR||Hernandez^Maria^^^^^L||19880906|F||2054-5^Black or   African American^HL70005|3248 E  FlorenceAve^^Huntington Park^CA^90255^^H||^^PH^^^323^5825421|||||||||H^Hispanic or Latino^HL70189\nORC|NW|ORD231-1^NIST EHR^2.16.840.1.113883.3.72.5.24^ISO|||||||20130116090021-0800|||134569827^Feller^Hans^^^^^^NPI&2.16.840.1.113883.4.6&ISO^L^^^NPI\nOBR|1|ORD231-1^NIST EHR^2.16.840.1.113883.3.72.5.24^ISO||34555-3^Creatinine 24H renal clearance panel^LN^^^^^^CreatinineClearance|||201301151130-0800|201301160912-0800||||||||134569827^Feller^Hans^^^^^^NPI&2.16.840.1.113883.4.6&ISO^L^^^NPI\nDG1|1||I10^Essential (primary) hypertension^I10C^^^^^^Hypertension, NOS|||F|||||||||2\nDG1|2||O10.93^Unspecified pre-existing hypertension complicating the puerperium^I10C^^^^^^Pregnancy with chronic hypertension|||W|||||||||1\nOBX|1|CWE|67471-3^Pregnancy status^LN^1903^Pregnancy status^99USL^2.44^^Isthe patient pregnant?||Y^Yes^HL70136^1^Yes, confirmed less than 12 weeks^99USL^2.5.1^^early pregnancy (pre 12 weeks)||||||O|||20130115|||||||||||||||SCI\nOBX|2|NM|3167-4^Volume of   24   hour Urine^LN^1904^Urine Volume of 24 hour collection^99USL^2.44^^Urine Volume 24hour collection||1250|mL^milliliter^UCUM^ml^mililiter^L^1.7^^ml|||||O|||20130116|||||||||||||||SCI\nOBX|3|NM|3141-9^Body weight Measured^LN^BWm^Body weight Measured^99USL^2.44^^patient weight measured in kg||59.5|kg^kilogram^UCUM|||||O|||20130116|||||||||||||||SCI\nSPM|1|S-2312987-1&NIST EHR&2.16.840.1.113883.3.72.5.24&ISO||276833005^24 hour urine sample (specimen)^SCT^UR24H^24hr Urine^99USL^^^24 hour urine|||||||||||||201301151130-0800^201301160912-0800\nSPM|2|S-2312987-2&NIST EHR&2.16.840.1.113883.3.72.5.24&ISO||119297000^Blood Specimen^SCT|||||||||||||201301160912-0800ORC|NW|ORD231-2^NIST EHR^2.16.840.1.113883.3.72.5.24^ISO|||||||20130115102146-0800|||134569827^Feller^Hans^^^^^^NPI&2.16.840.1.113883.4.6&ISO^L^^^NPI\nOBR|2|ORD231-2^NIST EHR^2.16.840.1.113883.3.72.5.24^ISO||21482-5^Protein [Mass/volume] in 24 hour Urine^LN^^^^^^24 hour Urine Protein|||201301151130-0800|201301160912-0800||||||||134569827^Feller^Hans^^^^^^NPI&2.16.840.1.113883.4.6&ISO^L^^^NPI\nDG1|1||I10^Essential (primary) hypertension^I10C^^^^^^Hypertension, NOS|||F|||||||||2";
     const ESCAPED_V2_JSON_MESSAGE_BASIC: &str = "{\"separators\":{\"segment_terminator\":\"\\r\",\"field_separator\":\"|\",\"component_separator\":\"^\",\"repetition_separator\":\"~\",\"escape_character\":\"\\\\\",\"subcomponent_separator\":\"&\",\"truncation_character\":\"#\"},\"segment_groups\":{\"89\":[{\"name\":\"MSH\",\"description\":\"Message Header\",\"fields\":[[{\"components\":[{\"component\":\"^~\\\\&#\"}]}],[{\"components\":[{\"component\":\"NIST EHR\"},{\"component\":\"2.16.840.1.113883.3.72.5.22\"},{\"component\":\"ISO\"}]}],[{\"components\":[{\"component\":\"NIST EHR Facility\"},{\"component\":\"2.16.840.1.113883.3.72.5.23\"},{\"component\":\"ISO\"}]}],[{\"components\":[{\"component\":\"NIST Test Lab APP\"},{\"component\":\"2.16.840.1.113883.3.72.5.20\"},{\"component\":\"ISO\"}]}],[{\"components\":[{\"component\":\"NIST Lab Facility\"},{\"component\":\"2.16.840.1.113883.3.72.5.21\"},{\"component\":\"ISO\"}]}],[{\"components\":[{\"component\":\"20130211184101-0500\"}]}],[{\"components\":[{\"component\":\"\"}]}],[{\"components\":[{\"component\":\"OML\"},{\"component\":\"O21\"},{\"component\":\"OML_O21\"}]}],[{\"components\":[{\"component\":\"NIST-LOI_9.0_1.1-GU_PRU\"}]}],[{\"components\":[{\"component\":\"T\"}]}],[{\"components\":[{\"component\":\"2.5.1\"}]}],[{\"components\":[{\"component\":\"\"}]}],[{\"components\":[{\"component\":\"\"}]}],[{\"components\":[{\"component\":\"AL\"}]}],[{\"components\":[{\"component\":\"AL\"}]}],[{\"components\":[{\"component\":\"\"}]}],[{\"components\":[{\"component\":\"\"}]}],[{\"components\":[{\"component\":\"\"}]}],[{\"components\":[{\"component\":\"\"}]}],[{\"components\":[{\"component\":\"LOI_Common_Component\"},{\"component\":\"LOI BaseProfile\"},{\"component\":\"2.16.840.1.113883.9.66\"},{\"component\":\"ISO\"}]},{\"components\":[{\"component\":\"LOI_GU_Component\"},{\"component\":\"LOI GU Profile\"},{\"component\":\"2.16.840.1.113883.9.78\"},{\"component\":\"ISO\"}]},{\"components\":[{\"component\":\"LAB_PRU_Component\"},{\"component\":\"LOI PRU Profile\"},{\"component\":\"2.16.840.1.113883.9.82\"},{\"component\":\"ISO\"}]}]]}]}}";
     const V2_JSON_MESSAGE_BASIC: &str = "MSH|^~\\&#|NIST EHR^2.16.840.1.113883.3.72.5.22^ISO|NIST EHR Facility^2.16.840.1.113883.3.72.5.23^ISO|NIST Test Lab APP^2.16.840.1.113883.3.72.5.20^ISO|NIST Lab Facility^2.16.840.1.113883.3.72.5.21^ISO|20130211184101-0500||OML^O21^OML_O21|NIST-LOI_9.0_1.1-GU_PRU|T|2.5.1|||AL|AL|||||LOI_Common_Component^LOI BaseProfile^2.16.840.1.113883.9.66^ISO~LOI_GU_Component^LOI GU Profile^2.16.840.1.113883.9.78^ISO~LAB_PRU_Component^LOI PRU Profile^2.16.840.1.113883.9.82^ISO\n";
+    const HL7_V2_CUSTOM_SEGMENT_MESSAGE: &str =
+        "MSH|^~\\&|LABVENDOR|LAB|EHR|HOSP|20240102120000||ORU^R01^ORU_R01|MSG0042|P|2.5.1\r\
+         PID|1||PATID1234^^^LAB^MR||DOE^JANE\r\
+         ZPI|1|VIP^Very Important Patient|Y\r\
+         OBR|1|ORD1||GLU^Glucose^L\r\
+         OBX|1|NM|GLU^Glucose^L||98|mg/dL\r\
+         ZOB|1|REVIEWED^DR SMITH\r\
+         XYZ|1|UNKNOWN";
+    const HL7_V2_ORU_MESSAGE: &str =
+        "MSH|^~\\&|LABVENDOR|LAB|EHR|HOSP|20240102120000||ORU^R01^ORU_R01|MSG0043|P|2.5.1\r\
+         PID|1||PATID1234^^^LAB^MR||DOE^JANE\r\
+         PV1|1|O\r\
+         OBR|1|ORD1||CMP^Metabolic Panel^L\r\
+         OBX|1|NM|GLU^Glucose^L||98|mg/dL\r\
+         OBX|2|NM|NA^Sodium^L||140|mmol/L\r\
+         OBR|2|ORD2||CBC^Blood Count^L\r\
+         OBX|1|NM|WBC^Leukocytes^L||6.1|10*3/uL\r\
+         NTE|1||Specimen slightly hemolyzed\r\
+         OBX|2|NM|HGB^Hemoglobin^L||13.2|g/dL\r\
+         OBX|3|NM|PLT^Platelets^L||250|10*3/uL";
     const SPANISH_NAME: &str = "Andrés";
     const SANSKRIT_NAME: &str = "आरवा";
     const HIRAGANA_NAME: &str = "ひなた";
@@ -533,408 +590,1508 @@ mod tests {
     }
 
     #[test]
-    fn test_handle_hl7_v2_search_pattern_parsing_full() {
-        let pattern = "MSH(1)-1[5].4";
-        let groups = string_search_named_captures(pattern, REGEX_V2_SEARCH_DEFAULT, "1");
-        let expected = SearchGroups::from([
-            (RUMString::new("segment_group"), RUMString::new("1")),
-            (RUMString::new("sub_field"), RUMString::new("5")),
-            (RUMString::new("segment"), RUMString::new("MSH")),
-            (RUMString::new("field"), RUMString::new("-1")),
-            (RUMString::new("component"), RUMString::new("4")),
-        ]);
-        println!(
-            "Input: {:?} Expected: {:?} Got: {:?}",
-            pattern, expected, groups
-        );
+    fn test_generating_v2_message_preserves_segment_order() {
+        for input in [
+            tests::DEFAULT_HL7_V2_MESSAGE,
+            tests::HL7_V2_MESSAGE,
+            tests::HL7_V2_REPEATING_FIELD_MESSAGE,
+        ] {
+            let message = rumtk_v2_parse_message!(&input).unwrap();
+            let sanitized_message = V2Message::sanitize(input);
+            let tokens: Vec<&str> = V2Message::tokenize_segments(&sanitized_message.as_str())
+                .into_iter()
+                .filter(|token| !token.is_empty())
+                .collect();
+            let expected = tokens.join("\r");
+            let generated_message_string = rumtk_v2_generate_message!(&message);
+            assert_eq!(
+                generated_message_string, expected,
+                "Generated message does not reproduce the original segment order!"
+            );
+        }
+    }
+
+    #[test]
+    fn test_deserialize_v2_message_without_segment_order() {
+        let message = rumtk_v2_parse_message!(tests::HL7_V2_REPEATING_FIELD_MESSAGE).unwrap();
+        let message_str = rumtk_serialize!(&message).unwrap();
+        let mut legacy: serde_json::Value = serde_json::from_str(&message_str).unwrap();
+        legacy.as_object_mut().unwrap().remove("segment_order");
+        let deserialized: V2Message = serde_json::from_value(legacy).unwrap();
+        let names: Vec<&str> = deserialized.segments().map(|s| s.name()).collect();
+        assert_eq!(names[0], "MSH", "MSH must come first in legacy messages!");
         assert_eq!(
-            groups, expected,
-            "Misparsed search expression MSH(1)-1[5].4!"
+            names.len(),
+            message.segments().count(),
+            "Legacy message lost segments!"
         );
     }
 
     #[test]
-    fn test_handle_hl7_v2_search_pattern_parsing_simple() {
-        let pattern = "MSH1.4";
-        let groups = string_search_named_captures(pattern, REGEX_V2_SEARCH_DEFAULT, "1");
-        let expected = SearchGroups::from([
-            (RUMString::new("segment_group"), RUMString::new("1")),
-            (RUMString::new("sub_field"), RUMString::new("1")),
-            (RUMString::new("segment"), RUMString::new("MSH")),
-            (RUMString::new("field"), RUMString::new("1")),
-            (RUMString::new("component"), RUMString::new("4")),
-        ]);
-        println!(
-            "Input: {:?} Expected: {:?} Got: {:?}",
-            pattern, expected, groups
+    fn test_load_hl7_v2_message_with_custom_segments() {
+        let input = tests::HL7_V2_CUSTOM_SEGMENT_MESSAGE.replace("\rXYZ|1|UNKNOWN", "");
+        let message = rumtk_v2_parse_message!(&input).unwrap();
+        let component = rumtk_v2_find_component!(message, "ZPI2.1").unwrap();
+        assert_eq!(
+            component.as_str(),
+            "VIP",
+            "Wrong component found in ZPI segment!"
+        );
+        let component = rumtk_v2_find_component!(message, "ZOB2.2").unwrap();
+        assert_eq!(
+            component.as_str(),
+            "DR SMITH",
+            "Wrong component found in ZOB segment!"
+        );
+        assert_eq!(
+            rumtk_v2_generate_message!(&message),
+            input,
+            "Custom segments were not regenerated in place!"
         );
-        assert_eq!(groups, expected, "Misparsed search expression MSH1.4!");
     }
 
     #[test]
-    fn test_v2_search_index() {
-        let expr = "MSH(1)-1[5].4";
-        let v2_search_index = V2SearchIndex::from(expr);
-        let expected = V2SearchIndex::new("MSH", 1, -1, 5, 4);
-        println!(
-            "Input: {:?} Expected: {:?} Got: {:?}",
-            expr, expected, v2_search_index
+    fn test_load_hl7_v2_message_with_unknown_segments() {
+        let input = tests::HL7_V2_CUSTOM_SEGMENT_MESSAGE;
+        match rumtk_v2_parse_message!(input) {
+            Ok(_) => panic!("Strict parsing accepted an unknown segment!"),
+            Err(e) => println!("Got error => {}", e),
+        }
+        let message = V2Message::try_from_str_with_mode(input, V2ParseMode::Lenient).unwrap();
+        let component = rumtk_v2_find_component!(message, "XYZ2").unwrap();
+        assert_eq!(
+            component.as_str(),
+            "UNKNOWN",
+            "Wrong component found in unknown segment!"
         );
         assert_eq!(
-            v2_search_index, expected,
-            "Failed to parse expression into correct SearchIndex object."
+            rumtk_v2_generate_message!(&message),
+            input,
+            "Unknown segments were not regenerated in place!"
+        );
+        let message_str = rumtk_serialize!(&message).unwrap();
+        let deserialized: V2Message = rumtk_deserialize!(&message_str).unwrap();
+        assert_eq!(
+            message, deserialized,
+            "Deserialized JSON does not match the expected value!"
         );
     }
 
     #[test]
-    fn test_load_hl7_v2_message_macro() {
-        let message = rumtk_v2_parse_message!(tests::DEFAULT_HL7_V2_MESSAGE).unwrap();
-        assert!(
-            message.segment_exists(&V2_SEGMENT_IDS["MSH"]),
-            "Missing MSH segment!"
+    fn test_custom_segment_ids_are_per_message() {
+        let names: Vec<String> = (0..100).map(|i| format!("Z{:02}", i)).collect();
+        for name in &names {
+            let input = format!("MSH|^~\\&|||||||ADT^A01|1|P|2.5.1\r{}|1|VALUE", name);
+            let message = V2Message::try_from_str(&input).unwrap();
+            assert_eq!(
+                message.segment_id(name),
+                Some(V2_CUSTOM_SEGMENT_ID_START),
+                "Custom ids should not depend on segments seen in other messages!"
+            );
+            let component = rumtk_v2_find_component!(message, &format!("{}2", name)).unwrap();
+            assert_eq!(component.as_str(), "VALUE");
+        }
+
+        let input = tests::HL7_V2_CUSTOM_SEGMENT_MESSAGE.replace("\rXYZ|1|UNKNOWN", "");
+        let message = rumtk_v2_parse_message!(&input).unwrap();
+        let segment_ids = message.segment_ids().clone();
+        assert!(rumtk_v2_find_component!(message, "ZXY1").is_err());
+        assert_eq!(
+            message.segment_ids(),
+            &segment_ids,
+            "Searching for a missing segment assigned it an id!"
         );
+        assert_eq!(message.segment_id("ZXY"), None);
+
+        let segments = |count: usize| {
+            names[..count]
+                .iter()
+                .map(|name| format!("\r{}|1", name))
+                .collect::<String>()
+        };
+        let capacity = (u8::MAX - V2_CUSTOM_SEGMENT_ID_START) as usize + 1;
+        let header = "MSH|^~\\&|||||||ADT^A01|1|P|2.5.1";
+        assert!(V2Message::try_from_str(&format!("{}{}", header, segments(capacity))).is_ok());
+        let error =
+            V2Message::try_from_str(&format!("{}{}", header, segments(capacity + 1))).unwrap_err();
         assert!(
-            message.segment_exists(&V2_SEGMENT_IDS["PID"]),
-            "Missing PID segment!"
+            error.contains("distinct custom segments"),
+            "Unclear error when a message has too many custom segments => {}",
+            error
         );
-        assert!(
-            message.segment_exists(&V2_SEGMENT_IDS["PV1"]),
-            "Missing PV1 segment!"
+
+        // Ids in serialized messages are remapped by segment name.
+        let message_str = rumtk_serialize!(&message).unwrap();
+        let swapped = message_str.replace("[\"ZPI\",\"ZOB\"]", "[\"ZOB\",\"ZPI\"]");
+        assert_ne!(
+            swapped, message_str,
+            "The segment id table was not serialized!"
         );
-        assert!(
-            message.segment_exists(&V2_SEGMENT_IDS["EVN"]),
-            "Missing EVN segment!"
+        let deserialized: V2Message = rumtk_deserialize!(&swapped).unwrap();
+        assert_eq!(
+            rumtk_v2_find_component!(deserialized, "ZPI2.1")
+                .unwrap()
+                .as_str(),
+            "VIP"
         );
-        assert!(
-            message.segment_exists(&V2_SEGMENT_IDS["NK1"]),
-            "Missing NK1 segment!"
+        assert_eq!(
+            rumtk_v2_generate_message!(&deserialized),
+            input,
+            "Remapped custom segments were not regenerated in place!"
         );
     }
 
     #[test]
-    fn test_load_hl7_v2_message_macro_failure() {
-        let input = "Hello World!";
-        let err_msg = format_compact!(
-            "Parsing did not fail as expected. Input {} => parsed?",
-            input
+    fn test_load_hl7_v2_message_with_diagnostics() {
+        let input = "\u{feff}MSH||ADT1|GOOD HEALTH HOSPITAL|GHH LAB|GOOD HEALTH HOSPITAL|198808181126||ADT^A01^ADT_A01|MSG00001|P|2.8\n\
+                     EVN|A01|20070818\u{0}1123\r\
+                     12X|garbage\r\
+                     XYZ|1\r\
+                     \u{1c}\r";
+        let (message, diagnostics) =
+            V2Message::try_from_str_with_diagnostics(input, V2ParseMode::Lenient).unwrap();
+        let expected = vec![
+            (
+                V2DiagnosticSeverity::Warning,
+                input.find('\n').unwrap(),
+                None,
+                None,
+                None,
+            ),
+            (V2DiagnosticSeverity::Warning, 7, Some(1), Some(1), None),
+            (V2DiagnosticSeverity::Warning, 0, Some(1), Some(0), None),
+            (
+                V2DiagnosticSeverity::Warning,
+                input.find('\u{0}').unwrap(),
+                Some(2),
+                Some(2),
+                Some(1),
+            ),
+            (
+                V2DiagnosticSeverity::Error,
+                input.find("12X").unwrap(),
+                Some(3),
+                Some(0),
+                None,
+            ),
+            (
+                V2DiagnosticSeverity::Warning,
+                input.find("XYZ").unwrap(),
+                Some(4),
+                Some(0),
+                None,
+            ),
+            (
+                V2DiagnosticSeverity::Warning,
+                input.find('\u{1c}').unwrap(),
+                Some(5),
+                Some(0),
+                None,
+            ),
+        ];
+        let found: Vec<(
+            V2DiagnosticSeverity,
+            usize,
+            Option<usize>,
+            Option<isize>,
+            Option<isize>,
+        )> = diagnostics
+            .iter()
+            .map(|d| (d.severity, d.offset, d.segment, d.field, d.component))
+            .collect();
+        assert_eq!(found, expected, "Wrong diagnostics => {:#?}", diagnostics);
+        assert_eq!(
+            message.segment_order().len(),
+            3,
+            "The malformed segment was not dropped!"
+        );
+        let component = rumtk_v2_find_component!(message, "EVN2").unwrap();
+        assert_eq!(
+            component.as_str(),
+            "200708181123",
+            "The stray character was not removed!"
+        );
+        assert!(
+            rumtk_v2_generate_message!(&message).starts_with("MSH|^~\\&|ADT1|"),
+            "The header was not repaired!"
         );
-        match rumtk_v2_parse_message!(input) {
-            Ok(v) => panic!("{}", err_msg.as_str()),
-            Err(e) => {
-                println!("{}", format_compact!("Got error => {}", e).as_str());
-                println!("Passed failed case!");
-            }
-        };
     }
 
     #[test]
-    fn test_find_hl7_v2_message_component_macro() {
-        let pattern = "PID(1)5.4";
-        let message = rumtk_v2_parse_message!(tests::DEFAULT_HL7_V2_MESSAGE).unwrap();
-        let component = rumtk_v2_find_component!(message, pattern).unwrap();
-        let expected = "III";
+    fn test_load_hl7_v2_message_strict_diagnostics() {
+        let input = "MSH|^~|ADT1|GOOD HEALTH HOSPITAL|GHH LAB|GOOD HEALTH HOSPITAL|198808181126||ADT^A01^ADT_A01|MSG00001|P|2.8\r\
+                     EVN|A01|200708181123";
+        let diagnostic = V2Message::try_from_str_with_diagnostics(input, V2ParseMode::Strict)
+            .err()
+            .unwrap();
         assert_eq!(
-            component.as_str(),
-            expected,
-            "Wrong component found! Looked for {} expecting {}, but got {}",
-            pattern,
-            expected,
-            component.as_str()
+            (
+                diagnostic.severity,
+                diagnostic.offset,
+                diagnostic.segment,
+                diagnostic.field
+            ),
+            (V2DiagnosticSeverity::Error, 4, Some(1), Some(1)),
+            "Wrong location for the strict failure!"
+        );
+        let error = V2Message::try_from_str(input).err().unwrap();
+        assert!(
+            error.contains("segment 1 (MSH), field 1"),
+            "The error does not carry its location => {}",
+            error
+        );
+
+        let input = input.replace("^~|", "^~\\&|").replace("EVN", "12X");
+        let diagnostic = V2Message::try_from_str_with_diagnostics(&input, V2ParseMode::Strict)
+            .err()
+            .unwrap();
+        assert_eq!(
+            (
+                diagnostic.offset,
+                diagnostic.segment,
+                diagnostic.segment_name
+            ),
+            (
+                input.find("12X").unwrap(),
+                Some(2),
+                Some(RUMString::from("12X"))
+            ),
+            "Wrong location for the strict failure!"
         );
     }
 
     #[test]
-    fn test_find_hl7_v2_message_component_simple_macro() {
-        let pattern = "PID5.4";
-        let message = rumtk_v2_parse_message!(tests::DEFAULT_HL7_V2_MESSAGE).unwrap();
-        let component = rumtk_v2_find_component!(message, pattern).unwrap();
-        let expected = "III";
+    fn test_editing_hl7_v2_message() {
+        let mut message = rumtk_v2_parse_message!(tests::HL7_V2_ORU_MESSAGE).unwrap();
+        message.set("PID13[3].2", "PRN").unwrap();
+        message.set("PID5[1].2", "JOHN").unwrap();
+        let repetition = message.add_repetition("PID3", "123456789").unwrap();
+        assert_eq!(repetition, 2, "Wrong position for the new repetition!");
+        message.delete_field("PV12").unwrap();
+        message.clear_field("OBR(1)4").unwrap();
+        message
+            .insert_segment_after("OBX(2)", "NTE|1||Fasting sample")
+            .unwrap();
+        let component = rumtk_v2_find_component!(message, "NTE(1)3").unwrap();
         assert_eq!(
             component.as_str(),
+            "Fasting sample",
+            "The inserted segment is not the first NTE in the message!"
+        );
+        let removed = message.remove_segment("NTE(2)").unwrap();
+        assert_eq!(
+            removed[3][0][1].as_str(),
+            "Specimen slightly hemolyzed",
+            "Removed the wrong segment!"
+        );
+
+        let expected =
+            "MSH|^~\\&|LABVENDOR|LAB|EHR|HOSP|20240102120000||ORU^R01^ORU_R01|MSG0043|P|2.5.1\r\
+                        PID|1||PATID1234^^^LAB^MR~123456789||DOE^JOHN||||||||~~^PRN\r\
+                        PV1|1|\"\"\r\
+                        OBR|1|ORD1||\r\
+                        OBX|1|NM|GLU^Glucose^L||98|mg/dL\r\
+                        OBX|2|NM|NA^Sodium^L||140|mmol/L\r\
+                        NTE|1||Fasting sample\r\
+                        OBR|2|ORD2||CBC^Blood Count^L\r\
+                        OBX|1|NM|WBC^Leukocytes^L||6.1|10*3/uL\r\
+                        OBX|2|NM|HGB^Hemoglobin^L||13.2|g/dL\r\
+                        OBX|3|NM|PLT^Platelets^L||250|10*3/uL";
+        assert_eq!(
+            message.to_string(),
             expected,
-            "Wrong component found! Looked for {} expecting {}, but got {}",
-            pattern,
-            expected,
-            component.as_str()
+            "The edited message was not generated as expected!"
+        );
+        assert_eq!(
+            message,
+            V2Message::try_from_str(expected).unwrap(),
+            "The edited message does not match the same message after parsing!"
+        );
+        assert_eq!(
+            message.get_group(&V2_SEGMENT_IDS["NTE"]).unwrap().len(),
+            1,
+            "The NTE group was not updated!"
+        );
+
+        assert!(message.set("MSH1", "$~\\&").is_err(), "Changed MSH-2!");
+        assert!(
+            message.set("ZZZ1", "VIP").is_err(),
+            "Set a missing segment!"
+        );
+        assert!(message.remove_segment("MSH").is_err(), "Removed MSH!");
+        assert!(
+            message.insert_segment_after("OBX(9)", "NTE|2").is_err(),
+            "Inserted after a missing segment!"
+        );
+
+        message.insert_segment_after("PID", "ZXY|1|VIP").unwrap();
+        assert_eq!(
+            rumtk_v2_find_component!(message, "ZXY(1)2")
+                .unwrap()
+                .to_string(),
+            "VIP",
+            "The Z-segment was not inserted!"
+        );
+        assert!(
+            message.insert_segment_after("PID", "AB1|1").is_err(),
+            "Inserted a segment that is not part of the standard in strict mode!"
+        );
+        message
+            .insert_segment_after_with_mode("ZXY", "AB1|1|LOCAL", V2ParseMode::Lenient)
+            .unwrap();
+        let order: Vec<&str> = message.segments().map(|segment| segment.name()).collect();
+        assert_eq!(
+            &order[..4],
+            &["MSH", "PID", "ZXY", "AB1"],
+            "The custom segments were not inserted in place!"
+        );
+        assert_eq!(
+            rumtk_v2_find_component!(message, "AB1(1)2")
+                .unwrap()
+                .to_string(),
+            "LOCAL",
+            "The segment inserted in lenient mode cannot be found!"
         );
     }
 
     #[test]
-    fn test_find_hl7_v2_message_msh_field() {
-        let pattern = "MSH1.1";
-        let message = rumtk_v2_parse_message!(tests::HL7_V2_MSH_ONLY).unwrap();
-        let component = rumtk_v2_find_component!(message, pattern).unwrap();
-        let expected = "^~\\&";
+    fn test_building_hl7_v2_message() {
+        let message = V2MessageBuilder::new()
+            .field(2, "ORDERENTRY")
+            .set("MSH8.1", "OML")
+            .set("MSH8.2", "O21")
+            .field(V2_MSH_CONTROL_ID as usize, "MSG0001")
+            .field(V2_MSH_VERSION as usize, "2.5.1")
+            .segment("PID")
+            .field(1, "1")
+            .repetition(3, 1, "PATID1234")
+            .repetition(3, 2, "123456789")
+            .set("PID3[2].4.2", "2.16.840.1.113883.4.1")
+            .component(5, 1, "O'BRIEN & SONS")
+            .named_component(11, "AD", "city", "GREENSBORO")
+            .segment("ORC")
+            .field(1, "NW")
+            .segment("OBR")
+            .field(4, "CBC|panel")
+            .segment("ZPI")
+            .field(1, "VIP")
+            .build()
+            .unwrap();
+        let expected = "MSH|^~\\&|ORDERENTRY||||||OML^O21|MSG0001||2.5.1\r\
+                        PID|1||PATID1234~123456789^^^&2.16.840.1.113883.4.1||O'BRIEN \\T\\ SONS||||||^^GREENSBORO\r\
+                        ORC|NW\r\
+                        OBR||||CBC\\F\\panel\r\
+                        ZPI|VIP";
         assert_eq!(
-            component.as_str(),
-            expected,
-            "Wrong component found! Looked for {} expecting {}, but got {}",
-            pattern,
+            message.to_string(),
             expected,
-            component.as_str()
+            "The built message was not generated as expected!"
+        );
+        let parsed = V2Message::try_from_str(expected).unwrap();
+        assert_eq!(
+            message, parsed,
+            "The built message does not match the same message after parsing!"
+        );
+        let component = rumtk_v2_find_component!(parsed, "PID5.1").unwrap();
+        assert_eq!(
+            component.decoded(parsed.separators()).unwrap(),
+            "O'BRIEN & SONS",
+            "The escaped value did not survive the round trip!"
         );
     }
 
     #[test]
-    fn test_find_hl7_v2_message_component_macro_failure() {
-        let pattern = "PID(1)15.4";
-        let err_msg = format_compact!(
-            "Search did not fail as expected. Input {} => found component?",
-            pattern
+    fn test_building_hl7_v2_message_with_custom_separators() {
+        let separators = V2ParserCharacters {
+            field_separator: RUMString::from("#"),
+            component_separator: RUMString::from("$"),
+            ..V2ParserCharacters::new()
+        };
+        let message = V2MessageBuilder::with_separators(separators)
+            .component(8, 1, "ADT")
+            .component(8, 2, "A01")
+            .build()
+            .unwrap();
+        assert_eq!(
+            message.to_string(),
+            "MSH#$~\\&#######ADT$A01",
+            "Custom separators were not used!"
         );
-        let message = rumtk_v2_parse_message!(tests::DEFAULT_HL7_V2_MESSAGE).unwrap();
-        match rumtk_v2_find_component!(message, pattern) {
-            Ok(v) => panic!("{}", err_msg.as_str()),
-            Err(e) => {
-                println!("{}", format_compact!("Got error => {}", e).as_str());
-                println!("Passed failed case!");
+
+        let failures = [
+            V2MessageBuilder::new().field(1, "$~\\&"),
+            V2MessageBuilder::new().segment("P1D!"),
+            V2MessageBuilder::new().named_component(5, "AD", "planet", "EARTH"),
+            V2MessageBuilder::new().set("PID5", "EVERYMAN"),
+        ];
+        for builder in failures {
+            match builder.build() {
+                Ok(message) => panic!("Built an invalid message => {}", message.to_string()),
+                Err(e) => println!("Got error => {}", e),
             }
         }
     }
 
     #[test]
-    fn test_cast_component_to_datetime_expected_functionality() {
-        let inputs = [
-            "2007",
-            "200708",
-            "20070818",
-            "200708181123",
-            "20070818112355",
-            "20070818112355.55",
-            "20070818112355.5555-5000",
-            "20070818112355-5000",
-        ];
-        let expected_outputs = [
-            "2007-01-01T00:00:00.0000",
-            "2007-08-01T00:00:00.0000",
-            "2007-08-18T00:00:00.0000",
-            "2007-08-18T11:23:00.0000",
-            "2007-08-18T11:23:55.0000",
-            "2007-08-18T11:23:55.5500",
-            "2007-08-18T11:23:55.5555-5000",
-            "2007-08-18T11:23:55.0000-5000",
-        ];
-        for i in 0..inputs.len() {
-            let input = inputs[i];
-            let expected_utc = expected_outputs[i];
-            print!(
-                "Testing input #{} \"{}\". Expected output is \"{}\". Casting to datetime type.",
-                i, input, expected_utc
-            );
-            let date = input.to_v2datetime().unwrap();
-            let err_msg = format_compact!("The expected date time string does not match the date time string generated from the input [In: {}, Got: {}]", input, date.as_utc_string());
-            assert_eq!(expected_utc, date.as_utc_string().as_str(), "{}", &err_msg);
-            println!(" ... Got: {} ✅ ", date.as_utc_string());
+    fn test_querying_hl7_v2_message() {
+        let message = rumtk_v2_parse_message!(tests::HL7_V2_ORU_MESSAGE).unwrap();
+        let matches: Vec<_> = message
+            .query("OBX(*)5 WHERE OBX3.1 = HGB")
+            .unwrap()
+            .collect();
+        assert_eq!(matches.len(), 1, "Wrong number of OBX-5 matches!");
+        assert_eq!(matches[0].value.as_str(), "13.2", "Wrong OBX-5 matched!");
+        assert_eq!(
+            matches[0].position.to_string(),
+            "OBX(4)5[1].1.1",
+            "Wrong position reported for match!"
+        );
+        let position = matches[0].position.to_string().to_rumstring();
+        assert_eq!(
+            message.find_component(&position).unwrap().as_str(),
+            "13.2",
+            "Match position is not a valid search pattern!"
+        );
+
+        let values: Vec<&str> = message
+            .query("OBX(2..3)5")
+            .unwrap()
+            .map(|found| found.value.as_str())
+            .collect();
+        assert_eq!(values, vec!["140", "6.1"], "Wrong OBX-5 range matched!");
+
+        let values: Vec<&str> = message
+            .query("OBX(1)4..6")
+            .unwrap()
+            .map(|found| found.value.as_str())
+            .collect();
+        assert_eq!(
+            values,
+            vec!["", "98", "mg/dL"],
+            "Wrong OBX field range matched!"
+        );
+        let positions: Vec<String> = message
+            .query("OBX(1)3.1..2")
+            .unwrap()
+            .map(|found| found.position.to_string())
+            .collect();
+        assert_eq!(
+            positions,
+            vec!["OBX(1)3[1].1.1", "OBX(1)3[1].2.1"],
+            "Wrong OBX-3 component range matched!"
+        );
+        let values: Vec<&str> = message
+            .query("OBX(*)3.-2..-1 WHERE OBX3.1 = NA")
+            .unwrap()
+            .map(|found| found.value.as_str())
+            .collect();
+        assert_eq!(
+            values,
+            vec!["Sodium", "L"],
+            "Negative component range did not count from the end!"
+        );
+
+        let values: Vec<&str> = message
+            .query("obx(*)3.* where OBX5 != 98 and .1 ~ \"^(NA|PLT)$\"")
+            .unwrap()
+            .map(|found| found.value.as_str())
+            .collect();
+        assert_eq!(
+            values,
+            vec!["NA", "Sodium", "L", "PLT", "Platelets", "L"],
+            "Wrong OBX-3 components matched!"
+        );
+
+        let component = message
+            .find_component(&RUMString::from("OBX(-1)5 WHERE OBX1 = 3"))
+            .unwrap();
+        assert_eq!(component.as_str(), "250", "Wrong last OBX-5 found!");
+        match message.find_component(&RUMString::from("OBX(*)5 WHERE OBX3.1 = K")) {
+            Ok(component) => panic!("Found a component that should not match => {:?}", component),
+            Err(e) => println!("Got error => {}", e),
         }
     }
 
     #[test]
-    fn test_cast_component_to_datetime_validation() {
-        let inputs = ["200"];
-        for input in inputs {
-            match input.to_v2datetime() {
-                Ok(date) => {
-                    panic!(
-                        "Validation failed [In: {} Got: {} Expected: None] ... ✕",
-                        input,
-                        date.as_utc_string()
-                    );
-                }
-                Err(e) => println!(
-                    "Validation correctly identified malformed input with message => [{}] ✅",
-                    e.as_str()
-                ),
+    fn test_querying_hl7_v2_repetitions() {
+        let message = rumtk_v2_parse_message!(tests::DEFAULT_HL7_V2_MESSAGE).unwrap();
+        let matches: Vec<_> = message.query("PID3[*] WHERE .5 = MR").unwrap().collect();
+        assert_eq!(
+            matches.len(),
+            1,
+            "Wrong number of PID-3 repetitions matched!"
+        );
+        assert_eq!(
+            matches[0].value.as_str(),
+            "PATID1234",
+            "Wrong PID-3 repetition matched!"
+        );
+        let matches: Vec<_> = message.query("PID3[*] WHERE .5 = SS").unwrap().collect();
+        assert_eq!(
+            matches[0].position.to_string(),
+            "PID(1)3[2].1.1",
+            "Wrong PID-3 repetition matched!"
+        );
+        assert_eq!(
+            message
+                .query("PID3[-1].5")
+                .unwrap()
+                .next()
+                .unwrap()
+                .value
+                .as_str(),
+            "SS",
+            "Negative repetition did not count from the end!"
+        );
+        assert_eq!(
+            message.query("ZZZ(*)1").unwrap().count(),
+            0,
+            "Missing segments should not match anything!"
+        );
+
+        let failures = [
+            "PID3[*] WHERE .5",
+            "PID3[*] WHERE .5 = MR OR .5 = SS",
+            "PID3[*] WHERE NK12 = NUCLEAR",
+            "PID3[*] WHERE .5 ~ \"(MR\"",
+            "PID(1..",
+        ];
+        for expr in failures {
+            match V2Query::from_str(expr) {
+                Ok(query) => panic!("Compiled an invalid query => {:?}", query),
+                Err(e) => println!("Got error => {}", e),
             }
         }
     }
 
     #[test]
-    fn test_cast_component_to_datetime_base_example() {
-        let location = "EVN2"; //EVN|A01|200708181123||\n\r; EVN2 => segment = EVN, field = 2
-        let expected_component = "200708181123";
+    fn test_hl7_v2_message_structure_groups() {
+        let message = rumtk_v2_parse_message!(tests::HL7_V2_ORU_MESSAGE).unwrap();
+        let tree = message.structure().unwrap();
+        assert_eq!(
+            tree.structure().name,
+            "ORU_R01",
+            "Wrong message structure selected!"
+        );
+        assert!(tree.is_valid(), "Unexpected issues => {:?}", tree.issues());
+        let component = tree.find_component("ORDER_OBSERVATION(2)/OBX(3)5").unwrap();
+        assert_eq!(
+            component.as_str(),
+            "250",
+            "Wrong OBX-5 found in second order!"
+        );
+        let component = tree
+            .find_component("PATIENT_RESULT/ORDER_OBSERVATION(1)/OBX(2)3.2")
+            .unwrap();
+        assert_eq!(
+            component.as_str(),
+            "Sodium",
+            "Wrong OBX-3.2 found in first order!"
+        );
+        let component = tree
+            .find_component("ORDER_OBSERVATION(2)/OBSERVATION(1)/NTE3")
+            .unwrap();
+        assert_eq!(
+            component.as_str(),
+            "Specimen slightly hemolyzed",
+            "Note was not grouped with its observation!"
+        );
+        let observations = tree.group_segments("ORDER_OBSERVATION(2)", "OBX").unwrap();
+        assert_eq!(
+            observations.len(),
+            3,
+            "Wrong number of observations in second order!"
+        );
+        match tree.find_segment("ORDER_OBSERVATION(3)") {
+            Ok(_) => panic!("Found a third order that does not exist!"),
+            Err(e) => println!("Got error => {}", e),
+        }
+    }
+
+    #[test]
+    fn test_hl7_v2_message_structure_by_trigger_event() {
         let message = rumtk_v2_parse_message!(tests::DEFAULT_HL7_V2_MESSAGE).unwrap();
-        let component = rumtk_v2_find_component!(message, location).unwrap();
-        assert_eq!(expected_component, component.as_str(), "We are not using the correct component for this test. Check that the original test message has not changed and update the location string appropriately!");
-        let date = component.to_v2datetime().unwrap();
-        let expected_utc = "2007-08-18T11:23:00.0000";
-        let err_msg = format_compact!("The expected date time string does not match the date time string generated from the input [{}]", component.as_str());
-        assert_eq!(expected_utc, date.as_utc_string().as_str(), "{}", &err_msg)
+        let tree = message.structure().unwrap();
+        assert_eq!(
+            tree.structure().name,
+            "ADT_A01",
+            "Wrong message structure selected!"
+        );
+        assert!(tree.is_valid(), "Unexpected issues => {:?}", tree.issues());
+        let input = tests::DEFAULT_HL7_V2_MESSAGE.replace("ADT^A01^ADT_A01", "ADT^A04");
+        let message = rumtk_v2_parse_message!(&input).unwrap();
+        let tree = message.structure().unwrap();
+        assert_eq!(
+            tree.structure().name,
+            "ADT_A01",
+            "A04 should reuse the ADT_A01 structure!"
+        );
+        let component = tree.find_component("NK12.1").unwrap();
+        assert_eq!(component.as_str(), "NUCLEAR", "Wrong NK1-2.1 found!");
     }
 
     #[test]
-    fn test_datetime_default() {
-        let input = V2DateTime::default().as_utc_string();
-        let expected_val = V2String::from("1970-01-01T00:00:00.00000");
-        let err_msg = format_compact!("The expected formatted string does not match the formatted string generated from the input [In: {}, Got: {}]", input, input);
-        assert_eq!(expected_val, input, "{}", &err_msg);
+    fn test_hl7_v2_message_structure_unexpected_segments() {
+        let input = tests::HL7_V2_CUSTOM_SEGMENT_MESSAGE.replace("\rXYZ|1|UNKNOWN", "");
+        let message = rumtk_v2_parse_message!(&input).unwrap();
+        let tree = message.structure().unwrap();
+        let expected = vec![
+            V2StructureIssue::UnexpectedSegment {
+                name: RUMString::from("ZPI"),
+                order: 2,
+            },
+            V2StructureIssue::UnexpectedSegment {
+                name: RUMString::from("ZOB"),
+                order: 5,
+            },
+        ];
+        assert_eq!(
+            tree.issues(),
+            &expected,
+            "Custom segments were not reported as unexpected!"
+        );
+        let component = tree.find_component("ORDER_OBSERVATION(1)/OBX(1)5").unwrap();
+        assert_eq!(component.as_str(), "98", "Wrong OBX-5 found!");
+
+        let message = rumtk_v2_parse_message!(tests::HL7_V2_REPEATING_FIELD_MESSAGE).unwrap();
+        let tree = message.structure().unwrap();
+        let component = tree.find_component("ORDER(1)/OBR4.2").unwrap();
+        assert_eq!(
+            component.as_str(),
+            "Creatinine 24H renal clearance panel",
+            "Wrong OBR-4.2 found in first order!"
+        );
+        assert_eq!(
+            tree.issues().len(),
+            2,
+            "The stray OBR and DG1 without an ORC should be unexpected => {:?}",
+            tree.issues()
+        );
     }
 
     #[test]
-    fn test_cast_component_to_date_expected_functionality() {
-        let inputs = ["2007", "200708", "20070818"];
-        let expected_outputs = [
-            "2007-01-01T00:00:00.0000",
-            "2007-08-01T00:00:00.0000",
-            "2007-08-18T00:00:00.0000",
+    fn test_load_hl7_v2_message_ref() {
+        let input = tests::HL7_V2_REPEATING_FIELD_MESSAGE;
+        let owned = V2Message::try_from_str(input).unwrap();
+        let borrowed = V2MessageRef::try_from_str(input).unwrap();
+        assert_eq!(
+            borrowed.len(),
+            owned.segments().count(),
+            "Borrowed message has a different number of segments!"
+        );
+        let patterns = [
+            "MSH1",
+            "MSH8.3",
+            "MSH20[2].1",
+            "MSH(1)-1[3].3",
+            "PID3.4.2",
+            "PID5.-1",
+            "OBX(3)6.2",
+            "SPM(2)2.1.3",
+            "DG1(3)3.9",
         ];
-        for i in 0..inputs.len() {
-            let input = inputs[i];
-            let expected_utc = expected_outputs[i];
-            print!(
-                "Testing input #{} \"{}\". Expected output is \"{}\". Casting to datetime type.",
-                i, input, expected_utc
+        for pattern in patterns {
+            let search = RUMString::from(pattern);
+            let expected = owned.find_component(&search).unwrap();
+            let found = borrowed.find_component(&search).unwrap();
+            assert_eq!(
+                found,
+                expected.as_str(),
+                "Borrowed search for {} does not match owned search!",
+                pattern
+            );
+        }
+        for pattern in ["PID5.20", "MSH1.2", "MSH20[4]", "OBX(4)1", "PID0"] {
+            let search = RUMString::from(pattern);
+            assert!(
+                owned.find_component(&search).is_err(),
+                "Owned search for {} should fail!",
+                pattern
+            );
+            assert!(
+                borrowed.find_component(&search).is_err(),
+                "Borrowed search for {} should fail!",
+                pattern
             );
-            let date = input.to_v2date().unwrap();
-            let err_msg = format_compact!("The expected date time string does not match the date time string generated from the input [In: {}, Got: {}]", input, date.as_utc_string());
-            assert_eq!(expected_utc, date.as_utc_string().as_str(), "{}", &err_msg);
-            println!(" ... Got: {} ✅ ", date.as_utc_string());
         }
     }
 
     #[test]
-    fn test_cast_component_to_date_validation() {
-        let inputs = ["200"];
-        for input in inputs {
-            match input.to_v2date() {
-                Ok(date) => {
-                    panic!(
-                        "Validation failed [In: {} Got: {} Expected: None] ... ✕",
-                        input,
-                        date.as_utc_string()
-                    );
-                }
-                Err(e) => println!(
-                    "Validation correctly identified malformed input with message => [{}] ✅",
-                    e.as_str()
-                ),
-            }
+    fn test_message_ref_search_syntax_matches_owned_search() {
+        let input = tests::HL7_V2_REPEATING_FIELD_MESSAGE;
+        let owned = V2Message::try_from_str(input).unwrap();
+        let borrowed = V2MessageRef::try_from_str(input).unwrap();
+        let patterns = [
+            "MSH8.2",
+            "MSH(1)8.2",
+            "MSH-1[3].3",
+            "MSH(1)-1[3].3",
+            "PID3.4.2",
+            "PID(1)3[1].4.2",
+            "PID5.-1",
+            "OBX(3)6.2",
+            "OBX(3)6.2.1",
+        ];
+        for pattern in patterns {
+            let search = RUMString::from(pattern);
+            assert_eq!(
+                borrowed.find_component(&search).unwrap(),
+                owned.find_component(&search).unwrap().as_str(),
+                "Borrowed and owned search disagree on {}!",
+                pattern
+            );
+            assert_eq!(
+                borrowed.find_subcomponent(&search).unwrap(),
+                owned.find_subcomponent(&search).unwrap().as_str(),
+                "Borrowed and owned subcomponent search disagree on {}!",
+                pattern
+            );
+        }
+        for pattern in ["PID5.1x", "PID5(2", "PID5.1.2.3"] {
+            let search = RUMString::from(pattern);
+            assert_eq!(
+                borrowed.find_component(&search).is_err(),
+                owned.find_component(&search).is_err(),
+                "Borrowed and owned search disagree on {}!",
+                pattern
+            );
         }
-    }
 
-    #[test]
-    fn test_cast_component_to_date_base_example() {
-        let location = "PD113"; //EVN|A01|200708181123||\n\r; PD113 => segment = PD1, field = 13
-        let expected_component = "20150625";
-        let message = rumtk_v2_parse_message!(tests::VXU_HL7_V2_MESSAGE).unwrap();
-        let component = rumtk_v2_find_component!(message, location).unwrap();
-        assert_eq!(expected_component, component.as_str(), "We are not using the correct component for this test. Check that the original test message has not changed and update the location string appropriately!");
-        let date = component.to_v2date().unwrap();
-        let expected_utc = "2015-06-25T00:00:00.0000";
-        let err_msg = format_compact!(
-            "The expected date string does not match the date string generated from the input [{}]",
-            component.as_str()
+        let query = RUMString::from("OBX(*)6.2");
+        assert!(owned.find_component(&query).is_ok());
+        let error = borrowed.find_component(&query).unwrap_err();
+        assert!(
+            error.contains("not supported on borrowed messages"),
+            "Unclear error for a query on a borrowed message => {}",
+            error
         );
-        assert_eq!(expected_utc, date.as_utc_string().as_str(), "{}", &err_msg)
     }
 
     #[test]
-    fn test_cast_component_to_time_expected_functionality() {
-        let inputs = ["1123", "112355", "112355.5555", "112355.5555-5000"];
-        let expected_outputs = [
-            "1970-01-01T11:23:00.0000",
-            "1970-01-01T11:23:55.0000",
-            "1970-01-01T11:23:55.5555",
-            "1970-01-01T11:23:55.5555-5000",
-        ];
-        for i in 0..inputs.len() {
-            let input = inputs[i];
-            let expected_utc = expected_outputs[i];
-            print!(
-                "Testing input #{} \"{}\". Expected output is \"{}\". Casting to datetime type.",
-                i, input, expected_utc
+    fn test_promote_hl7_v2_message_ref() {
+        for input in [
+            tests::DEFAULT_HL7_V2_MESSAGE,
+            tests::HL7_V2_MESSAGE,
+            tests::HL7_V2_PDF_MESSAGE,
+            tests::HL7_V2_REPEATING_FIELD_MESSAGE,
+        ] {
+            let borrowed = V2MessageRef::try_from_str(input).unwrap();
+            let promoted = borrowed.to_owned_message().unwrap();
+            let owned = V2Message::try_from_str(input).unwrap();
+            assert_eq!(
+                promoted, owned,
+                "Promoted message does not match the owned parser output!"
             );
-            let date = input.to_v2time().unwrap();
-            let err_msg = format_compact!("The expected date time string does not match the date time string generated from the input [In: {}, Got: {}]", input, date.as_utc_string());
-            assert_eq!(expected_utc, date.as_utc_string().as_str(), "{}", &err_msg);
-            println!(" ... Got: {} ✅ ", date.as_utc_string());
         }
+        match V2MessageRef::try_from_str(tests::HL7_V2_CUSTOM_SEGMENT_MESSAGE) {
+            Ok(_) => panic!("Strict borrowed parsing accepted an unknown segment!"),
+            Err(e) => println!("Got error => {}", e),
+        }
+        let borrowed = V2MessageRef::try_from_str_with_mode(
+            tests::HL7_V2_CUSTOM_SEGMENT_MESSAGE,
+            V2ParseMode::Lenient,
+        )
+        .unwrap();
+        let promoted = V2Message::try_from(&borrowed).unwrap();
+        assert_eq!(
+            rumtk_v2_generate_message!(&promoted),
+            tests::HL7_V2_CUSTOM_SEGMENT_MESSAGE,
+            "Promoted message did not regenerate the original input!"
+        );
     }
 
     #[test]
-    fn test_cast_component_to_time_validation() {
-        let inputs = ["2"];
-        for input in inputs {
-            match input.to_v2time() {
-                Ok(date) => {
-                    panic!(
-                        "Validation failed [In: {} Got: {} Expected: None] ... ✕",
-                        input,
-                        date.as_utc_string()
-                    );
-                }
-                Err(e) => println!(
-                    "Validation correctly identified malformed input with message => [{}] ✅",
-                    e.as_str()
-                ),
+    fn test_load_hl7_v2_file() {
+        let file = V2File::try_from_str(tests::HL7_V2_MESSAGE).unwrap();
+        assert!(file.is_valid(), "Unexpected errors => {:?}", file.errors());
+        assert_eq!(file.len(), 1, "Wrong number of batches in file!");
+        assert_eq!(
+            file.header().unwrap().name(),
+            "FHS",
+            "File header was not parsed!"
+        );
+        assert_eq!(
+            file.batches()[0].header().unwrap().name(),
+            "BHS",
+            "Batch header was not parsed!"
+        );
+        assert_eq!(
+            file.batches()[0].declared_count().unwrap(),
+            Some(1),
+            "Wrong BTS-1!"
+        );
+        let messages: Vec<&V2Message> = file.messages().collect();
+        assert_eq!(messages.len(), 1, "Wrong number of messages in file!");
+        let component = rumtk_v2_find_component!(messages[0], "PID5.1").unwrap();
+        assert_eq!(
+            component.as_str(),
+            "CHILD",
+            "Wrong PID-5.1 in batched message!"
+        );
+        assert!(
+            messages[0].segment_exists(&V2_SEGMENT_IDS["MSH"])
+                && !messages[0].segment_exists(&V2_SEGMENT_IDS["BHS"]),
+            "Batch segments leaked into the message!"
+        );
+    }
+
+    #[test]
+    fn test_load_hl7_v2_file_with_errors() {
+        let input = "FHS|^~\\&|BILLING\r\
+                     BHS|^~\\&|BILLING\r\
+                     MSH|^~\\&|BILLING|FAC|||20240102||ADT^A01^ADT_A01|1|P|2.5.1\r\
+                     PID|1||111^^^FAC^MR||DOE^JOHN\r\
+                     MSH|^~\\&|BILLING|FAC|||20240102||ADT^A01^ADT_A01|2|P|2.5.1\r\
+                     XYZ|1|NOT A SEGMENT\r\
+                     MSH|^~\\&|BILLING|FAC|||20240102||ADT^A01^ADT_A01|3|P|2.5.1\r\
+                     PID|1||333^^^FAC^MR||DOE^JANE\r\
+                     BTS|2\r\
+                     PID|1||444^^^FAC^MR\r\
+                     FTS|1";
+        let file = V2File::try_from_str(input).unwrap();
+        assert!(!file.is_valid(), "File with errors was reported as valid!");
+        assert_eq!(
+            file.messages().count(),
+            2,
+            "Good messages were not kept after a bad message!"
+        );
+        let errors = file.errors();
+        assert_eq!(errors.len(), 3, "Wrong errors => {:?}", errors);
+        assert_eq!(
+            errors[0],
+            &V2BatchError::Structure {
+                segment: 10,
+                error: RUMString::from("Segment PID is not part of any message!")
+            },
+            "Stray segment was not reported!"
+        );
+        match errors[1] {
+            V2BatchError::Message { batch, message, .. } => {
+                assert_eq!((*batch, *message), (1, 2), "Wrong failed message position!")
             }
+            _ => panic!("Expected a message error! Got {:?}", errors[1]),
         }
+        assert_eq!(
+            errors[2],
+            &V2BatchError::Count {
+                segment: RUMString::from("BTS"),
+                declared: 2,
+                found: 3
+            },
+            "BTS count mismatch was not reported!"
+        );
     }
 
     #[test]
-    fn test_cast_component_to_number_expected_functionality() {
-        let inputs = [
-            "5e3",
-            "5E3",
-            "112355.5555",
-            "5F",
-            "5.5F",
-            "5f",
-            "5.5e2",
-            "-5f",
-            "-05e1",
-        ];
-        let expected_outputs = [
-            5000.0,
-            5000.0,
-            112355.5555,
-            5.0,
-            5.5,
-            5.0,
-            550.0,
-            -5.0,
-            -50.0,
-        ];
-        for i in 0..inputs.len() {
-            let input = inputs[i];
-            let expected_val = expected_outputs[i];
-            print!(
-                "Testing input #{} \"{}\". Expected output is \"{}\". Casting to NM type.",
-                i, input, expected_val
-            );
-            let val = input.to_v2number().unwrap();
-            let err_msg = format_compact!("The expected date time string does not match the date time string generated from the input [In: {}, Got: {}]", input, val);
-            assert_eq!(expected_val, val, "{}", &err_msg);
-            println!(" ... Got: {} ✅ ", val);
+    fn test_generating_hl7_v2_batch_file() {
+        let first = V2Message::try_from_str(tests::DEFAULT_HL7_V2_MESSAGE).unwrap();
+        let second = V2Message::try_from_str(tests::VXU_HL7_V2_MESSAGE).unwrap();
+        let mut batch = V2Batch::new(vec![first]).unwrap();
+        batch.push(second).unwrap();
+        let batch_string = batch.to_string();
+        assert!(
+            batch_string.starts_with("BHS|^~\\&\rMSH|") && batch_string.ends_with("\rBTS|2"),
+            "Wrong batch generated => {}",
+            batch_string
+        );
+        let parsed_batch = V2Batch::try_from_str(&batch_string).unwrap();
+        assert!(
+            parsed_batch.is_valid(),
+            "Unexpected errors => {:?}",
+            parsed_batch.errors()
+        );
+        assert_eq!(
+            parsed_batch.messages(),
+            batch.messages(),
+            "Batched messages did not survive a round trip!"
+        );
+
+        let file = V2File::new(vec![batch, parsed_batch]).unwrap();
+        let file_string = file.to_string();
+        assert!(
+            file_string.starts_with("FHS|^~\\&\rBHS|") && file_string.ends_with("\rFTS|2"),
+            "Wrong file generated => {}",
+            file_string
+        );
+        let parsed_file = V2File::try_from_str(&file_string).unwrap();
+        assert!(
+            parsed_file.is_valid(),
+            "Unexpected errors => {:?}",
+            parsed_file.errors()
+        );
+        assert_eq!(parsed_file, file, "File did not survive a round trip!");
+        match V2Batch::try_from_str(&file_string) {
+            Ok(_) => panic!("Parsed a whole file as a single batch!"),
+            Err(e) => println!("Got error => {}", e),
         }
     }
 
     #[test]
-    fn test_cast_component_to_number_validation() {
-        let inputs = [".2"];
-        for input in inputs {
-            match input.to_v2number() {
-                Ok(val) => {
-                    panic!(
-                        "Validation failed [In: {} Got: {} Expected: None] ... ✕",
-                        input, val
-                    );
-                }
-                Err(e) => println!(
-                    "Validation correctly identified malformed input with message => [{}] ✅",
-                    e.as_str()
-                ),
+    fn test_hl7_v2_unescape() {
+        let encode_chars = V2ParserCharacters::new();
+        let input = "\\H\\Smith\\N\\ \\T\\ Wesson \\F\\ Co\\S\\ \\R\\ \\E\\ \\X416E6472C3A973\\\\C2D41\\ \\.br\\";
+        let expected = "Smith & Wesson | Co^ ~ \\ Andrés \\.br\\";
+        let decoded = encode_chars.unescape(input).unwrap();
+        assert_eq!(
+            decoded, expected,
+            "Escape sequences were not decoded correctly!"
+        );
+    }
+
+    #[test]
+    fn test_hl7_v2_unescape_malformed() {
+        let encode_chars = V2ParserCharacters::new();
+        for input in ["Smith \\T Wesson", "\\X4G\\", "\\X416\\"] {
+            match encode_chars.unescape(input) {
+                Ok(v) => panic!("Decoding {} did not fail as expected. Got => {}", input, v),
+                Err(e) => println!("Got error => {}", e),
             }
         }
     }
 
     #[test]
-    fn test_cast_component_to_st_expected_functionality() {
-        let inputs = [" Hello World!"];
-        let expected_outputs = ["Hello World!"];
-        for i in 0..inputs.len() {
-            let input = inputs[i];
-            let expected_val = expected_outputs[i];
-            print!(
+    fn test_hl7_v2_escape() {
+        let encode_chars = V2ParserCharacters::new();
+        let input = "Smith & Wesson | Co^ ~ \\ \r";
+        let escaped = encode_chars.escape(input);
+        assert_eq!(
+            escaped, "Smith \\T\\ Wesson \\F\\ Co\\S\\ \\R\\ \\E\\ \\X0D\\",
+            "Text was not escaped correctly!"
+        );
+        assert_eq!(
+            encode_chars.unescape(&escaped).unwrap(),
+            input,
+            "Escaped text did not decode back to the original!"
+        );
+    }
+
+    #[test]
+    fn test_generating_v2_message_escapes_delimiters() {
+        let mut message = rumtk_v2_parse_message!(&tests::DEFAULT_HL7_V2_MESSAGE).unwrap();
+        message.get_mut(&V2_SEGMENT_IDS["PID"], 1).unwrap()[5][0][1][1].set("EVERY|MAN^JR");
+        let generated_message_string = rumtk_v2_generate_message!(&message);
+        let generated_message = rumtk_v2_parse_message!(&generated_message_string).unwrap();
+        let pid = generated_message.get(&V2_SEGMENT_IDS["PID"], 1).unwrap();
+        assert_eq!(
+            pid.len(),
+            message.get(&V2_SEGMENT_IDS["PID"], 1).unwrap().len(),
+            "Generated PID has a different number of fields!"
+        );
+        let name = rumtk_v2_find_component!(generated_message, "PID5.1").unwrap();
+        assert_eq!(
+            name.as_str(),
+            "EVERY\\F\\MAN\\S\\JR",
+            "Delimiters were not escaped!"
+        );
+        let encode_chars = V2ParserCharacters::new();
+        assert_eq!(
+            name.decoded(&encode_chars).unwrap(),
+            "EVERY|MAN^JR",
+            "Escaped value did not decode back to the original!"
+        );
+        assert_eq!(
+            pid[5][0][1].decoded(&encode_chars).unwrap(),
+            "EVERY|MAN^JR",
+            "Component did not decode back to the original!"
+        );
+    }
+
+    #[test]
+    fn test_handle_hl7_v2_search_pattern_parsing_full() {
+        let pattern = "MSH(1)-1[5].4";
+        let groups = string_search_named_captures(pattern, REGEX_V2_SEARCH_DEFAULT, "1");
+        let expected = SearchGroups::from([
+            (RUMString::new("segment_group"), RUMString::new("1")),
+            (RUMString::new("sub_field"), RUMString::new("5")),
+            (RUMString::new("segment"), RUMString::new("MSH")),
+            (RUMString::new("field"), RUMString::new("-1")),
+            (RUMString::new("component"), RUMString::new("4")),
+        ]);
+        println!(
+            "Input: {:?} Expected: {:?} Got: {:?}",
+            pattern, expected, groups
+        );
+        assert_eq!(
+            groups, expected,
+            "Misparsed search expression MSH(1)-1[5].4!"
+        );
+    }
+
+    #[test]
+    fn test_handle_hl7_v2_search_pattern_parsing_simple() {
+        let pattern = "MSH1.4";
+        let groups = string_search_named_captures(pattern, REGEX_V2_SEARCH_DEFAULT, "1");
+        let expected = SearchGroups::from([
+            (RUMString::new("segment_group"), RUMString::new("1")),
+            (RUMString::new("sub_field"), RUMString::new("1")),
+            (RUMString::new("segment"), RUMString::new("MSH")),
+            (RUMString::new("field"), RUMString::new("1")),
+            (RUMString::new("component"), RUMString::new("4")),
+        ]);
+        println!(
+            "Input: {:?} Expected: {:?} Got: {:?}",
+            pattern, expected, groups
+        );
+        assert_eq!(groups, expected, "Misparsed search expression MSH1.4!");
+    }
+
+    #[test]
+    fn test_v2_search_index() {
+        let expr = "MSH(1)-1[5].4";
+        let v2_search_index = V2SearchIndex::from(expr);
+        let expected = V2SearchIndex::new("MSH", 1, -1, 5, 4);
+        println!(
+            "Input: {:?} Expected: {:?} Got: {:?}",
+            expr, expected, v2_search_index
+        );
+        assert_eq!(
+            v2_search_index, expected,
+            "Failed to parse expression into correct SearchIndex object."
+        );
+    }
+
+    #[test]
+    fn test_v2_search_index_subcomponent() {
+        let expr = "PID(1)3[2].4.2";
+        let v2_search_index = V2SearchIndex::from(expr);
+        let expected = V2SearchIndex::new("PID", 1, 3, 2, 4).with_subcomponent(2);
+        println!(
+            "Input: {:?} Expected: {:?} Got: {:?}",
+            expr, expected, v2_search_index
+        );
+        assert_eq!(
+            v2_search_index, expected,
+            "Failed to parse expression into correct SearchIndex object."
+        );
+        assert_eq!(
+            V2SearchIndex::from("MSH(1)-1[5].4.2"),
+            V2SearchIndex::new("MSH", 1, -1, 5, 4).with_subcomponent(2),
+            "The subcomponent should not change how the rest of the expression is read!"
+        );
+        assert_eq!(
+            V2SearchIndex::from("PID3.4"),
+            V2SearchIndex::new("PID", 1, 3, 1, 4).with_subcomponent(1),
+            "Subcomponent should default to the first one!"
+        );
+    }
+
+    #[test]
+    fn test_load_hl7_v2_message_macro() {
+        let message = rumtk_v2_parse_message!(tests::DEFAULT_HL7_V2_MESSAGE).unwrap();
+        assert!(
+            message.segment_exists(&V2_SEGMENT_IDS["MSH"]),
+            "Missing MSH segment!"
+        );
+        assert!(
+            message.segment_exists(&V2_SEGMENT_IDS["PID"]),
+            "Missing PID segment!"
+        );
+        assert!(
+            message.segment_exists(&V2_SEGMENT_IDS["PV1"]),
+            "Missing PV1 segment!"
+        );
+        assert!(
+            message.segment_exists(&V2_SEGMENT_IDS["EVN"]),
+            "Missing EVN segment!"
+        );
+        assert!(
+            message.segment_exists(&V2_SEGMENT_IDS["NK1"]),
+            "Missing NK1 segment!"
+        );
+    }
+
+    #[test]
+    fn test_load_hl7_v2_message_macro_failure() {
+        let input = "Hello World!";
+        let err_msg = format_compact!(
+            "Parsing did not fail as expected. Input {} => parsed?",
+            input
+        );
+        match rumtk_v2_parse_message!(input) {
+            Ok(v) => panic!("{}", err_msg.as_str()),
+            Err(e) => {
+                println!("{}", format_compact!("Got error => {}", e).as_str());
+                println!("Passed failed case!");
+            }
+        };
+    }
+
+    #[test]
+    fn test_find_hl7_v2_message_component_macro() {
+        let pattern = "PID(1)5.4";
+        let message = rumtk_v2_parse_message!(tests::DEFAULT_HL7_V2_MESSAGE).unwrap();
+        let component = rumtk_v2_find_component!(message, pattern).unwrap();
+        let expected = "III";
+        assert_eq!(
+            component.as_str(),
+            expected,
+            "Wrong component found! Looked for {} expecting {}, but got {}",
+            pattern,
+            expected,
+            component.as_str()
+        );
+    }
+
+    #[test]
+    fn test_find_hl7_v2_message_component_simple_macro() {
+        let pattern = "PID5.4";
+        let message = rumtk_v2_parse_message!(tests::DEFAULT_HL7_V2_MESSAGE).unwrap();
+        let component = rumtk_v2_find_component!(message, pattern).unwrap();
+        let expected = "III";
+        assert_eq!(
+            component.as_str(),
+            expected,
+            "Wrong component found! Looked for {} expecting {}, but got {}",
+            pattern,
+            expected,
+            component.as_str()
+        );
+    }
+
+    #[test]
+    fn test_find_hl7_v2_message_msh_field() {
+        let pattern = "MSH1.1";
+        let message = rumtk_v2_parse_message!(tests::HL7_V2_MSH_ONLY).unwrap();
+        let component = rumtk_v2_find_component!(message, pattern).unwrap();
+        let expected = "^~\\&";
+        assert_eq!(
+            component.as_str(),
+            expected,
+            "Wrong component found! Looked for {} expecting {}, but got {}",
+            pattern,
+            expected,
+            component.as_str()
+        );
+    }
+
+    #[test]
+    fn test_find_hl7_v2_message_subcomponent() {
+        let pattern = "PID3.4.2";
+        let message = rumtk_v2_parse_message!(tests::HL7_V2_REPEATING_FIELD_MESSAGE).unwrap();
+        let subcomponent = rumtk_v2_find_subcomponent!(message, pattern).unwrap();
+        let expected = "2.16.840.1.113883.3.72.5.30.2";
+        assert_eq!(
+            subcomponent.as_str(),
+            expected,
+            "Wrong subcomponent found! Looked for {} expecting {}, but got {}",
+            pattern,
+            expected,
+            subcomponent.as_str()
+        );
+        let pattern = "PID3.4.-1";
+        let subcomponent = rumtk_v2_find_subcomponent!(message, pattern).unwrap();
+        assert_eq!(
+            subcomponent.as_str(),
+            "ISO",
+            "Wrong subcomponent found! Looked for {} expecting {}, but got {}",
+            pattern,
+            "ISO",
+            subcomponent.as_str()
+        );
+        let component = rumtk_v2_find_component!(message, pattern).unwrap();
+        assert_eq!(
+            component.to_string(),
+            "NIST MPI&2.16.840.1.113883.3.72.5.30.2&ISO",
+            "The component should keep all of its subcomponents!"
+        );
+    }
+
+    #[test]
+    fn test_hl7_v2_subcomponent_parsing() {
+        let encode_chars = V2ParserCharacters::new();
+        let field = V2Field::from_str(
+            "PATID14567^^^NIST MPI&2.16.840.1.113883.3.72.5.30.2&ISO^MR",
+            &encode_chars,
+        );
+        let component = field.get(4).unwrap();
+        assert_eq!(
+            component.len(),
+            3,
+            "Wrong number of subcomponents in component"
+        );
+        assert_eq!(
+            component[1].as_str(),
+            "NIST MPI",
+            "Wrong value in subcomponent!"
+        );
+        assert_eq!(
+            component[-1].as_str(),
+            "ISO",
+            "Wrong value in subcomponent!"
+        );
+        assert_eq!(
+            component.as_str(),
+            "NIST MPI&2.16.840.1.113883.3.72.5.30.2&ISO",
+            "Component should read as a whole!"
+        );
+        assert_eq!(component.to_string(), component.as_str());
+        assert_eq!(
+            field.to_string(&encode_chars),
+            "PATID14567^^^NIST MPI&2.16.840.1.113883.3.72.5.30.2&ISO^MR",
+            "Field did not regenerate to its original form!"
+        );
+    }
+
+    #[test]
+    fn test_find_hl7_v2_message_component_macro_failure() {
+        let pattern = "PID(1)15.4";
+        let err_msg = format_compact!(
+            "Search did not fail as expected. Input {} => found component?",
+            pattern
+        );
+        let message = rumtk_v2_parse_message!(tests::DEFAULT_HL7_V2_MESSAGE).unwrap();
+        match rumtk_v2_find_component!(message, pattern) {
+            Ok(v) => panic!("{}", err_msg.as_str()),
+            Err(e) => {
+                println!("{}", format_compact!("Got error => {}", e).as_str());
+                println!("Passed failed case!");
+            }
+        }
+    }
+
+    #[test]
+    fn test_cast_component_to_datetime_expected_functionality() {
+        let inputs = [
+            "2007",
+            "200708",
+            "20070818",
+            "200708181123",
+            "20070818112355",
+            "20070818112355.55",
+            "20070818112355.5555-5000",
+            "20070818112355-5000",
+        ];
+        let expected_outputs = [
+            "2007-01-01T00:00:00.0000",
+            "2007-08-01T00:00:00.0000",
+            "2007-08-18T00:00:00.0000",
+            "2007-08-18T11:23:00.0000",
+            "2007-08-18T11:23:55.0000",
+            "2007-08-18T11:23:55.5500",
+            "2007-08-18T11:23:55.5555-5000",
+            "2007-08-18T11:23:55.0000-5000",
+        ];
+        for i in 0..inputs.len() {
+            let input = inputs[i];
+            let expected_utc = expected_outputs[i];
+            print!(
+                "Testing input #{} \"{}\". Expected output is \"{}\". Casting to datetime type.",
+                i, input, expected_utc
+            );
+            let date = input.to_v2datetime().unwrap();
+            let err_msg = format_compact!("The expected date time string does not match the date time string generated from the input [In: {}, Got: {}]", input, date.as_utc_string());
+            assert_eq!(expected_utc, date.as_utc_string().as_str(), "{}", &err_msg);
+            println!(" ... Got: {} ✅ ", date.as_utc_string());
+        }
+    }
+
+    #[test]
+    fn test_cast_component_to_datetime_validation() {
+        let inputs = ["200"];
+        for input in inputs {
+            match input.to_v2datetime() {
+                Ok(date) => {
+                    panic!(
+                        "Validation failed [In: {} Got: {} Expected: None] ... ✕",
+                        input,
+                        date.as_utc_string()
+                    );
+                }
+                Err(e) => println!(
+                    "Validation correctly identified malformed input with message => [{}] ✅",
+                    e.as_str()
+                ),
+            }
+        }
+    }
+
+    #[test]
+    fn test_cast_component_to_datetime_base_example() {
+        let location = "EVN2"; //EVN|A01|200708181123||\n\r; EVN2 => segment = EVN, field = 2
+        let expected_component = "200708181123";
+        let message = rumtk_v2_parse_message!(tests::DEFAULT_HL7_V2_MESSAGE).unwrap();
+        let component = rumtk_v2_find_component!(message, location).unwrap();
+        assert_eq!(expected_component, component.as_str(), "We are not using the correct component for this test. Check that the original test message has not changed and update the location string appropriately!");
+        let date = component.to_v2datetime().unwrap();
+        let expected_utc = "2007-08-18T11:23:00.0000";
+        let err_msg = format_compact!("The expected date time string does not match the date time string generated from the input [{}]", component.as_str());
+        assert_eq!(expected_utc, date.as_utc_string().as_str(), "{}", &err_msg)
+    }
+
+    #[test]
+    fn test_datetime_default() {
+        let input = V2DateTime::default().as_utc_string();
+        let expected_val = V2String::from("1970-01-01T00:00:00.00000");
+        let err_msg = format_compact!("The expected formatted string does not match the formatted string generated from the input [In: {}, Got: {}]", input, input);
+        assert_eq!(expected_val, input, "{}", &err_msg);
+    }
+
+    #[test]
+    fn test_cast_component_to_date_expected_functionality() {
+        let inputs = ["2007", "200708", "20070818"];
+        let expected_outputs = [
+            "2007-01-01T00:00:00.0000",
+            "2007-08-01T00:00:00.0000",
+            "2007-08-18T00:00:00.0000",
+        ];
+        for i in 0..inputs.len() {
+            let input = inputs[i];
+            let expected_utc = expected_outputs[i];
+            print!(
+                "Testing input #{} \"{}\". Expected output is \"{}\". Casting to datetime type.",
+                i, input, expected_utc
+            );
+            let date = input.to_v2date().unwrap();
+            let err_msg = format_compact!("The expected date time string does not match the date time string generated from the input [In: {}, Got: {}]", input, date.as_utc_string());
+            assert_eq!(expected_utc, date.as_utc_string().as_str(), "{}", &err_msg);
+            println!(" ... Got: {} ✅ ", date.as_utc_string());
+        }
+    }
+
+    #[test]
+    fn test_cast_component_to_date_validation() {
+        let inputs = ["200"];
+        for input in inputs {
+            match input.to_v2date() {
+                Ok(date) => {
+                    panic!(
+                        "Validation failed [In: {} Got: {} Expected: None] ... ✕",
+                        input,
+                        date.as_utc_string()
+                    );
+                }
+                Err(e) => println!(
+                    "Validation correctly identified malformed input with message => [{}] ✅",
+                    e.as_str()
+                ),
+            }
+        }
+    }
+
+    #[test]
+    fn test_cast_component_to_date_base_example() {
+        let location = "PD113"; //EVN|A01|200708181123||\n\r; PD113 => segment = PD1, field = 13
+        let expected_component = "20150625";
+        let message = rumtk_v2_parse_message!(tests::VXU_HL7_V2_MESSAGE).unwrap();
+        let component = rumtk_v2_find_component!(message, location).unwrap();
+        assert_eq!(expected_component, component.as_str(), "We are not using the correct component for this test. Check that the original test message has not changed and update the location string appropriately!");
+        let date = component.to_v2date().unwrap();
+        let expected_utc = "2015-06-25T00:00:00.0000";
+        let err_msg = format_compact!(
+            "The expected date string does not match the date string generated from the input [{}]",
+            component.as_str()
+        );
+        assert_eq!(expected_utc, date.as_utc_string().as_str(), "{}", &err_msg)
+    }
+
+    #[test]
+    fn test_cast_component_to_time_expected_functionality() {
+        let inputs = ["1123", "112355", "112355.5555", "112355.5555-5000"];
+        let expected_outputs = [
+            "1970-01-01T11:23:00.0000",
+            "1970-01-01T11:23:55.0000",
+            "1970-01-01T11:23:55.5555",
+            "1970-01-01T11:23:55.5555-5000",
+        ];
+        for i in 0..inputs.len() {
+            let input = inputs[i];
+            let expected_utc = expected_outputs[i];
+            print!(
+                "Testing input #{} \"{}\". Expected output is \"{}\". Casting to datetime type.",
+                i, input, expected_utc
+            );
+            let date = input.to_v2time().unwrap();
+            let err_msg = format_compact!("The expected date time string does not match the date time string generated from the input [In: {}, Got: {}]", input, date.as_utc_string());
+            assert_eq!(expected_utc, date.as_utc_string().as_str(), "{}", &err_msg);
+            println!(" ... Got: {} ✅ ", date.as_utc_string());
+        }
+    }
+
+    #[test]
+    fn test_cast_component_to_time_validation() {
+        let inputs = ["2"];
+        for input in inputs {
+            match input.to_v2time() {
+                Ok(date) => {
+                    panic!(
+                        "Validation failed [In: {} Got: {} Expected: None] ... ✕",
+                        input,
+                        date.as_utc_string()
+                    );
+                }
+                Err(e) => println!(
+                    "Validation correctly identified malformed input with message => [{}] ✅",
+                    e.as_str()
+                ),
+            }
+        }
+    }
+
+    #[test]
+    fn test_cast_component_to_number_expected_functionality() {
+        let inputs = [
+            "5e3",
+            "5E3",
+            "112355.5555",
+            "5F",
+            "5.5F",
+            "5f",
+            "5.5e2",
+            "-5f",
+            "-05e1",
+        ];
+        let expected_outputs = [
+            5000.0,
+            5000.0,
+            112355.5555,
+            5.0,
+            5.5,
+            5.0,
+            550.0,
+            -5.0,
+            -50.0,
+        ];
+        for i in 0..inputs.len() {
+            let input = inputs[i];
+            let expected_val = expected_outputs[i];
+            print!(
+                "Testing input #{} \"{}\". Expected output is \"{}\". Casting to NM type.",
+                i, input, expected_val
+            );
+            let val = input.to_v2number().unwrap();
+            let err_msg = format_compact!("The expected date time string does not match the date time string generated from the input [In: {}, Got: {}]", input, val);
+            assert_eq!(expected_val, val, "{}", &err_msg);
+            println!(" ... Got: {} ✅ ", val);
+        }
+    }
+
+    #[test]
+    fn test_cast_component_to_number_validation() {
+        let inputs = [".2"];
+        for input in inputs {
+            match input.to_v2number() {
+                Ok(val) => {
+                    panic!(
+                        "Validation failed [In: {} Got: {} Expected: None] ... ✕",
+                        input, val
+                    );
+                }
+                Err(e) => println!(
+                    "Validation correctly identified malformed input with message => [{}] ✅",
+                    e.as_str()
+                ),
+            }
+        }
+    }
+
+    #[test]
+    fn test_cast_component_to_st_expected_functionality() {
+        let inputs = [" Hello World!"];
+        let expected_outputs = ["Hello World!"];
+        for i in 0..inputs.len() {
+            let input = inputs[i];
+            let expected_val = expected_outputs[i];
+            print!(
                 "Testing input #{} \"{}\". Expected output is \"{}\". Casting to ST type.",
                 i, input, expected_val
             );
@@ -946,69 +2103,1358 @@ mod tests {
     }
 
     #[test]
-    fn test_cast_component_to_st_validation() {
-        let input = "2".duplicate(1001);
-        println!("{}", input);
-        match input.to_v2stringdata() {
-            Ok(val) => {
-                panic!(
-                    "Validation failed [In: {} Got: {} Expected: None] ... ✕",
-                    input, val
-                );
-            }
-            Err(e) => println!(
-                "Validation correctly identified malformed input with message => [{}] ✅",
-                e.as_str()
-            ),
+    fn test_cast_component_to_st_validation() {
+        let input = "2".duplicate(1001);
+        println!("{}", input);
+        match input.to_v2stringdata() {
+            Ok(val) => {
+                panic!(
+                    "Validation failed [In: {} Got: {} Expected: None] ... ✕",
+                    input, val
+                );
+            }
+            Err(e) => println!(
+                "Validation correctly identified malformed input with message => [{}] ✅",
+                e.as_str()
+            ),
+        }
+    }
+
+    #[test]
+    fn test_cast_component_to_ft_expected_functionality() {
+        let inputs = ["H", &"e".duplicate(120000)];
+        let expected_outputs = ["H", &"e".duplicate(TRUNCATE_FT as usize)];
+        for i in 0..inputs.len() {
+            let input = inputs[i];
+            let expected_val = expected_outputs[i];
+            print!(
+                "Testing input #{} \"{}\". Expected output is \"{}\". Casting to FT type.",
+                i, input, expected_val
+            );
+            let val = input.to_v2formattedtext("~").unwrap();
+            println!("{}", val.len());
+            let err_msg = format_compact!("The expected formatted string does not match the formatted string generated from the input [In: {}, Got: {}]", input, val);
+            assert_eq!(expected_val, val, "{}", &err_msg);
+            println!(" ... Got: {} ✅ ", val);
+        }
+    }
+
+    #[test]
+    fn test_validated_cast_component_to_type() {
+        let message = tests::DEFAULT_HL7_V2_MESSAGE;
+        let sanitized_message = V2Message::sanitize(message);
+        let tokens = V2Message::tokenize_segments(&sanitized_message.as_str());
+        let encode_chars = V2ParserCharacters::from_msh(tokens[0]).unwrap();
+        let v2_component = V2ComponentTypeDescriptor::new(
+            "date",
+            "Date",
+            V2ComponentType::Primitive(V2PrimitiveType::Date),
+            4,
+            1,
+            1,
+            Optionality::O,
+            true,
+        );
+        let input = "2007";
+        let val = cast_component(vec![&input], &v2_component, &encode_chars);
+        let expected = "2007-01-01T00:00:00.0000";
+        let err_msg = format_compact!("The expected formatted string does not match the formatted string generated from the input [In: {}, Got: {}]", input, expected);
+
+        match val {
+            V2Type::V2Date(result) => {
+                assert_eq!(expected, result.unwrap().as_utc_string(), "{}", &err_msg)
+            }
+            _ => panic!("Wrong type received!"),
+        }
+    }
+
+    #[test]
+    fn test_complex_type_descriptors() {
+        for (data_type, descriptor) in V2_FIELD_DESCRIPTORS.entries() {
+            assert!(
+                !descriptor.is_empty(),
+                "No components defined for {}!",
+                data_type
+            );
+            for (i, component) in descriptor.iter().enumerate() {
+                assert_eq!(
+                    component.seq as usize,
+                    i + 1,
+                    "Component {} of {} is out of sequence!",
+                    component.name,
+                    data_type
+                );
+                if let V2ComponentType::Complex(complex_type) = &component.data_type {
+                    let nested_type = complex_type_to_str(complex_type);
+                    assert!(
+                        V2_FIELD_DESCRIPTORS.contains_key(nested_type),
+                        "{}.{} references undefined type {}!",
+                        data_type,
+                        component.seq,
+                        nested_type
+                    );
+                }
+            }
+        }
+
+        let expected_types = [
+            ("XPN", 1, "FN"),
+            ("XAD", 1, "SAD"),
+            ("XCN", 9, "HD"),
+            ("CX", 4, "HD"),
+            ("PL", 10, "EI"),
+            ("TQ", 1, "CQ"),
+        ];
+        for (data_type, seq, expected) in expected_types {
+            let component = &V2_FIELD_DESCRIPTORS.get(data_type).unwrap()[seq - 1];
+            match &component.data_type {
+                V2ComponentType::Complex(complex_type) => assert_eq!(
+                    complex_type_to_str(complex_type),
+                    expected,
+                    "Wrong nested type for {}.{}!",
+                    data_type,
+                    seq
+                ),
+                _ => panic!("{}.{} should be a composite!", data_type, seq),
+            }
+        }
+        let cx = V2_FIELD_DESCRIPTORS.get("CX").unwrap();
+        assert_eq!(cx[4].valid_table, 203, "Wrong table for CX.5!");
+        assert!(cx[0].optionality.is_required(), "CX.1 should be required!");
+        assert_eq!(
+            complex_type_to_str(&V2ComplexType::XTN),
+            "XTN",
+            "Wrong key for XTN!"
+        );
+    }
+
+    #[test]
+    fn test_component_optionality_conditions() {
+        let coded = vec![vec!["2345-7"], vec!["Glucose"]];
+        assert!(
+            CONDITION_CF1(&V2ConditionContext::new(&coded)),
+            "CF.3 should be required!"
+        );
+        let mut coded_with_oid = coded.clone();
+        coded_with_oid.resize(14, vec![""]);
+        coded_with_oid[13] = vec!["2.16.840.1.113883.6.1"];
+        assert!(
+            !CONDITION_CF1(&V2ConditionContext::new(&coded_with_oid)),
+            "CF.3 should not be required when CF.14 is populated!"
+        );
+
+        let unnamed_provider = vec![vec![""], vec!["SMITH"]];
+        assert!(
+            !CONDITION_CNN1(&V2ConditionContext::new(&unnamed_provider)),
+            "CNN.8 should not be required without an ID!"
+        );
+        let provider = vec![vec!["004777"], vec!["ATTEND"]];
+        assert!(
+            CONDITION_CNN1(&V2ConditionContext::new(&provider)),
+            "CNN.8 should be required!"
+        );
+        assert!(
+            CONDITION_XCN1(&V2ConditionContext::new(&provider)),
+            "XCN.8 should be required!"
+        );
+        assert!(
+            CONDITION_XCN4(&V2ConditionContext::new(&provider)),
+            "XCN.22 should be required!"
+        );
+        let mut provider_with_authority = provider.clone();
+        provider_with_authority.resize(9, vec![""]);
+        provider_with_authority[8] = vec!["NPI", "2.16.840.1.113883.4.6", "ISO"];
+        assert!(
+            !CONDITION_XCN1(&V2ConditionContext::new(&provider_with_authority)),
+            "XCN.8 should not be required when XCN.9 is populated!"
+        );
+        assert!(
+            !CONDITION_XCN4(&V2ConditionContext::new(&provider_with_authority)),
+            "XCN.22 should not be required when XCN.9 is populated!"
+        );
+
+        let email = vec![vec![""], vec!["NET"], vec!["Internet"]];
+        assert!(
+            CONDITION_XTN1(&V2ConditionContext::new(&email)),
+            "XTN.4 should be required for email!"
+        );
+        assert!(
+            !CONDITION_XTN2(&V2ConditionContext::new(&email)),
+            "XTN.7 should not be required for email!"
+        );
+        let phone = vec![vec![""], vec!["PRN"], vec!["PH"]];
+        assert!(
+            !CONDITION_XTN1(&V2ConditionContext::new(&phone)),
+            "XTN.4 should not be required for phones!"
+        );
+        assert!(
+            CONDITION_XTN2(&V2ConditionContext::new(&phone)),
+            "XTN.7 should be required for phones!"
+        );
+    }
+
+    #[test]
+    fn test_segment_field_definitions() {
+        let message = rumtk_v2_parse_message!(tests::HL7_V2_ORU_MESSAGE).unwrap();
+        let pid = message.get(&find_segment_id("PID").unwrap(), 1).unwrap();
+        let patient_name = pid.field_definition(5).unwrap();
+        assert_eq!(patient_name.name, "patient_name", "Wrong PID-5 definition!");
+        assert_eq!(
+            patient_name.data_type,
+            V2ComponentType::Complex(V2ComplexType::XPN),
+            "PID-5 should be an XPN!"
+        );
+        assert!(patient_name.repeating, "PID-5 should repeat!");
+        assert_eq!(
+            pid.field_by_name("patient_name").unwrap()[0]
+                .get(1)
+                .unwrap()
+                .get(1)
+                .unwrap()
+                .as_str(),
+            "DOE",
+            "Wrong field returned for patient_name!"
+        );
+        assert!(
+            pid.field_by_name("no_such_field").is_err(),
+            "Unknown field names should not resolve!"
+        );
+
+        let msh = message.get(&find_segment_id("MSH").unwrap(), 1).unwrap();
+        let message_type = msh.field_definition(V2_MSH_MESSAGE_TYPE).unwrap();
+        assert_eq!(
+            message_type.seq, 9,
+            "MSH positions should map to HL7 sequences!"
+        );
+        assert_eq!(
+            msh.field_definition_by_name("message_control_id")
+                .unwrap()
+                .0,
+            V2_MSH_CONTROL_ID,
+            "Wrong position for MSH-10!"
+        );
+
+        let obx = message.get(&find_segment_id("OBX").unwrap(), 1).unwrap();
+        assert_eq!(
+            obx.field_definition(5).unwrap().data_type,
+            V2ComponentType::Varies,
+            "OBX-5 should vary!"
+        );
+        assert_eq!(
+            obx.field_data_type(5).unwrap(),
+            V2ComponentType::Primitive(V2PrimitiveType::NM),
+            "OBX-5 should resolve to NM from OBX-2!"
+        );
+    }
+
+    #[test]
+    fn test_typed_field_access() {
+        let message = rumtk_v2_parse_message!(tests::HL7_V2_ORU_MESSAGE).unwrap();
+        let patient_name = message.get_typed("PID5").unwrap();
+        match patient_name
+            .component("family_name")
+            .unwrap()
+            .component("surname")
+        {
+            Some(V2Type::V2ST(surname)) => {
+                assert_eq!(surname.as_ref().unwrap(), "DOE", "Wrong PID-5.1.1!")
+            }
+            other => panic!("PID-5.1.1 should be ST! Got {:?}", other),
+        }
+        assert!(
+            patient_name.errors().is_empty(),
+            "PID-5 should cast cleanly! Got {:?}",
+            patient_name.errors()
+        );
+        match message.get_typed("PID5.2").unwrap() {
+            V2Type::V2ST(given_name) => {
+                assert_eq!(given_name.unwrap(), "JANE", "Wrong PID-5.2!")
+            }
+            other => panic!("PID-5.2 should be ST! Got {:?}", other),
+        }
+        match message.get_typed("OBX5").unwrap() {
+            V2Type::V2NM(value) => assert_eq!(value.unwrap(), 98.0, "Wrong OBX-5!"),
+            other => panic!("OBX-5 should be NM! Got {:?}", other),
+        }
+        assert!(
+            message.get_typed("PID7").unwrap().is_empty(),
+            "PID-7 is not valued!"
+        );
+
+        let bad_message = rumtk_v2_parse_message!(
+            "MSH|^~\\&|LAB|LAB|EHR|HOSP|20240102120000||ORU^R01^ORU_R01|MSG1|P|2.5.1\r\
+             PID|1||^^^LAB^MR||DOE^JANE\r\
+             OBX|1|NM|GLU^Glucose^L||high|mg/dL"
+        )
+        .unwrap();
+        let errors = bad_message.get_typed("PID3").unwrap().errors();
+        assert_eq!(
+            errors.len(),
+            1,
+            "Only CX.1 should be flagged! Got {:?}",
+            errors
+        );
+        assert!(
+            errors[0].starts_with("id: Required"),
+            "Missing CX.1 should be reported! Got {}",
+            errors[0]
+        );
+        match bad_message.get_typed("OBX5").unwrap() {
+            V2Type::V2NM(value) => assert!(value.is_err(), "OBX-5 should not cast to NM!"),
+            other => panic!("OBX-5 should be NM! Got {:?}", other),
+        }
+    }
+
+    fn check_segment_type<T: V2SegmentType>() {
+        for (name, seq) in T::FIELDS {
+            let field = T::descriptor()
+                .iter()
+                .find(|field| field.seq == *seq)
+                .unwrap();
+            assert_eq!(
+                field.name,
+                *name,
+                "{}-{} does not match its definition!",
+                T::SEGMENT_NAME,
+                seq
+            );
+        }
+    }
+
+    fn check_composite_type<T: V2CompositeType>() {
+        let names: Vec<&str> = T::descriptor().iter().map(|c| c.name).collect();
+        assert_eq!(
+            names,
+            T::COMPONENTS,
+            "{} does not match its descriptor!",
+            T::V2_TYPE
+        );
+    }
+
+    #[test]
+    fn test_typed_segment_structs() {
+        check_segment_type::<Msh>();
+        check_segment_type::<Pid>();
+        check_segment_type::<Obx>();
+        check_composite_type::<Xpn>();
+        check_composite_type::<Cx>();
+        check_composite_type::<Xcn>();
+        assert_eq!(
+            V2_SEGMENT_FIELD_DESCRIPTORS["PID"].len(),
+            Pid::FIELDS.len(),
+            "Pid is missing fields!"
+        );
+
+        let message = rumtk_v2_parse_message!(tests::HL7_V2_ORU_MESSAGE).unwrap();
+        let pid: Vec<Pid> = message.segments_as().unwrap();
+        assert_eq!(
+            pid[0].patient_name[0].family_name.surname, "DOE",
+            "Wrong PID-5.1.1!"
+        );
+        assert_eq!(pid[0].patient_name[0].given_name, "JANE", "Wrong PID-5.2!");
+        assert_eq!(
+            pid[0].patient_identifier_list[0]
+                .assigning_authority
+                .namespace_id,
+            "LAB",
+            "Wrong PID-3.4.1!"
+        );
+
+        let obx: Vec<Obx> = message.segments_as().unwrap();
+        assert_eq!(obx.len(), 5, "Wrong number of OBX segments!");
+        assert_eq!(obx[0].value_type, "NM", "Wrong OBX-2!");
+        assert_eq!(
+            obx[0].observation_value[0].components[0][0], "98",
+            "Wrong OBX-5!"
+        );
+        assert_eq!(obx[0].units.id, "mg/dL", "Wrong OBX-6!");
+
+        let separators = message.separators();
+        let msh: Vec<Msh> = message.segments_as().unwrap();
+        for (typed, segment) in [
+            (
+                msh[0].to_segment(separators).unwrap(),
+                message.get(&find_segment_id("MSH").unwrap(), 1).unwrap(),
+            ),
+            (
+                pid[0].to_segment(separators).unwrap(),
+                message.get(&find_segment_id("PID").unwrap(), 1).unwrap(),
+            ),
+            (
+                obx[3].to_segment(separators).unwrap(),
+                message.get(&find_segment_id("OBX").unwrap(), 4).unwrap(),
+            ),
+        ] {
+            assert_eq!(
+                &typed, segment,
+                "Typed segment did not convert back to the original!"
+            );
+        }
+
+        let json = rumtk_serialize!(&pid[0]).unwrap();
+        let deserialized: Pid = rumtk_deserialize!(&json).unwrap();
+        assert_eq!(
+            deserialized, pid[0],
+            "Pid did not survive a JSON round trip!"
+        );
+        assert!(
+            Pid::try_from(message.get(&find_segment_id("PV1").unwrap(), 1).unwrap()).is_err(),
+            "A PV1 segment should not convert to Pid!"
+        );
+    }
+
+    #[test]
+    fn test_version_aware_definitions() {
+        for (version_id, expected) in [
+            ("2.3", V2Version::V2_3),
+            ("2.3.1", V2Version::V2_3_1),
+            ("2.2", V2Version::V2_3),
+            ("2.5.1", V2Version::V2_5_1),
+            ("2.7.1", V2Version::V2_7),
+            ("2.10", V2Version::V2_9),
+            ("", V2Version::V2_5_1),
+            ("3.0", V2Version::V2_5_1),
+        ] {
+            assert_eq!(
+                V2Version::from_str(version_id),
+                expected,
+                "Wrong version for [{}]!",
+                version_id
+            );
+        }
+        assert!(
+            V2Version::V2_9.distinguishes_zero_offset()
+                && !V2Version::V2_8.distinguishes_zero_offset(),
+            "Only v2.9 tells +0000 and -0000 apart!"
+        );
+
+        let legacy = rumtk_v2_parse_message!(
+            "MSH|^~\\&|LEGACY|HOSP|||20240102||ADT^A01|1|P|2.3\r\
+             EVN|A01|20240102\r\
+             PID|1||123^^^HOSP||DOE^JOHN"
+        )
+        .unwrap();
+        let modern = rumtk_v2_parse_message!(
+            "MSH|^~\\&|APP|HOSP|||20240102||ADT^A01|1|P|2.7\r\
+             EVN||20240102\r\
+             PID|1||123^^^HOSP||DOE^JOHN\r\
+             OBX|1|NM|GLU^Glucose^L||98"
+        )
+        .unwrap();
+        assert_eq!(legacy.version(), V2Version::V2_3, "Wrong legacy version!");
+        assert_eq!(modern.version(), V2Version::V2_7, "Wrong modern version!");
+
+        let msh = legacy.get(&find_segment_id("MSH").unwrap(), 1).unwrap();
+        assert_eq!(
+            msh.field_data_type_for(V2_MSH_VERSION, V2Version::V2_3)
+                .unwrap(),
+            V2ComponentType::Primitive(V2PrimitiveType::ID),
+            "MSH-12 is an ID in v2.3!"
+        );
+        assert_eq!(
+            msh.field_definition_by_name_for("conformance_statement_id", V2Version::V2_4)
+                .unwrap()
+                .0,
+            20,
+            "MSH-21 is the conformance statement in v2.4!"
+        );
+        let pid = modern.get(&find_segment_id("PID").unwrap(), 1).unwrap();
+        assert!(
+            matches!(
+                pid.field_definition_for(2, V2Version::V2_8)
+                    .unwrap()
+                    .optionality,
+                Optionality::X
+            ),
+            "PID-2 is withdrawn as of v2.7!"
+        );
+        let obx = modern.get(&find_segment_id("OBX").unwrap(), 1).unwrap();
+        assert_eq!(
+            obx.field_data_type_for(3, V2Version::V2_7).unwrap(),
+            V2ComponentType::Complex(V2ComplexType::CWE),
+            "CE fields are CWE as of v2.6!"
+        );
+
+        match legacy.get_typed("PID5").unwrap().component("family_name") {
+            Some(V2Type::V2ST(surname)) => {
+                assert_eq!(surname.as_ref().unwrap(), "DOE", "Wrong PID-5.1!")
+            }
+            other => panic!("XPN.1 is an ST in v2.3! Got {:?}", other),
+        }
+        assert!(
+            legacy.get_typed("PID3").unwrap().errors().is_empty(),
+            "CX.5 is optional in v2.3!"
+        );
+        assert_eq!(
+            modern.get_typed("PID3").unwrap().errors().len(),
+            1,
+            "CX.5 is required in v2.7!"
+        );
+        assert!(
+            matches!(
+                legacy.get_typed("EVN2").unwrap(),
+                V2Type::V2Complex(V2ComplexType::TS, _)
+            ),
+            "EVN-2 is a TS in v2.3!"
+        );
+        assert!(
+            matches!(legacy.get_typed("EVN1").unwrap(), V2Type::V2ID(_)),
+            "EVN-1 is required in v2.3!"
+        );
+        assert!(
+            matches!(modern.get_typed("EVN2").unwrap(), V2Type::V2DateTime(Ok(_))),
+            "EVN-2 is a DTM in v2.7!"
+        );
+    }
+
+    #[test]
+    fn test_table_registry_validation() {
+        let mut registry = V2TableRegistry::new();
+        assert_eq!(
+            registry.get(136).unwrap().table_type,
+            V2TableType::HL7,
+            "Table 0136 is an HL7 table!"
+        );
+        assert_eq!(
+            registry.get(1),
+            None,
+            "User-defined tables are not bundled!"
+        );
+        assert_eq!(
+            registry
+                .load_csv("table,code,description\n1,F,Female\n1,M,Male\n1,U,\"Unknown, not stated\"\n189,N,Not Hispanic\n")
+                .unwrap(),
+            2,
+            "Expected tables 0001 and 0189 from the CSV!"
+        );
+        assert_eq!(
+            registry
+                .load_json(
+                    r#"{"id": 296, "name": "Primary Language", "values": [{"code": "ENG"}]}"#
+                )
+                .unwrap(),
+            1,
+            "Expected table 0296 from the JSON!"
+        );
+        assert_eq!(
+            registry.description(1, "U"),
+            Some("Unknown, not stated"),
+            "Wrong display text from quoted CSV value!"
+        );
+        assert!(
+            registry.load_csv("1,F,\"Female").is_err(),
+            "Unterminated quotes should fail!"
+        );
+
+        let message = rumtk_v2_parse_message!(
+            "MSH|^~\\&|APP|FAC|||20240102||ADT^A01|1|P|2.5.1|||AL|ZZ\r\
+             PID|1||123^^^HOSP^MR||DOE^JOHN^^^^^Q||19800101|F|||||||SPA^Spanish|||||||N^Not Hispanic^HL70189~Q^Other^LOCAL||Q"
+        )
+        .unwrap();
+        let flagged: Vec<(Option<RUMString>, Option<isize>, Option<isize>)> = message
+            .validate_tables(&registry)
+            .into_iter()
+            .map(|d| (d.segment_name, d.field, d.component))
+            .collect();
+        assert_eq!(
+            flagged,
+            vec![
+                (Some("MSH".to_rumstring()), Some(15), None),
+                (Some("PID".to_rumstring()), Some(5), Some(7)),
+                (Some("PID".to_rumstring()), Some(15), Some(1)),
+                (Some("PID".to_rumstring()), Some(24), None),
+            ],
+            "Wrong values flagged!"
+        );
+        assert_eq!(
+            message.display_text("PID8", &registry).unwrap(),
+            Some("Female"),
+            "Wrong display text for PID-8!"
+        );
+        assert_eq!(
+            message.bound_table("MSH11").unwrap(),
+            104,
+            "MSH-12 is bound to table 0104!"
+        );
+        assert!(
+            message.bound_table("PID3.1").is_err(),
+            "PID-3.1 is not bound to a table!"
+        );
+    }
+
+    #[test]
+    fn test_conformance_profile_validation() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <HL7v2xConformanceProfile HL7Version="2.5.1" ProfileType="Implementation">
+              <MetaData Name="Test ADT" OrgName="RUMTK" Version="1"/>
+              <HL7v2xStaticDef MsgType="ADT" EventType="A01" MsgStructID="ADT_A01">
+                <Segment Name="MSH" Usage="R" Min="1" Max="1">
+                  <Field Name="Field Separator" Usage="R" Min="1" Max="1" Datatype="ST" Length="1"/>
+                  <Field Name="Encoding Characters" Usage="R" Min="1" Max="1" Datatype="ST" Length="4"/>
+                  <Field Name="Sending Application" Usage="R" Min="1" Max="1" Datatype="HD">
+                    <Component Name="Namespace ID" Usage="R" Datatype="IS" Length="5"/>
+                  </Field>
+                </Segment>
+                <Segment Name="EVN" Usage="RE" Min="0" Max="1"/>
+                <Segment Name="PID" Usage="R" Min="1" Max="1">
+                  <Field Name="Set ID" Usage="R" Min="1" Max="1" Datatype="SI"/>
+                  <Field Name="Patient ID" Usage="X" Min="0" Max="0" Datatype="CX"/>
+                  <Field Name="Patient Identifier List" Usage="R" Min="1" Max="2" Datatype="CX">
+                    <Component Name="ID Number" Usage="R" Datatype="ST" Length="15"/>
+                    <Component Name="Check Digit" Usage="O" Datatype="ST"/>
+                    <Component Name="Check Digit Scheme" Usage="O" Datatype="ID"/>
+                    <Component Name="Assigning Authority" Usage="R" Datatype="HD">
+                      <SubComponent Name="Namespace ID" Usage="R" Datatype="IS"/>
+                    </Component>
+                    <Component Name="Identifier Type Code" Usage="R" Datatype="ID" Table="HL70203"/>
+                  </Field>
+                  <Field Name="Alternate Patient ID" Usage="O" Min="0" Max="1"/>
+                  <Field Name="Patient Name" Usage="R" Min="1" Max="*" Datatype="XPN"/>
+                  <Field Name="Mother&apos;s Maiden Name" Usage="O" Min="0" Max="1"/>
+                  <Field Name="Date/Time of Birth" Usage="RE" Min="0" Max="1" Datatype="DTM" Length="8"/>
+                  <Field Name="Administrative Sex" Usage="RE" Min="0" Max="1" Datatype="IS" Table="0001"/>
+                </Segment>
+                <Segment Name="PV1" Usage="R" Min="1" Max="1">
+                  <Field Name="Set ID" Usage="O" Min="0" Max="1"/>
+                  <Field Name="Patient Class" Usage="R" Min="1" Max="1" Datatype="IS" Table="0004"/>
+                </Segment>
+                <!-- Insurance is optional but IN1 anchors the group. -->
+                <SegGroup Name="INSURANCE" Usage="O" Min="0" Max="*">
+                  <Segment Name="IN1" Usage="R" Min="1" Max="1"/>
+                  <Segment Name="IN2" Usage="O" Min="0" Max="1"/>
+                </SegGroup>
+              </HL7v2xStaticDef>
+            </HL7v2xConformanceProfile>"#;
+        let profile = V2Profile::from_xml(xml).unwrap();
+        assert_eq!(profile.name, "Test ADT", "Wrong profile name!");
+        assert_eq!(
+            profile.segments.len(),
+            5,
+            "Wrong number of profile segments!"
+        );
+        assert_eq!(
+            profile.segments[2].fields[2].components[3].components.len(),
+            1,
+            "Expected the subcomponent of PID-3.4!"
+        );
+
+        let mut registry = V2TableRegistry::new();
+        registry
+            .load_csv("1,F,Female\n1,M,Male\n4,I,Inpatient\n4,O,Outpatient\n203,MR,Medical record number\n")
+            .unwrap();
+        let message = rumtk_v2_parse_message!(
+            "MSH|^~\\&|LONGAPPNAME|FAC|||20240102||ADT^A04^ADT_A01|1|P|2.5.1\r\
+             PID|1|99|123^^^HOSP^MR~456^^^^XX~789^^^HOSP^MR||DOE^JOHN||198001011230|F\r\
+             ZPI|1\r\
+             PV1|1|Q\r\
+             IN2|1"
+        )
+        .unwrap();
+        let report = message.validate_profile(&profile, &registry);
+        let issues: Vec<(V2DiagnosticSeverity, V2ConformanceIssueKind, &str)> = report
+            .issues
+            .iter()
+            .map(|issue| (issue.severity, issue.kind, issue.location.as_str()))
+            .collect();
+        let error = V2DiagnosticSeverity::Error;
+        assert_eq!(
+            issues,
+            vec![
+                (error, V2ConformanceIssueKind::Structure, "MSH(1)8.2"),
+                (error, V2ConformanceIssueKind::Length, "MSH(1)2[1].1"),
+                (error, V2ConformanceIssueKind::Usage, "PID(1)2"),
+                (error, V2ConformanceIssueKind::Cardinality, "PID(1)3"),
+                (error, V2ConformanceIssueKind::Usage, "PID(1)3[2].4"),
+                (error, V2ConformanceIssueKind::ValueSet, "PID(1)3[2].5"),
+                (error, V2ConformanceIssueKind::Length, "PID(1)7[1]"),
+                (
+                    V2DiagnosticSeverity::Warning,
+                    V2ConformanceIssueKind::Structure,
+                    "ZPI"
+                ),
+                (error, V2ConformanceIssueKind::ValueSet, "PV1(1)2[1]"),
+                (error, V2ConformanceIssueKind::Structure, "IN2"),
+            ],
+            "Wrong conformance issues!"
+        );
+        assert_eq!(
+            report.issues[7].segment,
+            Some(3),
+            "ZPI is the third segment!"
+        );
+        assert!(!report.is_conformant(), "The message should not conform!");
+        assert_eq!(report.warnings().count(), 1, "Expected one warning!");
+
+        let json = rumtk_serialize!(&profile).unwrap();
+        let from_json = V2Profile::from_json(&json).unwrap();
+        assert_eq!(
+            message.validate_profile(&from_json, &registry),
+            report,
+            "The JSON form of the profile should validate the same!"
+        );
+    }
+
+    #[test]
+    fn test_conditional_usage_context() {
+        let local_code = vec![vec!["Y"], vec!["Yes"], vec!["L"]];
+        assert!(
+            CONDITION_CWE3(&V2ConditionContext::new(&local_code)),
+            "CWE.7 should be required for local coding systems!"
+        );
+        let hl7_code = vec![vec!["Y"], vec!["Yes"], vec!["HL70136"]];
+        assert!(
+            !CONDITION_CWE3(&V2ConditionContext::new(&hl7_code)),
+            "CWE.7 should not be required for HL7 tables!"
+        );
+
+        let result = rumtk_v2_parse_message!(
+            "MSH|^~\\&|LAB||EHR||20240101||ORU^R01|1|P|2.5.1\r\
+             ORC|RE\r\
+             OBR|1||FIL456|GLU^Glucose^L\r\
+             OBX|1||GLU^Glucose^L||95||||||F"
+        )
+        .unwrap();
+        let is_missing = |message: &V2Message, pattern: &str| match message.get_typed(pattern) {
+            Ok(V2Type::Err(e)) => e.starts_with("Conditionally required"),
+            _ => false,
+        };
+        assert!(
+            is_missing(&result, "OBR7"),
+            "OBR-7 should be required in results!"
+        );
+        assert!(
+            is_missing(&result, "OBX2"),
+            "OBX-2 should be required unless OBX-11 is X!"
+        );
+        assert!(
+            is_missing(&result, "ORC2"),
+            "ORC-2 should be required without ORC-3!"
+        );
+        assert!(
+            !is_missing(&result, "OBR2"),
+            "OBR-2 should not be required with OBR-3!"
+        );
+        let obr = result.get(&find_segment_id("OBR").unwrap(), 1).unwrap();
+        assert!(
+            obr.get_typed(7, 1, result.separators(), result.version())
+                .unwrap()
+                .is_empty(),
+            "Without the message, OBR-7 cannot be known to be in a result!"
+        );
+
+        let order = rumtk_v2_parse_message!(
+            "MSH|^~\\&|EHR||LAB||20240101||OML^O21|1|P|2.5.1\r\
+             ORC|NW|PLC123\r\
+             OBR|1|PLC123||GLU^Glucose^L"
+        )
+        .unwrap();
+        assert!(
+            !is_missing(&order, "OBR7"),
+            "OBR-7 should not be required in orders!"
+        );
+
+        let profile = V2Profile::from_json(
+            r#"{"name": "Results", "segments": [
+                {"name": "MSH", "usage": "R", "min": 1},
+                {"name": "ORC", "usage": "O"},
+                {"name": "OBR", "usage": "R", "min": 1},
+                {"name": "OBX", "usage": "R", "min": 1, "fields": [
+                    {"usage": "R", "min": 1},
+                    {"usage": "C"}
+                ]}
+            ]}"#,
+        )
+        .unwrap();
+        let report = result.validate_profile(&profile, &V2TableRegistry::new());
+        let issues: Vec<(V2ConformanceIssueKind, &str)> = report
+            .issues
+            .iter()
+            .map(|issue| (issue.kind, issue.location.as_str()))
+            .collect();
+        assert_eq!(
+            issues,
+            vec![(V2ConformanceIssueKind::Usage, "OBX(1)2")],
+            "OBX-2 should be evaluated with its standard predicate!"
+        );
+
+        let panel = rumtk_v2_parse_message!(
+            "MSH|^~\\&|LAB||EHR||20240101||ORU^R01|1|P|2.5.1\r\
+             ORC|RE||FIL456|||||||||||||||||4\r\
+             OBR|1||FIL456|CBC^Blood Count^L|||20240101\r\
+             OBX|1|NM|WBC^Leukocytes^L||||||||F\r\
+             OBX|2|NM|WBC^Leukocytes^L||7.2||||||F\r\
+             OBX|3|NM|HGB^Hemoglobin^L||||||||X\r\
+             OBR|2||FIL789|GLU^Glucose^L|||20240101\r\
+             OBX|1|NM|WBC^Leukocytes^L||6.1||||||F"
+        )
+        .unwrap();
+        assert!(
+            is_missing(&panel, "OBX(1)4"),
+            "OBX-4 should tell apart the WBC results under the first OBR!"
+        );
+        assert!(
+            !is_missing(&panel, "OBX(3)4"),
+            "OBX-4 should not be required for a single HGB result!"
+        );
+        assert!(
+            !is_missing(&panel, "OBX(4)4"),
+            "OBX-4 should only compare the OBX under the same OBR!"
+        );
+        assert!(
+            is_missing(&panel, "OBX(1)5"),
+            "OBX-5 should be required unless OBX-11 is X, N, or D!"
+        );
+        assert!(
+            !is_missing(&panel, "OBX(3)5"),
+            "OBX-5 should not be required when results cannot be obtained!"
+        );
+        assert!(
+            is_missing(&panel, "ORC26"),
+            "ORC-26 should be required when the notice was not signed!"
+        );
+
+        let profile = V2Profile::from_json(
+            r#"{"name": "Patients", "segments": [
+                {"name": "MSH", "usage": "R", "min": 1},
+                {"name": "PID", "usage": "R", "min": 1, "fields": [{"usage": "C"}]}
+            ]}"#,
+        )
+        .unwrap();
+        let patient = rumtk_v2_parse_message!("MSH|^~\\&|||||||ADT^A01|1|P|2.5.1\rPID|1").unwrap();
+        let report = patient.validate_profile(&profile, &V2TableRegistry::new());
+        let issues: Vec<(V2DiagnosticSeverity, &str)> = report
+            .issues
+            .iter()
+            .map(|issue| (issue.severity, issue.location.as_str()))
+            .collect();
+        assert_eq!(
+            issues,
+            vec![(V2DiagnosticSeverity::Warning, "PID(1)1")],
+            "PID-1 has no standard predicate, so it should be reported as not evaluated!"
+        );
+        assert!(
+            report.is_conformant(),
+            "Conditions that were not evaluated should not fail the message!"
+        );
+    }
+
+    #[test]
+    fn test_length_enforcement() {
+        let given_name = "ALEXANDRIA".repeat(4);
+        let declared = V2ParserCharacters {
+            truncation_declared: true,
+            ..V2ParserCharacters::new()
+        };
+        let build_with =
+            |separators: &V2ParserCharacters, version: &str, policy: V2LengthPolicy| {
+                V2MessageBuilder::with_separators(separators.clone())
+                    .component(8, 1, "ADT")
+                    .component(8, 2, "A01")
+                    .field(11, version)
+                    .segment("PID")
+                    .component(5, 1, "DOE")
+                    .component(5, 2, &given_name)
+                    .with_length_policy(policy)
+                    .build()
+            };
+        let build = |version: &str, policy: V2LengthPolicy| build_with(&declared, version, policy);
+        let given_name_of = |message: &V2Message| {
+            message
+                .find_component(&"PID5.2".to_rumstring())
+                .unwrap()
+                .to_string()
+        };
+
+        let untouched = build("2.7", V2LengthPolicy::Ignore).unwrap();
+        assert_eq!(
+            given_name_of(&untouched),
+            given_name,
+            "Values should be kept by default!"
+        );
+
+        let truncated = build("2.7", V2LengthPolicy::Truncate).unwrap();
+        assert_eq!(
+            given_name_of(&truncated),
+            format!("{}#", &given_name[..29]),
+            "XPN.2 should be cut to 30 characters ending in the truncation character!"
+        );
+        let diagnostics = truncated.truncated_values();
+        assert_eq!(diagnostics.len(), 1, "Expected one truncated value!");
+        assert_eq!(
+            (diagnostics[0].field, diagnostics[0].component),
+            (Some(5), Some(2)),
+            "PID-5.2 should be flagged as truncated!"
+        );
+
+        let undeclared = V2ParserCharacters::new();
+        let truncated = build_with(&undeclared, "2.7", V2LengthPolicy::Truncate).unwrap();
+        assert_eq!(
+            given_name_of(&truncated),
+            &given_name[..30],
+            "There is no truncation marker unless MSH-2 declares it!"
+        );
+        assert!(
+            truncated.truncated_values().is_empty(),
+            "Nothing is flagged unless MSH-2 declares a truncation character!"
+        );
+
+        let truncated = build("2.5.1", V2LengthPolicy::Truncate).unwrap();
+        assert_eq!(
+            given_name_of(&truncated),
+            &given_name[..30],
+            "There is no truncation character before v2.7!"
+        );
+        assert!(
+            truncated.truncated_values().is_empty(),
+            "Nothing is flagged before v2.7!"
+        );
+
+        let error = build("2.7", V2LengthPolicy::Reject).unwrap_err();
+        assert!(
+            error.starts_with("PID(1)5[1].2 is 40 characters long"),
+            "Wrong error {}!",
+            error
+        );
+        let error = V2MessageBuilder::new()
+            .field(11, "2.5.1")
+            .segment("PID")
+            .field(8, "MALE")
+            .with_length_policy(V2LengthPolicy::Truncate)
+            .build()
+            .unwrap_err();
+        assert!(
+            error.starts_with("PID(1)8[1] is 4 characters long"),
+            "IS values cannot be truncated! Got {}!",
+            error
+        );
+        let raw = format!(
+            "MSH|^~\\&|||||||ADT^A01|1|P|2.5.1\rPID|1||123||DOE^{}|||MALE",
+            given_name
+        );
+        let mut rejected = V2Message::try_from_str(&raw).unwrap();
+        assert!(
+            rejected.enforce_lengths(V2LengthPolicy::Truncate).is_err(),
+            "PID-8 is too long and cannot be truncated!"
+        );
+        assert_eq!(
+            rejected.to_string(),
+            raw,
+            "PID-5.2 was truncated even though the enforcement failed!"
+        );
+
+        let received =
+            rumtk_v2_parse_message!("MSH|^~\\&#|||||||ADT^A01|1|P|2.7\rPID|1||123||DOE^JOHN#")
+                .unwrap();
+        assert_eq!(
+            received.truncated_values().len(),
+            1,
+            "Expected PID-5.2 to be flagged as truncated!"
+        );
+
+        let received =
+            rumtk_v2_parse_message!("MSH|^~\\&|||||||ADT^A01|1|P|2.7\rPID|1||123||DOE^JOHN#")
+                .unwrap();
+        assert!(
+            received.truncated_values().is_empty(),
+            "A literal # was flagged as truncation without a declared truncation character!"
+        );
+    }
+
+    #[test]
+    fn test_datetime_precision_and_offsets() {
+        let birth = "201301".to_v2datetime().unwrap();
+        assert_eq!(birth.precision(), V2DateTimePrecision::Month);
+        assert_eq!(birth.to_v2(), "201301");
+        let timestamp = "20130102030405.12-0500".to_v2datetime().unwrap();
+        assert_eq!(timestamp.precision(), V2DateTimePrecision::HundredthSecond);
+        assert_eq!(timestamp.to_v2(), "20130102030405.12-0500");
+        assert_eq!(timestamp.offset_minutes(), Some(-300));
+        assert_eq!(
+            timestamp.as_utc_datetime().unwrap().to_rfc3339(),
+            "2013-01-02T08:04:05.120+00:00"
+        );
+        assert!(
+            "201301020304+9959"
+                .to_v2datetime()
+                .unwrap()
+                .as_utc_datetime()
+                .is_err(),
+            "An offset past a day should not become the Unix epoch!"
+        );
+        assert_ne!(birth, "20130101".to_v2datetime().unwrap());
+
+        let known_zero = "201301020304+0000".to_v2datetime().unwrap();
+        let utc = "201301020304-0000".to_v2datetime().unwrap();
+        assert_eq!(utc.to_v2(), "201301020304-0000");
+        assert_eq!(known_zero.time_zone(V2Version::V2_8), V2TimeZone::Utc);
+        assert_eq!(known_zero.time_zone(V2Version::V2_9), V2TimeZone::Offset(0));
+        assert_eq!(utc.time_zone(V2Version::V2_9), V2TimeZone::Utc);
+        assert_eq!(
+            "2013010203"
+                .to_v2datetime()
+                .unwrap()
+                .time_zone(V2Version::V2_9),
+            V2TimeZone::Local
+        );
+
+        let eastern = "201301020304-0500".to_v2datetime().unwrap();
+        assert!(known_zero < eastern);
+        assert_eq!(
+            eastern.duration_since(&known_zero).unwrap(),
+            chrono::Duration::hours(5)
+        );
+        let later = eastern.checked_add(chrono::Duration::days(30)).unwrap();
+        assert_eq!(later.to_v2(), "201302010304-0500");
+        let round_trip = V2DateTime::from_chrono(
+            &eastern
+                .to_chrono(chrono::FixedOffset::east_opt(0).unwrap())
+                .unwrap(),
+        );
+        assert_eq!(
+            round_trip.duration_since(&eastern).unwrap(),
+            chrono::Duration::zero()
+        );
+        assert!("20130231".to_v2datetime().is_err());
+
+        let json = serde_json::to_string(&timestamp).unwrap();
+        assert_eq!(json, "\"20130102030405.12-0500\"");
+        let parsed: V2DateTime = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, timestamp);
+
+        let message = rumtk_v2_parse_message!(tests::DEFAULT_HL7_V2_MESSAGE).unwrap();
+        let before_birthday = "20240614".to_v2datetime().unwrap();
+        let on_birthday = "20240615".to_v2datetime().unwrap();
+        assert_eq!(message.patient_age(&before_birthday).unwrap(), 62);
+        assert_eq!(message.patient_age(&on_birthday).unwrap(), 63);
+        assert!(message
+            .patient_age(&"19600101".to_v2datetime().unwrap())
+            .is_err());
+    }
+
+    #[test]
+    fn test_fallible_component_conversions() {
+        let message = rumtk_v2_parse_message!(
+            "MSH|^~\\&|LAB|FAC|EHR|FAC|20240102||ORU^R01^ORU_R01|1|P|2.5.1\r\
+             OBX|1|SN|1554-5^GLUCOSE^LN||>^100||||||F\r\
+             OBX|2|CQ|8302-2^HEIGHT^LN||123.7^kg&kilogram&UCUM||||||F\r\
+             OBX|3|NM|8867-4^PULSE^LN||+72|||Y||F\r\
+             OBX|4|NM|8867-4^PULSE^LN||1,250|||N||F"
+        )
+        .unwrap();
+        let obx = |n: usize, field: isize| {
+            message
+                .get(&V2_SEGMENT_IDS["OBX"], n)
+                .unwrap()
+                .get(field)
+                .unwrap()
+        };
+
+        let sn = obx(1, 5)[0].to_structured_numeric().unwrap();
+        assert_eq!(sn.comparator.as_deref(), Some(">"));
+        assert_eq!(sn.num1, Some(100.0));
+        assert_eq!(sn.num2, None);
+        let cq = obx(2, 5)[0].to_quantity().unwrap();
+        assert_eq!(cq.quantity, 123.7);
+        assert_eq!(cq.units.as_deref(), Some("kg"));
+        let pulse = obx(3, 5)[0].get(1).unwrap();
+        assert_eq!(pulse.to_v2integer().unwrap(), 72);
+        assert_eq!(pulse.to_v2decimal().unwrap(), 72.0);
+        assert!(obx(3, 8)[0].get(1).unwrap().to_v2boolean().unwrap());
+        assert!(!obx(4, 8)[0].get(1).unwrap().to_v2boolean().unwrap());
+        assert!(obx(4, 5)[0].get(1).unwrap().to_v2decimal().is_err());
+        assert!(obx(4, 5)[0].get(1).unwrap().to_v2integer().is_err());
+        assert!(obx(1, 2)[0].get(1).unwrap().to_v2boolean().is_err());
+        assert!("12.5".to_v2integer().is_err());
+        assert_eq!("12.00".to_v2integer().unwrap(), 12);
+        assert!(V2StructuredNumeric::from_parts(&["~", "1"]).is_err());
+
+        let descriptor = V2_FIELD_DESCRIPTORS["CQ"][0];
+        let quantity = obx(2, 5)[0].get(1).unwrap();
+        match quantity.to_typed(&descriptor, message.separators()) {
+            V2Type::V2NM(Ok(value)) => assert_eq!(value, 123.7),
+            other => panic!("Expected a number but got {:?}", other),
         }
     }
 
     #[test]
-    fn test_cast_component_to_ft_expected_functionality() {
-        let inputs = ["H", &"e".duplicate(120000)];
-        let expected_outputs = ["H", &"e".duplicate(TRUNCATE_FT as usize)];
-        for i in 0..inputs.len() {
-            let input = inputs[i];
-            let expected_val = expected_outputs[i];
-            print!(
-                "Testing input #{} \"{}\". Expected output is \"{}\". Casting to FT type.",
-                i, input, expected_val
+    fn test_declared_charsets() {
+        let latin1 =
+            b"MSH|^~\\&|LAB|FAC|EHR|FAC|20240102||ADT^A01^ADT_A01|1|P|2.5.1|||||DEU|8859/1\r\
+                       PID|1||123||M\xfcller^J\xfcrgen";
+        let message = V2Message::try_from(&latin1[..]).unwrap();
+        assert_eq!(message.charset(), "8859/1");
+        assert_eq!(
+            message
+                .find_component(&"PID5.1".to_rumstring())
+                .unwrap()
+                .to_string(),
+            "Müller"
+        );
+        assert_eq!(message.to_bytes().unwrap(), latin1.to_vec());
+        let framed = mllp_encode_bytes(latin1);
+        assert_eq!(mllp_decode(&framed).unwrap(), message.to_string());
+
+        let escapes = b"MSH|^~\\&|LAB|FAC|EHR|FAC|20240102||ADT^A01^ADT_A01|1|P|2.5.1|||||JPN|~ISO IR87||2.3\r\
+                        PID|1||123||\\M2442\\;3ED\\C2842\\^Taro";
+        let message = V2Message::from_bytes(escapes).unwrap();
+        assert_eq!(
+            message
+                .find_component(&"PID5.1".to_rumstring())
+                .unwrap()
+                .to_string(),
+            "山田"
+        );
+        assert_eq!(
+            message.to_bytes().unwrap(),
+            escapes.to_vec(),
+            "The 2.3 escape sequences were not written back!"
+        );
+        let malformed = b"MSH|^~\\&|LAB|FAC|EHR|FAC|20240102||ADT^A01^ADT_A01|1|P|2.5.1|||||JPN|~ISO IR87||2.3\r\
+                          PID|1||123||Yamada \\C2G42\\^Taro \\M414243\\";
+        let message = V2Message::from_bytes(malformed).unwrap();
+        assert_eq!(
+            message
+                .find_component(&"PID5.1".to_rumstring())
+                .unwrap()
+                .to_string(),
+            "Yamada \\C2G42\\",
+            "A malformed escape sequence should be kept as data!"
+        );
+        assert_eq!(
+            message
+                .find_component(&"PID5.2".to_rumstring())
+                .unwrap()
+                .to_string(),
+            "Taro \\M414243\\",
+            "An unknown character set should be kept as data!"
+        );
+        assert_eq!(
+            mllp_decode(&mllp_encode_bytes(malformed)).unwrap(),
+            message.to_string(),
+            "A malformed escape sequence should not abort MLLP decoding!"
+        );
+
+        let iso_2022 = b"MSH|^~\\&|LAB|FAC|EHR|FAC|20240102||ADT^A01^ADT_A01|1|P|2.5.1|||||JPN|~ISO IR87||ISO 2022-1994\r\
+                         PID|1||123||\x1b$B;3ED\x1b(B^Taro";
+        let message = V2Message::from_bytes(iso_2022).unwrap();
+        assert_eq!(
+            message
+                .find_component(&"PID5.1".to_rumstring())
+                .unwrap()
+                .to_string(),
+            "山田"
+        );
+
+        assert_eq!(
+            message.to_bytes().unwrap(),
+            iso_2022.to_vec(),
+            "The ISO 2022 escape sequences were not written back!"
+        );
+
+        let multi = b"MSH|^~\\&|LAB|FAC|EHR|FAC|20240102||ADT^A01^ADT_A01|1|P|2.5.1|||||DEU|8859/1~ISO IR87~8859/7||2.3\r\
+                      PID|1||123||M\xfcller \\M2442\\;3ED\\C2842\\^J\xfcrgen \\C2D46\\\xc1\xe8\xde\xed\xe1\r\
+                      NK1|1|\\M2442\\;3ED\\C2842\\^Hanako";
+        let message = V2Message::from_bytes(multi).unwrap();
+        assert_eq!(
+            message
+                .find_component(&"PID5.1".to_rumstring())
+                .unwrap()
+                .to_string(),
+            "Müller 山田"
+        );
+        assert_eq!(
+            message
+                .find_component(&"PID5.2".to_rumstring())
+                .unwrap()
+                .to_string(),
+            "Jürgen Αθήνα"
+        );
+        assert_eq!(
+            message.to_bytes().unwrap(),
+            multi.to_vec(),
+            "Multi character set message did not survive the byte level round trip!"
+        );
+
+        let mut message = V2Message::try_from(&latin1[..]).unwrap();
+        message.set("PID5.1", "山田").unwrap();
+        let error = message.to_bytes().unwrap_err();
+        assert!(
+            error.contains("MSH-20"),
+            "Unclear error when the message needs character set switching => {}",
+            error
+        );
+
+        let mut message = V2Message::from_bytes(iso_2022).unwrap();
+        message.set_charset("8859/1").unwrap();
+        assert_eq!(
+            V2Message::from_bytes(&message.to_bytes().unwrap()).unwrap(),
+            message,
+            "Switching from a Latin-1 default did not round trip!"
+        );
+        message.set_charset("UNICODE UTF-8").unwrap();
+        assert_eq!(
+            V2Message::from_bytes(&message.to_bytes().unwrap()).unwrap(),
+            message
+        );
+        assert!(message.set_charset("EBCDIC").is_err());
+    }
+
+    #[test]
+    fn test_redelimit_message() {
+        let message = V2Message::try_from_str(
+            "MSH|^~\\&|LAB|FAC|EHR|FAC|20240102||ADT^A01^ADT_A01|1|P|2.5.1\r\
+             PID|1||123||SMITH!JONES^JOHN\\T\\JR||19800101\r\
+             NTE|1||Call me @ 5*2 \\F\\ ok",
+        )
+        .unwrap();
+        let target = V2ParserCharacters {
+            field_separator: "*".to_rumstring(),
+            component_separator: "!".to_rumstring(),
+            escape_character: "@".to_rumstring(),
+            subcomponent_separator: "%".to_rumstring(),
+            ..V2ParserCharacters::new()
+        };
+        let redelimited = message.to_string_with(&target).unwrap();
+        assert!(redelimited.starts_with("MSH*!~@%*LAB*"));
+        assert!(redelimited.contains("PID*1**123**SMITH@S@JONES!JOHN&JR*"));
+        assert!(redelimited.contains("NTE*1**Call me @E@ 5@F@2 | ok"));
+
+        let reparsed = V2Message::try_from_str(&redelimited).unwrap();
+        for pattern in ["PID5.1", "PID5.2", "NTE3"] {
+            let pattern = pattern.to_rumstring();
+            assert_eq!(
+                reparsed
+                    .find_component(&pattern)
+                    .unwrap()
+                    .decoded(reparsed.separators())
+                    .unwrap(),
+                message
+                    .find_component(&pattern)
+                    .unwrap()
+                    .decoded(message.separators())
+                    .unwrap()
             );
-            let val = input.to_v2formattedtext("~").unwrap();
-            println!("{}", val.len());
-            let err_msg = format_compact!("The expected formatted string does not match the formatted string generated from the input [In: {}, Got: {}]", input, val);
-            assert_eq!(expected_val, val, "{}", &err_msg);
-            println!(" ... Got: {} ✅ ", val);
         }
+
+        let mut switched = V2Message::try_from_str(&message.to_string()).unwrap();
+        switched.set_separators(target.clone()).unwrap();
+        assert_eq!(switched.to_string(), redelimited);
+        assert_eq!(
+            switched.to_string_with(message.separators()).unwrap(),
+            message.to_string()
+        );
+
+        let file_output = V2ParserCharacters {
+            segment_terminator: "\r\n".to_rumstring(),
+            ..V2ParserCharacters::new()
+        };
+        assert_eq!(
+            message.to_string_with(&file_output).unwrap(),
+            message.to_string().replace("\r", "\r\n")
+        );
+        let clashing = V2ParserCharacters {
+            component_separator: "|".to_rumstring(),
+            ..V2ParserCharacters::new()
+        };
+        assert!(message.to_string_with(&clashing).is_err());
     }
 
     #[test]
-    fn test_validated_cast_component_to_type() {
-        let message = tests::DEFAULT_HL7_V2_MESSAGE;
-        let sanitized_message = V2Message::sanitize(message);
-        let tokens = V2Message::tokenize_segments(&sanitized_message.as_str());
-        let encode_chars = V2ParserCharacters::from_msh(tokens[0]).unwrap();
-        let v2_component = V2ComponentTypeDescriptor::new(
-            "date",
-            "Date",
-            V2ComponentType::Primitive(V2PrimitiveType::Date),
-            4,
-            1,
-            1,
-            Optionality::O,
-            true,
+    fn test_message_diff() {
+        let old = V2Message::try_from_str(
+            "MSH|^~\\&|LAB|FAC|EHR|FAC|20240102||ADT^A01|1|P|2.5.1\r\
+             PID|1||123||EVERYMAN^ADAM^A||19800101|M\r\
+             NK1|1|NUCLEAR^NELDA",
+        )
+        .unwrap();
+        let new = V2Message::try_from_str(
+            "MSH|^~\\&|LAB|FAC|EHR|FAC|20240103||ADT^A01|2|P|2.5.1\r\
+             PID|1||123~456||EVERYWOMAN^ADAM||19800101|M\r\
+             OBX|1|ST|CODE||VAL",
+        )
+        .unwrap();
+
+        let diff = old.diff(&new, &V2DiffRules::volatile());
+        assert_eq!(
+            diff.report(),
+            "+ PID(1)3[2]: 456\n\
+             ~ PID(1)5[1].1: EVERYMAN -> EVERYWOMAN\n\
+             - PID(1)5[1].3: A\n\
+             - NK1(1): NK1|1|NUCLEAR^NELDA\n\
+             + OBX(1): OBX|1|ST|CODE||VAL"
+        );
+        let position = diff.changes()[1].position().to_string().to_rumstring();
+        assert_eq!(
+            new.find_component(&position).unwrap().as_str(),
+            "EVERYWOMAN"
         );
-        let input = "2007";
-        let val = cast_component(vec![&input], &v2_component, &encode_chars);
-        let expected = "2007-01-01T00:00:00.0000";
-        let err_msg = format_compact!("The expected formatted string does not match the formatted string generated from the input [In: {}, Got: {}]", input, expected);
 
-        match val {
-            V2Type::V2Date(result) => {
-                assert_eq!(expected, result.unwrap().as_utc_string(), "{}", &err_msg)
+        let everything = old.diff(&new, &V2DiffRules::new());
+        assert_eq!(everything.len(), 7);
+        assert_eq!(
+            everything.changes()[0],
+            V2Change::Modified {
+                position: V2DiffPosition {
+                    segment: "MSH".to_rumstring(),
+                    segment_group: 1,
+                    field: Some(6),
+                    repetition: Some(1),
+                    component: None,
+                    subcomponent: None,
+                },
+                old: "20240102".to_rumstring(),
+                new: "20240103".to_rumstring(),
             }
-            _ => panic!("Wrong type received!"),
-        }
+        );
+        let rules = V2DiffRules::volatile()
+            .ignore_segment("NK1")
+            .ignore_segment("OBX")
+            .ignore_field("PID", 3)
+            .ignore_field("PID", 5);
+        assert!(old.diff(&new, &rules).is_empty());
+
+        let target = V2ParserCharacters {
+            field_separator: "*".to_rumstring(),
+            component_separator: "!".to_rumstring(),
+            ..V2ParserCharacters::new()
+        };
+        let redelimited = V2Message::try_from_str(&old.to_string_with(&target).unwrap()).unwrap();
+        assert!(old.diff(&redelimited, &V2DiffRules::new()).is_empty());
+    }
+
+    #[test]
+    fn test_truncation_character_escaping() {
+        let declared = "MSH|^~\\&#|||||||ADT^A01|1|P|2.7\rPID|1||1234||DOE^JOHN";
+        let mut message = V2Message::try_from_str(declared).unwrap();
+        assert!(message.separators().declares_truncation());
+        message.set("PID11.1", "APT #4").unwrap();
+        let generated = message.to_string();
+        assert!(
+            generated.contains("APT \\P\\4"),
+            "The declared truncation character was not escaped!"
+        );
+        let reparsed = V2Message::try_from_str(&generated).unwrap();
+        let component = rumtk_v2_find_component!(reparsed, "PID11.1").unwrap();
+        assert_eq!(
+            component.decoded(reparsed.separators()).unwrap(),
+            "APT #4",
+            "The truncation character did not survive the round trip!"
+        );
+
+        let undeclared = "MSH|^~\\&|||||||ADT^A01|1|P|2.7\rPID|1||1234||DOE^JOHN";
+        let mut message = V2Message::try_from_str(undeclared).unwrap();
+        assert!(!message.separators().declares_truncation());
+        assert_eq!(message.separators().escape("APT #4"), "APT #4");
+        message.set("PID11.1", "APT #4").unwrap();
+        let generated = message.to_string();
+        assert!(
+            generated.starts_with("MSH|^~\\&|"),
+            "MSH-2 gained a truncation character!"
+        );
+        assert!(generated.contains("APT #4"), "A literal # was escaped!");
+        let reparsed = V2Message::try_from_str(&generated).unwrap();
+        let component = rumtk_v2_find_component!(reparsed, "PID11.1").unwrap();
+        assert_eq!(component.decoded(reparsed.separators()).unwrap(), "APT #4");
     }
 
     // TODO: Add tests for sequenceid and telephonestring